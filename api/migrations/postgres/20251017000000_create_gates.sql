@@ -0,0 +1,23 @@
+CREATE TABLE IF NOT EXISTS gates
+(
+    "group"       TEXT        NOT NULL,
+    service       TEXT        NOT NULL,
+    environment   TEXT        NOT NULL,
+    state         TEXT        NOT NULL CHECK (state IN ('open', 'closed')),
+    last_updated  TIMESTAMPTZ NOT NULL,
+    display_order BIGINT CHECK (display_order >= 0),
+    PRIMARY KEY ("group", service, environment)
+);
+
+CREATE TABLE IF NOT EXISTS comments
+(
+    "group"     TEXT        NOT NULL,
+    service     TEXT        NOT NULL,
+    environment TEXT        NOT NULL,
+    id          TEXT        NOT NULL,
+    message     TEXT        NOT NULL,
+    created     TIMESTAMPTZ NOT NULL,
+    PRIMARY KEY ("group", service, environment, id),
+    FOREIGN KEY ("group", service, environment)
+        REFERENCES gates ("group", service, environment) ON DELETE CASCADE
+);
@@ -0,0 +1 @@
+ALTER TABLE gates ADD COLUMN version BIGINT NOT NULL DEFAULT 0 CHECK (version >= 0);
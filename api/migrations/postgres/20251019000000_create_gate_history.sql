@@ -0,0 +1,16 @@
+CREATE TABLE IF NOT EXISTS gate_history
+(
+    position    BIGSERIAL PRIMARY KEY,
+    id          TEXT        NOT NULL UNIQUE,
+    "group"     TEXT        NOT NULL,
+    service     TEXT        NOT NULL,
+    environment TEXT        NOT NULL,
+    timestamp   TIMESTAMPTZ NOT NULL,
+    event       TEXT        NOT NULL,
+    old_value   TEXT,
+    new_value   TEXT,
+    actor       TEXT
+);
+
+CREATE INDEX IF NOT EXISTS gate_history_by_gate
+    ON gate_history ("group", service, environment, position);
@@ -0,0 +1 @@
+ALTER TABLE gates ADD COLUMN deleted_at TIMESTAMPTZ;
@@ -0,0 +1,6 @@
+-- days on which all gates are closed
+CREATE TABLE IF NOT EXISTS closed_day
+(
+    date   DATE PRIMARY KEY,
+    reason TEXT NOT NULL
+);
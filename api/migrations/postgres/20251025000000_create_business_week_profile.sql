@@ -0,0 +1,9 @@
+-- business weeks of groups, services or single gates, an empty service or environment covers all of them
+CREATE TABLE IF NOT EXISTS business_week_profile
+(
+    "group"     TEXT NOT NULL,
+    service     TEXT NOT NULL,
+    environment TEXT NOT NULL,
+    week        TEXT NOT NULL,
+    PRIMARY KEY ("group", service, environment)
+);
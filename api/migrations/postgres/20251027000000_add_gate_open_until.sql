@@ -0,0 +1 @@
+ALTER TABLE gates ADD COLUMN open_until TIMESTAMPTZ;
@@ -0,0 +1 @@
+ALTER TABLE gates ADD COLUMN version INTEGER NOT NULL DEFAULT 0;
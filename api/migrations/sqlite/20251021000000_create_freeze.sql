@@ -0,0 +1,9 @@
+-- the global freeze, there is at most one
+CREATE TABLE IF NOT EXISTS freeze
+(
+    id      INTEGER PRIMARY KEY CHECK (id = 1),
+    reason  TEXT NOT NULL,
+    actor   TEXT,
+    created TEXT NOT NULL,
+    until   TEXT
+);
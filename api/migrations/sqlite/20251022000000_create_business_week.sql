@@ -0,0 +1,6 @@
+-- the business week changed at runtime, there is at most one
+CREATE TABLE IF NOT EXISTS business_week
+(
+    id   INTEGER PRIMARY KEY CHECK (id = 1),
+    week TEXT NOT NULL
+);
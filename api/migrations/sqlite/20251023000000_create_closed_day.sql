@@ -0,0 +1,6 @@
+-- days on which all gates are closed
+CREATE TABLE IF NOT EXISTS closed_day
+(
+    date   TEXT PRIMARY KEY,
+    reason TEXT NOT NULL
+);
@@ -0,0 +1,13 @@
+-- named freezes between two instants, scoped to a group, an environment or all gates
+CREATE TABLE IF NOT EXISTS freeze_window
+(
+    id                TEXT PRIMARY KEY,
+    name              TEXT NOT NULL,
+    reason            TEXT NOT NULL,
+    starts_at         TEXT NOT NULL,
+    ends_at           TEXT NOT NULL,
+    scope_group       TEXT,
+    scope_environment TEXT,
+    actor             TEXT,
+    CHECK (scope_group IS NULL OR scope_environment IS NULL)
+);
@@ -0,0 +1,11 @@
+-- state changes of a gate at a future instant, deleted once they are applied
+CREATE TABLE IF NOT EXISTS scheduled_state_change
+(
+    id          TEXT PRIMARY KEY,
+    "group"     TEXT NOT NULL,
+    service     TEXT NOT NULL,
+    environment TEXT NOT NULL,
+    state       TEXT NOT NULL,
+    at          TEXT NOT NULL,
+    actor       TEXT
+);
@@ -0,0 +1,9 @@
+-- the gates a gate depends on, encoded as JSON
+CREATE TABLE IF NOT EXISTS gate_dependency
+(
+    "group"     TEXT NOT NULL,
+    service     TEXT NOT NULL,
+    environment TEXT NOT NULL,
+    depends_on  TEXT NOT NULL,
+    PRIMARY KEY ("group", service, environment)
+);
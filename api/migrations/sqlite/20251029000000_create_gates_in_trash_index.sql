@@ -0,0 +1,3 @@
+-- only gates in the trash are indexed, so purging the trash does not read all gates
+CREATE INDEX IF NOT EXISTS gates_in_trash
+    ON gates (deleted_at) WHERE deleted_at IS NOT NULL;
@@ -0,0 +1 @@
+ALTER TABLE gate_history ADD COLUMN open_until TEXT;
@@ -0,0 +1,45 @@
+use axum::http::HeaderMap;
+
+pub const ACTOR_HEADER: &str = "x-gates-actor";
+
+pub fn from_headers(headers: &HeaderMap) -> Option<String> {
+    headers
+        .get(ACTOR_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .map(str::trim)
+        .filter(|actor| !actor.is_empty())
+        .map(ToOwned::to_owned)
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use axum::http::HeaderValue;
+    use rstest::rstest;
+    use similar_asserts::assert_eq;
+
+    use super::*;
+
+    #[rstest]
+    #[case("Max", Some("Max".to_owned()))]
+    #[case(" Max Mustermann ", Some("Max Mustermann".to_owned()))]
+    #[case(" ", None)]
+    fn should_read_actor_header(#[case] value: &str, #[case] expected: Option<String>) {
+        // given
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            ACTOR_HEADER,
+            HeaderValue::from_str(value).expect("invalid header value"),
+        );
+
+        // when
+        let actor = from_headers(&headers);
+
+        // then
+        assert_eq!(actor, expected);
+    }
+
+    #[test]
+    fn should_not_require_actor_header() {
+        assert_eq!(from_headers(&HeaderMap::new()), None);
+    }
+}
@@ -0,0 +1,91 @@
+use chrono::{DateTime, Utc};
+
+use crate::history;
+use crate::id_provider::IdProvider;
+use crate::storage::{Storage, UpdateError};
+use crate::types::{Comment, Gate, GateEvent, GateState, HistoryEntry};
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Error {
+    Internal(String),
+}
+
+impl From<UpdateError> for Error {
+    fn from(value: UpdateError) -> Self {
+        match value {
+            UpdateError::ItemToUpdateNotFound(error)
+            | UpdateError::VersionConflict(error)
+            | UpdateError::Other(error) => Self::Internal(error),
+        }
+    }
+}
+
+/// Closes the open gate on behalf of the system, recorded like a state change without an actor and
+/// with a comment on why. Returns whether the gate was closed, it is left as it is if it was changed
+/// or deleted in the meantime.
+pub async fn close(
+    gate: &Gate,
+    message: String,
+    now: DateTime<Utc>,
+    storage: &(dyn Storage + Send + Sync),
+    id_provider: &(dyn IdProvider + Send + Sync),
+) -> Result<bool, Error> {
+    match storage
+        .update_state_and_last_updated(
+            gate.key.clone(),
+            GateState::Closed,
+            None,
+            now,
+            Some(gate.version),
+        )
+        .await
+    {
+        Ok(_) => {}
+        Err(UpdateError::VersionConflict(_) | UpdateError::ItemToUpdateNotFound(_)) => {
+            return Ok(false)
+        }
+        Err(error) => return Err(error.into()),
+    }
+    history::record(
+        storage,
+        &HistoryEntry {
+            id: id_provider.get(),
+            key: gate.key.clone(),
+            timestamp: now,
+            event: GateEvent::StateChanged,
+            old_value: Some(String::try_from(gate.state.clone()).map_err(Error::Internal)?),
+            new_value: Some(String::try_from(GateState::Closed).map_err(Error::Internal)?),
+            open_until: None,
+            actor: None,
+        },
+    )
+    .await;
+
+    storage
+        .update_comment_and_last_updated(
+            gate.key.clone(),
+            Comment {
+                id: id_provider.get(),
+                message: message.clone(),
+                created: now,
+            },
+            now,
+            None,
+        )
+        .await?;
+    history::record(
+        storage,
+        &HistoryEntry {
+            id: id_provider.get(),
+            key: gate.key.clone(),
+            timestamp: now,
+            event: GateEvent::CommentAdded,
+            old_value: None,
+            new_value: Some(message),
+            open_until: None,
+            actor: None,
+        },
+    )
+    .await;
+    Ok(true)
+}
@@ -0,0 +1,289 @@
+use std::path::Path;
+use std::{env, fs};
+
+use crate::types::{BusinessWeek, EnvironmentPatterns};
+
+pub const ENV_GATES_BUSINESS_WEEK: &str = "GATES_BUSINESS_WEEK";
+pub const ENV_GATES_BUSINESS_WEEK_FILE: &str = "GATES_BUSINESS_WEEK_FILE";
+pub const ENV_GATES_BUSINESS_HOURS_ENABLED: &str = "GATES_BUSINESS_HOURS_ENABLED";
+pub const ENV_GATES_BUSINESS_HOURS_ENVIRONMENTS: &str = "GATES_BUSINESS_HOURS_ENVIRONMENTS";
+pub const ENV_GATES_BUSINESS_HOURS_AUTO_CLOSE: &str = "GATES_BUSINESS_HOURS_AUTO_CLOSE";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Format {
+    Json,
+    Yaml,
+}
+
+/// The business week given as JSON by `GATES_BUSINESS_WEEK` or as JSON or YAML file by
+/// `GATES_BUSINESS_WEEK_FILE`, otherwise the default one.
+pub fn configured() -> BusinessWeek {
+    load(
+        env::var(ENV_GATES_BUSINESS_WEEK).ok().as_deref(),
+        env::var(ENV_GATES_BUSINESS_WEEK_FILE).ok().as_deref(),
+    )
+    .unwrap_or_else(|error| panic!("{error}"))
+}
+
+/// Whether gates are closed outside of business hours, given by `GATES_BUSINESS_HOURS_ENABLED`,
+/// disabled by default.
+pub fn configured_enabled() -> bool {
+    configured_flag(ENV_GATES_BUSINESS_HOURS_ENABLED)
+}
+
+/// Whether open gates are really closed at the end of business hours instead of only being
+/// reported closed, given by `GATES_BUSINESS_HOURS_AUTO_CLOSE`, disabled by default.
+pub fn configured_auto_close() -> bool {
+    configured_flag(ENV_GATES_BUSINESS_HOURS_AUTO_CLOSE)
+}
+
+fn configured_flag(variable: &str) -> bool {
+    parse_flag(variable, env::var(variable).ok().as_deref())
+        .unwrap_or_else(|error| panic!("{error}"))
+}
+
+fn parse_flag(variable: &str, value: Option<&str>) -> Result<bool, String> {
+    match value.map(|value| value.trim().to_lowercase()).as_deref() {
+        None | Some("false") => Ok(false),
+        Some("true") => Ok(true),
+        Some(value) => Err(format!("unsupported value '{value}' for {variable}")),
+    }
+}
+
+/// The comma separated environment names or patterns given by `GATES_BUSINESS_HOURS_ENVIRONMENTS`,
+/// otherwise the business hours apply to all environments.
+pub fn configured_environments() -> EnvironmentPatterns {
+    parse_environments(
+        env::var(ENV_GATES_BUSINESS_HOURS_ENVIRONMENTS)
+            .ok()
+            .as_deref(),
+    )
+    .unwrap_or_else(|error| panic!("{error}"))
+}
+
+fn parse_environments(value: Option<&str>) -> Result<EnvironmentPatterns, String> {
+    let Some(value) = value else {
+        return Ok(EnvironmentPatterns::default());
+    };
+    EnvironmentPatterns::new(
+        value
+            .split(',')
+            .map(|pattern| pattern.trim().to_owned())
+            .collect(),
+    )
+    .map_err(|error| format!("invalid value for {ENV_GATES_BUSINESS_HOURS_ENVIRONMENTS}: {error}"))
+}
+
+fn load(value: Option<&str>, path: Option<&str>) -> Result<BusinessWeek, String> {
+    match (value, path) {
+        (None, None) => Ok(BusinessWeek::default()),
+        (Some(value), None) => parse(value, Format::Json)
+            .map_err(|error| format!("invalid value for {ENV_GATES_BUSINESS_WEEK}: {error}")),
+        (None, Some(path)) => load_file(path)
+            .map_err(|error| format!("invalid file for {ENV_GATES_BUSINESS_WEEK_FILE}: {error}")),
+        (Some(_), Some(_)) => Err(format!(
+            "only one of {ENV_GATES_BUSINESS_WEEK} and {ENV_GATES_BUSINESS_WEEK_FILE} can be set"
+        )),
+    }
+}
+
+fn load_file(path: &str) -> Result<BusinessWeek, String> {
+    let format = match Path::new(path).extension().and_then(|it| it.to_str()) {
+        Some("json") => Format::Json,
+        Some("yaml" | "yml") => Format::Yaml,
+        _ => return Err(format!("unsupported file type of '{path}'")),
+    };
+    let content =
+        fs::read_to_string(path).map_err(|error| format!("could not read '{path}': {error}"))?;
+    parse(&content, format)
+}
+
+fn parse(content: &str, format: Format) -> Result<BusinessWeek, String> {
+    let business_week: BusinessWeek = match format {
+        Format::Json => serde_json::from_str(content).map_err(|error| error.to_string())?,
+        Format::Yaml => serde_yaml_ng::from_str(content).map_err(|error| error.to_string())?,
+    };
+    business_week.validate()?;
+    Ok(business_week)
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use chrono::NaiveTime;
+    use rstest::rstest;
+    use similar_asserts::assert_eq;
+
+    use super::*;
+    use crate::types::BusinessTimes;
+
+    fn given_business_week() -> BusinessWeek {
+        BusinessWeek {
+            monday: vec![BusinessTimes {
+                start: NaiveTime::from_hms_opt(7, 0, 0).unwrap(),
+                end: NaiveTime::from_hms_opt(18, 30, 0).unwrap(),
+            }],
+            tuesday: vec![],
+            wednesday: vec![],
+            thursday: vec![],
+            friday: vec![],
+            saturday: vec![],
+            sunday: vec![],
+            time_zone: chrono_tz::Europe::London,
+        }
+    }
+
+    #[test]
+    fn should_use_default_business_week_if_nothing_is_configured() {
+        assert_eq!(load(None, None), Ok(BusinessWeek::default()));
+    }
+
+    #[test]
+    fn should_load_business_week_from_value() {
+        // given
+        let value =
+            r#"{"monday": {"start": "07:00:00", "end": "18:30:00"}, "time_zone": "Europe/London"}"#;
+
+        // when
+        let actual = load(Some(value), None);
+
+        // then
+        assert_eq!(actual, Ok(given_business_week()));
+    }
+
+    #[rstest]
+    #[case(
+        "json",
+        r#"{"monday": {"start": "07:00:00", "end": "18:30:00"}, "time_zone": "Europe/London"}"#
+    )]
+    #[case(
+        "yaml",
+        "monday:\n  start: \"07:00:00\"\n  end: \"18:30:00\"\ntime_zone: Europe/London\n"
+    )]
+    fn should_load_business_week_from_file(#[case] extension: &str, #[case] content: &str) {
+        // given
+        let path = env::temp_dir().join(format!(
+            "gates-business-week-{}.{extension}",
+            cuid2::create_id()
+        ));
+        fs::write(&path, content).expect("failed to write business week file");
+
+        // when
+        let actual = load(None, path.to_str());
+
+        // then
+        fs::remove_file(&path).expect("failed to remove business week file");
+        assert_eq!(actual, Ok(given_business_week()));
+    }
+
+    #[test]
+    fn should_load_several_business_times_per_day() {
+        // given
+        let value = r#"{"monday": [{"start": "08:00:00", "end": "12:00:00"}, {"start": "22:00:00", "end": "02:00:00"}]}"#;
+
+        // when
+        let actual = load(Some(value), None);
+
+        // then
+        assert_eq!(
+            actual.map(|business_week| business_week.monday),
+            Ok(vec![
+                BusinessTimes {
+                    start: NaiveTime::from_hms_opt(8, 0, 0).unwrap(),
+                    end: NaiveTime::from_hms_opt(12, 0, 0).unwrap(),
+                },
+                BusinessTimes {
+                    start: NaiveTime::from_hms_opt(22, 0, 0).unwrap(),
+                    end: NaiveTime::from_hms_opt(2, 0, 0).unwrap(),
+                },
+            ])
+        );
+    }
+
+    #[test]
+    fn should_default_to_berlin_time_zone() {
+        // given
+        let value = r#"{"monday": {"start": "07:00:00", "end": "18:30:00"}}"#;
+
+        // when
+        let actual = load(Some(value), None);
+
+        // then
+        assert_eq!(
+            actual.map(|business_week| business_week.time_zone),
+            Ok(chrono_tz::Europe::Berlin)
+        );
+    }
+
+    #[rstest]
+    #[case(r#"{"monday": {"start": "07:00:00", "end": "07:00:00"}}"#)]
+    #[case(r#"{"monday": [{"start": "07:00:00", "end": "07:00:00"}]}"#)]
+    #[case(r#"{"monday": {"start": "7 o'clock", "end": "18:30:00"}}"#)]
+    #[case(r#"{"monday": {"start": "07:00:00"}}"#)]
+    #[case(r#"{"mondays": {"start": "07:00:00", "end": "18:30:00"}}"#)]
+    #[case(r#"{"time_zone": "Europe/Atlantis"}"#)]
+    fn should_reject_invalid_business_week(#[case] value: &str) {
+        assert!(load(Some(value), None).is_err());
+    }
+
+    #[rstest]
+    #[case(None, Ok(false))]
+    #[case(Some("true"), Ok(true))]
+    #[case(Some(" TRUE "), Ok(true))]
+    #[case(Some("false"), Ok(false))]
+    fn should_parse_enabled(#[case] value: Option<&str>, #[case] expected: Result<bool, String>) {
+        assert_eq!(
+            parse_flag(ENV_GATES_BUSINESS_HOURS_ENABLED, value),
+            expected
+        );
+    }
+
+    #[rstest]
+    #[case("")]
+    #[case("yes")]
+    fn should_reject_invalid_enabled(#[case] value: &str) {
+        assert!(parse_flag(ENV_GATES_BUSINESS_HOURS_ENABLED, Some(value)).is_err());
+    }
+
+    #[test]
+    fn should_name_variable_of_invalid_flag() {
+        assert_eq!(
+            parse_flag(ENV_GATES_BUSINESS_HOURS_AUTO_CLOSE, Some("yes")),
+            Err("unsupported value 'yes' for GATES_BUSINESS_HOURS_AUTO_CLOSE".to_owned())
+        );
+    }
+
+    #[test]
+    fn should_apply_business_hours_to_all_environments_if_nothing_is_configured() {
+        assert_eq!(parse_environments(None), Ok(EnvironmentPatterns::default()));
+    }
+
+    #[test]
+    fn should_parse_environments() {
+        // when
+        let actual = parse_environments(Some("live, prod*"));
+
+        // then
+        assert_eq!(
+            actual,
+            EnvironmentPatterns::new(vec!["live".to_owned(), "prod*".to_owned()])
+        );
+    }
+
+    #[rstest]
+    #[case("")]
+    #[case("live,,prod")]
+    #[case(" , ")]
+    fn should_reject_invalid_environments(#[case] value: &str) {
+        assert!(parse_environments(Some(value)).is_err());
+    }
+
+    #[test]
+    fn should_reject_unsupported_file_type() {
+        assert!(load(None, Some("business-week.toml")).is_err());
+    }
+
+    #[test]
+    fn should_reject_value_and_file_at_once() {
+        assert!(load(Some("{}"), Some("business-week.json")).is_err());
+    }
+}
@@ -0,0 +1,305 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use openapi::models;
+
+use crate::date_time_switch::DateTimeSwitch;
+use crate::freeze;
+use crate::freeze::Freezes;
+use crate::storage::{FindError, Storage};
+use crate::types::{ClosureReason, Gate, GateKey, GateState};
+
+/// Everything that closes gates at one instant regardless of their stored state.
+pub struct Closures<'a> {
+    pub now: DateTime<Utc>,
+    pub freezes: Freezes,
+    dependencies: HashMap<GateKey, Vec<GateKey>>,
+    storage: &'a (dyn Storage + Send + Sync),
+    date_time_switch: &'a (dyn DateTimeSwitch + Send + Sync),
+}
+
+impl<'a> Closures<'a> {
+    pub async fn at(
+        now: DateTime<Utc>,
+        storage: &'a (dyn Storage + Send + Sync),
+        date_time_switch: &'a (dyn DateTimeSwitch + Send + Sync),
+    ) -> Result<Self, FindError> {
+        Ok(Self {
+            now,
+            freezes: freeze::active(storage, now).await?,
+            dependencies: storage
+                .find_gate_dependencies()
+                .await?
+                .into_iter()
+                .map(|dependencies| (dependencies.key, dependencies.depends_on))
+                .collect(),
+            storage,
+            date_time_switch,
+        })
+    }
+
+    /// The gates the gate depends on directly.
+    pub fn depends_on(&self, key: &GateKey) -> &[GateKey] {
+        self.dependencies.get(key).map_or(&[], Vec::as_slice)
+    }
+
+    /// The gates the given gates depend on directly by their key, taken from the given gates if they
+    /// are among them, only the others are read. Gates that do not exist anymore are left out.
+    pub async fn dependencies_of(
+        &self,
+        gates: &[Gate],
+    ) -> Result<HashMap<GateKey, Gate>, FindError> {
+        let loaded: HashMap<&GateKey, &Gate> = gates.iter().map(|gate| (&gate.key, gate)).collect();
+        let mut dependencies = HashMap::new();
+        for key in gates.iter().flat_map(|gate| self.depends_on(&gate.key)) {
+            if dependencies.contains_key(key) {
+                continue;
+            }
+            let dependency = match loaded.get(key) {
+                Some(gate) => Some((*gate).clone()),
+                None => self.storage.find_one(key.clone()).await?,
+            };
+            if let Some(dependency) = dependency {
+                dependencies.insert(key.clone(), dependency);
+            }
+        }
+        Ok(dependencies)
+    }
+
+    /// Why the gate is closed, a closed gate it depends on directly only counts if nothing else
+    /// closes it. The dependencies are looked up in the given gates, see `dependencies_of`.
+    pub async fn reason(
+        &self,
+        gate: &Gate,
+        dependencies: &HashMap<GateKey, Gate>,
+    ) -> Option<ClosureReason> {
+        if let Some(reason) = self.own_reason(gate).await {
+            return Some(reason);
+        }
+        for key in self.depends_on(&gate.key) {
+            if let Some(dependency) = dependencies.get(key) {
+                if self.own_reason(dependency).await.is_some() {
+                    return Some(ClosureReason::Dependency);
+                }
+            }
+        }
+        None
+    }
+
+    /// The representation of the gate in the state it is in effect, including why it is closed.
+    pub async fn rep(&self, gate: Gate, dependencies: &HashMap<GateKey, Gate>) -> models::Gate {
+        let closure_reason = self.reason(&gate, dependencies).await;
+        let gate = match closure_reason {
+            Some(ClosureReason::BusinessHours) => Gate {
+                state: GateState::Closed,
+                open_until: None,
+                ..gate
+            },
+            Some(_) => Gate {
+                state: GateState::Closed,
+                ..gate.close_if_expired(self.now)
+            },
+            None => gate,
+        };
+        models::Gate {
+            closure_reason: closure_reason.map(Into::into),
+            ..gate.into_rep(self.now)
+        }
+    }
+
+    async fn own_reason(&self, gate: &Gate) -> Option<ClosureReason> {
+        if self.freezes.reason(&gate.key).is_some() {
+            Some(ClosureReason::Freeze)
+        } else if self.date_time_switch.is_closed(self.now, &gate.key).await {
+            Some(ClosureReason::BusinessHours)
+        } else if gate.is_expired(self.now) {
+            Some(ClosureReason::Expired)
+        } else {
+            (gate.state == GateState::Closed).then_some(ClosureReason::Manual)
+        }
+    }
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use std::collections::HashSet;
+    use std::slice;
+
+    use mockall::predicate::eq;
+    use rstest::rstest;
+    use similar_asserts::assert_eq;
+
+    use crate::date_time_switch::MockDateTimeSwitch;
+    use crate::storage::MockStorage;
+    use crate::types::GateDependencies;
+
+    use super::*;
+
+    fn instant(value: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(value)
+            .expect("failed to parse date")
+            .into()
+    }
+
+    fn some_gate(environment: &str, state: GateState) -> Gate {
+        Gate {
+            key: GateKey {
+                group: "some group".to_owned(),
+                service: "some service".to_owned(),
+                environment: environment.to_owned(),
+            },
+            state,
+            comments: HashSet::new(),
+            last_updated: instant("2023-04-12T10:00:00Z"),
+            display_order: None,
+            version: 0,
+            open_until: None,
+        }
+    }
+
+    #[rstest]
+    #[case(GateState::Closed, false, Some(ClosureReason::Dependency))]
+    #[case(GateState::Open, true, Some(ClosureReason::Dependency))]
+    #[case(GateState::Open, false, None)]
+    #[tokio::test]
+    async fn should_close_gate_if_gate_it_depends_on_is_closed(
+        #[case] dependency_state: GateState,
+        #[case] dependency_outside_of_business_hours: bool,
+        #[case] expected: Option<ClosureReason>,
+    ) {
+        // given
+        let now = instant("2023-04-12T12:00:00Z");
+        let gate = some_gate("live", GateState::Open);
+        let dependency = some_gate("staging", dependency_state);
+
+        let mut mock_storage = MockStorage::new();
+        mock_storage.expect_find_freeze().return_once(|| Ok(None));
+        mock_storage
+            .expect_find_freeze_windows()
+            .return_once(|| Ok(vec![]));
+        let dependencies = GateDependencies {
+            key: gate.key.clone(),
+            depends_on: vec![
+                GateKey {
+                    environment: "deleted".to_owned(),
+                    ..gate.key.clone()
+                },
+                dependency.key.clone(),
+            ],
+        };
+        mock_storage
+            .expect_find_gate_dependencies()
+            .return_once(|| Ok(vec![dependencies]));
+        mock_storage
+            .expect_find_one()
+            .with(eq(GateKey {
+                environment: "deleted".to_owned(),
+                ..gate.key.clone()
+            }))
+            .return_once(|_| Ok(None));
+        let stored_dependency = dependency.clone();
+        mock_storage
+            .expect_find_one()
+            .with(eq(dependency.key.clone()))
+            .return_once(|_| Ok(Some(stored_dependency)));
+
+        let mut mock_date_time_switch = MockDateTimeSwitch::new();
+        mock_date_time_switch
+            .expect_is_closed()
+            .with(eq(now), eq(gate.key.clone()))
+            .return_const(false);
+        mock_date_time_switch
+            .expect_is_closed()
+            .with(eq(now), eq(dependency.key.clone()))
+            .return_const(dependency_outside_of_business_hours);
+
+        // when
+        let closures = Closures::at(now, &mock_storage, &mock_date_time_switch)
+            .await
+            .expect("failed to find closures");
+        let dependencies = closures
+            .dependencies_of(slice::from_ref(&gate))
+            .await
+            .expect("failed to find dependencies");
+        let rep = closures.rep(gate, &dependencies).await;
+
+        // then
+        assert_eq!(rep.closure_reason, expected.map(Into::into));
+        assert_eq!(
+            rep.state,
+            expected.map_or(models::GateState::Open, |_| models::GateState::Closed)
+        );
+    }
+
+    #[tokio::test]
+    async fn should_prefer_own_closure_reason_over_dependency() {
+        // given
+        let now = instant("2023-04-12T12:00:00Z");
+        let gate = Gate {
+            open_until: Some(instant("2023-04-12T11:00:00Z")),
+            ..some_gate("live", GateState::Open)
+        };
+
+        let mut mock_storage = MockStorage::new();
+        mock_storage.expect_find_freeze().return_once(|| Ok(None));
+        mock_storage
+            .expect_find_freeze_windows()
+            .return_once(|| Ok(vec![]));
+        let dependencies = GateDependencies {
+            key: gate.key.clone(),
+            depends_on: vec![some_gate("staging", GateState::Closed).key],
+        };
+        mock_storage
+            .expect_find_gate_dependencies()
+            .return_once(|| Ok(vec![dependencies]));
+
+        let mut mock_date_time_switch = MockDateTimeSwitch::new();
+        mock_date_time_switch.expect_is_closed().return_const(false);
+
+        // when
+        let closures = Closures::at(now, &mock_storage, &mock_date_time_switch)
+            .await
+            .expect("failed to find closures");
+        let rep = closures.rep(gate, &HashMap::new()).await;
+
+        // then
+        assert_eq!(rep.closure_reason, Some(models::ClosureReason::Expired));
+        assert_eq!(rep.state, models::GateState::Closed);
+        assert_eq!(rep.open_until, None);
+    }
+
+    #[tokio::test]
+    async fn should_take_dependencies_from_loaded_gates() {
+        // given
+        let now = instant("2023-04-12T12:00:00Z");
+        let gate = some_gate("live", GateState::Open);
+        let dependency = some_gate("staging", GateState::Closed);
+
+        let mut mock_storage = MockStorage::new();
+        mock_storage.expect_find_freeze().return_once(|| Ok(None));
+        mock_storage
+            .expect_find_freeze_windows()
+            .return_once(|| Ok(vec![]));
+        let dependencies = GateDependencies {
+            key: gate.key.clone(),
+            depends_on: vec![dependency.key.clone()],
+        };
+        mock_storage
+            .expect_find_gate_dependencies()
+            .return_once(|| Ok(vec![dependencies]));
+        mock_storage.expect_find_one().never();
+        let mock_date_time_switch = MockDateTimeSwitch::new();
+
+        // when
+        let closures = Closures::at(now, &mock_storage, &mock_date_time_switch)
+            .await
+            .expect("failed to find closures");
+        let result = closures.dependencies_of(&[gate, dependency.clone()]).await;
+
+        // then
+        assert_eq!(
+            result.expect("failed to find dependencies"),
+            HashMap::from([(dependency.key.clone(), dependency)])
+        );
+    }
+}
@@ -1,124 +1,413 @@
-use chrono::{DateTime, Datelike, Utc};
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
 
-use crate::types::GateState::Closed;
-use crate::types::{BusinessWeek, Gate};
+use async_trait::async_trait;
+use chrono::{DateTime, Datelike, Days, NaiveDate, NaiveTime, TimeDelta, TimeZone, Utc};
+use chrono_tz::Tz;
+use openapi::models;
 
-pub struct DefaultDateTimeCircuitBreaker;
+use crate::storage;
+use crate::types::{BusinessWeek, BusinessWeekScope, EnvironmentPatterns, GateKey};
+
+type Storage = dyn storage::Storage + Send + Sync;
+
+// keeps the calendar from being read on every state check
+const CACHE_TIME_TO_LIVE: Duration = Duration::from_secs(30);
+// keeps a failing storage from being asked on every state check, but retries it soon
+const FALLBACK_TIME_TO_LIVE: Duration = Duration::from_secs(5);
+// business hours are closed for good if they do not change within a year
+const MAX_TRANSITION_DAYS: u64 = 366;
+
+pub struct DefaultDateTimeCircuitBreaker {
+    storage: Arc<Storage>,
+    enabled: bool,
+    configured_business_week: BusinessWeek,
+    environments: EnvironmentPatterns,
+    /// The calendar and until when it may be used.
+    cache: RwLock<Option<(Instant, Arc<Calendar>)>>,
+}
+
+struct Calendar {
+    business_week: BusinessWeek,
+    profiles: HashMap<BusinessWeekScope, BusinessWeek>,
+    closed_days: HashSet<NaiveDate>,
+    /// The stored calendar could not be read, so gates are kept closed rather than opened on days
+    /// or at times that may be closed.
+    unavailable: bool,
+}
+
+impl Calendar {
+    /// The business week of the most specific profile containing the gate, otherwise the global one.
+    fn business_week_of(&self, key: &GateKey) -> &BusinessWeek {
+        BusinessWeekScope::of_gate(key)
+            .iter()
+            .find_map(|scope| self.profiles.get(scope))
+            .unwrap_or(&self.business_week)
+    }
+
+    /// Closed days start and end at midnight in the time zone of the business week of the gate.
+    fn is_closed(&self, utc: DateTime<Utc>, key: &GateKey) -> bool {
+        if self.unavailable {
+            return true;
+        }
+        let business_week = self.business_week_of(key);
+        is_closed_day(&self.closed_days, business_week.time_zone, utc)
+            || is_outside_of_business_times(business_week, utc)
+    }
+
+    fn next_transitions(&self, utc: DateTime<Utc>, key: &GateKey) -> Transitions {
+        self.next_transitions_of(self.business_week_of(key), utc)
+    }
+
+    /// Closed days open or close business hours at midnight in addition to business times.
+    fn next_transitions_of(&self, business_week: &BusinessWeek, utc: DateTime<Utc>) -> Transitions {
+        if self.unavailable {
+            return Transitions::default();
+        }
+        let mut candidates = transition_candidates(business_week, utc);
+        candidates.extend(
+            local_dates(utc, business_week.time_zone)
+                .filter_map(|date| to_utc(business_week.time_zone, date, NaiveTime::MIN)),
+        );
+        find_transitions(candidates, utc, |instant| {
+            is_closed_day(&self.closed_days, business_week.time_zone, instant)
+                || is_outside_of_business_times(business_week, instant)
+        })
+    }
+}
+
+/// When business hours next open and close, if they ever do.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Transitions {
+    pub next_open: Option<DateTime<Utc>>,
+    pub next_close: Option<DateTime<Utc>>,
+}
+
+impl From<Transitions> for models::Transitions {
+    fn from(value: Transitions) -> Self {
+        Self {
+            next_open: value.next_open.map(|instant| instant.to_rfc3339()),
+            next_close: value.next_close.map(|instant| instant.to_rfc3339()),
+        }
+    }
+}
+
+/// Business hours only open or close when business times start or end, so these instants
+/// within a year are checked in order.
+fn find_transitions(
+    mut candidates: Vec<DateTime<Utc>>,
+    utc: DateTime<Utc>,
+    is_closed: impl Fn(DateTime<Utc>) -> bool,
+) -> Transitions {
+    candidates.retain(|candidate| *candidate >= utc);
+    candidates.sort_unstable();
+    candidates.dedup();
+
+    let mut transitions = Transitions::default();
+    let mut closed = is_closed(utc);
+    for candidate in candidates {
+        // business times include their end, so the state right after an instant counts
+        let closed_after = is_closed(candidate + TimeDelta::seconds(1));
+        if closed_after != closed {
+            closed = closed_after;
+            if closed {
+                transitions.next_close.get_or_insert(candidate);
+            } else {
+                transitions.next_open.get_or_insert(candidate);
+            }
+        }
+        if transitions.next_open.is_some() && transitions.next_close.is_some() {
+            break;
+        }
+    }
+    transitions
+}
+
+fn transition_candidates(business_week: &BusinessWeek, utc: DateTime<Utc>) -> Vec<DateTime<Utc>> {
+    let time_zone = business_week.time_zone;
+    local_dates(utc, time_zone)
+        .flat_map(|date| {
+            business_week
+                .business_times_by_weekday(date.weekday())
+                .iter()
+                .flat_map(move |business_times| {
+                    let end_date = if business_times.spans_midnight() {
+                        date.succ_opt().unwrap_or(date)
+                    } else {
+                        date
+                    };
+                    [
+                        to_utc(time_zone, date, business_times.start),
+                        to_utc(time_zone, end_date, business_times.end),
+                    ]
+                })
+        })
+        .flatten()
+        .collect()
+}
+
+/// The local dates from the day before the instant on, business times spanning midnight may
+/// have started on it.
+fn local_dates(utc: DateTime<Utc>, time_zone: Tz) -> impl Iterator<Item = NaiveDate> {
+    let today = utc.with_timezone(&time_zone).date_naive();
+    (0..=MAX_TRANSITION_DAYS)
+        .filter_map(move |days| today.pred_opt()?.checked_add_days(Days::new(days)))
+}
+
+fn to_utc(time_zone: Tz, date: NaiveDate, time: NaiveTime) -> Option<DateTime<Utc>> {
+    time_zone
+        .from_local_datetime(&date.and_time(time))
+        .earliest()
+        .map(|local| local.to_utc())
+}
 
 #[cfg_attr(test, mockall::automock)]
+#[async_trait]
 pub trait DateTimeSwitch {
-    fn is_closed(&self, utc: DateTime<Utc>) -> bool;
-    fn close_if_time(&self, utc: DateTime<Utc>, gate: Gate) -> Gate;
+    async fn is_closed(&self, utc: DateTime<Utc>, key: &GateKey) -> bool;
+    /// When business hours and closed days next open and close the gate, unknown if business hours
+    /// do not apply to it.
+    async fn next_transitions(&self, utc: DateTime<Utc>, key: &GateKey) -> Option<Transitions>;
+    /// When the global business week and closed days next open and close gates without a business
+    /// week profile.
+    async fn next_global_transitions(&self, utc: DateTime<Utc>) -> Transitions;
+    /// Whether gates are closed outside of business hours at all.
+    fn is_enabled(&self) -> bool;
+    /// The business week stored at runtime, otherwise the one configured on startup.
+    async fn business_week(&self) -> BusinessWeek;
+    fn clear_cache(&self);
 }
 
+#[async_trait]
 impl DateTimeSwitch for DefaultDateTimeCircuitBreaker {
-    fn is_closed(&self, utc: DateTime<Utc>) -> bool {
-        #[cfg(not(feature = "date_time_switch"))]
-        return false;
-        is_outside_of_business_times(&BusinessWeek::default(), utc)
-    }
-
-    fn close_if_time(&self, utc: DateTime<Utc>, gate: Gate) -> Gate {
-        if self.is_closed(utc) {
-            Gate {
-                key: gate.key,
-                state: Closed,
-                comments: gate.comments,
-                last_updated: gate.last_updated,
-                display_order: gate.display_order,
+    async fn is_closed(&self, utc: DateTime<Utc>, key: &GateKey) -> bool {
+        if !self.enabled || !self.environments.matches(&key.environment) {
+            return false;
+        }
+        self.calendar().await.is_closed(utc, key)
+    }
+
+    async fn next_transitions(&self, utc: DateTime<Utc>, key: &GateKey) -> Option<Transitions> {
+        if !self.enabled || !self.environments.matches(&key.environment) {
+            return None;
+        }
+        Some(self.calendar().await.next_transitions(utc, key))
+    }
+
+    async fn next_global_transitions(&self, utc: DateTime<Utc>) -> Transitions {
+        let calendar = self.calendar().await;
+        calendar.next_transitions_of(&calendar.business_week, utc)
+    }
+
+    fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    async fn business_week(&self) -> BusinessWeek {
+        self.calendar().await.business_week.clone()
+    }
+
+    fn clear_cache(&self) {
+        if let Ok(mut cache) = self.cache.write() {
+            *cache = None;
+        }
+    }
+}
+
+impl DefaultDateTimeCircuitBreaker {
+    async fn calendar(&self) -> Arc<Calendar> {
+        if let Some(calendar) = self.cached_calendar() {
+            return calendar;
+        }
+        let (calendar, time_to_live) = match self.find_calendar().await {
+            Ok(calendar) => (calendar, CACHE_TIME_TO_LIVE),
+            Err(error) => {
+                tracing::error!("could not find calendar, keeping gates closed: {error:?}");
+                (
+                    Calendar {
+                        business_week: self.configured_business_week.clone(),
+                        profiles: HashMap::new(),
+                        closed_days: HashSet::new(),
+                        unavailable: true,
+                    },
+                    FALLBACK_TIME_TO_LIVE,
+                )
             }
-        } else {
-            gate
+        };
+        let calendar = Arc::new(calendar);
+        if let Ok(mut cache) = self.cache.write() {
+            *cache = Some((Instant::now() + time_to_live, calendar.clone()));
         }
+        calendar
+    }
+
+    fn cached_calendar(&self) -> Option<Arc<Calendar>> {
+        self.cache
+            .read()
+            .ok()?
+            .as_ref()
+            .filter(|(expires, _)| Instant::now() < *expires)
+            .map(|(_, calendar)| calendar.clone())
+    }
+
+    async fn find_calendar(&self) -> Result<Calendar, storage::FindError> {
+        Ok(Calendar {
+            business_week: self
+                .storage
+                .find_business_week()
+                .await?
+                .unwrap_or_else(|| self.configured_business_week.clone()),
+            profiles: self
+                .storage
+                .find_business_week_profiles()
+                .await?
+                .into_iter()
+                .map(|profile| (profile.scope, profile.business_week))
+                .collect(),
+            closed_days: self
+                .storage
+                .find_closed_days()
+                .await?
+                .into_iter()
+                .map(|closed_day| closed_day.date)
+                .collect(),
+            unavailable: false,
+        })
     }
 }
 
+fn is_closed_day(
+    closed_days: &HashSet<NaiveDate>,
+    time_zone: Tz,
+    time_to_check: DateTime<Utc>,
+) -> bool {
+    closed_days.contains(&time_to_check.with_timezone(&time_zone).date_naive())
+}
+
 fn is_outside_of_business_times(
     business_week: &BusinessWeek,
     time_to_check: DateTime<Utc>,
 ) -> bool {
     business_week
-        .business_times_by_weekday(time_to_check.weekday())
-        .as_ref()
-        .is_none_or(|hours| hours.is_outside_of_business_times(time_to_check))
+        .is_outside_of_business_times(&time_to_check.with_timezone(&business_week.time_zone))
 }
 
+pub fn new(
+    storage: Arc<Storage>,
+    enabled: bool,
+    configured_business_week: BusinessWeek,
+    environments: EnvironmentPatterns,
+) -> impl DateTimeSwitch {
+    DefaultDateTimeCircuitBreaker {
+        storage,
+        enabled,
+        configured_business_week,
+        environments,
+        cache: RwLock::default(),
+    }
+}
+
+#[cfg(test)]
 pub fn default() -> impl DateTimeSwitch {
-    DefaultDateTimeCircuitBreaker {}
+    new(
+        Arc::new(storage::in_memory()),
+        true,
+        BusinessWeek::default(),
+        EnvironmentPatterns::default(),
+    )
 }
 
 #[cfg(test)]
 mod unit_test {
-    use std::collections::HashSet;
     use std::str::FromStr;
+    use std::sync::Arc;
 
     //
-    use chrono::{DateTime, NaiveTime};
+    use chrono::{DateTime, NaiveDate, NaiveTime, Utc};
     use rstest::rstest;
 
     use crate::date_time_switch;
-    use crate::date_time_switch::DateTimeSwitch;
-    use crate::types::GateState::{Closed, Open};
-    use crate::types::{BusinessTimes, BusinessWeek, Gate, GateKey};
+    use crate::date_time_switch::{is_outside_of_business_times, DateTimeSwitch, Transitions};
+    use crate::storage;
+    use crate::storage::{FindError, MockStorage, Storage};
+    use crate::types::{
+        BusinessTimes, BusinessWeek, BusinessWeekProfile, BusinessWeekScope, ClosedDay,
+        EnvironmentPatterns, GateKey,
+    };
+
+    fn business_times(start: &str, end: &str) -> BusinessTimes {
+        BusinessTimes {
+            start: NaiveTime::from_str(start).unwrap(),
+            end: NaiveTime::from_str(end).unwrap(),
+        }
+    }
+
+    fn some_key() -> GateKey {
+        GateKey {
+            group: "some group".to_owned(),
+            service: "some service".to_owned(),
+            environment: "some environment".to_owned(),
+        }
+    }
 
-    // TODO use this test configuration
-    #[allow(dead_code)]
     fn get_test_configuration() -> BusinessWeek {
         BusinessWeek {
-            monday: Some(BusinessTimes {
+            monday: vec![BusinessTimes {
                 start: NaiveTime::from_str("07:00:00").unwrap(),
                 end: NaiveTime::from_str("18:30:00").unwrap(),
-            }),
-            tuesday: None,
-            wednesday: None,
-            thursday: None,
-            friday: None,
-            saturday: None,
-            sunday: None,
+            }],
+            tuesday: vec![],
+            wednesday: vec![],
+            thursday: vec![],
+            friday: vec![],
+            saturday: vec![],
+            sunday: vec![],
+            time_zone: chrono_tz::Europe::Berlin,
         }
     }
 
     #[rstest]
-    #[test]
-    fn should_be_open_during_business_times(#[values("08", "11", "18")] hour: &str) {
+    #[tokio::test]
+    async fn should_be_open_during_business_times(#[values("08", "11", "18")] hour: &str) {
         // given
-        let monday = DateTime::parse_from_rfc3339(&format!("2023-06-05T{hour}:00:00+00:00"))
+        let monday = DateTime::parse_from_rfc3339(&format!("2023-06-05T{hour}:00:00+02:00"))
             .expect("failed to parse date");
         let expected = false;
         let switch = date_time_switch::default();
 
         // when
-        let actual = switch.is_closed(DateTime::from(monday));
+        let actual = switch.is_closed(DateTime::from(monday), &some_key()).await;
 
         // then
         assert_eq!(expected, actual);
     }
 
     #[rstest]
-    #[test]
-    fn should_be_closed_outside_of_business_times(#[values("06", "20")] hour: &str) {
+    #[tokio::test]
+    async fn should_be_closed_outside_of_business_times(#[values("06", "20")] hour: &str) {
         // given
-        let monday = DateTime::parse_from_rfc3339(&format!("2023-06-05T{hour}:00:00+00:00"))
+        let monday = DateTime::parse_from_rfc3339(&format!("2023-06-05T{hour}:00:00+02:00"))
             .expect("failed to parse date");
         let expected = true;
         let switch = date_time_switch::default();
 
         // when
-        let actual = switch.is_closed(DateTime::from(monday));
+        let actual = switch.is_closed(DateTime::from(monday), &some_key()).await;
 
         // then
         assert_eq!(expected, actual);
     }
 
-    #[test]
-    fn should_be_closed_on_a_day_without_configured_business_times() {
+    #[tokio::test]
+    async fn should_be_closed_on_a_day_without_configured_business_times() {
         // given
-        let sunday = DateTime::parse_from_rfc3339("2023-06-04T13:59:59+00:00")
+        let sunday = DateTime::parse_from_rfc3339("2023-06-04T13:59:59+02:00")
             .expect("failed to parse date");
         let switch = date_time_switch::default();
 
         // when
-        let closed = switch.is_closed(DateTime::from(sunday));
+        let closed = switch.is_closed(DateTime::from(sunday), &some_key()).await;
 
         // then
         assert!(closed);
@@ -128,20 +417,20 @@ mod unit_test {
     #[case("06:59", true, "should be closed right before start business times")]
     #[case("07:00", false, "should be open at the start of business times")]
     #[case("07:01", false, "should be open a second into business times")]
-    #[test]
-    fn should_be_open_at_start(
+    #[tokio::test]
+    async fn should_be_open_at_start(
         #[case] hour_and_minute: &str,
         #[case] expected: bool,
         #[case] msg: String,
     ) {
         // given
         let monday =
-            DateTime::parse_from_rfc3339(&format!("2023-06-05T{hour_and_minute}:00+00:00"))
+            DateTime::parse_from_rfc3339(&format!("2023-06-05T{hour_and_minute}:00+02:00"))
                 .expect("failed to parse date");
         let switch = date_time_switch::default();
 
         // when
-        let actual = switch.is_closed(DateTime::from(monday));
+        let actual = switch.is_closed(DateTime::from(monday), &some_key()).await;
 
         // then
         assert_eq!(expected, actual, "{msg}");
@@ -151,51 +440,816 @@ mod unit_test {
     #[case("18:29", false, "should be open right before end of business times")]
     #[case("18:30", false, "should be open at the end of business times")]
     #[case("18:31", true, "should be closed a second after business times")]
-    #[test]
-    fn should_be_closed_at_end(
+    #[tokio::test]
+    async fn should_be_closed_at_end(
         #[case] hour_and_minute: &str,
         #[case] expected: bool,
         #[case] msg: String,
     ) {
         // given
         let monday =
-            DateTime::parse_from_rfc3339(&format!("2023-06-05T{hour_and_minute}:00+00:00"))
+            DateTime::parse_from_rfc3339(&format!("2023-06-05T{hour_and_minute}:00+02:00"))
                 .expect("failed to parse date");
         let switch = date_time_switch::default();
 
         // when
-        let actual = switch.is_closed(DateTime::from(monday));
+        let actual = switch.is_closed(DateTime::from(monday), &some_key()).await;
+
+        // then
+        assert_eq!(expected, actual, "{msg}");
+    }
+
+    #[rstest]
+    #[case(
+        "2023-03-20T05:30:00Z",
+        true,
+        "should be closed at 06:30 in winter time"
+    )]
+    #[case(
+        "2023-03-27T05:30:00Z",
+        false,
+        "should be open at 07:30 in summer time"
+    )]
+    #[case(
+        "2023-10-23T05:30:00Z",
+        false,
+        "should be open at 07:30 in summer time"
+    )]
+    #[case(
+        "2023-10-30T05:30:00Z",
+        true,
+        "should be closed at 06:30 in winter time"
+    )]
+    #[case(
+        "2023-10-30T06:30:00Z",
+        false,
+        "should be open at 07:30 in winter time"
+    )]
+    #[test]
+    fn should_follow_daylight_saving_time_transitions(
+        #[case] utc: &str,
+        #[case] expected: bool,
+        #[case] msg: String,
+    ) {
+        // given
+        let monday = DateTime::parse_from_rfc3339(utc).expect("failed to parse date");
+        let business_week = get_test_configuration();
+
+        // when
+        let actual = is_outside_of_business_times(&business_week, monday.into());
+
+        // then
+        assert_eq!(expected, actual, "{msg}");
+    }
+
+    #[test]
+    fn should_use_weekday_of_time_zone() {
+        // given
+        let sunday_in_utc =
+            DateTime::parse_from_rfc3339("2023-06-04T20:00:00Z").expect("failed to parse date");
+        let business_week = BusinessWeek {
+            time_zone: chrono_tz::Pacific::Auckland,
+            ..get_test_configuration()
+        };
+
+        // when
+        let actual = is_outside_of_business_times(&business_week, sunday_in_utc.into());
+
+        // then
+        assert!(!actual, "should be open at 08:00 on monday in Auckland");
+    }
+
+    #[rstest]
+    #[case("2023-06-05T09:00:00+02:00", false, "should be open in the morning")]
+    #[case("2023-06-05T12:30:00+02:00", true, "should be closed over lunch")]
+    #[case("2023-06-05T13:00:00+02:00", false, "should be open in the afternoon")]
+    #[case("2023-06-05T17:30:00+02:00", true, "should be closed in the evening")]
+    #[test]
+    fn should_be_open_during_any_business_times_of_a_day(
+        #[case] monday: &str,
+        #[case] expected: bool,
+        #[case] msg: String,
+    ) {
+        // given
+        let monday = DateTime::parse_from_rfc3339(monday).expect("failed to parse date");
+        let business_week = BusinessWeek {
+            monday: vec![
+                business_times("08:00:00", "12:00:00"),
+                business_times("13:00:00", "17:00:00"),
+            ],
+            ..get_test_configuration()
+        };
+
+        // when
+        let actual = is_outside_of_business_times(&business_week, monday.into());
 
         // then
         assert_eq!(expected, actual, "{msg}");
     }
 
+    #[rstest]
+    #[case(
+        "2023-06-05T21:59:00+02:00",
+        true,
+        "should be closed before start on monday"
+    )]
+    #[case(
+        "2023-06-05T22:00:00+02:00",
+        false,
+        "should be open at start on monday"
+    )]
+    #[case("2023-06-06T00:00:00+02:00", false, "should be open at midnight")]
+    #[case("2023-06-06T02:00:00+02:00", false, "should be open at end on tuesday")]
+    #[case(
+        "2023-06-06T02:01:00+02:00",
+        true,
+        "should be closed after end on tuesday"
+    )]
+    #[case("2023-06-06T23:00:00+02:00", true, "should be closed on tuesday night")]
+    #[case(
+        "2023-06-05T01:00:00+02:00",
+        false,
+        "should be open after sunday night"
+    )]
     #[test]
-    fn should_return_closed_gate() {
+    fn should_be_open_during_business_times_spanning_midnight(
+        #[case] utc: &str,
+        #[case] expected: bool,
+        #[case] msg: String,
+    ) {
+        // given
+        let date_time = DateTime::parse_from_rfc3339(utc).expect("failed to parse date");
+        let business_week = BusinessWeek {
+            monday: vec![business_times("22:00:00", "02:00:00")],
+            sunday: vec![business_times("23:00:00", "01:00:00")],
+            ..get_test_configuration()
+        };
+
+        // when
+        let actual = is_outside_of_business_times(&business_week, date_time.into());
+
+        // then
+        assert_eq!(expected, actual, "{msg}");
+    }
+
+    #[tokio::test]
+    async fn should_use_configured_business_week_if_none_is_stored() {
+        // given
+        let tuesday = DateTime::parse_from_rfc3339("2023-06-06T13:00:00+02:00")
+            .expect("failed to parse date");
+        let switch = date_time_switch::new(
+            Arc::new(storage::in_memory()),
+            true,
+            get_test_configuration(),
+            EnvironmentPatterns::default(),
+        );
+
+        // when
+        let closed = switch.is_closed(DateTime::from(tuesday), &some_key()).await;
+
+        // then
+        assert!(closed);
+        assert_eq!(switch.business_week().await, get_test_configuration());
+    }
+
+    #[tokio::test]
+    async fn should_use_stored_business_week() {
+        // given
+        let storage = Arc::new(storage::in_memory());
+        storage
+            .save_business_week(&get_test_configuration())
+            .await
+            .expect("storage failed to save business week");
+        let switch = date_time_switch::new(
+            storage,
+            true,
+            BusinessWeek::default(),
+            EnvironmentPatterns::default(),
+        );
+
+        // when
+        let actual = switch.business_week().await;
+
+        // then
+        assert_eq!(actual, get_test_configuration());
+    }
+
+    #[tokio::test]
+    async fn should_cache_stored_business_week_until_cleared() {
+        // given
+        let mut mock_storage = MockStorage::new();
+        mock_storage
+            .expect_find_business_week()
+            .times(2)
+            .returning(|| Ok(Some(get_test_configuration())));
+        mock_storage
+            .expect_find_business_week_profiles()
+            .times(2)
+            .returning(|| Ok(vec![]));
+        mock_storage
+            .expect_find_closed_days()
+            .times(2)
+            .returning(|| Ok(vec![]));
+        let switch = date_time_switch::new(
+            Arc::new(mock_storage),
+            true,
+            BusinessWeek::default(),
+            EnvironmentPatterns::default(),
+        );
+
+        // when
+        switch.business_week().await;
+        switch.business_week().await;
+        switch.clear_cache();
+        let actual = switch.business_week().await;
+
+        // then
+        assert_eq!(actual, get_test_configuration());
+    }
+
+    #[tokio::test]
+    async fn should_use_configured_business_week_if_storage_fails() {
+        // given
+        let mut mock_storage = MockStorage::new();
+        mock_storage
+            .expect_find_business_week()
+            .returning(|| Err(FindError::Other("some error".to_owned())));
+        let switch = date_time_switch::new(
+            Arc::new(mock_storage),
+            true,
+            get_test_configuration(),
+            EnvironmentPatterns::default(),
+        );
+
+        // when
+        let actual = switch.business_week().await;
+
+        // then
+        assert_eq!(actual, get_test_configuration());
+    }
+
+    #[tokio::test]
+    async fn should_keep_gates_closed_for_a_while_if_storage_fails() {
+        // given
+        let monday = DateTime::parse_from_rfc3339("2023-06-05T11:00:00+02:00")
+            .expect("failed to parse date");
+        let mut mock_storage = MockStorage::new();
+        mock_storage
+            .expect_find_business_week()
+            .times(1)
+            .returning(|| Err(FindError::Other("some error".to_owned())));
+        let switch = date_time_switch::new(
+            Arc::new(mock_storage),
+            true,
+            get_test_configuration(),
+            EnvironmentPatterns::default(),
+        );
+
+        // when
+        let closed = switch.is_closed(DateTime::from(monday), &some_key()).await;
+        let transitions = switch
+            .next_transitions(DateTime::from(monday), &some_key())
+            .await;
+
+        // then
+        assert!(closed);
+        assert_eq!(transitions, Some(Transitions::default()));
+    }
+
+    #[rstest]
+    #[case(
+        "some service",
+        "some environment",
+        false,
+        "should use profile of gate"
+    )]
+    #[case(
+        "some service",
+        "other environment",
+        true,
+        "should use profile of service"
+    )]
+    #[case(
+        "other service",
+        "some environment",
+        false,
+        "should use profile of group"
+    )]
+    #[tokio::test]
+    async fn should_use_most_specific_business_week_profile(
+        #[case] service: &str,
+        #[case] environment: &str,
+        #[case] expected: bool,
+        #[case] msg: String,
+    ) {
+        // given
+        let storage = Arc::new(storage::in_memory());
+        for (service, environment, monday) in [
+            (None, None, business_times("12:00:00", "14:00:00")),
+            (
+                Some("some service"),
+                None,
+                business_times("07:00:00", "08:00:00"),
+            ),
+            (
+                Some("some service"),
+                Some("some environment"),
+                business_times("07:00:00", "20:00:00"),
+            ),
+        ] {
+            storage
+                .save_business_week_profile(&BusinessWeekProfile {
+                    scope: BusinessWeekScope::new(
+                        "some group".to_owned(),
+                        service.map(str::to_owned),
+                        environment.map(str::to_owned),
+                    )
+                    .unwrap(),
+                    business_week: BusinessWeek {
+                        monday: vec![monday],
+                        ..get_test_configuration()
+                    },
+                })
+                .await
+                .expect("storage failed to save business week profile");
+        }
+        let switch = date_time_switch::new(
+            storage,
+            true,
+            BusinessWeek::default(),
+            EnvironmentPatterns::default(),
+        );
+        let monday = DateTime::parse_from_rfc3339("2023-06-05T13:00:00+02:00")
+            .expect("failed to parse date");
+        let key = GateKey {
+            group: "some group".to_owned(),
+            service: service.to_owned(),
+            environment: environment.to_owned(),
+        };
+
+        // when
+        let actual = switch.is_closed(DateTime::from(monday), &key).await;
+
+        // then
+        assert_eq!(expected, actual, "{msg}");
+    }
+
+    #[tokio::test]
+    async fn should_use_global_business_week_for_gates_without_profile() {
+        // given
+        let storage = Arc::new(storage::in_memory());
+        storage
+            .save_business_week_profile(&BusinessWeekProfile {
+                scope: BusinessWeekScope::new("other group".to_owned(), None, None).unwrap(),
+                business_week: get_test_configuration(),
+            })
+            .await
+            .expect("storage failed to save business week profile");
+        let switch = date_time_switch::new(
+            storage,
+            true,
+            BusinessWeek::default(),
+            EnvironmentPatterns::default(),
+        );
+        let tuesday = DateTime::parse_from_rfc3339("2023-06-06T13:00:00+02:00")
+            .expect("failed to parse date");
+
+        // when
+        let actual = switch.is_closed(DateTime::from(tuesday), &some_key()).await;
+
+        // then
+        assert!(!actual);
+    }
+
+    #[rstest]
+    #[case("live", true)]
+    #[case("prod-eu", true)]
+    #[case("develop", false)]
+    #[tokio::test]
+    async fn should_only_close_gates_of_configured_environments(
+        #[case] environment: &str,
+        #[case] expected: bool,
+    ) {
         // given
-        let sunday = DateTime::parse_from_rfc3339("2023-06-04T13:59:59+00:00")
+        let sunday = DateTime::parse_from_rfc3339("2023-06-04T13:59:59+02:00")
             .expect("failed to parse date");
+        let switch = date_time_switch::new(
+            Arc::new(storage::in_memory()),
+            true,
+            BusinessWeek::default(),
+            EnvironmentPatterns::new(vec!["live".to_owned(), "prod*".to_owned()]).unwrap(),
+        );
+        let key = GateKey {
+            environment: environment.to_owned(),
+            ..some_key()
+        };
+
+        // when
+        let actual = switch.is_closed(DateTime::from(sunday), &key).await;
+
+        // then
+        assert_eq!(expected, actual);
+    }
+
+    #[tokio::test]
+    async fn should_never_be_closed_if_disabled() {
+        // given
+        let sunday = DateTime::parse_from_rfc3339("2023-06-04T13:59:59+02:00")
+            .expect("failed to parse date");
+        let switch = date_time_switch::new(
+            Arc::new(storage::in_memory()),
+            false,
+            BusinessWeek::default(),
+            EnvironmentPatterns::default(),
+        );
+
+        // when
+        let actual = switch.is_closed(DateTime::from(sunday), &some_key()).await;
+
+        // then
+        assert!(!actual);
+        assert!(!switch.is_enabled());
+        assert_eq!(
+            switch
+                .next_transitions(DateTime::from(sunday), &some_key())
+                .await,
+            None
+        );
+    }
+
+    fn transitions(next_open: &str, next_close: &str) -> Transitions {
+        let parse = |instant| {
+            DateTime::parse_from_rfc3339(instant)
+                .expect("failed to parse date")
+                .to_utc()
+        };
+        Transitions {
+            next_open: Some(parse(next_open)),
+            next_close: Some(parse(next_close)),
+        }
+    }
+
+    #[rstest]
+    #[case(
+        "2023-06-05T13:00:00+02:00",
+        transitions("2023-06-06T08:00:00+02:00", "2023-06-05T18:30:00+02:00"),
+        "should close at end of business times"
+    )]
+    #[case(
+        "2023-06-05T20:00:00+02:00",
+        transitions("2023-06-06T08:00:00+02:00", "2023-06-06T18:00:00+02:00"),
+        "should open at start of next business times"
+    )]
+    #[case(
+        "2023-06-09T17:00:00+02:00",
+        transitions("2023-06-12T07:00:00+02:00", "2023-06-12T18:30:00+02:00"),
+        "should open after days without business times"
+    )]
+    #[case(
+        "2023-03-24T17:00:00+01:00",
+        transitions("2023-03-27T07:00:00+02:00", "2023-03-27T18:30:00+02:00"),
+        "should open in summer time after daylight saving time transition"
+    )]
+    #[case(
+        "2023-06-05T07:00:00+02:00",
+        transitions("2023-06-06T08:00:00+02:00", "2023-06-05T18:30:00+02:00"),
+        "should close at end if open at start"
+    )]
+    #[tokio::test]
+    async fn should_find_next_transitions(
+        #[case] utc: &str,
+        #[case] expected: Transitions,
+        #[case] msg: String,
+    ) {
+        // given
+        let instant = DateTime::parse_from_rfc3339(utc).expect("failed to parse date");
         let switch = date_time_switch::default();
-        assert!(switch.is_closed(DateTime::from(sunday)));
 
         // when
-        let actual = switch.close_if_time(
-            sunday.into(),
-            Gate {
-                key: GateKey {
-                    group: "unused".to_string(),
-                    service: "unused".to_string(),
-                    environment: "unused".to_string(),
+        let actual = switch
+            .next_transitions(DateTime::from(instant), &some_key())
+            .await;
+
+        // then
+        assert_eq!(actual, Some(expected), "{msg}");
+    }
+
+    #[rstest]
+    #[case(
+        "2023-06-05T23:00:00+02:00",
+        transitions("2023-06-12T22:00:00+02:00", "2023-06-06T02:00:00+02:00"),
+        "should close after midnight"
+    )]
+    #[case(
+        "2023-06-06T03:00:00+02:00",
+        transitions("2023-06-12T22:00:00+02:00", "2023-06-13T02:00:00+02:00"),
+        "should open a week later"
+    )]
+    #[tokio::test]
+    async fn should_find_next_transitions_of_business_times_spanning_midnight(
+        #[case] utc: &str,
+        #[case] expected: Transitions,
+        #[case] msg: String,
+    ) {
+        // given
+        let storage = Arc::new(storage::in_memory());
+        storage
+            .save_business_week(&BusinessWeek {
+                monday: vec![business_times("22:00:00", "02:00:00")],
+                ..get_test_configuration()
+            })
+            .await
+            .expect("storage failed to save business week");
+        let switch = date_time_switch::new(
+            storage,
+            true,
+            BusinessWeek::default(),
+            EnvironmentPatterns::default(),
+        );
+        let instant = DateTime::parse_from_rfc3339(utc).expect("failed to parse date");
+
+        // when
+        let actual = switch
+            .next_transitions(DateTime::from(instant), &some_key())
+            .await;
+
+        // then
+        assert_eq!(actual, Some(expected), "{msg}");
+    }
+
+    #[tokio::test]
+    async fn should_find_next_transitions_after_closed_days() {
+        // given
+        let storage = Arc::new(storage::in_memory());
+        for day in [5, 6] {
+            storage
+                .save_closed_day(&ClosedDay {
+                    date: NaiveDate::from_ymd_opt(2023, 6, day).unwrap(),
+                    reason: "some holiday".to_owned(),
+                })
+                .await
+                .expect("storage failed to save closed day");
+        }
+        let switch = date_time_switch::new(
+            storage,
+            true,
+            BusinessWeek::default(),
+            EnvironmentPatterns::default(),
+        );
+        let friday = DateTime::parse_from_rfc3339("2023-06-02T12:00:00+02:00")
+            .expect("failed to parse date");
+        let monday = DateTime::parse_from_rfc3339("2023-06-05T12:00:00+02:00")
+            .expect("failed to parse date");
+
+        // when
+        let on_friday = switch
+            .next_transitions(DateTime::from(friday), &some_key())
+            .await;
+        let on_monday = switch
+            .next_transitions(DateTime::from(monday), &some_key())
+            .await;
+
+        // then
+        assert_eq!(
+            on_friday,
+            Some(transitions(
+                "2023-06-07T08:00:00+02:00",
+                "2023-06-02T16:00:00+02:00"
+            ))
+        );
+        assert_eq!(
+            on_monday,
+            Some(transitions(
+                "2023-06-07T08:00:00+02:00",
+                "2023-06-07T17:00:00+02:00"
+            ))
+        );
+    }
+
+    #[tokio::test]
+    async fn should_not_find_next_transitions_without_business_times() {
+        // given
+        let storage = Arc::new(storage::in_memory());
+        storage
+            .save_business_week(&BusinessWeek {
+                monday: vec![],
+                ..get_test_configuration()
+            })
+            .await
+            .expect("storage failed to save business week");
+        let switch = date_time_switch::new(
+            storage,
+            true,
+            BusinessWeek::default(),
+            EnvironmentPatterns::default(),
+        );
+        let monday = DateTime::parse_from_rfc3339("2023-06-05T12:00:00+02:00")
+            .expect("failed to parse date");
+
+        // when
+        let actual = switch
+            .next_transitions(DateTime::from(monday), &some_key())
+            .await;
+
+        // then
+        assert_eq!(actual, Some(Transitions::default()));
+    }
+
+    #[rstest]
+    #[case(
+        "2023-04-12T20:10:57Z",
+        transitions("2023-04-19T09:00:00+12:00", "2023-04-19T17:00:00+12:00"),
+        "should skip weekdays without business times"
+    )]
+    #[case(
+        "2023-04-18T22:00:00Z",
+        transitions("2023-04-26T09:00:00+12:00", "2023-04-19T17:00:00+12:00"),
+        "should open a week later if open"
+    )]
+    #[case(
+        "2023-09-21T00:00:00Z",
+        transitions("2023-09-27T09:00:00+13:00", "2023-09-27T17:00:00+13:00"),
+        "should open in daylight saving time of the time zone"
+    )]
+    #[tokio::test]
+    async fn should_find_next_global_transitions_in_time_zone(
+        #[case] utc: &str,
+        #[case] expected: Transitions,
+        #[case] msg: String,
+    ) {
+        // given
+        let switch = date_time_switch::new(
+            Arc::new(storage::in_memory()),
+            true,
+            BusinessWeek {
+                monday: vec![],
+                wednesday: vec![business_times("09:00:00", "17:00:00")],
+                time_zone: chrono_tz::Pacific::Auckland,
+                ..get_test_configuration()
+            },
+            EnvironmentPatterns::default(),
+        );
+        let instant = DateTime::parse_from_rfc3339(utc).expect("failed to parse date");
+
+        // when
+        let actual = switch.next_global_transitions(instant.to_utc()).await;
+
+        // then
+        assert_eq!(actual, expected, "{msg}");
+    }
+
+    #[tokio::test]
+    async fn should_find_next_global_transitions_after_closed_days() {
+        // given
+        let storage = Arc::new(storage::in_memory());
+        storage
+            .save_business_week_profile(&BusinessWeekProfile {
+                scope: BusinessWeekScope::new("some group".to_owned(), None, None).unwrap(),
+                business_week: BusinessWeek {
+                    time_zone: chrono_tz::UTC,
+                    ..BusinessWeek::default()
                 },
-                state: Open,
-                comments: HashSet::default(),
-                last_updated: DateTime::default(),
-                display_order: Option::default(),
+            })
+            .await
+            .expect("storage failed to save business week profile");
+        storage
+            .save_closed_day(&ClosedDay {
+                date: NaiveDate::from_ymd_opt(2023, 6, 5).unwrap(),
+                reason: "some holiday".to_owned(),
+            })
+            .await
+            .expect("storage failed to save closed day");
+        let switch = date_time_switch::new(
+            storage,
+            true,
+            BusinessWeek::default(),
+            EnvironmentPatterns::default(),
+        );
+        let friday = DateTime::parse_from_rfc3339("2023-06-02T12:00:00+02:00")
+            .expect("failed to parse date");
+
+        // when
+        let actual = switch.next_global_transitions(DateTime::from(friday)).await;
+
+        // then
+        assert_eq!(
+            actual,
+            transitions("2023-06-06T08:00:00+02:00", "2023-06-02T16:00:00+02:00")
+        );
+    }
+
+    #[tokio::test]
+    async fn should_not_find_next_global_transitions_without_business_times() {
+        // given
+        let switch = date_time_switch::new(
+            Arc::new(storage::in_memory()),
+            true,
+            BusinessWeek {
+                monday: vec![],
+                ..get_test_configuration()
             },
+            EnvironmentPatterns::default(),
+        );
+
+        // when
+        let actual = switch.next_global_transitions(Utc::now()).await;
+
+        // then
+        assert_eq!(actual, Transitions::default());
+    }
+
+    #[tokio::test]
+    async fn should_be_closed_on_closed_day() {
+        // given
+        let storage = Arc::new(storage::in_memory());
+        storage
+            .save_closed_day(&ClosedDay {
+                date: NaiveDate::from_ymd_opt(2023, 6, 5).unwrap(),
+                reason: "some holiday".to_owned(),
+            })
+            .await
+            .expect("storage failed to save closed day");
+        let switch = date_time_switch::new(
+            storage,
+            true,
+            BusinessWeek::default(),
+            EnvironmentPatterns::default(),
         );
+        let monday = DateTime::parse_from_rfc3339("2023-06-05T13:00:00+02:00")
+            .expect("failed to parse date");
+        let next_monday = DateTime::parse_from_rfc3339("2023-06-12T13:00:00+02:00")
+            .expect("failed to parse date");
+
+        // when
+        let closed = switch.is_closed(DateTime::from(monday), &some_key()).await;
+        let closed_next_week = switch
+            .is_closed(DateTime::from(next_monday), &some_key())
+            .await;
 
         // then
+        assert!(closed);
+        assert!(!closed_next_week);
+    }
 
-        assert_eq!(actual.state, Closed);
+    #[rstest]
+    #[case("2023-06-06T02:00:00Z", true)] // monday evening in New York
+    #[case("2023-06-06T05:00:00Z", false)] // tuesday night in New York
+    #[tokio::test]
+    async fn should_be_closed_on_closed_day_in_time_zone_of_business_week_profile(
+        #[case] instant: &str,
+        #[case] expected: bool,
+    ) {
+        // given
+        let storage = Arc::new(storage::in_memory());
+        storage
+            .save_closed_day(&ClosedDay {
+                date: NaiveDate::from_ymd_opt(2023, 6, 5).unwrap(),
+                reason: "some holiday".to_owned(),
+            })
+            .await
+            .expect("storage failed to save closed day");
+        storage
+            .save_business_week_profile(&BusinessWeekProfile {
+                scope: BusinessWeekScope::new("some group".to_owned(), None, None).unwrap(),
+                business_week: BusinessWeek {
+                    monday: vec![business_times("00:00:00", "23:00:00")],
+                    tuesday: vec![business_times("00:00:00", "23:00:00")],
+                    time_zone: chrono_tz::America::New_York,
+                    ..get_test_configuration()
+                },
+            })
+            .await
+            .expect("storage failed to save business week profile");
+        let switch = date_time_switch::new(
+            storage,
+            true,
+            BusinessWeek::default(),
+            EnvironmentPatterns::default(),
+        );
+        let instant = DateTime::parse_from_rfc3339(instant)
+            .expect("failed to parse date")
+            .to_utc();
+
+        // when
+        let closed = switch.is_closed(instant, &some_key()).await;
+        let transitions = switch
+            .next_transitions(
+                DateTime::parse_from_rfc3339("2023-06-05T12:00:00Z")
+                    .expect("failed to parse date")
+                    .to_utc(),
+                &some_key(),
+            )
+            .await;
+
+        // then
+        assert_eq!(closed, expected);
+        assert_eq!(
+            transitions.and_then(|transitions| transitions.next_open),
+            Some(
+                DateTime::parse_from_rfc3339("2023-06-06T04:00:00Z")
+                    .expect("failed to parse date")
+                    .to_utc()
+            )
+        );
     }
 }
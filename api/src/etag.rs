@@ -0,0 +1,109 @@
+use axum::http::header::IF_MATCH;
+use axum::http::HeaderMap;
+
+pub fn from_version(version: i64) -> String {
+    format!("\"{version}\"")
+}
+
+pub fn if_match(headers: &HeaderMap) -> Result<Option<u32>, String> {
+    let Some(value) = headers.get(IF_MATCH) else {
+        return Ok(None);
+    };
+
+    match value.to_str().map(str::trim) {
+        Ok("*") => Ok(None),
+        Ok(value) => value
+            .strip_prefix('"')
+            .and_then(|value| value.strip_suffix('"'))
+            .and_then(|version| version.parse().ok())
+            .map(Some)
+            .ok_or_else(|| format!("invalid If-Match header {value}, expected a single gate ETag")),
+        Err(_) => Err("invalid If-Match header, expected a single gate ETag".to_owned()),
+    }
+}
+
+/// The version a change is based on, given by the `If-Match` header or the `expected_version` of
+/// the request body, which must not contradict each other.
+pub fn expected_version(
+    headers: &HeaderMap,
+    from_body: Option<u32>,
+) -> Result<Option<u32>, String> {
+    match (if_match(headers)?, from_body) {
+        (Some(from_header), Some(from_body)) if from_header != from_body => Err(format!(
+            "If-Match header \"{from_header}\" contradicts expected_version {from_body}"
+        )),
+        (from_header, from_body) => Ok(from_header.or(from_body)),
+    }
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use axum::http::HeaderValue;
+    use rstest::rstest;
+    use similar_asserts::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn should_format_version_as_etag() {
+        assert_eq!(from_version(3), "\"3\"");
+    }
+
+    #[rstest]
+    #[case("\"3\"", Ok(Some(3)))]
+    #[case(" \"0\" ", Ok(Some(0)))]
+    #[case("*", Ok(None))]
+    #[case("W/\"3\"", Err("invalid If-Match header W/\"3\", expected a single gate ETag".to_owned()))]
+    #[case("\"1\", \"2\"", Err("invalid If-Match header \"1\", \"2\", expected a single gate ETag".to_owned()))]
+    #[case("3", Err("invalid If-Match header 3, expected a single gate ETag".to_owned()))]
+    fn should_parse_if_match_header(
+        #[case] value: &str,
+        #[case] expected: Result<Option<u32>, String>,
+    ) {
+        // given
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            IF_MATCH,
+            HeaderValue::from_str(value).expect("invalid header value"),
+        );
+
+        // when
+        let actual = if_match(&headers);
+
+        // then
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn should_not_require_if_match_header() {
+        assert_eq!(if_match(&HeaderMap::new()), Ok(None));
+    }
+
+    #[rstest]
+    #[case(None, None, Ok(None))]
+    #[case(Some("\"3\""), None, Ok(Some(3)))]
+    #[case(None, Some(3), Ok(Some(3)))]
+    #[case(Some("\"3\""), Some(3), Ok(Some(3)))]
+    #[case(Some("*"), Some(3), Ok(Some(3)))]
+    #[case(Some("\"3\""), Some(4), Err("If-Match header \"3\" contradicts expected_version 4".to_owned()))]
+    fn should_take_expected_version_from_if_match_header_or_body(
+        #[case] header: Option<&str>,
+        #[case] from_body: Option<u32>,
+        #[case] expected: Result<Option<u32>, String>,
+    ) {
+        // given
+        let mut headers = HeaderMap::new();
+        if let Some(header) = header {
+            headers.insert(
+                IF_MATCH,
+                HeaderValue::from_str(header).expect("invalid header value"),
+            );
+        }
+
+        // when
+        let actual = expected_version(&headers, from_body);
+
+        // then
+        assert_eq!(actual, expected);
+    }
+}
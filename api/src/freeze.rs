@@ -0,0 +1,300 @@
+use chrono::{DateTime, Utc};
+
+use crate::storage::{FindError, Storage};
+use crate::types::{Freeze, FreezeWindow, GateKey};
+
+/// The global freeze and the freeze windows in effect at one instant, and the freeze windows
+/// starting later.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Freezes {
+    pub freeze: Option<Freeze>,
+    pub windows: Vec<FreezeWindow>,
+    pub upcoming: Vec<FreezeWindow>,
+}
+
+impl Freezes {
+    /// Why the gate is frozen, the reason of the global freeze takes precedence over the name of a
+    /// freeze window.
+    pub fn reason(&self, key: &GateKey) -> Option<String> {
+        self.freeze
+            .as_ref()
+            .map(|freeze| freeze.reason.clone())
+            .or_else(|| {
+                self.windows
+                    .iter()
+                    .find(|window| window.scope.contains(key))
+                    .map(|window| window.name.clone())
+            })
+    }
+
+    /// When the gate is not frozen anymore, unknown for a global freeze without end.
+    pub fn frozen_until(&self, key: &GateKey) -> Option<DateTime<Utc>> {
+        self.freeze.as_ref().map_or_else(
+            || {
+                self.windows
+                    .iter()
+                    .filter(|window| window.scope.contains(key))
+                    .map(|window| window.end)
+                    .max()
+            },
+            |freeze| freeze.until,
+        )
+    }
+
+    /// When the next freeze window containing the gate starts.
+    pub fn next_start(&self, key: &GateKey) -> Option<DateTime<Utc>> {
+        self.upcoming
+            .iter()
+            .filter(|window| window.scope.contains(key))
+            .map(|window| window.start)
+            .min()
+    }
+}
+
+/// The global freeze and the freeze windows, if they are in effect at the given instant or later.
+pub async fn active(
+    storage: &(dyn Storage + Send + Sync),
+    now: DateTime<Utc>,
+) -> Result<Freezes, FindError> {
+    let (windows, upcoming) = storage
+        .find_freeze_windows()
+        .await?
+        .into_iter()
+        .filter(|window| now < window.end)
+        .partition(|window| window.is_active(now));
+    Ok(Freezes {
+        freeze: storage
+            .find_freeze()
+            .await?
+            .filter(|freeze| freeze.is_active(now)),
+        windows,
+        upcoming,
+    })
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use std::collections::HashSet;
+
+    use rstest::rstest;
+    use similar_asserts::assert_eq;
+
+    use crate::storage::MockStorage;
+    use crate::types::{FreezeScope, Gate, GateState};
+
+    use super::*;
+
+    #[rstest]
+    #[case(None, true)]
+    #[case(Some("2023-04-12T12:00:01Z"), true)]
+    #[case(Some("2023-04-12T12:00:00Z"), false)]
+    #[tokio::test]
+    async fn should_find_active_freeze(#[case] until: Option<&str>, #[case] active: bool) {
+        // given
+        let now = instant("2023-04-12T12:00:00Z");
+        let freeze = Freeze {
+            reason: "release freeze".to_owned(),
+            actor: None,
+            created: instant("2023-04-01T12:00:00Z"),
+            until: until.map(instant),
+        };
+        let mut mock_storage = MockStorage::new();
+        let stored_freeze = freeze.clone();
+        mock_storage
+            .expect_find_freeze()
+            .return_once(|| Ok(Some(stored_freeze)));
+        mock_storage
+            .expect_find_freeze_windows()
+            .return_once(|| Ok(vec![]));
+
+        // when
+        let result = super::active(&mock_storage, now).await;
+
+        // then
+        assert_eq!(
+            result.expect("storage failed to find freeze").freeze,
+            active.then_some(freeze)
+        );
+    }
+
+    #[rstest]
+    #[case("2023-04-12T12:00:00Z", "2023-04-12T12:00:01Z", true, false)]
+    #[case("2023-04-12T12:00:01Z", "2023-04-12T13:00:00Z", false, true)]
+    #[case("2023-04-12T11:00:00Z", "2023-04-12T12:00:00Z", false, false)]
+    #[tokio::test]
+    async fn should_find_active_and_upcoming_freeze_windows(
+        #[case] start: &str,
+        #[case] end: &str,
+        #[case] active: bool,
+        #[case] upcoming: bool,
+    ) {
+        // given
+        let now = instant("2023-04-12T12:00:00Z");
+        let window = FreezeWindow {
+            start: instant(start),
+            end: instant(end),
+            ..freeze_window(FreezeScope::All)
+        };
+        let mut mock_storage = MockStorage::new();
+        mock_storage.expect_find_freeze().return_once(|| Ok(None));
+        let stored_window = window.clone();
+        mock_storage
+            .expect_find_freeze_windows()
+            .return_once(|| Ok(vec![stored_window]));
+
+        // when
+        let result = super::active(&mock_storage, now).await;
+
+        // then
+        let freezes = result.expect("storage failed to find freeze windows");
+        assert_eq!(
+            freezes.windows,
+            if active { vec![window.clone()] } else { vec![] }
+        );
+        assert_eq!(
+            freezes.upcoming,
+            if upcoming { vec![window] } else { vec![] }
+        );
+    }
+
+    #[test]
+    fn should_freeze_gate_with_reason_of_global_freeze() {
+        // given
+        let gate = some_gate();
+        let freezes = Freezes {
+            freeze: Some(Freeze {
+                reason: "release freeze".to_owned(),
+                actor: None,
+                created: instant("2023-04-01T12:00:00Z"),
+                until: None,
+            }),
+            windows: vec![freeze_window(FreezeScope::All)],
+            upcoming: vec![],
+        };
+
+        // when
+        let frozen = freezes.reason(&gate.key);
+        let not_frozen = Freezes::default().reason(&gate.key);
+
+        // then
+        assert_eq!(frozen, Some("release freeze".to_owned()));
+        assert_eq!(not_frozen, None);
+    }
+
+    #[rstest]
+    #[case(FreezeScope::All, GateState::Closed)]
+    #[case(FreezeScope::Group("some group".to_owned()), GateState::Closed)]
+    #[case(FreezeScope::Group("some other group".to_owned()), GateState::Open)]
+    #[case(FreezeScope::Environment("some environment".to_owned()), GateState::Closed)]
+    #[case(FreezeScope::Environment("some group".to_owned()), GateState::Open)]
+    fn should_freeze_gate_in_scope_of_freeze_window(
+        #[case] scope: FreezeScope,
+        #[case] expected_state: GateState,
+    ) {
+        // given
+        let gate = some_gate();
+        let freezes = Freezes {
+            freeze: None,
+            windows: vec![freeze_window(scope)],
+            upcoming: vec![],
+        };
+
+        // when
+        let actual = freezes.reason(&gate.key);
+
+        // then
+        assert_eq!(
+            actual,
+            (expected_state == GateState::Closed).then(|| "Year-end freeze".to_owned())
+        );
+    }
+
+    #[test]
+    fn should_find_end_of_freeze_and_start_of_next_freeze_window() {
+        // given
+        let key = some_gate().key;
+        let freezes = Freezes {
+            freeze: None,
+            windows: vec![
+                freeze_window(FreezeScope::All),
+                FreezeWindow {
+                    end: instant("2023-06-01T12:00:00Z"),
+                    ..freeze_window(FreezeScope::Group("some other group".to_owned()))
+                },
+            ],
+            upcoming: vec![
+                FreezeWindow {
+                    start: instant("2023-07-01T12:00:00Z"),
+                    ..freeze_window(FreezeScope::All)
+                },
+                FreezeWindow {
+                    start: instant("2023-06-01T12:00:00Z"),
+                    ..freeze_window(FreezeScope::Environment("live".to_owned()))
+                },
+            ],
+        };
+
+        // when
+        let frozen_until = freezes.frozen_until(&key);
+        let next_start = freezes.next_start(&key);
+
+        // then
+        assert_eq!(frozen_until, Some(instant("2023-05-01T12:00:00Z")));
+        assert_eq!(next_start, Some(instant("2023-07-01T12:00:00Z")));
+    }
+
+    #[test]
+    fn should_not_know_end_of_global_freeze_without_end() {
+        // given
+        let freezes = Freezes {
+            freeze: Some(Freeze {
+                reason: "release freeze".to_owned(),
+                actor: None,
+                created: instant("2023-04-01T12:00:00Z"),
+                until: None,
+            }),
+            windows: vec![freeze_window(FreezeScope::All)],
+            upcoming: vec![],
+        };
+
+        // when
+        let actual = freezes.frozen_until(&some_gate().key);
+
+        // then
+        assert_eq!(actual, None);
+    }
+
+    fn some_gate() -> Gate {
+        Gate {
+            key: GateKey {
+                group: "some group".to_owned(),
+                service: "some service".to_owned(),
+                environment: "some environment".to_owned(),
+            },
+            state: GateState::Open,
+            comments: HashSet::default(),
+            last_updated: instant("2023-04-12T12:00:00Z"),
+            display_order: None,
+            version: 3,
+            open_until: None,
+        }
+    }
+
+    fn freeze_window(scope: FreezeScope) -> FreezeWindow {
+        FreezeWindow {
+            id: "some id".to_owned(),
+            name: "Year-end freeze".to_owned(),
+            reason: "annual closing".to_owned(),
+            start: instant("2023-04-01T12:00:00Z"),
+            end: instant("2023-05-01T12:00:00Z"),
+            scope,
+            actor: None,
+        }
+    }
+
+    fn instant(value: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(value)
+            .expect("failed to parse date")
+            .to_utc()
+    }
+}
@@ -0,0 +1,14 @@
+use crate::storage::Storage;
+use crate::types::HistoryEntry;
+
+/// Records the history entry of a change that is already persisted. The change must not be
+/// reported as failed because its history could not be written, so that failure is only logged.
+pub async fn record(storage: &(dyn Storage + Send + Sync), entry: &HistoryEntry) {
+    if let Err(error) = storage.insert_history_entry(entry).await {
+        tracing::error!(
+            "could not record {:?} of gate {} in history: {error:?}",
+            entry.event,
+            entry.key
+        );
+    }
+}
@@ -9,14 +9,29 @@ use tower_http::trace::TraceLayer;
 
 use crate::types::app_state::AppState;
 use crate::use_cases::{
-    add_comment, api_info, create_gate, delete_comment, delete_gate, get_config, get_gate,
-    get_gate_state, list_gates, update_display_order, update_gate_state,
+    add_comment, api_info, cancel_scheduled_state_change, clear_freeze, create_freeze_window,
+    create_gate, create_gates, delete_business_week_profile, delete_closed_day, delete_comment,
+    delete_freeze_window, delete_gate, get_config, get_gate, get_gate_dependencies,
+    get_gate_history, get_gate_state, import_closed_days, list_business_week_profiles,
+    list_closed_days, list_freeze_windows, list_gates, list_gates_by_group, list_gates_by_service,
+    list_scheduled_state_changes, restore_gate, schedule_state_change, set_business_week,
+    set_business_week_profile, set_closed_day, set_freeze, set_gate_dependencies,
+    update_display_order, update_gate_state, update_gate_states,
 };
 
+mod actor;
+mod auto_close;
+mod business_week;
 mod clock;
+mod closure;
 mod date_time_switch;
+mod etag;
+mod freeze;
+mod history;
 mod id_provider;
+mod scheduler;
 mod storage;
+mod trash;
 mod types;
 mod use_cases;
 
@@ -29,14 +44,24 @@ async fn main() -> Result<(), Error> {
         .compact()
         .init();
 
-    let result = run(create_router(AppState::new(
-        #[allow(clippy::large_futures)]
-        storage::default().await,
+    #[allow(clippy::large_futures)]
+    let storage = storage::default().await;
+    let date_time_switch = date_time_switch::new(
+        storage.clone(),
+        business_week::configured_enabled(),
+        business_week::configured(),
+        business_week::configured_environments(),
+    );
+    let app_state = AppState::new(
+        storage,
         Arc::new(clock::default()),
         Arc::new(id_provider::default()),
-        Arc::new(date_time_switch::default()),
-    )))
-    .await;
+        Arc::new(date_time_switch),
+    );
+    let result = match scheduler::configured_mode() {
+        scheduler::Mode::Off => run(create_router(app_state)).await,
+        mode => scheduler::run(app_state, mode).await,
+    };
 
     return result;
 }
@@ -47,14 +72,27 @@ fn create_router(app_state: AppState) -> Router {
             "/",
             get(list_gates::route::handler).post(create_gate::route::handler),
         )
+        .route("/{group}", get(list_gates_by_group::route::handler))
+        .route(
+            "/{group}/{service}",
+            get(list_gates_by_service::route::handler),
+        )
         .route(
             "/{group}/{service}/{environment}",
             get(get_gate::route::handler).delete(delete_gate::route::handler),
         )
+        .route(
+            "/{group}/{service}/{environment}/restore",
+            post(restore_gate::route::handler),
+        )
         .route(
             "/{group}/{service}/{environment}/state",
             put(update_gate_state::route::handler).get(get_gate_state::route::handler),
         )
+        .route(
+            "/{group}/{service}/{environment}/history",
+            get(get_gate_history::route::handler),
+        )
         .route(
             "/{group}/{service}/{environment}/display-order",
             put(update_display_order::route::handler),
@@ -67,6 +105,19 @@ fn create_router(app_state: AppState) -> Router {
             "/{group}/{service}/{environment}/comments/{comment_id}",
             delete(delete_comment::route::handler),
         )
+        .route(
+            "/{group}/{service}/{environment}/dependencies",
+            get(get_gate_dependencies::route::handler).put(set_gate_dependencies::route::handler),
+        )
+        .route(
+            "/{group}/{service}/{environment}/scheduled-state-changes",
+            get(list_scheduled_state_changes::route::handler)
+                .post(schedule_state_change::route::handler),
+        )
+        .route(
+            "/{group}/{service}/{environment}/scheduled-state-changes/{scheduled_state_change_id}",
+            delete(cancel_scheduled_state_change::route::handler),
+        )
         .layer(
             TraceLayer::new_for_http()
                 .make_span_with(trace::DefaultMakeSpan::new().level(tracing::Level::INFO))
@@ -77,6 +128,38 @@ fn create_router(app_state: AppState) -> Router {
         Router::new()
             .route("/", get(api_info::route::handler))
             .route("/config", get(get_config::route::handler))
+            .route(
+                "/config/business-week",
+                put(set_business_week::route::handler),
+            )
+            .route(
+                "/config/business-week-profiles",
+                get(list_business_week_profiles::route::handler)
+                    .put(set_business_week_profile::route::handler)
+                    .delete(delete_business_week_profile::route::handler),
+            )
+            .route(
+                "/config/closed-days",
+                get(list_closed_days::route::handler).post(import_closed_days::route::handler),
+            )
+            .route(
+                "/config/closed-days/{date}",
+                put(set_closed_day::route::handler).delete(delete_closed_day::route::handler),
+            )
+            .route(
+                "/freeze",
+                put(set_freeze::route::handler).delete(clear_freeze::route::handler),
+            )
+            .route(
+                "/freeze-windows",
+                get(list_freeze_windows::route::handler).post(create_freeze_window::route::handler),
+            )
+            .route(
+                "/freeze-windows/{freeze_window_id}",
+                delete(delete_freeze_window::route::handler),
+            )
+            .route("/bulk/gates", post(create_gates::route::handler))
+            .route("/bulk/state", put(update_gate_states::route::handler))
             .nest("/gates", gates_router)
             .with_state(app_state),
     )
@@ -163,6 +246,10 @@ mod integration_tests_lambda {
                             comments: vec![],
                             last_updated: now.to_rfc3339(),
                             display_order: None,
+                            version: 0,
+                            open_until: None,
+                            open_remaining_seconds: None,
+                            closure_reason: Some(models::ClosureReason::Manual),
                         },
                     }],
                 }],
@@ -176,18 +263,59 @@ mod acceptance_tests {
     use openapi::models::Config;
     use std::sync::Arc;
 
+    use axum::http::header::{ETAG, IF_MATCH};
     use axum::http::StatusCode;
     use axum_test::TestServer;
-    use chrono::{DateTime, Utc};
+    use chrono::{DateTime, TimeDelta, Utc};
     use openapi::models;
+    use rstest::rstest;
     use testcontainers::runners::AsyncRunner;
+    use testcontainers::ContainerAsync;
     use testcontainers_modules::dynamodb_local::DynamoDb;
 
+    use crate::actor::ACTOR_HEADER;
     use crate::clock::MockClock;
     use crate::id_provider::MockIdProvider;
     use crate::types::app_state::AppState;
     use crate::types::GateState;
-    use crate::{create_router, date_time_switch, id_provider, storage, types, use_cases};
+    use crate::{
+        create_router, date_time_switch, id_provider, scheduler, storage, types, use_cases,
+    };
+
+    /// The storage backends the acceptance tests run against.
+    #[derive(Debug, Clone, Copy)]
+    enum Backend {
+        InMemory,
+        DynamoDb,
+    }
+
+    impl Backend {
+        /// Starts a storage for a single test, `DynamoDB` Local runs as long as the container lives.
+        async fn start(
+            self,
+        ) -> (
+            Arc<dyn storage::Storage + Send + Sync>,
+            Option<ContainerAsync<DynamoDb>>,
+        ) {
+            match self {
+                Self::InMemory => (Arc::new(storage::in_memory()), None),
+                Self::DynamoDb => {
+                    let dynamodb_container = DynamoDb::default()
+                        .start()
+                        .await
+                        .expect("dynamoDb docker container to be started");
+                    let port = dynamodb_container
+                        .get_host_port_ipv4(8000)
+                        .await
+                        .expect("dynamoDb docker container host port go be found");
+                    (
+                        Arc::new(storage::test(port).await),
+                        Some(dynamodb_container),
+                    )
+                }
+            }
+        }
+    }
 
     fn inside_business_times() -> DateTime<Utc> {
         DateTime::parse_from_rfc3339("2023-06-05T13:00:00+00:00") // monday afternoon
@@ -195,31 +323,31 @@ mod acceptance_tests {
             .into()
     }
 
+    fn at(value: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(value)
+            .expect("failed to parse date")
+            .into()
+    }
+
     fn outside_business_times() -> DateTime<Utc> {
         DateTime::parse_from_rfc3339("2023-06-04T13:00:00+00:00") // sunday afternoon
             .expect("failed to parse date")
             .into()
     }
 
+    #[rstest]
     #[tokio::test]
-    async fn should_create_and_list_gates() {
+    async fn should_create_and_list_gates(
+        #[values(Backend::InMemory, Backend::DynamoDb)] backend: Backend,
+    ) {
         // given
+        let (test_storage, _container) = backend.start().await;
         let now = inside_business_times();
         let mut mock_clock = MockClock::new();
         mock_clock.expect_now().return_const(now);
 
-        let dynamodb_container = DynamoDb::default()
-            .start()
-            .await
-            .expect("dynamoDb docker container to be started");
-        let port = dynamodb_container
-            .get_host_port_ipv4(8000)
-            .await
-            .expect("dynamoDb docker container host port go be found");
-
-        let dynamodb_storage = storage::test(port).await;
         let router = create_router(AppState::new(
-            Arc::new(dynamodb_storage),
+            test_storage,
             Arc::new(mock_clock),
             Arc::new(id_provider::default()),
             Arc::new(date_time_switch::default()),
@@ -273,6 +401,10 @@ mod acceptance_tests {
                                 comments: vec![],
                                 last_updated: now.to_rfc3339(),
                                 display_order: None,
+                                version: 0,
+                                open_until: None,
+                                open_remaining_seconds: None,
+                                closure_reason: Some(models::ClosureReason::Manual),
                             },
                         },
                         models::Environment {
@@ -285,6 +417,10 @@ mod acceptance_tests {
                                 comments: vec![],
                                 last_updated: now.to_rfc3339(),
                                 display_order: Some(123.),
+                                version: 0,
+                                open_until: None,
+                                open_remaining_seconds: None,
+                                closure_reason: Some(models::ClosureReason::Manual),
                             },
                         },
                     ],
@@ -293,25 +429,19 @@ mod acceptance_tests {
         );
     }
 
+    #[rstest]
     #[tokio::test]
-    async fn should_open_and_close_gates() {
+    async fn should_open_and_close_gates(
+        #[values(Backend::InMemory, Backend::DynamoDb)] backend: Backend,
+    ) {
         // given
+        let (test_storage, _container) = backend.start().await;
         let now = inside_business_times();
         let mut mock_clock = MockClock::new();
         mock_clock.expect_now().return_const(now);
 
-        let dynamodb_container = DynamoDb::default()
-            .start()
-            .await
-            .expect("dynamoDb docker container to be started");
-        let port = dynamodb_container
-            .get_host_port_ipv4(8000)
-            .await
-            .expect("dynamoDb docker container host port go be found");
-
-        let dynamodb_storage = storage::test(port).await;
         let router = create_router(AppState::new(
-            Arc::new(dynamodb_storage),
+            test_storage,
             Arc::new(mock_clock),
             Arc::new(id_provider::default()),
             Arc::new(date_time_switch::default()),
@@ -352,6 +482,7 @@ mod acceptance_tests {
             .put("/api/gates/somegroup/someservice/develop/state")
             .json(&crate::use_cases::update_gate_state::route::Payload {
                 state: GateState::Open,
+                ..Default::default()
             })
             .await;
 
@@ -366,6 +497,10 @@ mod acceptance_tests {
                 comments: vec![],
                 last_updated: now.to_rfc3339(),
                 display_order: Option::default(),
+                version: 1,
+                open_until: None,
+                open_remaining_seconds: None,
+                closure_reason: None,
             }
         );
 
@@ -373,59 +508,43 @@ mod acceptance_tests {
             .put("/api/gates/somegroup/someservice/develop/state")
             .json(&crate::use_cases::update_gate_state::route::Payload {
                 state: GateState::Closed,
+                ..Default::default()
             })
             .await;
 
+        let closed_gate = models::Gate {
+            group: "somegroup".to_string(),
+            service: "someservice".to_string(),
+            environment: "develop".to_string(),
+            state: models::GateState::Closed,
+            comments: vec![],
+            last_updated: now.to_rfc3339(),
+            display_order: Option::default(),
+            version: 2,
+            open_until: None,
+            open_remaining_seconds: None,
+            closure_reason: Some(models::ClosureReason::Manual),
+        };
         assert_eq!(response.status_code(), StatusCode::OK);
-        assert_eq!(
-            response.json::<models::Gate>(),
-            models::Gate {
-                group: "somegroup".to_string(),
-                service: "someservice".to_string(),
-                environment: "develop".to_string(),
-                state: models::GateState::Closed,
-                comments: vec![],
-                last_updated: now.to_rfc3339(),
-                display_order: Option::default(),
-            }
-        );
+        assert_eq!(response.json::<models::Gate>(), closed_gate);
 
         let response = server.get("/api/gates/somegroup/someservice/develop").await;
 
         assert_eq!(response.status_code(), StatusCode::OK);
-        assert_eq!(
-            response.json::<models::Gate>(),
-            models::Gate {
-                group: "somegroup".to_string(),
-                service: "someservice".to_string(),
-                environment: "develop".to_string(),
-                state: models::GateState::Closed,
-                comments: vec![],
-                last_updated: now.to_rfc3339(),
-                display_order: Option::default(),
-            }
-        );
+        assert_eq!(response.json::<models::Gate>(), closed_gate);
     }
 
+    #[rstest]
     #[tokio::test]
-    async fn should_delete_gates() {
+    async fn should_delete_gates(#[values(Backend::InMemory, Backend::DynamoDb)] backend: Backend) {
         // given
+        let (test_storage, _container) = backend.start().await;
         let now = inside_business_times();
         let mut mock_clock = MockClock::new();
         mock_clock.expect_now().return_const(now);
 
-        let dynamodb_container = DynamoDb::default()
-            .start()
-            .await
-            .expect("dynamoDb docker container to be started");
-        let port = dynamodb_container
-            .get_host_port_ipv4(8000)
-            .await
-            .expect("dynamoDb docker container host port go be found");
-
-        let dynamodb_storage = storage::test(port).await;
         let router = create_router(AppState::new(
-            Arc::new(dynamodb_storage),
+            test_storage,
             Arc::new(mock_clock),
             Arc::new(id_provider::default()),
             Arc::new(date_time_switch::default()),
@@ -486,6 +605,10 @@ mod acceptance_tests {
                             comments: vec![],
                             last_updated: now.to_rfc3339(),
                             display_order: Option::default(),
+                            version: 0,
+                            open_until: None,
+                            open_remaining_seconds: None,
+                            closure_reason: Some(models::ClosureReason::Manual),
                         },
                     },],
                 }],
@@ -493,27 +616,268 @@ mod acceptance_tests {
         );
     }
 
+    #[rstest]
     #[tokio::test]
-    async fn should_add_and_remove_comments() {
+    async fn should_restore_deleted_gates(
+        #[values(Backend::InMemory, Backend::DynamoDb)] backend: Backend,
+    ) {
         // given
+        let (test_storage, _container) = backend.start().await;
         let now = inside_business_times();
         let mut mock_clock = MockClock::new();
         mock_clock.expect_now().return_const(now);
 
-        let dynamodb_container = DynamoDb::default()
-            .start()
-            .await
-            .expect("dynamoDb docker container to be started");
-        let port = dynamodb_container
-            .get_host_port_ipv4(8000)
-            .await
-            .expect("dynamoDb docker container host port go be found");
+        let router = create_router(AppState::new(
+            test_storage,
+            Arc::new(mock_clock),
+            Arc::new(id_provider::default()),
+            Arc::new(date_time_switch::default()),
+        ));
+
+        let server = TestServer::new(router).expect("failed to create test server");
+
+        let response = server
+            .post("/api/gates")
+            .json(&use_cases::create_gate::route::Payload {
+                group: "somegroup".to_owned(),
+                service: "someservice".to_owned(),
+                environment: "develop".to_owned(),
+                display_order: Some(3),
+            })
+            .await;
+        assert_eq!(response.status_code(), StatusCode::OK);
+
+        let response = server
+            .delete("/api/gates/somegroup/someservice/develop")
+            .await;
+        assert_eq!(response.status_code(), StatusCode::OK);
+
+        let response = server.get("/api/gates/somegroup/someservice/develop").await;
+        assert_eq!(response.status_code(), StatusCode::NO_CONTENT);
+
+        // when
+        let response = server
+            .post("/api/gates/somegroup/someservice/develop/restore")
+            .await;
+
+        // then
+        assert_eq!(response.status_code(), StatusCode::OK);
+        assert_eq!(
+            response.json::<models::Gate>(),
+            models::Gate {
+                version: 0,
+                ..expected_gate_representation_with_display_order(now, "develop".to_string(), 3)
+            },
+        );
+
+        let response = server.get("/api/gates/somegroup/someservice/develop").await;
+        assert_eq!(response.status_code(), StatusCode::OK);
+
+        // when
+        let response = server
+            .post("/api/gates/somegroup/someservice/develop/restore")
+            .await;
+
+        // then
+        assert_eq!(response.status_code(), StatusCode::NO_CONTENT);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn should_create_gates_in_bulk(
+        #[values(Backend::InMemory, Backend::DynamoDb)] backend: Backend,
+    ) {
+        // given
+        let (test_storage, _container) = backend.start().await;
+        let now = inside_business_times();
+        let mut mock_clock = MockClock::new();
+        mock_clock.expect_now().return_const(now);
+
+        let router = create_router(AppState::new(
+            test_storage,
+            Arc::new(mock_clock),
+            Arc::new(id_provider::default()),
+            Arc::new(date_time_switch::default()),
+        ));
+
+        let server = TestServer::new(router).expect("failed to create test server");
+
+        let gate = |environment: &str| use_cases::create_gates::route::GatePayload {
+            group: "somegroup".to_owned(),
+            service: "someservice".to_owned(),
+            environment: environment.to_owned(),
+            display_order: None,
+        };
+
+        // when
+        let response = server
+            .post("/api/bulk/gates")
+            .json(&use_cases::create_gates::route::Payload {
+                gates: vec![gate("develop"), gate("live"), gate("develop")],
+                all_or_nothing: false,
+            })
+            .await;
+
+        // then
+        assert_eq!(response.status_code(), StatusCode::OK);
+        assert_eq!(
+            response
+                .json::<models::CreateGatesResult>()
+                .results
+                .into_iter()
+                .map(|result| result.status)
+                .collect::<Vec<_>>(),
+            vec![
+                models::CreateGateStatus::Created,
+                models::CreateGateStatus::Created,
+                models::CreateGateStatus::Invalid,
+            ]
+        );
+
+        // when
+        let response = server
+            .post("/api/bulk/gates")
+            .json(&use_cases::create_gates::route::Payload {
+                gates: vec![gate("staging"), gate("live")],
+                all_or_nothing: true,
+            })
+            .await;
+
+        // then
+        assert_eq!(response.status_code(), StatusCode::CONFLICT);
+        assert_eq!(
+            response
+                .json::<models::CreateGatesResult>()
+                .results
+                .into_iter()
+                .map(|result| result.status)
+                .collect::<Vec<_>>(),
+            vec![
+                models::CreateGateStatus::Skipped,
+                models::CreateGateStatus::AlreadyExists,
+            ]
+        );
+
+        let response = server.get("/api/gates/somegroup/someservice/staging").await;
+        assert_eq!(response.status_code(), StatusCode::NO_CONTENT);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn should_change_states_of_multiple_gates(
+        #[values(Backend::InMemory, Backend::DynamoDb)] backend: Backend,
+    ) {
+        // given
+        let (test_storage, _container) = backend.start().await;
+        let now = inside_business_times();
+        let mut mock_clock = MockClock::new();
+        mock_clock.expect_now().return_const(now);
+
+        let router = create_router(AppState::new(
+            test_storage,
+            Arc::new(mock_clock),
+            Arc::new(id_provider::default()),
+            Arc::new(date_time_switch::default()),
+        ));
+
+        let server = TestServer::new(router).expect("failed to create test server");
+
+        for (service, environment) in [
+            ("someservice", "develop"),
+            ("someservice", "live"),
+            ("someotherservice", "live"),
+        ] {
+            let response = server
+                .post("/api/gates")
+                .json(&use_cases::create_gate::route::Payload {
+                    group: "somegroup".to_owned(),
+                    service: service.to_owned(),
+                    environment: environment.to_owned(),
+                    display_order: None,
+                })
+                .await;
+            assert_eq!(response.status_code(), StatusCode::OK);
+        }
+
+        // when
+        let response = server
+            .put("/api/bulk/state")
+            .json(&use_cases::update_gate_states::route::Payload {
+                gates: None,
+                filter: Some(use_cases::update_gate_states::route::FilterPayload {
+                    group: "somegroup".to_owned(),
+                    environment: Some("live".to_owned()),
+                }),
+                state: GateState::Open,
+                comment: Some("release 1.2.3".to_owned()),
+            })
+            .await;
+
+        // then
+        assert_eq!(response.status_code(), StatusCode::OK);
+        let gates = response.json::<Vec<models::Gate>>();
+        assert_eq!(
+            gates
+                .iter()
+                .map(|gate| (gate.service.as_str(), gate.environment.as_str(), gate.state))
+                .collect::<Vec<_>>(),
+            vec![
+                ("someotherservice", "live", models::GateState::Open),
+                ("someservice", "live", models::GateState::Open),
+            ]
+        );
+        assert!(gates
+            .iter()
+            .all(|gate| gate.comments.len() == 1 && gate.comments[0].message == "release 1.2.3"));
+
+        // when
+        let response = server
+            .put("/api/bulk/state")
+            .json(&use_cases::update_gate_states::route::Payload {
+                gates: Some(vec![
+                    use_cases::update_gate_states::route::GateKeyPayload {
+                        group: "somegroup".to_owned(),
+                        service: "someservice".to_owned(),
+                        environment: "develop".to_owned(),
+                    },
+                    use_cases::update_gate_states::route::GateKeyPayload {
+                        group: "somegroup".to_owned(),
+                        service: "someservice".to_owned(),
+                        environment: "unknown".to_owned(),
+                    },
+                ]),
+                filter: None,
+                state: GateState::Open,
+                comment: None,
+            })
+            .await;
+
+        // then
+        assert_eq!(response.status_code(), StatusCode::NO_CONTENT);
+        let response = server
+            .get("/api/gates/somegroup/someservice/develop/state")
+            .await;
+        assert_eq!(
+            response.json::<models::GateStateRep>().state,
+            models::GateState::Closed
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn should_add_and_remove_comments(
+        #[values(Backend::InMemory, Backend::DynamoDb)] backend: Backend,
+    ) {
+        // given
+        let (test_storage, _container) = backend.start().await;
+        let now = inside_business_times();
+        let mut mock_clock = MockClock::new();
+        mock_clock.expect_now().return_const(now);
 
-        let dynamodb_storage = storage::test(port).await;
         let mut mock_id_provider = MockIdProvider::new();
         mock_id_provider.expect_get().return_const("some_id");
         let router = create_router(AppState::new(
-            Arc::new(dynamodb_storage),
+            test_storage,
             Arc::new(mock_clock),
             Arc::new(mock_id_provider),
             Arc::new(date_time_switch::default()),
@@ -542,6 +906,7 @@ mod acceptance_tests {
             .post("/api/gates/somegroup/someservice/develop/comments")
             .json(&crate::use_cases::add_comment::route::Payload {
                 message: "Some comment message".to_owned(),
+                expected_version: None,
             })
             .await;
 
@@ -571,6 +936,10 @@ mod acceptance_tests {
                             }],
                             last_updated: now.to_rfc3339(),
                             display_order: Option::default(),
+                            version: 1,
+                            open_until: None,
+                            open_remaining_seconds: None,
+                            closure_reason: Some(models::ClosureReason::Manual),
                         },
                     },],
                 }],
@@ -603,6 +972,10 @@ mod acceptance_tests {
                             comments: vec![],
                             last_updated: now.to_rfc3339(),
                             display_order: Option::default(),
+                            version: 2,
+                            open_until: None,
+                            open_remaining_seconds: None,
+                            closure_reason: Some(models::ClosureReason::Manual),
                         },
                     },],
                 }],
@@ -610,25 +983,19 @@ mod acceptance_tests {
         );
     }
 
+    #[rstest]
     #[tokio::test]
-    async fn should_get_gate_state() {
+    async fn should_get_gate_state(
+        #[values(Backend::InMemory, Backend::DynamoDb)] backend: Backend,
+    ) {
         // given
+        let (test_storage, _container) = backend.start().await;
         let now = inside_business_times();
         let mut mock_clock = MockClock::new();
         mock_clock.expect_now().return_const(now);
 
-        let dynamodb_container = DynamoDb::default()
-            .start()
-            .await
-            .expect("dynamoDb docker container to be started");
-        let port = dynamodb_container
-            .get_host_port_ipv4(8000)
-            .await
-            .expect("dynamoDb docker container host port go be found");
-
-        let dynamodb_storage = storage::test(port).await;
         let router = create_router(AppState::new(
-            Arc::new(dynamodb_storage),
+            test_storage,
             Arc::new(mock_clock),
             Arc::new(id_provider::default()),
             Arc::new(date_time_switch::default()),
@@ -669,6 +1036,7 @@ mod acceptance_tests {
             .put("/api/gates/somegroup/someservice/live/state")
             .json(&crate::use_cases::update_gate_state::route::Payload {
                 state: GateState::Open,
+                ..Default::default()
             })
             .await;
 
@@ -684,29 +1052,35 @@ mod acceptance_tests {
             response.json::<models::GateStateRep>(),
             models::GateStateRep {
                 state: models::GateState::Open,
+                freeze: None,
+                reason: None,
+                closure_reason: None,
+                latest_comment: None,
+                next_transition: Some("2023-06-05T16:30:00+00:00".to_owned()),
+                transitions: Some(models::Transitions {
+                    next_open: Some("2023-06-06T06:00:00+00:00".to_owned()),
+                    next_close: Some("2023-06-05T16:30:00+00:00".to_owned()),
+                }),
             },
         );
     }
 
+    #[rstest]
     #[tokio::test]
-    async fn should_auto_close_gates() {
+    async fn should_get_gate_state_at_instant(
+        #[values(Backend::InMemory, Backend::DynamoDb)] backend: Backend,
+    ) {
         // given
-        let now = outside_business_times();
+        let (test_storage, _container) = backend.start().await;
+        let now = Arc::new(std::sync::Mutex::new(at("2023-06-05T10:00:00+00:00")));
         let mut mock_clock = MockClock::new();
-        mock_clock.expect_now().return_const(now);
-
-        let dynamodb_container = DynamoDb::default()
-            .start()
-            .await
-            .expect("dynamoDb docker container to be started");
-        let port = dynamodb_container
-            .get_host_port_ipv4(8000)
-            .await
-            .expect("dynamoDb docker container host port go be found");
+        let clock_now = now.clone();
+        mock_clock
+            .expect_now()
+            .returning(move || *clock_now.lock().expect("failed to lock clock"));
 
-        let dynamodb_storage = storage::test(port).await;
         let router = create_router(AppState::new(
-            Arc::new(dynamodb_storage),
+            test_storage,
             Arc::new(mock_clock),
             Arc::new(id_provider::default()),
             Arc::new(date_time_switch::default()),
@@ -714,21 +1088,129 @@ mod acceptance_tests {
 
         let server = TestServer::new(router).expect("failed to create test server");
 
-        let group = "somegroup".to_owned();
-        let service = "someservice".to_owned();
-        let environment = "develop".to_owned();
         let response = server
             .post("/api/gates")
             .json(&use_cases::create_gate::route::Payload {
-                group,
-                service,
-                environment,
+                group: "somegroup".to_owned(),
+                service: "someservice".to_owned(),
+                environment: "live".to_owned(),
                 display_order: None,
             })
             .await;
         assert_eq!(response.status_code(), StatusCode::OK);
 
-        let group = "somegroup".to_owned();
+        for (time, state) in [
+            ("2023-06-05T12:00:00+00:00", GateState::Open),
+            ("2023-06-05T14:00:00+00:00", GateState::Closed),
+        ] {
+            *now.lock().expect("failed to lock clock") = at(time);
+            let response = server
+                .put("/api/gates/somegroup/someservice/live/state")
+                .json(&use_cases::update_gate_state::route::Payload {
+                    state,
+                    ..Default::default()
+                })
+                .await;
+            assert_eq!(response.status_code(), StatusCode::OK);
+        }
+        *now.lock().expect("failed to lock clock") = at("2023-06-05T16:00:00+00:00");
+
+        // when
+        let response = server
+            .get("/api/gates/somegroup/someservice/live/state")
+            .add_query_param("at", "2023-06-05T13:00:00Z")
+            .await;
+
+        // then
+        assert_eq!(response.status_code(), StatusCode::OK);
+        assert_eq!(
+            response.json::<models::GateStateRep>(),
+            models::GateStateRep {
+                state: models::GateState::Open,
+                freeze: None,
+                reason: None,
+                closure_reason: None,
+                latest_comment: None,
+                next_transition: None,
+                transitions: None,
+            },
+        );
+
+        // when
+        let response = server
+            .get("/api/gates/somegroup/someservice/live/state")
+            .add_query_param("at", "2023-06-05T11:00:00Z")
+            .await;
+
+        // then
+        assert_eq!(response.status_code(), StatusCode::OK);
+        assert_eq!(
+            response.json::<models::GateStateRep>(),
+            models::GateStateRep {
+                state: models::GateState::Closed,
+                freeze: None,
+                reason: None,
+                closure_reason: Some(models::ClosureReason::Manual),
+                latest_comment: None,
+                next_transition: None,
+                transitions: None,
+            },
+        );
+
+        // when
+        let response = server
+            .get("/api/gates/somegroup/someservice/live/state")
+            .add_query_param("at", "2023-06-05T09:00:00Z")
+            .await;
+
+        // then
+        assert_eq!(response.status_code(), StatusCode::NO_CONTENT);
+
+        // when
+        let response = server
+            .get("/api/gates/somegroup/someservice/live/state")
+            .add_query_param("at", "2023-06-05T17:00:00Z")
+            .await;
+
+        // then
+        assert_eq!(response.status_code(), StatusCode::BAD_REQUEST);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn should_auto_close_gates(
+        #[values(Backend::InMemory, Backend::DynamoDb)] backend: Backend,
+    ) {
+        // given
+        let (test_storage, _container) = backend.start().await;
+        let now = outside_business_times();
+        let mut mock_clock = MockClock::new();
+        mock_clock.expect_now().return_const(now);
+
+        let router = create_router(AppState::new(
+            test_storage,
+            Arc::new(mock_clock),
+            Arc::new(id_provider::default()),
+            Arc::new(date_time_switch::default()),
+        ));
+
+        let server = TestServer::new(router).expect("failed to create test server");
+
+        let group = "somegroup".to_owned();
+        let service = "someservice".to_owned();
+        let environment = "develop".to_owned();
+        let response = server
+            .post("/api/gates")
+            .json(&use_cases::create_gate::route::Payload {
+                group,
+                service,
+                environment,
+                display_order: None,
+            })
+            .await;
+        assert_eq!(response.status_code(), StatusCode::OK);
+
+        let group = "somegroup".to_owned();
         let service = "someservice".to_owned();
         let environment = "live".to_owned();
         let response = server
@@ -742,59 +1224,1098 @@ mod acceptance_tests {
             .await;
         assert_eq!(response.status_code(), StatusCode::OK);
 
-        // when
-        // try to set state of live - CLOSED on sunday
+        // when
+        // try to set state of live - CLOSED on sunday
+        let response = server
+            .put("/api/gates/somegroup/someservice/live/state")
+            .json(&crate::use_cases::update_gate_state::route::Payload {
+                state: GateState::Open,
+                ..Default::default()
+            })
+            .await;
+
+        assert_eq!(response.status_code(), StatusCode::CONFLICT);
+        assert_eq!(
+            response.json::<String>(),
+            "Already after business hours - rejecting attempt to change state".to_owned()
+        );
+
+        let response = server.get("/api/gates/somegroup/someservice/live").await;
+
+        // then
+        assert_eq!(response.status_code(), StatusCode::OK);
+        assert_eq!(
+            response.json::<models::Gate>(),
+            models::Gate {
+                group: "somegroup".to_string(),
+                service: "someservice".to_string(),
+                environment: "live".to_string(),
+                state: models::GateState::Closed,
+                comments: vec![],
+                last_updated: now.to_rfc3339(),
+                display_order: Option::default(),
+                version: 0,
+                open_until: None,
+                open_remaining_seconds: None,
+                closure_reason: Some(models::ClosureReason::BusinessHours),
+            },
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn should_close_gates_depending_on_closed_gates(
+        #[values(Backend::InMemory, Backend::DynamoDb)] backend: Backend,
+    ) {
+        // given
+        let (test_storage, _container) = backend.start().await;
+        let mut mock_clock = MockClock::new();
+        mock_clock
+            .expect_now()
+            .return_const(inside_business_times());
+
+        let router = create_router(AppState::new(
+            test_storage,
+            Arc::new(mock_clock),
+            Arc::new(id_provider::default()),
+            Arc::new(date_time_switch::default()),
+        ));
+        let server = TestServer::new(router).expect("failed to create test server");
+
+        for environment in ["staging", "live"] {
+            let response = server
+                .post("/api/gates")
+                .json(&use_cases::create_gate::route::Payload {
+                    group: "somegroup".to_owned(),
+                    service: "someservice".to_owned(),
+                    environment: environment.to_owned(),
+                    display_order: None,
+                })
+                .await;
+            assert_eq!(response.status_code(), StatusCode::OK);
+            let response = server
+                .put(&format!(
+                    "/api/gates/somegroup/someservice/{environment}/state"
+                ))
+                .json(&use_cases::update_gate_state::route::Payload {
+                    state: GateState::Open,
+                    ..Default::default()
+                })
+                .await;
+            assert_eq!(response.status_code(), StatusCode::OK);
+        }
+        let staging = models::GateKey {
+            group: "somegroup".to_owned(),
+            service: "someservice".to_owned(),
+            environment: "staging".to_owned(),
+        };
+
+        // when
+        let response = server
+            .put("/api/gates/somegroup/someservice/live/dependencies")
+            .json(&use_cases::set_gate_dependencies::route::Payload {
+                depends_on: vec![staging.clone()],
+            })
+            .await;
+
+        // then
+        assert_eq!(response.status_code(), StatusCode::OK);
+        let response = server
+            .get("/api/gates/somegroup/someservice/live/dependencies")
+            .await;
+        assert_eq!(
+            response.json::<models::GateDependencies>(),
+            models::GateDependencies {
+                depends_on: vec![staging],
+            }
+        );
+        let response = server.get("/api/gates/somegroup/someservice/live").await;
+        assert_eq!(
+            response.json::<models::Gate>().state,
+            models::GateState::Open
+        );
+
+        // when
+        let response = server
+            .put("/api/gates/somegroup/someservice/staging/state")
+            .json(&use_cases::update_gate_state::route::Payload {
+                state: GateState::Closed,
+                ..Default::default()
+            })
+            .await;
+        assert_eq!(response.status_code(), StatusCode::OK);
+
+        // then
+        let response = server.get("/api/gates/somegroup/someservice/live").await;
+        let gate = response.json::<models::Gate>();
+        assert_eq!(gate.state, models::GateState::Closed);
+        assert_eq!(gate.closure_reason, Some(models::ClosureReason::Dependency));
+
+        let response = server.get("/api/gates/somegroup/someservice").await;
+        let service = response.json::<models::Service>();
+        assert_eq!(
+            service
+                .environments
+                .iter()
+                .map(|environment| environment.gate.closure_reason)
+                .collect::<Vec<_>>(),
+            vec![
+                Some(models::ClosureReason::Dependency),
+                Some(models::ClosureReason::Manual),
+            ]
+        );
+
+        let response = server
+            .get("/api/gates/somegroup/someservice/live/state")
+            .await;
+        assert_eq!(
+            response.json::<models::GateStateRep>().closure_reason,
+            Some(models::ClosureReason::Dependency)
+        );
+
+        // when
+        let response = server
+            .put("/api/gates/somegroup/someservice/live/dependencies")
+            .json(&use_cases::set_gate_dependencies::route::Payload { depends_on: vec![] })
+            .await;
+        assert_eq!(response.status_code(), StatusCode::OK);
+
+        // then
+        let response = server.get("/api/gates/somegroup/someservice/live").await;
+        let gate = response.json::<models::Gate>();
+        assert_eq!(gate.state, models::GateState::Open);
+        assert_eq!(gate.closure_reason, None);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn should_freeze_all_gates(
+        #[values(Backend::InMemory, Backend::DynamoDb)] backend: Backend,
+    ) {
+        // given
+        let (test_storage, _container) = backend.start().await;
+        let now = inside_business_times();
+        let mut mock_clock = MockClock::new();
+        mock_clock.expect_now().return_const(now);
+
+        let router = create_router(AppState::new(
+            test_storage,
+            Arc::new(mock_clock),
+            Arc::new(id_provider::default()),
+            Arc::new(date_time_switch::default()),
+        ));
+
+        let server = TestServer::new(router).expect("failed to create test server");
+
+        let response = server
+            .post("/api/gates")
+            .json(&use_cases::create_gate::route::Payload {
+                group: "somegroup".to_owned(),
+                service: "someservice".to_owned(),
+                environment: "live".to_owned(),
+                display_order: None,
+            })
+            .await;
+        assert_eq!(response.status_code(), StatusCode::OK);
+
+        let response = server
+            .put("/api/gates/somegroup/someservice/live/state")
+            .json(&use_cases::update_gate_state::route::Payload {
+                state: GateState::Open,
+                ..Default::default()
+            })
+            .await;
+        assert_eq!(response.status_code(), StatusCode::OK);
+
+        // when
+        let response = server
+            .put("/api/freeze")
+            .add_header(ACTOR_HEADER, "Max")
+            .json(&use_cases::set_freeze::route::Payload {
+                reason: "release freeze".to_owned(),
+                until: None,
+            })
+            .await;
+
+        // then
+        let expected_freeze = models::Freeze {
+            reason: "release freeze".to_owned(),
+            actor: Some("Max".to_owned()),
+            created: now.to_rfc3339(),
+            until: None,
+        };
+        assert_eq!(response.status_code(), StatusCode::OK);
+        assert_eq!(response.json::<models::Freeze>(), expected_freeze);
+
+        let response = server
+            .get("/api/gates/somegroup/someservice/live/state")
+            .await;
+        assert_eq!(
+            response.json::<models::GateStateRep>(),
+            models::GateStateRep {
+                state: models::GateState::Closed,
+                freeze: Some(expected_freeze.clone()),
+                reason: Some(expected_freeze.reason.clone()),
+                closure_reason: Some(models::ClosureReason::Freeze),
+                latest_comment: None,
+                next_transition: None,
+                transitions: Some(models::Transitions {
+                    next_open: Some("2023-06-06T06:00:00+00:00".to_owned()),
+                    next_close: Some("2023-06-05T16:30:00+00:00".to_owned()),
+                }),
+            },
+        );
+
+        let response = server.get("/api/config").await;
+        assert_eq!(response.json::<Config>().freeze, Some(expected_freeze));
+
+        // when
+        let response = server.delete("/api/freeze").await;
+
+        // then
+        assert_eq!(response.status_code(), StatusCode::OK);
+
+        let response = server
+            .get("/api/gates/somegroup/someservice/live/state")
+            .await;
+        assert_eq!(
+            response.json::<models::GateStateRep>(),
+            models::GateStateRep {
+                state: models::GateState::Open,
+                freeze: None,
+                reason: None,
+                closure_reason: None,
+                latest_comment: None,
+                next_transition: Some("2023-06-05T16:30:00+00:00".to_owned()),
+                transitions: Some(models::Transitions {
+                    next_open: Some("2023-06-06T06:00:00+00:00".to_owned()),
+                    next_close: Some("2023-06-05T16:30:00+00:00".to_owned()),
+                }),
+            },
+        );
+
+        let response = server.delete("/api/freeze").await;
+        assert_eq!(response.status_code(), StatusCode::NO_CONTENT);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn should_freeze_gates_during_freeze_window(
+        #[values(Backend::InMemory, Backend::DynamoDb)] backend: Backend,
+    ) {
+        // given
+        let (test_storage, _container) = backend.start().await;
+        let now = inside_business_times();
+        let mut mock_clock = MockClock::new();
+        mock_clock.expect_now().return_const(now);
+
+        let router = create_router(AppState::new(
+            test_storage,
+            Arc::new(mock_clock),
+            Arc::new(id_provider::default()),
+            Arc::new(date_time_switch::default()),
+        ));
+
+        let server = TestServer::new(router).expect("failed to create test server");
+
+        for environment in ["live", "test"] {
+            let response = server
+                .post("/api/gates")
+                .json(&use_cases::create_gate::route::Payload {
+                    group: "somegroup".to_owned(),
+                    service: "someservice".to_owned(),
+                    environment: environment.to_owned(),
+                    display_order: None,
+                })
+                .await;
+            assert_eq!(response.status_code(), StatusCode::OK);
+
+            let response = server
+                .put(&format!(
+                    "/api/gates/somegroup/someservice/{environment}/state"
+                ))
+                .json(&use_cases::update_gate_state::route::Payload {
+                    state: GateState::Open,
+                    ..Default::default()
+                })
+                .await;
+            assert_eq!(response.status_code(), StatusCode::OK);
+        }
+
+        // when
+        let response = server
+            .post("/api/freeze-windows")
+            .add_header(ACTOR_HEADER, "Max")
+            .json(&use_cases::create_freeze_window::route::Payload {
+                name: "Year-end freeze".to_owned(),
+                reason: "annual closing".to_owned(),
+                start: now - TimeDelta::hours(1),
+                end: now + TimeDelta::hours(1),
+                group: None,
+                environment: Some("live".to_owned()),
+            })
+            .await;
+
+        // then
+        assert_eq!(response.status_code(), StatusCode::OK);
+        let freeze_window = response.json::<models::FreezeWindow>();
+        assert_eq!(freeze_window.name, "Year-end freeze");
+        assert_eq!(freeze_window.actor, Some("Max".to_owned()));
+
+        let response = server.get("/api/freeze-windows").await;
+        assert_eq!(
+            response.json::<Vec<models::FreezeWindow>>(),
+            vec![freeze_window.clone()]
+        );
+
+        let response = server
+            .get("/api/gates/somegroup/someservice/live/state")
+            .await;
+        assert_eq!(
+            response.json::<models::GateStateRep>(),
+            models::GateStateRep {
+                state: models::GateState::Closed,
+                freeze: None,
+                reason: Some("Year-end freeze".to_owned()),
+                closure_reason: Some(models::ClosureReason::Freeze),
+                latest_comment: None,
+                next_transition: Some(freeze_window.end.clone()),
+                transitions: Some(models::Transitions {
+                    next_open: Some("2023-06-06T06:00:00+00:00".to_owned()),
+                    next_close: Some("2023-06-05T16:30:00+00:00".to_owned()),
+                }),
+            },
+        );
+
+        let response = server
+            .get("/api/gates/somegroup/someservice/test/state")
+            .await;
+        assert_eq!(
+            response.json::<models::GateStateRep>().state,
+            models::GateState::Open
+        );
+
+        // when
+        let response = server
+            .delete(&format!("/api/freeze-windows/{}", freeze_window.id))
+            .await;
+
+        // then
+        assert_eq!(response.status_code(), StatusCode::OK);
+
+        let response = server
+            .get("/api/gates/somegroup/someservice/live/state")
+            .await;
+        assert_eq!(
+            response.json::<models::GateStateRep>().state,
+            models::GateState::Open
+        );
+
+        let response = server
+            .delete(&format!("/api/freeze-windows/{}", freeze_window.id))
+            .await;
+        assert_eq!(response.status_code(), StatusCode::NO_CONTENT);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn should_apply_scheduled_state_changes(
+        #[values(Backend::InMemory, Backend::DynamoDb)] backend: Backend,
+    ) {
+        // given
+        let (test_storage, _container) = backend.start().await;
+        let now = Arc::new(std::sync::Mutex::new(inside_business_times()));
+        let mut mock_clock = MockClock::new();
+        let clock_now = now.clone();
+        mock_clock
+            .expect_now()
+            .returning(move || *clock_now.lock().expect("failed to lock clock"));
+
+        let app_state = AppState::new(
+            test_storage,
+            Arc::new(mock_clock),
+            Arc::new(id_provider::default()),
+            Arc::new(date_time_switch::default()),
+        );
+        let server = TestServer::new(create_router(app_state.clone()))
+            .expect("failed to create test server");
+
+        let response = server
+            .post("/api/gates")
+            .json(&use_cases::create_gate::route::Payload {
+                group: "somegroup".to_owned(),
+                service: "someservice".to_owned(),
+                environment: "live".to_owned(),
+                display_order: None,
+            })
+            .await;
+        assert_eq!(response.status_code(), StatusCode::OK);
+
+        // when
+        let mut changes = vec![];
+        for (state, at) in [
+            (
+                GateState::Open,
+                inside_business_times() + TimeDelta::hours(1),
+            ),
+            (
+                GateState::Closed,
+                inside_business_times() + TimeDelta::hours(2),
+            ),
+        ] {
+            let response = server
+                .post("/api/gates/somegroup/someservice/live/scheduled-state-changes")
+                .add_header(ACTOR_HEADER, "Max")
+                .json(&use_cases::schedule_state_change::route::Payload { state, at })
+                .await;
+            assert_eq!(response.status_code(), StatusCode::OK);
+            changes.push(response.json::<models::ScheduledStateChange>());
+        }
+
+        // then
+        assert_eq!(changes[0].state, models::GateState::Open);
+        assert_eq!(changes[0].actor, Some("Max".to_owned()));
+        let response = server
+            .get("/api/gates/somegroup/someservice/live/scheduled-state-changes")
+            .await;
+        assert_eq!(
+            response.json::<Vec<models::ScheduledStateChange>>(),
+            changes
+        );
+
+        // when
+        let response = server
+            .delete(&format!(
+                "/api/gates/somegroup/someservice/live/scheduled-state-changes/{}",
+                changes[1].id
+            ))
+            .await;
+
+        // then
+        assert_eq!(response.status_code(), StatusCode::OK);
+        let response = server
+            .delete(&format!(
+                "/api/gates/somegroup/someservice/live/scheduled-state-changes/{}",
+                changes[1].id
+            ))
+            .await;
+        assert_eq!(response.status_code(), StatusCode::NO_CONTENT);
+
+        // when
+        *now.lock().expect("failed to lock clock") = inside_business_times() + TimeDelta::hours(1);
+        let applied = scheduler::apply_due_state_changes(&app_state).await;
+
+        // then
+        assert_eq!(applied, Ok(1));
+        let response = server.get("/api/gates/somegroup/someservice/live").await;
+        let gate = response.json::<models::Gate>();
+        assert_eq!(gate.state, models::GateState::Open);
+        assert_eq!(
+            gate.comments
+                .into_iter()
+                .map(|comment| comment.message)
+                .collect::<Vec<_>>(),
+            vec!["Changed to open as scheduled by Max".to_owned()]
+        );
+        let response = server
+            .get("/api/gates/somegroup/someservice/live/scheduled-state-changes")
+            .await;
+        assert_eq!(response.json::<Vec<models::ScheduledStateChange>>(), vec![]);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn should_close_gate_once_its_open_state_expired(
+        #[values(Backend::InMemory, Backend::DynamoDb)] backend: Backend,
+    ) {
+        // given
+        let (test_storage, _container) = backend.start().await;
+        let now = Arc::new(std::sync::Mutex::new(inside_business_times()));
+        let mut mock_clock = MockClock::new();
+        let clock_now = now.clone();
+        mock_clock
+            .expect_now()
+            .returning(move || *clock_now.lock().expect("failed to lock clock"));
+
+        let app_state = AppState::new(
+            test_storage,
+            Arc::new(mock_clock),
+            Arc::new(id_provider::default()),
+            Arc::new(date_time_switch::default()),
+        );
+        let server = TestServer::new(create_router(app_state.clone()))
+            .expect("failed to create test server");
+
+        let response = server
+            .post("/api/gates")
+            .json(&use_cases::create_gate::route::Payload {
+                group: "somegroup".to_owned(),
+                service: "someservice".to_owned(),
+                environment: "live".to_owned(),
+                display_order: None,
+            })
+            .await;
+        assert_eq!(response.status_code(), StatusCode::OK);
+
+        // when
+        let response = server
+            .put("/api/gates/somegroup/someservice/live/state")
+            .json(&use_cases::update_gate_state::route::Payload {
+                state: GateState::Open,
+                ttl_seconds: Some(1800),
+                ..Default::default()
+            })
+            .await;
+
+        // then
+        assert_eq!(response.status_code(), StatusCode::OK);
+        let gate = response.json::<models::Gate>();
+        assert_eq!(gate.state, models::GateState::Open);
+        assert_eq!(
+            gate.open_until,
+            Some((inside_business_times() + TimeDelta::minutes(30)).to_rfc3339())
+        );
+        assert_eq!(gate.open_remaining_seconds, Some(1800));
+
+        // when
+        *now.lock().expect("failed to lock clock") =
+            inside_business_times() + TimeDelta::minutes(20);
+
+        // then
+        let response = server.get("/api/gates/somegroup/someservice/live").await;
+        let gate = response.json::<models::Gate>();
+        assert_eq!(gate.state, models::GateState::Open);
+        assert_eq!(gate.open_remaining_seconds, Some(600));
+
+        // when
+        *now.lock().expect("failed to lock clock") =
+            inside_business_times() + TimeDelta::minutes(30);
+
+        // then
+        let response = server
+            .get("/api/gates/somegroup/someservice/live/state")
+            .await;
+        let state = response.json::<models::GateStateRep>();
+        assert_eq!(state.state, models::GateState::Closed);
+        assert_eq!(state.closure_reason, Some(models::ClosureReason::Expired));
+
+        // when
+        let gates = scheduler::find_gates(&app_state)
+            .await
+            .expect("failed to find gates");
+        let closed = scheduler::close_expired_gates(&app_state, &gates).await;
+
+        // then
+        assert_eq!(closed, Ok(1));
+        let response = server.get("/api/gates/somegroup/someservice/live").await;
+        let gate = response.json::<models::Gate>();
+        assert_eq!(gate.state, models::GateState::Closed);
+        assert_eq!(gate.open_until, None);
+        assert_eq!(
+            gate.comments
+                .into_iter()
+                .map(|comment| comment.message)
+                .collect::<Vec<_>>(),
+            vec![format!(
+                "Closed automatically, the gate was only opened until {}",
+                (inside_business_times() + TimeDelta::minutes(30)).to_rfc3339()
+            )]
+        );
+        let response = server
+            .get("/api/gates/somegroup/someservice/live/state")
+            .await;
+        assert_eq!(
+            response.json::<models::GateStateRep>().closure_reason,
+            Some(models::ClosureReason::Manual)
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn should_keep_gate_closed_after_it_was_closed_at_the_end_of_business_hours(
+        #[values(Backend::InMemory, Backend::DynamoDb)] backend: Backend,
+    ) {
+        // given
+        let (test_storage, _container) = backend.start().await;
+        let now = Arc::new(std::sync::Mutex::new(inside_business_times()));
+        let mut mock_clock = MockClock::new();
+        let clock_now = now.clone();
+        mock_clock
+            .expect_now()
+            .returning(move || *clock_now.lock().expect("failed to lock clock"));
+
+        let app_state = AppState::new(
+            test_storage,
+            Arc::new(mock_clock),
+            Arc::new(id_provider::default()),
+            Arc::new(date_time_switch::default()),
+        );
+        let server = TestServer::new(create_router(app_state.clone()))
+            .expect("failed to create test server");
+
+        let response = server
+            .post("/api/gates")
+            .json(&use_cases::create_gate::route::Payload {
+                group: "somegroup".to_owned(),
+                service: "someservice".to_owned(),
+                environment: "live".to_owned(),
+                display_order: None,
+            })
+            .await;
+        assert_eq!(response.status_code(), StatusCode::OK);
+        let response = server
+            .put("/api/gates/somegroup/someservice/live/state")
+            .json(&use_cases::update_gate_state::route::Payload {
+                state: GateState::Open,
+                ..Default::default()
+            })
+            .await;
+        assert_eq!(response.status_code(), StatusCode::OK);
+
+        // when
+        let gates = scheduler::find_gates(&app_state)
+            .await
+            .expect("failed to find gates");
+        let closed = scheduler::close_gates_after_business_hours(&app_state, &gates).await;
+
+        // then
+        assert_eq!(closed, Ok(0));
+
+        // when
+        *now.lock().expect("failed to lock clock") = at("2023-06-05T20:00:00+00:00");
+        let gates = scheduler::find_gates(&app_state)
+            .await
+            .expect("failed to find gates");
+        let closed = scheduler::close_gates_after_business_hours(&app_state, &gates).await;
+
+        // then
+        assert_eq!(closed, Ok(1));
+        let response = server.get("/api/gates/somegroup/someservice/live").await;
+        let gate = response.json::<models::Gate>();
+        assert_eq!(gate.state, models::GateState::Closed);
+        assert_eq!(
+            gate.comments
+                .into_iter()
+                .map(|comment| comment.message)
+                .collect::<Vec<_>>(),
+            vec!["Closed automatically at the end of business hours".to_owned()]
+        );
+
+        // when
+        *now.lock().expect("failed to lock clock") = at("2023-06-06T10:00:00+00:00");
+
+        // then
+        let response = server
+            .get("/api/gates/somegroup/someservice/live/state")
+            .await;
+        let state = response.json::<models::GateStateRep>();
+        assert_eq!(state.state, models::GateState::Closed);
+        assert_eq!(state.closure_reason, Some(models::ClosureReason::Manual));
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn should_reject_open_until_when_closing_gate(
+        #[values(Backend::InMemory, Backend::DynamoDb)] backend: Backend,
+    ) {
+        // given
+        let (test_storage, _container) = backend.start().await;
+        let mut mock_clock = MockClock::new();
+        mock_clock
+            .expect_now()
+            .return_const(inside_business_times());
+        let server = TestServer::new(create_router(AppState::new(
+            test_storage,
+            Arc::new(mock_clock),
+            Arc::new(id_provider::default()),
+            Arc::new(date_time_switch::default()),
+        )))
+        .expect("failed to create test server");
+
+        let response = server
+            .post("/api/gates")
+            .json(&use_cases::create_gate::route::Payload {
+                group: "somegroup".to_owned(),
+                service: "someservice".to_owned(),
+                environment: "live".to_owned(),
+                display_order: None,
+            })
+            .await;
+        assert_eq!(response.status_code(), StatusCode::OK);
+
+        // when
+        let response = server
+            .put("/api/gates/somegroup/someservice/live/state")
+            .json(&use_cases::update_gate_state::route::Payload {
+                state: GateState::Closed,
+                open_until: Some(inside_business_times() + TimeDelta::hours(1)),
+                ..Default::default()
+            })
+            .await;
+
+        // then
+        assert_eq!(response.status_code(), StatusCode::BAD_REQUEST);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn should_get_config(#[values(Backend::InMemory, Backend::DynamoDb)] backend: Backend) {
+        // given
+        let (test_storage, _container) = backend.start().await;
+        let now = inside_business_times();
+        let mut mock_clock = MockClock::new();
+        mock_clock.expect_now().return_const(now);
+
+        let router = create_router(AppState::new(
+            test_storage,
+            Arc::new(mock_clock),
+            Arc::new(id_provider::default()),
+            Arc::new(date_time_switch::default()),
+        ));
+
+        let server = TestServer::new(router).expect("failed to create test server");
+
+        // when
+        // try to set get the config with the system_time
+        let response = server.get("/api/config").await;
+
+        let openapi_business_week: models::BusinessWeek = types::BusinessWeek::default().into();
+        // then
+        assert_eq!(response.status_code(), StatusCode::OK);
+        assert_eq!(
+            response.json::<Config>(),
+            Config {
+                transitions: Some(models::Transitions {
+                    next_open: Some("2023-06-06T06:00:00+00:00".to_owned()),
+                    next_close: Some("2023-06-05T16:30:00+00:00".to_owned()),
+                }),
+                ..Config::new(
+                    now.to_rfc3339(),
+                    true,
+                    openapi_business_week,
+                    "Europe/Berlin".to_owned(),
+                    vec![]
+                )
+            }
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn should_set_business_week(
+        #[values(Backend::InMemory, Backend::DynamoDb)] backend: Backend,
+    ) {
+        // given
+        let (test_storage, _container) = backend.start().await;
+        let now = inside_business_times();
+        let mut mock_clock = MockClock::new();
+        mock_clock.expect_now().return_const(now);
+
+        let router = create_router(AppState::new(
+            test_storage.clone(),
+            Arc::new(mock_clock),
+            Arc::new(id_provider::default()),
+            Arc::new(date_time_switch::new(
+                test_storage,
+                true,
+                types::BusinessWeek::default(),
+                types::EnvironmentPatterns::default(),
+            )),
+        ));
+
+        let server = TestServer::new(router).expect("failed to create test server");
+
+        let response = server
+            .post("/api/gates")
+            .json(&use_cases::create_gate::route::Payload {
+                group: "somegroup".to_owned(),
+                service: "someservice".to_owned(),
+                environment: "live".to_owned(),
+                display_order: None,
+            })
+            .await;
+        assert_eq!(response.status_code(), StatusCode::OK);
+
+        let response = server
+            .put("/api/gates/somegroup/someservice/live/state")
+            .json(&use_cases::update_gate_state::route::Payload {
+                state: GateState::Open,
+                ..Default::default()
+            })
+            .await;
+        assert_eq!(response.status_code(), StatusCode::OK);
+
+        // when
+        let business_week = models::BusinessWeek {
+            monday: Some(vec![models::BusinessTimes::new(
+                "07:00:00Z".to_owned(),
+                "12:00:00Z".to_owned(),
+            )]),
+            ..models::BusinessWeek::default()
+        };
+        let response = server
+            .put("/api/config/business-week")
+            .json(&use_cases::set_business_week::route::Payload {
+                business_week: business_week.clone(),
+                time_zone: "UTC".to_owned(),
+            })
+            .await;
+
+        // then
+        assert_eq!(response.status_code(), StatusCode::OK);
+        assert_eq!(response.json::<models::BusinessWeek>(), business_week);
+
+        let response = server.get("/api/config").await;
+        assert_eq!(
+            response.json::<Config>(),
+            Config {
+                transitions: Some(models::Transitions {
+                    next_open: Some("2023-06-12T07:00:00+00:00".to_owned()),
+                    next_close: Some("2023-06-12T12:00:00+00:00".to_owned()),
+                }),
+                ..Config::new(
+                    now.to_rfc3339(),
+                    true,
+                    business_week,
+                    "UTC".to_owned(),
+                    vec![]
+                )
+            }
+        );
+
+        let response = server
+            .get("/api/gates/somegroup/someservice/live/state")
+            .await;
+        assert_eq!(
+            response.json::<models::GateStateRep>().state,
+            models::GateState::Closed
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn should_use_business_week_profile_of_gate(
+        #[values(Backend::InMemory, Backend::DynamoDb)] backend: Backend,
+    ) {
+        // given
+        let (test_storage, _container) = backend.start().await;
+        let now = inside_business_times();
+        let mut mock_clock = MockClock::new();
+        mock_clock.expect_now().return_const(now);
+
+        let router = create_router(AppState::new(
+            test_storage.clone(),
+            Arc::new(mock_clock),
+            Arc::new(id_provider::default()),
+            Arc::new(date_time_switch::new(
+                test_storage,
+                true,
+                types::BusinessWeek::default(),
+                types::EnvironmentPatterns::default(),
+            )),
+        ));
+
+        let server = TestServer::new(router).expect("failed to create test server");
+
+        for environment in ["develop", "live"] {
+            let response = server
+                .post("/api/gates")
+                .json(&use_cases::create_gate::route::Payload {
+                    group: "somegroup".to_owned(),
+                    service: "someservice".to_owned(),
+                    environment: environment.to_owned(),
+                    display_order: None,
+                })
+                .await;
+            assert_eq!(response.status_code(), StatusCode::OK);
+
+            let response = server
+                .put(&format!(
+                    "/api/gates/somegroup/someservice/{environment}/state"
+                ))
+                .json(&use_cases::update_gate_state::route::Payload {
+                    state: GateState::Open,
+                    ..Default::default()
+                })
+                .await;
+            assert_eq!(response.status_code(), StatusCode::OK);
+        }
+
+        // when
+        let business_week = models::BusinessWeek {
+            monday: Some(vec![models::BusinessTimes::new(
+                "07:00:00Z".to_owned(),
+                "12:00:00Z".to_owned(),
+            )]),
+            ..models::BusinessWeek::default()
+        };
+        let response = server
+            .put("/api/config/business-week-profiles")
+            .json(&use_cases::set_business_week_profile::route::Payload {
+                group: "somegroup".to_owned(),
+                service: Some("someservice".to_owned()),
+                environment: Some("live".to_owned()),
+                business_week: business_week.clone(),
+                time_zone: "UTC".to_owned(),
+            })
+            .await;
+
+        // then
+        assert_eq!(response.status_code(), StatusCode::OK);
+
+        let response = server.get("/api/config/business-week-profiles").await;
+        assert_eq!(
+            response.json::<Vec<models::BusinessWeekProfile>>(),
+            vec![models::BusinessWeekProfile {
+                group: "somegroup".to_owned(),
+                service: Some("someservice".to_owned()),
+                environment: Some("live".to_owned()),
+                business_week,
+                time_zone: "UTC".to_owned(),
+            }]
+        );
+
+        let response = server
+            .get("/api/gates/somegroup/someservice/live/state")
+            .await;
+        assert_eq!(
+            response.json::<models::GateStateRep>().state,
+            models::GateState::Closed
+        );
+        let response = server
+            .get("/api/gates/somegroup/someservice/develop/state")
+            .await;
+        assert_eq!(
+            response.json::<models::GateStateRep>().state,
+            models::GateState::Open
+        );
+
+        // when
+        let response = server
+            .delete("/api/config/business-week-profiles")
+            .add_query_param("group", "somegroup")
+            .add_query_param("service", "someservice")
+            .add_query_param("environment", "live")
+            .await;
+
+        // then
+        assert_eq!(response.status_code(), StatusCode::OK);
+
+        let response = server
+            .get("/api/gates/somegroup/someservice/live/state")
+            .await;
+        assert_eq!(
+            response.json::<models::GateStateRep>().state,
+            models::GateState::Open
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn should_close_gates_on_closed_days(
+        #[values(Backend::InMemory, Backend::DynamoDb)] backend: Backend,
+    ) {
+        // given
+        let (test_storage, _container) = backend.start().await;
+        let now = inside_business_times();
+        let mut mock_clock = MockClock::new();
+        mock_clock.expect_now().return_const(now);
+
+        let router = create_router(AppState::new(
+            test_storage.clone(),
+            Arc::new(mock_clock),
+            Arc::new(id_provider::default()),
+            Arc::new(date_time_switch::new(
+                test_storage,
+                true,
+                types::BusinessWeek::default(),
+                types::EnvironmentPatterns::default(),
+            )),
+        ));
+
+        let server = TestServer::new(router).expect("failed to create test server");
+
+        let response = server
+            .post("/api/gates")
+            .json(&use_cases::create_gate::route::Payload {
+                group: "somegroup".to_owned(),
+                service: "someservice".to_owned(),
+                environment: "live".to_owned(),
+                display_order: None,
+            })
+            .await;
+        assert_eq!(response.status_code(), StatusCode::OK);
+
+        let response = server
+            .put("/api/gates/somegroup/someservice/live/state")
+            .json(&use_cases::update_gate_state::route::Payload {
+                state: GateState::Open,
+                ..Default::default()
+            })
+            .await;
+        assert_eq!(response.status_code(), StatusCode::OK);
+
+        // when
+        let response = server
+            .put("/api/config/closed-days/2023-06-05")
+            .json(&use_cases::set_closed_day::route::Payload {
+                reason: "some holiday".to_owned(),
+            })
+            .await;
+        assert_eq!(response.status_code(), StatusCode::OK);
+        let response = server
+            .post("/api/config/closed-days")
+            .text("BEGIN:VCALENDAR\nBEGIN:VEVENT\nDTSTART;VALUE=DATE:20231225\nSUMMARY:Christmas\nEND:VEVENT\nEND:VCALENDAR\n")
+            .await;
+        assert_eq!(response.status_code(), StatusCode::OK);
+
+        // then
+        let expected_closed_days = vec![
+            models::ClosedDay::new("2023-06-05".to_owned(), "some holiday".to_owned()),
+            models::ClosedDay::new("2023-12-25".to_owned(), "Christmas".to_owned()),
+        ];
+        let response = server.get("/api/config/closed-days").await;
+        assert_eq!(
+            response.json::<Vec<models::ClosedDay>>(),
+            expected_closed_days
+        );
+        let response = server.get("/api/config").await;
+        assert_eq!(response.json::<Config>().closed_days, expected_closed_days);
+
         let response = server
-            .put("/api/gates/somegroup/someservice/live/state")
-            .json(&crate::use_cases::update_gate_state::route::Payload {
-                state: GateState::Open,
-            })
+            .get("/api/gates/somegroup/someservice/live/state")
             .await;
-
-        assert_eq!(response.status_code(), StatusCode::CONFLICT);
         assert_eq!(
-            response.json::<String>(),
-            "Already after business hours - rejecting attempt to change state".to_owned()
+            response.json::<models::GateStateRep>().state,
+            models::GateState::Closed
         );
 
-        let response = server.get("/api/gates/somegroup/someservice/live").await;
+        // when
+        let response = server.delete("/api/config/closed-days/2023-06-05").await;
 
         // then
         assert_eq!(response.status_code(), StatusCode::OK);
+        let response = server
+            .get("/api/gates/somegroup/someservice/live/state")
+            .await;
         assert_eq!(
-            response.json::<models::Gate>(),
-            models::Gate {
-                group: "somegroup".to_string(),
-                service: "someservice".to_string(),
-                environment: "live".to_string(),
-                state: models::GateState::Closed,
-                comments: vec![],
-                last_updated: now.to_rfc3339(),
-                display_order: Option::default(),
-            },
+            response.json::<models::GateStateRep>().state,
+            models::GateState::Open
         );
+        let response = server.delete("/api/config/closed-days/2023-06-05").await;
+        assert_eq!(response.status_code(), StatusCode::NO_CONTENT);
     }
 
+    #[rstest]
     #[tokio::test]
-    async fn should_get_config() {
+    async fn should_not_set_invalid_business_week(
+        #[values(Backend::InMemory, Backend::DynamoDb)] backend: Backend,
+    ) {
         // given
-        let now = inside_business_times();
-        let mut mock_clock = MockClock::new();
-        mock_clock.expect_now().return_const(now);
-
-        let dynamodb_container = DynamoDb::default()
-            .start()
-            .await
-            .expect("dynamoDb docker container to be started");
-        let port = dynamodb_container
-            .get_host_port_ipv4(8000)
-            .await
-            .expect("dynamoDb docker container host port go be found");
-
-        let dynamodb_storage = storage::test(port).await;
+        let (test_storage, _container) = backend.start().await;
         let router = create_router(AppState::new(
-            Arc::new(dynamodb_storage),
-            Arc::new(mock_clock),
+            test_storage,
+            Arc::new(MockClock::new()),
             Arc::new(id_provider::default()),
             Arc::new(date_time_switch::default()),
         ));
@@ -802,36 +2323,36 @@ mod acceptance_tests {
         let server = TestServer::new(router).expect("failed to create test server");
 
         // when
-        // try to set get the config with the system_time
-        let response = server.get("/api/config").await;
+        let response = server
+            .put("/api/config/business-week")
+            .json(&use_cases::set_business_week::route::Payload {
+                business_week: models::BusinessWeek {
+                    monday: Some(vec![models::BusinessTimes::new(
+                        "07:00:00Z".to_owned(),
+                        "07:00:00Z".to_owned(),
+                    )]),
+                    ..models::BusinessWeek::default()
+                },
+                time_zone: "UTC".to_owned(),
+            })
+            .await;
 
-        let openapi_business_week: models::BusinessWeek = types::BusinessWeek::default().into();
         // then
-        assert_eq!(response.status_code(), StatusCode::OK);
-        assert_eq!(
-            response.json::<Config>(),
-            Config::new(now.to_rfc3339(), openapi_business_week)
-        );
+        assert_eq!(response.status_code(), StatusCode::BAD_REQUEST);
     }
+    #[rstest]
     #[tokio::test]
-    async fn should_set_display_order() {
+    async fn should_set_display_order(
+        #[values(Backend::InMemory, Backend::DynamoDb)] backend: Backend,
+    ) {
         // given
+        let (test_storage, _container) = backend.start().await;
         let now = inside_business_times();
         let mut mock_clock = MockClock::new();
         mock_clock.expect_now().return_const(now);
 
-        let dynamodb_container = DynamoDb::default()
-            .start()
-            .await
-            .expect("dynamoDb docker container to be started");
-        let port = dynamodb_container
-            .get_host_port_ipv4(8000)
-            .await
-            .expect("dynamoDb docker container host port go be found");
-
-        let dynamodb_storage = storage::test(port).await;
         let router = create_router(AppState::new(
-            Arc::new(dynamodb_storage),
+            test_storage,
             Arc::new(mock_clock),
             Arc::new(id_provider::default()),
             Arc::new(date_time_switch::default()),
@@ -870,7 +2391,10 @@ mod acceptance_tests {
         // when
         let response = server
             .put("/api/gates/somegroup/someservice/develop/display-order")
-            .json(&crate::use_cases::update_display_order::route::Payload { display_order: 1 })
+            .json(&crate::use_cases::update_display_order::route::Payload {
+                display_order: 1,
+                expected_version: None,
+            })
             .await;
 
         assert_eq!(response.status_code(), StatusCode::OK);
@@ -884,6 +2408,10 @@ mod acceptance_tests {
                 comments: vec![],
                 last_updated: now.to_rfc3339(),
                 display_order: Some(1f64),
+                version: 1,
+                open_until: None,
+                open_remaining_seconds: None,
+                closure_reason: Some(models::ClosureReason::Manual),
             }
         );
 
@@ -917,6 +2445,322 @@ mod acceptance_tests {
         );
     }
 
+    #[rstest]
+    #[tokio::test]
+    async fn should_reject_changes_of_outdated_gates(
+        #[values(Backend::InMemory, Backend::DynamoDb)] backend: Backend,
+    ) {
+        // given
+        let (test_storage, _container) = backend.start().await;
+        let now = inside_business_times();
+        let mut mock_clock = MockClock::new();
+        mock_clock.expect_now().return_const(now);
+
+        let router = create_router(AppState::new(
+            test_storage,
+            Arc::new(mock_clock),
+            Arc::new(id_provider::default()),
+            Arc::new(date_time_switch::default()),
+        ));
+
+        let server = TestServer::new(router).expect("failed to create test server");
+
+        let response = server
+            .post("/api/gates")
+            .json(&use_cases::create_gate::route::Payload {
+                group: "somegroup".to_owned(),
+                service: "someservice".to_owned(),
+                environment: "develop".to_owned(),
+                display_order: None,
+            })
+            .await;
+        assert_eq!(response.status_code(), StatusCode::OK);
+        assert_eq!(response.header(ETAG), "\"0\"");
+
+        // when
+        let response = server
+            .put("/api/gates/somegroup/someservice/develop/state")
+            .add_header(IF_MATCH, "\"0\"")
+            .json(&crate::use_cases::update_gate_state::route::Payload {
+                state: GateState::Open,
+                ..Default::default()
+            })
+            .await;
+
+        // then
+        assert_eq!(response.status_code(), StatusCode::OK);
+        assert_eq!(response.header(ETAG), "\"1\"");
+
+        // when
+        let response = server
+            .put("/api/gates/somegroup/someservice/develop/state")
+            .add_header(IF_MATCH, "\"0\"")
+            .json(&crate::use_cases::update_gate_state::route::Payload {
+                state: GateState::Closed,
+                ..Default::default()
+            })
+            .await;
+
+        // then
+        assert_eq!(response.status_code(), StatusCode::PRECONDITION_FAILED);
+
+        // when
+        let response = server
+            .put("/api/gates/somegroup/someservice/develop/display-order")
+            .json(&crate::use_cases::update_display_order::route::Payload {
+                display_order: 1,
+                expected_version: Some(0),
+            })
+            .await;
+
+        // then
+        assert_eq!(response.status_code(), StatusCode::PRECONDITION_FAILED);
+
+        // when
+        let response = server
+            .put("/api/gates/somegroup/someservice/develop/display-order")
+            .add_header(IF_MATCH, "\"1\"")
+            .json(&crate::use_cases::update_display_order::route::Payload {
+                display_order: 1,
+                expected_version: Some(0),
+            })
+            .await;
+
+        // then
+        assert_eq!(response.status_code(), StatusCode::BAD_REQUEST);
+
+        let response = server.get("/api/gates/somegroup/someservice/develop").await;
+        assert_eq!(response.status_code(), StatusCode::OK);
+        assert_eq!(response.header(ETAG), "\"1\"");
+        assert_eq!(
+            response.json::<models::Gate>(),
+            models::Gate {
+                group: "somegroup".to_string(),
+                service: "someservice".to_string(),
+                environment: "develop".to_string(),
+                state: models::GateState::Open,
+                comments: vec![],
+                last_updated: now.to_rfc3339(),
+                display_order: Option::default(),
+                version: 1,
+                open_until: None,
+                open_remaining_seconds: None,
+                closure_reason: None,
+            }
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn should_list_gates_by_group_and_service(
+        #[values(Backend::InMemory, Backend::DynamoDb)] backend: Backend,
+    ) {
+        // given
+        let (test_storage, _container) = backend.start().await;
+        let now = inside_business_times();
+        let mut mock_clock = MockClock::new();
+        mock_clock.expect_now().return_const(now);
+
+        let router = create_router(AppState::new(
+            test_storage,
+            Arc::new(mock_clock),
+            Arc::new(id_provider::default()),
+            Arc::new(date_time_switch::default()),
+        ));
+
+        let server = TestServer::new(router).expect("failed to create test server");
+
+        for (group, service) in [
+            ("somegroup", "someservice"),
+            ("somegroup", "someotherservice"),
+            ("someothergroup", "someservice"),
+        ] {
+            let response = server
+                .post("/api/gates")
+                .json(&use_cases::create_gate::route::Payload {
+                    group: group.to_owned(),
+                    service: service.to_owned(),
+                    environment: "develop".to_owned(),
+                    display_order: None,
+                })
+                .await;
+            assert_eq!(response.status_code(), StatusCode::OK);
+        }
+
+        // when
+        let response = server.get("/api/gates/somegroup").await;
+
+        // then
+        assert_eq!(response.status_code(), StatusCode::OK);
+        assert_eq!(
+            response.json::<models::Group>(),
+            models::Group {
+                name: "somegroup".to_string(),
+                services: vec![
+                    models::Service {
+                        name: "someotherservice".to_string(),
+                        environments: vec![models::Environment {
+                            name: "develop".to_string(),
+                            gate: models::Gate {
+                                service: "someotherservice".to_string(),
+                                ..expected_gate_representation(now, "develop".to_string())
+                            },
+                        }],
+                    },
+                    models::Service {
+                        name: "someservice".to_string(),
+                        environments: vec![models::Environment {
+                            name: "develop".to_string(),
+                            gate: expected_gate_representation(now, "develop".to_string()),
+                        }],
+                    },
+                ],
+            }
+        );
+
+        // when
+        let response = server.get("/api/gates/somegroup/someservice").await;
+
+        // then
+        assert_eq!(response.status_code(), StatusCode::OK);
+        assert_eq!(
+            response.json::<models::Service>(),
+            models::Service {
+                name: "someservice".to_string(),
+                environments: vec![models::Environment {
+                    name: "develop".to_string(),
+                    gate: expected_gate_representation(now, "develop".to_string()),
+                }],
+            }
+        );
+
+        // when
+        let response = server.get("/api/gates/unknowngroup").await;
+
+        // then
+        assert_eq!(response.status_code(), StatusCode::NO_CONTENT);
+
+        // when
+        let response = server.get("/api/gates/somegroup/unknownservice").await;
+
+        // then
+        assert_eq!(response.status_code(), StatusCode::NO_CONTENT);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn should_record_gate_history(
+        #[values(Backend::InMemory, Backend::DynamoDb)] backend: Backend,
+    ) {
+        // given
+        let (test_storage, _container) = backend.start().await;
+        let now = inside_business_times();
+        let mut mock_clock = MockClock::new();
+        mock_clock.expect_now().return_const(now);
+
+        let router = create_router(AppState::new(
+            test_storage,
+            Arc::new(mock_clock),
+            Arc::new(id_provider::default()),
+            Arc::new(date_time_switch::default()),
+        ));
+
+        let server = TestServer::new(router).expect("failed to create test server");
+
+        let response = server
+            .post("/api/gates")
+            .add_header(ACTOR_HEADER, "Max")
+            .json(&use_cases::create_gate::route::Payload {
+                group: "somegroup".to_owned(),
+                service: "someservice".to_owned(),
+                environment: "develop".to_owned(),
+                display_order: None,
+            })
+            .await;
+        assert_eq!(response.status_code(), StatusCode::OK);
+
+        let response = server
+            .put("/api/gates/somegroup/someservice/develop/state")
+            .add_header(ACTOR_HEADER, "Erika")
+            .json(&use_cases::update_gate_state::route::Payload {
+                state: GateState::Open,
+                ..Default::default()
+            })
+            .await;
+        assert_eq!(response.status_code(), StatusCode::OK);
+
+        let response = server
+            .post("/api/gates/somegroup/someservice/develop/comments")
+            .json(&use_cases::add_comment::route::Payload {
+                message: "Some comment message".to_owned(),
+                expected_version: None,
+            })
+            .await;
+        assert_eq!(response.status_code(), StatusCode::OK);
+
+        // when
+        let response = server
+            .get("/api/gates/somegroup/someservice/develop/history")
+            .add_query_param("limit", 2)
+            .await;
+
+        // then
+        assert_eq!(response.status_code(), StatusCode::OK);
+        let first_page = response.json::<models::GateHistory>();
+        assert_eq!(
+            first_page
+                .entries
+                .iter()
+                .map(|entry| (
+                    entry.event,
+                    entry.old_value.clone(),
+                    entry.new_value.clone(),
+                    entry.actor.clone()
+                ))
+                .collect::<Vec<_>>(),
+            vec![
+                (
+                    models::GateEvent::CommentAdded,
+                    None,
+                    Some("Some comment message".to_owned()),
+                    None
+                ),
+                (
+                    models::GateEvent::StateChanged,
+                    Some("closed".to_owned()),
+                    Some("open".to_owned()),
+                    Some("Erika".to_owned())
+                ),
+            ]
+        );
+        let next_cursor = first_page.next_cursor.expect("next cursor to be present");
+
+        // when
+        let response = server
+            .get("/api/gates/somegroup/someservice/develop/history")
+            .add_query_param("limit", 2)
+            .add_query_param("cursor", next_cursor)
+            .await;
+
+        // then
+        assert_eq!(response.status_code(), StatusCode::OK);
+        let second_page = response.json::<models::GateHistory>();
+        assert_eq!(second_page.entries.len(), 1);
+        assert_eq!(second_page.entries[0].event, models::GateEvent::Created);
+        assert_eq!(second_page.entries[0].actor, Some("Max".to_owned()));
+        assert_eq!(second_page.entries[0].timestamp, now.to_rfc3339());
+        assert_eq!(second_page.next_cursor, None);
+
+        // when
+        let response = server
+            .get("/api/gates/somegroup/someservice/develop/history")
+            .add_query_param("cursor", "not a cursor")
+            .await;
+
+        // then
+        assert_eq!(response.status_code(), StatusCode::BAD_REQUEST);
+    }
+
     fn expected_gate_representation(now: DateTime<Utc>, environment: String) -> models::Gate {
         models::Gate {
             group: "somegroup".to_string(),
@@ -926,6 +2770,10 @@ mod acceptance_tests {
             comments: vec![],
             last_updated: now.to_rfc3339(),
             display_order: Option::default(),
+            version: 0,
+            open_until: None,
+            open_remaining_seconds: None,
+            closure_reason: Some(models::ClosureReason::Manual),
         }
     }
     fn expected_gate_representation_with_display_order(
@@ -941,6 +2789,10 @@ mod acceptance_tests {
             comments: vec![],
             last_updated: now.to_rfc3339(),
             display_order: Some(f64::from(display_order)),
+            version: 1,
+            open_until: None,
+            open_remaining_seconds: None,
+            closure_reason: Some(models::ClosureReason::Manual),
         }
     }
 }
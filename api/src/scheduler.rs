@@ -0,0 +1,215 @@
+use std::env;
+use std::time::Duration;
+
+use lambda_runtime::{service_fn, LambdaEvent};
+use serde_json::Value;
+
+use crate::business_week;
+use crate::types::app_state::AppState;
+use crate::types::Gate;
+
+pub const ENV_GATES_SCHEDULER: &str = "GATES_SCHEDULER";
+pub const ENV_GATES_SCHEDULER_INTERVAL_SECONDS: &str = "GATES_SCHEDULER_INTERVAL_SECONDS";
+
+const DEFAULT_INTERVAL_SECONDS: u64 = 60;
+
+/// How the binary runs the periodic jobs instead of serving the API, if at all.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    Off,
+    /// Once per invocation, e.g. by a scheduled event.
+    Lambda,
+    /// Repeatedly in a standalone process.
+    Loop(Duration),
+}
+
+pub fn configured_mode() -> Mode {
+    parse_mode(
+        env::var(ENV_GATES_SCHEDULER).ok().as_deref(),
+        env::var(ENV_GATES_SCHEDULER_INTERVAL_SECONDS)
+            .ok()
+            .as_deref(),
+    )
+}
+
+fn parse_mode(mode: Option<&str>, interval_seconds: Option<&str>) -> Mode {
+    match mode.map(str::trim) {
+        None | Some("off") => Mode::Off,
+        Some("lambda") => Mode::Lambda,
+        Some("loop") => Mode::Loop(Duration::from_secs(interval_seconds.map_or(
+            DEFAULT_INTERVAL_SECONDS,
+            |seconds| {
+                seconds
+                    .trim()
+                    .parse()
+                    .ok()
+                    .filter(|seconds| *seconds > 0)
+                    .unwrap_or_else(|| {
+                        panic!(
+                            "unsupported value '{seconds}' for {ENV_GATES_SCHEDULER_INTERVAL_SECONDS}"
+                        )
+                    })
+            },
+        ))),
+        Some(mode) => panic!("unsupported value '{mode}' for {ENV_GATES_SCHEDULER}"),
+    }
+}
+
+pub async fn run(app_state: AppState, mode: Mode) -> Result<(), lambda_runtime::Error> {
+    let auto_close = business_week::configured_auto_close();
+    match mode {
+        Mode::Off => Ok(()),
+        Mode::Lambda => {
+            lambda_runtime::run(service_fn(|_: LambdaEvent<Value>| {
+                run_jobs(&app_state, auto_close)
+            }))
+            .await
+        }
+        Mode::Loop(interval) => {
+            let mut interval = tokio::time::interval(interval);
+            loop {
+                interval.tick().await;
+                // a failed run is retried with the next tick, nothing is lost until a job succeeds
+                if let Err(error) = run_jobs(&app_state, auto_close).await {
+                    tracing::error!("failed to run periodic jobs: {error}");
+                }
+            }
+        }
+    }
+}
+
+/// Runs all periodic jobs, each of them even if another one failed. Open gates are only closed at
+/// the end of business hours if `auto_close` is set.
+pub async fn run_jobs(app_state: &AppState, auto_close: bool) -> Result<(), String> {
+    let errors = [
+        close_gates(app_state, auto_close).await.err(),
+        apply_due_state_changes(app_state).await.err(),
+        purge_trash(app_state).await.err(),
+    ];
+    errors.into_iter().flatten().next().map_or(Ok(()), Err)
+}
+
+/// Both jobs that close gates look at all of them, so they share one read.
+async fn close_gates(app_state: &AppState, auto_close: bool) -> Result<(), String> {
+    let gates = find_gates(app_state).await?;
+    let errors = [
+        close_expired_gates(app_state, &gates).await.err(),
+        if auto_close {
+            close_gates_after_business_hours(app_state, &gates)
+                .await
+                .err()
+        } else {
+            None
+        },
+    ];
+    errors.into_iter().flatten().next().map_or(Ok(()), Err)
+}
+
+pub async fn find_gates(app_state: &AppState) -> Result<Vec<Gate>, String> {
+    app_state
+        .storage
+        .find_all()
+        .await
+        .map_err(|error| format!("{error:?}"))
+}
+
+pub async fn close_expired_gates(app_state: &AppState, gates: &[Gate]) -> Result<usize, String> {
+    let closed = app_state
+        .use_cases
+        .close_expired_gates
+        .execute(
+            gates,
+            app_state.storage.as_ref(),
+            app_state.clock.as_ref(),
+            app_state.id_provider.as_ref(),
+        )
+        .await
+        .map_err(|error| format!("{error:?}"))?;
+    tracing::info!("closed {closed} gates whose open state expired");
+    Ok(closed)
+}
+
+pub async fn close_gates_after_business_hours(
+    app_state: &AppState,
+    gates: &[Gate],
+) -> Result<usize, String> {
+    let closed = app_state
+        .use_cases
+        .close_gates_after_business_hours
+        .execute(
+            gates,
+            app_state.storage.as_ref(),
+            app_state.clock.as_ref(),
+            app_state.date_time_switch.as_ref(),
+            app_state.id_provider.as_ref(),
+        )
+        .await
+        .map_err(|error| format!("{error:?}"))?;
+    tracing::info!("closed {closed} gates at the end of business hours");
+    Ok(closed)
+}
+
+pub async fn apply_due_state_changes(app_state: &AppState) -> Result<usize, String> {
+    let applied = app_state
+        .use_cases
+        .apply_scheduled_state_changes
+        .execute(
+            app_state.storage.as_ref(),
+            app_state.clock.as_ref(),
+            app_state.date_time_switch.as_ref(),
+            app_state.id_provider.as_ref(),
+        )
+        .await
+        .map_err(|error| format!("{error:?}"))?;
+    tracing::info!("applied {applied} scheduled state changes");
+    Ok(applied)
+}
+
+pub async fn purge_trash(app_state: &AppState) -> Result<u64, String> {
+    let purged = app_state
+        .use_cases
+        .purge_trash
+        .execute(
+            app_state.storage.as_ref(),
+            app_state.clock.as_ref(),
+            app_state.trash_retention,
+        )
+        .await
+        .map_err(|error| format!("{error:?}"))?;
+    tracing::info!("purged {purged} gates from the trash");
+    Ok(purged)
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use rstest::rstest;
+    use similar_asserts::assert_eq;
+
+    use super::*;
+
+    #[rstest]
+    #[case(None, None, Mode::Off)]
+    #[case(Some("off"), None, Mode::Off)]
+    #[case(Some("lambda"), None, Mode::Lambda)]
+    #[case(Some("loop"), None, Mode::Loop(Duration::from_mins(1)))]
+    #[case(Some(" loop "), Some("5"), Mode::Loop(Duration::from_secs(5)))]
+    fn should_parse_mode(
+        #[case] mode: Option<&str>,
+        #[case] interval_seconds: Option<&str>,
+        #[case] expected: Mode,
+    ) {
+        assert_eq!(parse_mode(mode, interval_seconds), expected);
+    }
+
+    #[rstest]
+    #[case(Some("cron"), None)]
+    #[case(Some("loop"), Some("0"))]
+    #[case(Some("loop"), Some("a minute"))]
+    #[should_panic(expected = "GATES_SCHEDULER")]
+    fn should_reject_invalid_mode(
+        #[case] mode: Option<&str>,
+        #[case] interval_seconds: Option<&str>,
+    ) {
+        parse_mode(mode, interval_seconds);
+    }
+}
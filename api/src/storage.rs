@@ -1,20 +1,32 @@
 use async_trait::async_trait;
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
 use serde::Serialize;
 use std::env;
-use std::future::Future;
+use std::future::{ready, Future};
 use std::sync::Arc;
 
 use types::GateState;
 
 use crate::storage::demo::ReadOnlyStorage;
 use crate::storage::dynamodb::DynamoDbStorage;
+use crate::storage::in_memory::InMemoryStorage;
+use crate::storage::postgres::PostgresStorage;
+use crate::storage::sqlite::SqliteStorage;
 use crate::types;
-use crate::types::{Comment, Gate, GateKey};
+use crate::types::{
+    BusinessWeek, BusinessWeekProfile, BusinessWeekScope, ClosedDay, Comment, Freeze, FreezeWindow,
+    Gate, GateDependencies, GateKey, HistoryEntry, HistoryPage, ScheduledStateChange,
+};
 
+#[cfg(test)]
+mod conformance;
 mod demo;
 pub mod dynamodb;
+pub mod in_memory;
+pub mod postgres;
 mod quote;
+mod sql;
+pub mod sqlite;
 
 const fn is_local() -> bool {
     #[cfg(feature = "local")]
@@ -24,22 +36,42 @@ const fn is_local() -> bool {
 }
 
 pub const DEMO_MODE_ACTIVE: &str = "DEMO_MODE";
+pub const STORAGE_BACKEND: &str = "GATES_STORAGE_BACKEND";
 
 fn is_demo_mode() -> bool {
     env::var(DEMO_MODE_ACTIVE).is_ok()
 }
 
+enum Backend {
+    DynamoDb,
+    InMemory,
+    Sqlite,
+    Postgres,
+}
+
+fn backend() -> Backend {
+    match env::var(STORAGE_BACKEND).as_deref() {
+        Err(_) | Ok("dynamodb") => Backend::DynamoDb,
+        Ok("in_memory") => Backend::InMemory,
+        Ok("sqlite") => Backend::Sqlite,
+        Ok("postgres") => Backend::Postgres,
+        Ok(other) => panic!("unsupported value '{other}' for {STORAGE_BACKEND}"),
+    }
+}
+
 pub async fn default() -> Arc<dyn Storage + Send + Sync> {
-    if is_local() {
-        get_storage(get_local_database()).await
-    } else {
+    match backend() {
+        Backend::InMemory => get_storage(ready(InMemoryStorage::default())).await,
+        Backend::Sqlite => get_storage(SqliteStorage::new()).await,
+        Backend::Postgres => get_storage(PostgresStorage::new()).await,
+        Backend::DynamoDb if is_local() => get_storage(get_local_database()).await,
         #[allow(clippy::large_futures)]
-        get_storage(get_live_database()).await
+        Backend::DynamoDb => get_storage(get_live_database()).await,
     }
 }
 
-async fn get_storage(
-    database: impl Future<Output = DynamoDbStorage> + Sized + Send,
+async fn get_storage<S: Storage + Send + Sync + 'static>(
+    database: impl Future<Output = S> + Sized + Send,
 ) -> Arc<dyn Storage + Send + Sync> {
     if is_demo_mode() {
         Arc::new(ReadOnlyStorage::new(Box::new(database.await)))
@@ -61,9 +93,75 @@ pub async fn test(port: u16) -> impl Storage {
     DynamoDbStorage::new_local(port).await
 }
 
+#[allow(dead_code)]
+pub fn in_memory() -> impl Storage {
+    InMemoryStorage::default()
+}
+
+fn check_version(
+    key: &GateKey,
+    version: u32,
+    expected_version: Option<u32>,
+) -> Result<(), UpdateError> {
+    match expected_version {
+        Some(expected_version) if expected_version != version => Err(UpdateError::VersionConflict(
+            format!("gate {key} has version {version} but version {expected_version} was expected"),
+        )),
+        _ => Ok(()),
+    }
+}
+
+fn parse_position_cursor(cursor: Option<String>) -> Result<Option<i64>, FindError> {
+    cursor
+        .map(|cursor| {
+            cursor
+                .parse()
+                .map_err(|_| FindError::InvalidCursor(format!("invalid history cursor {cursor}")))
+        })
+        .transpose()
+}
+
+/// Expects up to `limit + 1` entries with their cursor, newest first. The additional entry only
+/// signals that there is a next page.
+fn history_page(mut entries: Vec<(String, HistoryEntry)>, limit: u32) -> HistoryPage {
+    let has_next_page = entries.len() > limit as usize;
+    entries.truncate(limit as usize);
+    HistoryPage {
+        next_cursor: has_next_page
+            .then(|| entries.last().map(|(cursor, _)| cursor.clone()))
+            .flatten(),
+        entries: entries.into_iter().map(|(_, entry)| entry).collect(),
+    }
+}
+
+fn encode_business_week(business_week: &BusinessWeek) -> Result<String, InsertError> {
+    serde_json::to_string(business_week).map_err(|error| InsertError::Other(error.to_string()))
+}
+
+fn decode_business_week(value: &str) -> Result<BusinessWeek, FindError> {
+    serde_json::from_str(value).map_err(|error| {
+        FindError::ItemCouldNotBeDecoded(format!(
+            "could not decode business week (mapping error: {error})"
+        ))
+    })
+}
+
+fn encode_gate_keys(keys: &[GateKey]) -> Result<String, InsertError> {
+    serde_json::to_string(keys).map_err(|error| InsertError::Other(error.to_string()))
+}
+
+fn decode_gate_keys(value: &str) -> Result<Vec<GateKey>, FindError> {
+    serde_json::from_str(value).map_err(|error| {
+        FindError::ItemCouldNotBeDecoded(format!(
+            "could not decode gate dependencies (mapping error: {error})"
+        ))
+    })
+}
+
 #[derive(Debug, Eq, PartialEq, Serialize)]
 pub enum UpdateError {
     ItemToUpdateNotFound(String),
+    VersionConflict(String),
     Other(String),
 }
 
@@ -77,6 +175,7 @@ pub enum InsertError {
 #[derive(Debug)]
 pub enum FindError {
     ItemCouldNotBeDecoded(String),
+    InvalidCursor(String),
     Other(String),
 }
 
@@ -91,22 +190,63 @@ pub enum DeleteError {
 #[async_trait]
 pub trait Storage {
     async fn insert(&self, gate: &Gate) -> Result<(), InsertError>;
+    /// Inserts either all gates or, if any of them already exists, none of them.
+    async fn insert_all(&self, gates: &[Gate]) -> Result<(), InsertError>;
+    /// Inserts all gates at once, but each on its own, so a gate that already exists does not keep
+    /// the others from being inserted. Returns the result of each gate in the given order.
+    async fn insert_each(
+        &self,
+        gates: &[Gate],
+    ) -> Result<Vec<Result<(), InsertError>>, InsertError>;
     async fn find_one(&self, key: GateKey) -> Result<Option<Gate>, FindError>;
     async fn find_all(&self) -> Result<Vec<Gate>, FindError>;
-    async fn delete(&self, key: GateKey) -> Result<(), DeleteError>;
+    async fn find_by_group(&self, group: String) -> Result<Vec<Gate>, FindError>;
+    async fn find_by_group_and_service(
+        &self,
+        group: String,
+        service: String,
+    ) -> Result<Vec<Gate>, FindError>;
+    /// Hides the gate from all other operations until it is restored or purged.
+    async fn move_to_trash(
+        &self,
+        key: GateKey,
+        deleted_at: DateTime<Utc>,
+    ) -> Result<(), DeleteError>;
+    /// Restores the gate only if it was moved to the trash at or after the given instant.
+    async fn restore(
+        &self,
+        key: GateKey,
+        deleted_since: DateTime<Utc>,
+    ) -> Result<Gate, UpdateError>;
+    /// Permanently deletes all gates moved to the trash before the given instant.
+    async fn purge_trash(&self, deleted_before: DateTime<Utc>) -> Result<u64, DeleteError>;
 
+    /// Opening the gate with `open_until` closes it again at that instant, any other change keeps it in its state.
     async fn update_state_and_last_updated(
         &self,
         key: GateKey,
         state: GateState,
+        open_until: Option<DateTime<Utc>>,
         last_updated: DateTime<Utc>,
+        expected_version: Option<u32>,
     ) -> Result<Gate, UpdateError>;
 
+    /// Updates either all gates or, if any of them does not exist or was changed since the version
+    /// given with its key, none of them.
+    async fn update_states_and_last_updated(
+        &self,
+        keys: &[(GateKey, u32)],
+        state: GateState,
+        comment: Option<Comment>,
+        last_updated: DateTime<Utc>,
+    ) -> Result<Vec<Gate>, UpdateError>;
+
     async fn update_display_order_and_last_updated(
         &self,
         key: GateKey,
         display_order: u32,
         last_updated: DateTime<Utc>,
+        expected_version: Option<u32>,
     ) -> Result<Gate, UpdateError>;
 
     async fn update_comment_and_last_updated(
@@ -114,11 +254,72 @@ pub trait Storage {
         key: GateKey,
         comment: Comment,
         last_updated: DateTime<Utc>,
+        expected_version: Option<u32>,
     ) -> Result<Gate, UpdateError>;
     async fn delete_comment_by_id_and_update_last_updated(
         &self,
         key: GateKey,
         comment_id: String,
         last_updated: DateTime<Utc>,
+        expected_version: Option<u32>,
     ) -> Result<Gate, UpdateError>;
+
+    async fn insert_history_entry(&self, entry: &HistoryEntry) -> Result<(), InsertError>;
+
+    async fn find_history(
+        &self,
+        key: GateKey,
+        limit: u32,
+        cursor: Option<String>,
+    ) -> Result<HistoryPage, FindError>;
+
+    /// Replaces the global freeze, if there is one.
+    async fn save_freeze(&self, freeze: &Freeze) -> Result<(), InsertError>;
+    async fn find_freeze(&self) -> Result<Option<Freeze>, FindError>;
+    async fn delete_freeze(&self) -> Result<(), DeleteError>;
+
+    /// Replaces the business week, if there is one.
+    async fn save_business_week(&self, business_week: &BusinessWeek) -> Result<(), InsertError>;
+    async fn find_business_week(&self) -> Result<Option<BusinessWeek>, FindError>;
+
+    /// Replaces the business week profile with the same scope, if there is one.
+    async fn save_business_week_profile(
+        &self,
+        profile: &BusinessWeekProfile,
+    ) -> Result<(), InsertError>;
+    /// Returns all business week profiles ordered by their scope.
+    async fn find_business_week_profiles(&self) -> Result<Vec<BusinessWeekProfile>, FindError>;
+    async fn delete_business_week_profile(
+        &self,
+        scope: BusinessWeekScope,
+    ) -> Result<(), DeleteError>;
+
+    /// Replaces the closed day on the same date, if there is one.
+    async fn save_closed_day(&self, closed_day: &ClosedDay) -> Result<(), InsertError>;
+    /// Saves either all closed days or, if any of them cannot be saved, none of them.
+    async fn save_closed_days(&self, closed_days: &[ClosedDay]) -> Result<(), InsertError>;
+    /// Returns all closed days ordered by date.
+    async fn find_closed_days(&self) -> Result<Vec<ClosedDay>, FindError>;
+    async fn delete_closed_day(&self, date: NaiveDate) -> Result<(), DeleteError>;
+
+    async fn insert_freeze_window(&self, freeze_window: &FreezeWindow) -> Result<(), InsertError>;
+    /// Returns all freeze windows ordered by their start.
+    async fn find_freeze_windows(&self) -> Result<Vec<FreezeWindow>, FindError>;
+    async fn delete_freeze_window(&self, id: String) -> Result<(), DeleteError>;
+
+    /// Replaces the gates the gate depends on, an empty list removes them.
+    async fn save_gate_dependencies(
+        &self,
+        dependencies: &GateDependencies,
+    ) -> Result<(), InsertError>;
+    /// Returns the dependencies of all gates ordered by the gate that depends on the others.
+    async fn find_gate_dependencies(&self) -> Result<Vec<GateDependencies>, FindError>;
+
+    async fn insert_scheduled_state_change(
+        &self,
+        change: &ScheduledStateChange,
+    ) -> Result<(), InsertError>;
+    /// Returns the scheduled state changes of all gates ordered by the instant they are due.
+    async fn find_scheduled_state_changes(&self) -> Result<Vec<ScheduledStateChange>, FindError>;
+    async fn delete_scheduled_state_change(&self, id: String) -> Result<(), DeleteError>;
 }
@@ -0,0 +1,1731 @@
+//! Checks every storage backend has to pass, so they all behave the same. Each backend runs them on
+//! a fresh storage with `storage_conformance_tests!`.
+
+use std::collections::HashSet;
+
+use chrono::{DateTime, NaiveDate, TimeDelta, Utc};
+use itertools::concat;
+use mockall::Any;
+use similar_asserts::assert_eq;
+
+use crate::storage::{DeleteError, FindError, InsertError, Storage, UpdateError};
+use crate::types::{
+    BusinessWeek, BusinessWeekProfile, BusinessWeekScope, ClosedDay, Comment, Freeze, FreezeScope,
+    FreezeWindow, Gate, GateDependencies, GateEvent, GateKey, GateState, HistoryEntry,
+    ScheduledStateChange,
+};
+
+pub async fn should_insert_and_find_one(storage: &(dyn Storage + Send + Sync)) {
+    // given
+    let gate = some_gate("some group", "some service", "some environment");
+
+    storage
+        .insert(&gate)
+        .await
+        .expect("storage failed to insert gate");
+
+    // when
+    let result = storage.find_one(gate.key.clone()).await;
+
+    // then
+    let stored_gate = result
+        .expect("storage failed to find gate")
+        .expect("gate not found");
+    assert_eq!(stored_gate, gate);
+}
+
+pub async fn should_not_insert_if_item_already_exists(storage: &(dyn Storage + Send + Sync)) {
+    // given
+    let gate = some_gate("some group", "some service", "some environment");
+
+    storage
+        .insert(&gate)
+        .await
+        .expect("storage failed to insert gate");
+
+    // when
+    let result = storage.insert(&gate).await;
+
+    // then
+    assert_eq!(
+        result.expect_err("expected error not found").type_name(),
+        InsertError::ItemAlreadyExists(String::default()).type_name()
+    );
+}
+
+pub async fn should_not_find_one_if_gate_not_exists(storage: &(dyn Storage + Send + Sync)) {
+    // given
+
+    // when
+    let result = storage
+        .find_one(some_gate("some group", "some service", "some environment").key)
+        .await;
+
+    // then
+    assert_eq!(result.expect("storage failed to find gate"), None);
+}
+
+pub async fn should_insert_all(storage: &(dyn Storage + Send + Sync)) {
+    // given
+    let gate1 = some_gate("some group", "some service", "some environment");
+    let gate2 = some_gate("some group", "some service", "some other environment");
+
+    // when
+    let result = storage.insert_all(&[gate1.clone(), gate2.clone()]).await;
+
+    // then
+    result.expect("storage failed to insert gates");
+    assert_eq!(
+        storage
+            .find_all()
+            .await
+            .expect("storage failed to find gates"),
+        vec![gate1, gate2]
+    );
+}
+
+pub async fn should_insert_none_if_any_item_already_exists(storage: &(dyn Storage + Send + Sync)) {
+    // given
+    let gate1 = some_gate("some group", "some service", "some environment");
+    let gate2 = some_gate("some group", "some service", "some other environment");
+
+    storage
+        .insert(&gate2)
+        .await
+        .expect("storage failed to insert gate");
+
+    // when
+    let result = storage.insert_all(&[gate1.clone(), gate2.clone()]).await;
+
+    // then
+    assert_eq!(
+        result.expect_err("expected error not found").type_name(),
+        InsertError::ItemAlreadyExists(String::default()).type_name()
+    );
+    assert_eq!(
+        storage
+            .find_one(gate1.key)
+            .await
+            .expect("storage failed to find gate"),
+        None
+    );
+}
+
+pub async fn should_insert_each_gate_that_does_not_exist_yet(
+    storage: &(dyn Storage + Send + Sync),
+) {
+    // given
+    let gate1 = some_gate("some group", "some service", "some environment");
+    let gate2 = some_gate("some group", "some service", "some other environment");
+    let gate3 = some_gate("some group", "some other service", "some environment");
+
+    storage
+        .insert(&gate2)
+        .await
+        .expect("storage failed to insert gate");
+
+    // when
+    let results = storage
+        .insert_each(&[gate1.clone(), gate2.clone(), gate3.clone()])
+        .await
+        .expect("storage failed to insert gates");
+
+    // then
+    assert_eq!(
+        results
+            .iter()
+            .map(|result| match result {
+                Ok(()) => "inserted",
+                Err(InsertError::ItemAlreadyExists(_)) => "already exists",
+                Err(InsertError::Other(_)) => "failed",
+            })
+            .collect::<Vec<_>>(),
+        vec!["inserted", "already exists", "inserted"]
+    );
+    assert_eq!(
+        storage
+            .find_all()
+            .await
+            .expect("storage failed to find gates"),
+        vec![gate3, gate1, gate2]
+    );
+}
+
+pub async fn should_insert_and_find_all(storage: &(dyn Storage + Send + Sync)) {
+    // given
+    let gate1 = some_gate("some group", "some service", "some environment");
+    let gate2 = some_gate(
+        "some other group",
+        "some other service",
+        "some other environment",
+    );
+
+    storage
+        .insert(&gate1)
+        .await
+        .expect("storage failed to insert gate");
+    storage
+        .insert(&gate2)
+        .await
+        .expect("storage failed to insert gate");
+
+    // when
+    let result = storage.find_all().await;
+
+    // then
+    assert_eq!(
+        result.expect("storage failed to find gates"),
+        vec![gate1, gate2]
+    );
+}
+
+pub async fn should_find_by_group(storage: &(dyn Storage + Send + Sync)) {
+    // given
+    let gate1 = some_gate("some group", "some service", "develop");
+    let gate2 = some_gate("some group", "some service", "live");
+    let gate3 = some_gate("some group", "some other service", "live");
+    let gate4 = some_gate("some other group", "some service", "live");
+
+    for gate in [&gate1, &gate2, &gate3, &gate4] {
+        storage
+            .insert(gate)
+            .await
+            .expect("storage failed to insert gate");
+    }
+
+    // when
+    let result = storage.find_by_group("some group".to_owned()).await;
+
+    // then
+    assert_eq!(
+        result.expect("storage failed to find gates"),
+        vec![gate3, gate1, gate2]
+    );
+}
+
+pub async fn should_find_by_group_and_service(storage: &(dyn Storage + Send + Sync)) {
+    // given
+    let gate1 = some_gate("some group", "some service", "develop");
+    let gate2 = some_gate("some group", "some service", "live");
+    let gate3 = some_gate("some group", "some other service", "live");
+    let gate4 = some_gate("some other group", "some service", "live");
+
+    for gate in [&gate1, &gate2, &gate3, &gate4] {
+        storage
+            .insert(gate)
+            .await
+            .expect("storage failed to insert gate");
+    }
+
+    // when
+    let result = storage
+        .find_by_group_and_service("some group".to_owned(), "some service".to_owned())
+        .await;
+
+    // then
+    assert_eq!(
+        result.expect("storage failed to find gates"),
+        vec![gate1, gate2]
+    );
+}
+
+pub async fn should_insert_and_delete(storage: &(dyn Storage + Send + Sync)) {
+    // given
+    let gate = some_gate("some group", "some service", "some environment");
+
+    storage
+        .insert(&gate)
+        .await
+        .expect("storage failed to insert gate");
+
+    // when
+    let result = storage
+        .move_to_trash(
+            gate.key.clone(),
+            some_date_time("2024-04-12T22:10:57+02:00"),
+        )
+        .await;
+
+    // then
+    result.expect("storage failed to delete gate");
+    assert_empty(storage).await;
+    assert_eq!(
+        storage
+            .find_one(gate.key)
+            .await
+            .expect("storage failed to find gate"),
+        None
+    );
+}
+
+pub async fn should_restore_deleted_gate(storage: &(dyn Storage + Send + Sync)) {
+    // given
+    let gate = some_gate("some group", "some service", "some environment");
+
+    storage
+        .insert(&gate)
+        .await
+        .expect("storage failed to insert gate");
+    storage
+        .move_to_trash(
+            gate.key.clone(),
+            some_date_time("2024-04-12T22:10:57+02:00"),
+        )
+        .await
+        .expect("storage failed to delete gate");
+    storage
+        .update_state_and_last_updated(
+            gate.key.clone(),
+            GateState::Closed,
+            None,
+            some_date_time("2024-04-13T22:10:57+02:00"),
+            None,
+        )
+        .await
+        .expect_err("deleted gate was updated");
+
+    // when
+    let result = storage
+        .restore(gate.key.clone(), DateTime::<Utc>::MIN_UTC)
+        .await;
+
+    // then
+    assert_eq!(result.expect("storage failed to restore gate"), gate);
+    assert_eq!(
+        storage
+            .find_all()
+            .await
+            .expect("storage failed to find gates"),
+        vec![gate.clone()]
+    );
+    assert_eq!(
+        storage
+            .restore(gate.key, DateTime::<Utc>::MIN_UTC)
+            .await
+            .expect_err("expected error not found")
+            .type_name(),
+        UpdateError::ItemToUpdateNotFound(String::default()).type_name()
+    );
+}
+
+pub async fn should_not_restore_gate_deleted_before_given_instant(
+    storage: &(dyn Storage + Send + Sync),
+) {
+    // given
+    let gate = some_gate("some group", "some service", "some environment");
+
+    storage
+        .insert(&gate)
+        .await
+        .expect("storage failed to insert gate");
+    storage
+        .move_to_trash(
+            gate.key.clone(),
+            some_date_time("2024-04-12T22:10:57+02:00"),
+        )
+        .await
+        .expect("storage failed to delete gate");
+
+    // when
+    let result = storage
+        .restore(
+            gate.key.clone(),
+            some_date_time("2024-04-13T00:00:00+02:00"),
+        )
+        .await;
+
+    // then
+    assert_eq!(
+        result.expect_err("expected error not found").type_name(),
+        UpdateError::ItemToUpdateNotFound(String::default()).type_name()
+    );
+    storage
+        .restore(gate.key, DateTime::<Utc>::MIN_UTC)
+        .await
+        .expect("storage failed to restore gate");
+}
+
+pub async fn should_fail_to_restore_gate_that_is_not_deleted(
+    storage: &(dyn Storage + Send + Sync),
+) {
+    // given
+    let gate = some_gate("some group", "some service", "some environment");
+
+    storage
+        .insert(&gate)
+        .await
+        .expect("storage failed to insert gate");
+
+    // when
+    let result = storage.restore(gate.key, DateTime::<Utc>::MIN_UTC).await;
+
+    // then
+    assert_eq!(
+        result.expect_err("expected error not found").type_name(),
+        UpdateError::ItemToUpdateNotFound(String::default()).type_name()
+    );
+}
+
+pub async fn should_purge_gates_deleted_before_given_instant(
+    storage: &(dyn Storage + Send + Sync),
+) {
+    // given
+    let gate1 = some_gate("some group", "some service", "develop");
+    let gate2 = some_gate("some group", "some service", "live");
+    let gate3 = some_gate("some group", "some service", "test");
+
+    for gate in [&gate1, &gate2, &gate3] {
+        storage
+            .insert(gate)
+            .await
+            .expect("storage failed to insert gate");
+    }
+    for (gate, deleted_at) in [
+        (&gate1, some_date_time("2024-04-10T12:00:00+00:00")),
+        (&gate2, some_date_time("2024-04-12T12:00:00+00:00")),
+    ] {
+        storage
+            .move_to_trash(gate.key.clone(), deleted_at)
+            .await
+            .expect("storage failed to delete gate");
+    }
+
+    // when
+    let result = storage
+        .purge_trash(some_date_time("2024-04-11T12:00:00+00:00"))
+        .await;
+
+    // then
+    assert_eq!(result.expect("storage failed to purge trash"), 1);
+    storage
+        .restore(gate1.key, DateTime::<Utc>::MIN_UTC)
+        .await
+        .expect_err("purged gate was restored");
+    storage
+        .restore(gate2.key.clone(), DateTime::<Utc>::MIN_UTC)
+        .await
+        .expect("storage failed to restore gate");
+    assert_eq!(
+        storage
+            .find_all()
+            .await
+            .expect("storage failed to find gates"),
+        vec![gate2, gate3]
+    );
+}
+
+pub async fn should_replace_deleted_gate_on_insert(storage: &(dyn Storage + Send + Sync)) {
+    // given
+    let gate = some_gate("some group", "some service", "some environment");
+
+    storage
+        .insert(&gate)
+        .await
+        .expect("storage failed to insert gate");
+    storage
+        .move_to_trash(
+            gate.key.clone(),
+            some_date_time("2024-04-12T22:10:57+02:00"),
+        )
+        .await
+        .expect("storage failed to delete gate");
+
+    // when
+    let result = storage.insert(&gate).await;
+
+    // then
+    result.expect("storage failed to insert gate");
+    storage
+        .restore(gate.key, DateTime::<Utc>::MIN_UTC)
+        .await
+        .expect_err("replaced gate was restored");
+}
+
+pub async fn should_fail_to_delete_item_if_item_does_not_exist(
+    storage: &(dyn Storage + Send + Sync),
+) {
+    // given
+    let gate = some_gate("some group", "some service", "some environment");
+
+    storage
+        .insert(&gate)
+        .await
+        .expect("storage failed to insert gate");
+
+    // when
+    let result = storage
+        .move_to_trash(
+            some_gate("some group", "some service", "some wrong environment").key,
+            some_date_time("2024-04-12T22:10:57+02:00"),
+        )
+        .await;
+
+    // then
+    assert_eq!(
+        result.expect_err("expected error not found").type_name(),
+        DeleteError::ItemToDeleteNotFound(String::default()).type_name()
+    );
+    assert_eq!(
+        storage
+            .find_all()
+            .await
+            .expect("storage failed to find gates"),
+        vec![gate]
+    );
+}
+
+pub async fn should_update_state_and_last_modified(storage: &(dyn Storage + Send + Sync)) {
+    // given
+    let gate = some_gate("some group", "some service", "some environment");
+
+    storage
+        .insert(&gate)
+        .await
+        .expect("storage failed to insert gate");
+
+    // when
+    let now = some_date_time("2025-04-12T22:10:57+02:00");
+    let result = storage
+        .update_state_and_last_updated(gate.key.clone(), GateState::Closed, None, now, None)
+        .await;
+
+    // then
+    let expected = Gate {
+        key: gate.key,
+        state: GateState::Closed,
+        comments: gate.comments,
+        last_updated: now,
+        display_order: gate.display_order,
+        version: gate.version + 1,
+        open_until: None,
+    };
+    assert_eq!(
+        result.expect("storage failed to update gate state"),
+        expected
+    );
+    assert_eq!(
+        storage
+            .find_all()
+            .await
+            .expect("storage failed to find gates"),
+        vec![expected]
+    );
+}
+
+pub async fn should_open_gate_until_instant_and_clear_it_with_next_state_change(
+    storage: &(dyn Storage + Send + Sync),
+) {
+    // given
+    let gate = some_gate("some group", "some service", "some environment");
+
+    storage
+        .insert(&gate)
+        .await
+        .expect("storage failed to insert gate");
+
+    // when
+    let open_until = some_date_time("2025-04-13T22:10:57+02:00");
+    let opened = storage
+        .update_state_and_last_updated(
+            gate.key.clone(),
+            GateState::Open,
+            Some(open_until),
+            some_date_time("2025-04-12T22:10:57+02:00"),
+            None,
+        )
+        .await
+        .expect("storage failed to update gate state");
+    let closed = storage
+        .update_states_and_last_updated(
+            &[(gate.key.clone(), opened.version)],
+            GateState::Closed,
+            None,
+            some_date_time("2025-04-12T23:10:57+02:00"),
+        )
+        .await
+        .expect("storage failed to update gate states");
+
+    // then
+    assert_eq!(opened.open_until, Some(open_until));
+    assert_eq!(
+        storage
+            .find_one(gate.key)
+            .await
+            .expect("storage failed to find gate")
+            .and_then(|gate| gate.open_until),
+        None
+    );
+    assert_eq!(
+        closed
+            .into_iter()
+            .map(|gate| gate.open_until)
+            .collect::<Vec<_>>(),
+        vec![None]
+    );
+}
+
+pub async fn should_update_state_and_last_modified_if_expected_version_matches(
+    storage: &(dyn Storage + Send + Sync),
+) {
+    // given
+    let gate = some_gate("some group", "some service", "some environment");
+
+    storage
+        .insert(&gate)
+        .await
+        .expect("storage failed to insert gate");
+
+    // when
+    let now = some_date_time("2025-04-12T22:10:57+02:00");
+    let result = storage
+        .update_state_and_last_updated(
+            gate.key.clone(),
+            GateState::Closed,
+            None,
+            now,
+            Some(gate.version),
+        )
+        .await;
+
+    // then
+    assert_eq!(
+        result.expect("storage failed to update gate state"),
+        Gate {
+            state: GateState::Closed,
+            last_updated: now,
+            version: gate.version + 1,
+            ..gate
+        }
+    );
+}
+
+pub async fn should_fail_to_update_state_and_last_modified_if_expected_version_does_not_match(
+    storage: &(dyn Storage + Send + Sync),
+) {
+    // given
+    let gate = some_gate("some group", "some service", "some environment");
+
+    storage
+        .insert(&gate)
+        .await
+        .expect("storage failed to insert gate");
+
+    // when
+    let result = storage
+        .update_state_and_last_updated(
+            gate.key.clone(),
+            GateState::Closed,
+            None,
+            some_date_time("2025-04-12T22:10:57+02:00"),
+            Some(gate.version + 1),
+        )
+        .await;
+
+    // then
+    assert!(matches!(result, Err(UpdateError::VersionConflict(_))));
+    assert_eq!(
+        storage
+            .find_all()
+            .await
+            .expect("storage failed to find gates"),
+        vec![gate]
+    );
+}
+
+pub async fn should_fail_to_update_state_and_last_modified_of_item_that_does_not_exist(
+    storage: &(dyn Storage + Send + Sync),
+) {
+    // given
+    let gate = some_gate("some group", "some service", "some environment");
+
+    storage
+        .insert(&gate)
+        .await
+        .expect("storage failed to insert gate");
+
+    // when
+    let result = storage
+        .update_state_and_last_updated(
+            some_gate("some group", "some service", "some wrong environment").key,
+            GateState::Closed,
+            None,
+            some_date_time("2025-04-12T22:10:57+02:00"),
+            None,
+        )
+        .await;
+
+    // then
+    assert_eq!(
+        result.expect_err("expected error not found").type_name(),
+        UpdateError::ItemToUpdateNotFound(String::default()).type_name()
+    );
+    assert_eq!(
+        storage
+            .find_all()
+            .await
+            .expect("storage failed to find gates"),
+        vec![gate]
+    );
+}
+
+pub async fn should_update_display_order_and_last_modified(storage: &(dyn Storage + Send + Sync)) {
+    // given
+    let gate = some_gate("some group", "some service", "some environment");
+
+    storage
+        .insert(&gate)
+        .await
+        .expect("storage failed to insert gate");
+
+    // when
+    let now = some_date_time("2025-04-12T22:10:57+02:00");
+    let result = storage
+        .update_display_order_and_last_updated(gate.key.clone(), 7, now, None)
+        .await;
+
+    // then
+    assert_eq!(
+        result.expect("storage failed to update display order"),
+        Gate {
+            key: gate.key,
+            state: gate.state,
+            comments: gate.comments,
+            last_updated: now,
+            display_order: Some(7),
+            version: gate.version + 1,
+            open_until: None,
+        }
+    );
+}
+
+pub async fn should_update_states_and_add_comment(storage: &(dyn Storage + Send + Sync)) {
+    // given
+    let gate1 = some_gate("some group", "some service", "some environment");
+    let gate2 = some_gate("some group", "some service", "some other environment");
+
+    storage
+        .insert_all(&[gate1.clone(), gate2.clone()])
+        .await
+        .expect("storage failed to insert gates");
+
+    let now = DateTime::parse_from_rfc3339("2025-04-12T22:10:57+02:00")
+        .expect("failed creating date")
+        .into();
+    let new_comment = Comment {
+        id: "NewCommentId".to_owned(),
+        message: "Some new comment message".to_owned(),
+        created: now,
+    };
+
+    // when
+    let result = storage
+        .update_states_and_last_updated(
+            &[
+                (gate1.key.clone(), gate1.version),
+                (gate2.key.clone(), gate2.version),
+            ],
+            GateState::Closed,
+            Some(new_comment.clone()),
+            now,
+        )
+        .await;
+
+    // then
+    let expected = |gate: Gate| Gate {
+        state: GateState::Closed,
+        comments: concat(vec![gate.comments, HashSet::from([new_comment.clone()])]),
+        last_updated: now,
+        version: gate.version + 1,
+        ..gate
+    };
+    assert_eq!(
+        result.expect("storage failed to update gates"),
+        vec![expected(gate1.clone()), expected(gate2)]
+    );
+    assert_eq!(
+        storage
+            .find_one(gate1.key.clone())
+            .await
+            .expect("storage failed to find gate"),
+        Some(expected(gate1))
+    );
+}
+
+pub async fn should_update_no_state_if_any_gate_does_not_exist(
+    storage: &(dyn Storage + Send + Sync),
+) {
+    // given
+    let gate1 = some_gate("some group", "some service", "some environment");
+    let gate2 = some_gate("some group", "some service", "some other environment");
+
+    storage
+        .insert(&gate1)
+        .await
+        .expect("storage failed to insert gate");
+
+    // when
+    let result = storage
+        .update_states_and_last_updated(
+            &[
+                (gate1.key.clone(), gate1.version),
+                (gate2.key, gate2.version),
+            ],
+            GateState::Closed,
+            None,
+            DateTime::parse_from_rfc3339("2025-04-12T22:10:57+02:00")
+                .expect("failed creating date")
+                .into(),
+        )
+        .await;
+
+    // then
+    assert_eq!(
+        result.expect_err("expected error not found").type_name(),
+        UpdateError::ItemToUpdateNotFound(String::default()).type_name()
+    );
+    assert_eq!(
+        storage
+            .find_one(gate1.key.clone())
+            .await
+            .expect("storage failed to find gate"),
+        Some(gate1)
+    );
+}
+
+pub async fn should_update_no_state_if_any_gate_was_changed_in_the_meantime(
+    storage: &(dyn Storage + Send + Sync),
+) {
+    // given
+    let gate1 = some_gate("some group", "some service", "some environment");
+    let gate2 = some_gate("some group", "some service", "some other environment");
+
+    storage
+        .insert(&gate1)
+        .await
+        .expect("storage failed to insert gate");
+    storage
+        .insert(&gate2)
+        .await
+        .expect("storage failed to insert gate");
+
+    // when
+    let result = storage
+        .update_states_and_last_updated(
+            &[
+                (gate1.key.clone(), gate1.version),
+                (gate2.key, gate2.version + 1),
+            ],
+            GateState::Closed,
+            None,
+            DateTime::parse_from_rfc3339("2025-04-12T22:10:57+02:00")
+                .expect("failed creating date")
+                .into(),
+        )
+        .await;
+
+    // then
+    assert_eq!(
+        result.expect_err("expected error not found").type_name(),
+        UpdateError::VersionConflict(String::default()).type_name()
+    );
+    assert_eq!(
+        storage
+            .find_one(gate1.key.clone())
+            .await
+            .expect("storage failed to find gate"),
+        Some(gate1)
+    );
+}
+
+pub async fn should_add_new_comment_and_update_last_modified(
+    storage: &(dyn Storage + Send + Sync),
+) {
+    // given
+    let gate = some_gate("some group", "some service", "some environment");
+
+    storage
+        .insert(&gate)
+        .await
+        .expect("storage failed to insert gate");
+
+    let now = some_date_time("2025-04-12T22:10:57+02:00");
+    let new_comment = Comment {
+        id: "NewCommentId".to_owned(),
+        message: "Some new comment message".to_owned(),
+        created: now,
+    };
+
+    // when
+    let result = storage
+        .update_comment_and_last_updated(gate.key.clone(), new_comment.clone(), now, None)
+        .await;
+
+    // then
+    assert_eq!(
+        result.expect("storage failed to update gate comment"),
+        Gate {
+            key: gate.key,
+            state: gate.state,
+            comments: concat(vec![gate.comments, HashSet::from([new_comment])]),
+            last_updated: now,
+            display_order: gate.display_order,
+            version: gate.version + 1,
+            open_until: None,
+        }
+    );
+}
+
+pub async fn should_update_existing_comment_and_update_last_modified(
+    storage: &(dyn Storage + Send + Sync),
+) {
+    // given
+    let gate = some_gate("some group", "some service", "some environment");
+
+    storage
+        .insert(&gate)
+        .await
+        .expect("storage failed to insert gate");
+
+    let now = some_date_time("2025-04-12T22:10:57+02:00");
+    let changed_comment = Comment {
+        id: "Comment1".to_owned(),
+        message: "Some changed comment message".to_owned(),
+        created: now,
+    };
+
+    // when
+    let result = storage
+        .update_comment_and_last_updated(gate.key.clone(), changed_comment.clone(), now, None)
+        .await;
+
+    // then
+    assert_eq!(
+        result.expect("storage failed to update gate comment"),
+        Gate {
+            key: gate.key,
+            state: gate.state,
+            comments: HashSet::from([
+                changed_comment,
+                Comment {
+                    id: "Comment2".to_owned(),
+                    message: "Some other comment message".to_owned(),
+                    created: some_date_time("2022-04-12T22:10:57+02:00"),
+                },
+            ]),
+            last_updated: now,
+            display_order: gate.display_order,
+            version: gate.version + 1,
+            open_until: None,
+        }
+    );
+}
+
+pub async fn should_fail_updating_comment_and_update_last_modified_if_item_does_not_exist(
+    storage: &(dyn Storage + Send + Sync),
+) {
+    // given
+    let now = some_date_time("2025-04-12T22:10:57+02:00");
+
+    // when
+    let result = storage
+        .update_comment_and_last_updated(
+            some_gate("some group", "some service", "some environment").key,
+            Comment {
+                id: "Comment1".to_owned(),
+                message: "Some changed comment message".to_owned(),
+                created: now,
+            },
+            now,
+            None,
+        )
+        .await;
+
+    // then
+    assert_eq!(
+        result.expect_err("expected error not found").type_name(),
+        UpdateError::ItemToUpdateNotFound(String::default()).type_name()
+    );
+    assert_empty(storage).await;
+}
+
+pub async fn should_delete_comment_by_id_and_update_last_modified(
+    storage: &(dyn Storage + Send + Sync),
+) {
+    // given
+    let gate = some_gate("some group", "some service", "some environment");
+
+    storage
+        .insert(&gate)
+        .await
+        .expect("storage failed to insert gate");
+
+    let now = some_date_time("2025-04-12T22:10:57+02:00");
+
+    // when
+    let result = storage
+        .delete_comment_by_id_and_update_last_updated(
+            gate.key.clone(),
+            "Comment1".to_owned(),
+            now,
+            None,
+        )
+        .await;
+
+    // then
+    assert_eq!(
+        result.expect("storage failed to delete gate comment"),
+        Gate {
+            key: gate.key,
+            state: gate.state,
+            comments: HashSet::from([Comment {
+                id: "Comment2".to_owned(),
+                message: "Some other comment message".to_owned(),
+                created: some_date_time("2022-04-12T22:10:57+02:00"),
+            }]),
+            last_updated: now,
+            display_order: gate.display_order,
+            version: gate.version + 1,
+            open_until: None,
+        }
+    );
+}
+
+pub async fn should_fail_to_delete_comment_by_id_and_update_last_modified_if_comment_does_not_exist(
+    storage: &(dyn Storage + Send + Sync),
+) {
+    // given
+    let gate = some_gate("some group", "some service", "some environment");
+
+    storage
+        .insert(&gate)
+        .await
+        .expect("storage failed to insert gate");
+
+    // when
+    let result = storage
+        .delete_comment_by_id_and_update_last_updated(
+            gate.key.clone(),
+            "NonExistentCommentId".to_owned(),
+            some_date_time("2025-04-12T22:10:57+02:00"),
+            None,
+        )
+        .await;
+
+    // then
+    assert_eq!(
+        result.expect_err("expected error not found").type_name(),
+        UpdateError::ItemToUpdateNotFound(String::default()).type_name()
+    );
+    assert_eq!(
+        storage
+            .find_all()
+            .await
+            .expect("storage failed to find gates"),
+        vec![gate]
+    );
+}
+
+pub async fn should_fail_to_delete_comment_by_id_and_update_last_modified_if_item_does_not_exist(
+    storage: &(dyn Storage + Send + Sync),
+) {
+    // given
+
+    // when
+    let result = storage
+        .delete_comment_by_id_and_update_last_updated(
+            some_gate("some group", "some service", "some environment").key,
+            "Comment1".to_owned(),
+            some_date_time("2025-04-12T22:10:57+02:00"),
+            None,
+        )
+        .await;
+
+    // then
+    assert_eq!(
+        result.expect_err("expected error not found").type_name(),
+        UpdateError::ItemToUpdateNotFound(String::default()).type_name()
+    );
+    assert_empty(storage).await;
+}
+
+pub async fn should_save_find_and_delete_freeze(storage: &(dyn Storage + Send + Sync)) {
+    // given
+    let freeze = Freeze {
+        reason: "some reason".to_owned(),
+        actor: Some("Max".to_owned()),
+        created: DateTime::parse_from_rfc3339("2024-04-12T12:00:00+00:00")
+            .expect("failed creating date")
+            .into(),
+        until: None,
+    };
+    let replacing_freeze = Freeze {
+        reason: "some other reason".to_owned(),
+        actor: None,
+        until: Some(
+            DateTime::parse_from_rfc3339("2024-04-20T06:00:00+00:00")
+                .expect("failed creating date")
+                .into(),
+        ),
+        ..freeze.clone()
+    };
+    assert_eq!(
+        storage
+            .find_freeze()
+            .await
+            .expect("storage failed to find freeze"),
+        None
+    );
+
+    // when
+    storage
+        .save_freeze(&freeze)
+        .await
+        .expect("storage failed to save freeze");
+    storage
+        .save_freeze(&replacing_freeze)
+        .await
+        .expect("storage failed to save freeze");
+
+    // then
+    assert_eq!(
+        storage
+            .find_freeze()
+            .await
+            .expect("storage failed to find freeze"),
+        Some(replacing_freeze)
+    );
+
+    // when
+    storage
+        .delete_freeze()
+        .await
+        .expect("storage failed to delete freeze");
+
+    // then
+    assert_eq!(
+        storage
+            .find_freeze()
+            .await
+            .expect("storage failed to find freeze"),
+        None
+    );
+    assert_eq!(
+        storage
+            .delete_freeze()
+            .await
+            .expect_err("expected error not found")
+            .type_name(),
+        DeleteError::ItemToDeleteNotFound(String::default()).type_name()
+    );
+}
+
+pub async fn should_save_and_find_business_week(storage: &(dyn Storage + Send + Sync)) {
+    // given
+    let business_week = BusinessWeek {
+        saturday: BusinessWeek::default().friday,
+        time_zone: chrono_tz::America::New_York,
+        ..BusinessWeek::default()
+    };
+    assert_eq!(
+        storage
+            .find_business_week()
+            .await
+            .expect("storage failed to find business week"),
+        None
+    );
+
+    // when
+    storage
+        .save_business_week(&BusinessWeek::default())
+        .await
+        .expect("storage failed to save business week");
+    storage
+        .save_business_week(&business_week)
+        .await
+        .expect("storage failed to save business week");
+
+    // then
+    assert_eq!(
+        storage
+            .find_business_week()
+            .await
+            .expect("storage failed to find business week"),
+        Some(business_week)
+    );
+}
+
+pub async fn should_save_find_and_remove_gate_dependencies(storage: &(dyn Storage + Send + Sync)) {
+    // given
+    let key = |environment: &str| GateKey {
+        group: "some group".to_owned(),
+        service: "some service".to_owned(),
+        environment: environment.to_owned(),
+    };
+    let live = GateDependencies {
+        key: key("live"),
+        depends_on: vec![key("staging")],
+    };
+    let develop = GateDependencies {
+        key: key("develop"),
+        depends_on: vec![key("database"), key("staging")],
+    };
+
+    // when
+    storage
+        .save_gate_dependencies(&GateDependencies {
+            depends_on: vec![key("database")],
+            ..live.clone()
+        })
+        .await
+        .expect("storage failed to save gate dependencies");
+    for dependencies in [&live, &develop] {
+        storage
+            .save_gate_dependencies(dependencies)
+            .await
+            .expect("storage failed to save gate dependencies");
+    }
+
+    // then
+    assert_eq!(
+        storage
+            .find_gate_dependencies()
+            .await
+            .expect("storage failed to find gate dependencies"),
+        vec![develop.clone(), live.clone()]
+    );
+
+    // when
+    storage
+        .save_gate_dependencies(&GateDependencies {
+            depends_on: vec![],
+            ..live
+        })
+        .await
+        .expect("storage failed to remove gate dependencies");
+
+    // then
+    assert_eq!(
+        storage
+            .find_gate_dependencies()
+            .await
+            .expect("storage failed to find gate dependencies"),
+        vec![develop]
+    );
+}
+
+pub async fn should_save_find_and_delete_business_week_profiles(
+    storage: &(dyn Storage + Send + Sync),
+) {
+    // given
+    let service = BusinessWeekProfile {
+        scope: BusinessWeekScope::new(
+            "some group".to_owned(),
+            Some("some service".to_owned()),
+            None,
+        )
+        .unwrap(),
+        business_week: BusinessWeek {
+            saturday: BusinessWeek::default().friday,
+            ..BusinessWeek::default()
+        },
+    };
+    let gate = BusinessWeekProfile {
+        scope: BusinessWeekScope::new(
+            "some group".to_owned(),
+            Some("some service".to_owned()),
+            Some("live".to_owned()),
+        )
+        .unwrap(),
+        business_week: BusinessWeek {
+            time_zone: chrono_tz::America::New_York,
+            ..BusinessWeek::default()
+        },
+    };
+    let group = BusinessWeekProfile {
+        scope: BusinessWeekScope::new("some group".to_owned(), None, None).unwrap(),
+        business_week: BusinessWeek::default(),
+    };
+
+    // when
+    for business_week_profile in [&gate, &group, &service] {
+        storage
+            .save_business_week_profile(&BusinessWeekProfile {
+                business_week: BusinessWeek {
+                    sunday: BusinessWeek::default().monday,
+                    ..BusinessWeek::default()
+                },
+                ..business_week_profile.clone()
+            })
+            .await
+            .expect("storage failed to save business week profile");
+        storage
+            .save_business_week_profile(business_week_profile)
+            .await
+            .expect("storage failed to save business week profile");
+    }
+
+    // then
+    assert_eq!(
+        storage
+            .find_business_week_profiles()
+            .await
+            .expect("storage failed to find business week profiles"),
+        vec![group.clone(), service.clone(), gate.clone()]
+    );
+
+    // when
+    storage
+        .delete_business_week_profile(service.scope.clone())
+        .await
+        .expect("storage failed to delete business week profile");
+
+    // then
+    assert_eq!(
+        storage
+            .find_business_week_profiles()
+            .await
+            .expect("storage failed to find business week profiles"),
+        vec![group, gate]
+    );
+    assert_eq!(
+        storage
+            .delete_business_week_profile(service.scope)
+            .await
+            .expect_err("expected error not found")
+            .type_name(),
+        DeleteError::ItemToDeleteNotFound(String::default()).type_name()
+    );
+}
+
+pub async fn should_save_find_and_delete_closed_days(storage: &(dyn Storage + Send + Sync)) {
+    // given
+    let christmas = ClosedDay {
+        date: NaiveDate::from_ymd_opt(2025, 12, 25).unwrap(),
+        reason: "Christmas".to_owned(),
+    };
+    let christmas_eve = ClosedDay {
+        date: NaiveDate::from_ymd_opt(2025, 12, 24).unwrap(),
+        reason: "Christmas Eve".to_owned(),
+    };
+
+    // when
+    storage
+        .save_closed_day(&christmas)
+        .await
+        .expect("storage failed to save closed day");
+    storage
+        .save_closed_day(&ClosedDay {
+            reason: "some reason".to_owned(),
+            ..christmas_eve.clone()
+        })
+        .await
+        .expect("storage failed to save closed day");
+    storage
+        .save_closed_day(&christmas_eve)
+        .await
+        .expect("storage failed to save closed day");
+
+    // then
+    assert_eq!(
+        storage
+            .find_closed_days()
+            .await
+            .expect("storage failed to find closed days"),
+        vec![christmas_eve.clone(), christmas.clone()]
+    );
+
+    // when
+    storage
+        .delete_closed_day(christmas.date)
+        .await
+        .expect("storage failed to delete closed day");
+
+    // then
+    assert_eq!(
+        storage
+            .find_closed_days()
+            .await
+            .expect("storage failed to find closed days"),
+        vec![christmas_eve]
+    );
+    assert_eq!(
+        storage
+            .delete_closed_day(christmas.date)
+            .await
+            .expect_err("expected error not found")
+            .type_name(),
+        DeleteError::ItemToDeleteNotFound(String::default()).type_name()
+    );
+}
+
+pub async fn should_save_closed_days_at_once(storage: &(dyn Storage + Send + Sync)) {
+    // given
+    let christmas_eve = ClosedDay {
+        date: NaiveDate::from_ymd_opt(2025, 12, 24).unwrap(),
+        reason: "Christmas Eve".to_owned(),
+    };
+    let christmas = ClosedDay {
+        date: NaiveDate::from_ymd_opt(2025, 12, 25).unwrap(),
+        reason: "Christmas".to_owned(),
+    };
+
+    storage
+        .save_closed_day(&ClosedDay {
+            reason: "some reason".to_owned(),
+            ..christmas_eve.clone()
+        })
+        .await
+        .expect("storage failed to save closed day");
+
+    // when
+    storage
+        .save_closed_days(&[christmas_eve.clone(), christmas.clone()])
+        .await
+        .expect("storage failed to save closed days");
+
+    // then
+    assert_eq!(
+        storage
+            .find_closed_days()
+            .await
+            .expect("storage failed to find closed days"),
+        vec![christmas_eve, christmas]
+    );
+}
+
+pub async fn should_insert_find_and_delete_freeze_windows(storage: &(dyn Storage + Send + Sync)) {
+    // given
+    let year_end = FreezeWindow {
+        id: "year-end".to_owned(),
+        name: "Year-end freeze".to_owned(),
+        reason: "annual closing".to_owned(),
+        start: DateTime::parse_from_rfc3339("2025-12-19T17:00:00+00:00")
+            .expect("failed creating date")
+            .into(),
+        end: DateTime::parse_from_rfc3339("2026-01-05T06:00:00+00:00")
+            .expect("failed creating date")
+            .into(),
+        scope: FreezeScope::All,
+        actor: Some("Max".to_owned()),
+    };
+    let release = FreezeWindow {
+        id: "release".to_owned(),
+        name: "Release freeze".to_owned(),
+        reason: "release of version 2".to_owned(),
+        start: DateTime::parse_from_rfc3339("2025-11-03T06:00:00+00:00")
+            .expect("failed creating date")
+            .into(),
+        end: DateTime::parse_from_rfc3339("2025-11-04T06:00:00+00:00")
+            .expect("failed creating date")
+            .into(),
+        scope: FreezeScope::Group("some group".to_owned()),
+        actor: None,
+    };
+    let maintenance = FreezeWindow {
+        id: "maintenance".to_owned(),
+        name: "Maintenance".to_owned(),
+        reason: "database upgrade".to_owned(),
+        scope: FreezeScope::Environment("live".to_owned()),
+        ..release.clone()
+    };
+
+    // when
+    for freeze_window in [&year_end, &release, &maintenance] {
+        storage
+            .insert_freeze_window(freeze_window)
+            .await
+            .expect("storage failed to insert freeze window");
+    }
+
+    // then
+    let freeze_windows = storage
+        .find_freeze_windows()
+        .await
+        .expect("storage failed to find freeze windows");
+    assert_eq!(freeze_windows.len(), 3);
+    assert_eq!(freeze_windows.last(), Some(&year_end));
+    assert!(freeze_windows.contains(&release));
+    assert!(freeze_windows.contains(&maintenance));
+    assert_eq!(
+        storage
+            .insert_freeze_window(&year_end)
+            .await
+            .expect_err("expected error already exists")
+            .type_name(),
+        InsertError::ItemAlreadyExists(String::default()).type_name()
+    );
+
+    // when
+    storage
+        .delete_freeze_window(year_end.id.clone())
+        .await
+        .expect("storage failed to delete freeze window");
+
+    // then
+    assert!(!storage
+        .find_freeze_windows()
+        .await
+        .expect("storage failed to find freeze windows")
+        .contains(&year_end));
+    assert_eq!(
+        storage
+            .delete_freeze_window(year_end.id)
+            .await
+            .expect_err("expected error not found")
+            .type_name(),
+        DeleteError::ItemToDeleteNotFound(String::default()).type_name()
+    );
+}
+
+pub async fn should_insert_find_and_delete_scheduled_state_changes(
+    storage: &(dyn Storage + Send + Sync),
+) {
+    // given
+    let opening = ScheduledStateChange {
+        id: "opening".to_owned(),
+        key: GateKey {
+            group: "some group".to_owned(),
+            service: "some service".to_owned(),
+            environment: "some environment".to_owned(),
+        },
+        state: GateState::Open,
+        at: DateTime::parse_from_rfc3339("2025-11-03T06:00:00+00:00")
+            .expect("failed creating date")
+            .into(),
+        actor: Some("Max".to_owned()),
+    };
+    let closing = ScheduledStateChange {
+        id: "closing".to_owned(),
+        state: GateState::Closed,
+        at: DateTime::parse_from_rfc3339("2025-11-07T15:00:00+00:00")
+            .expect("failed creating date")
+            .into(),
+        actor: None,
+        ..opening.clone()
+    };
+
+    // when
+    for change in [&closing, &opening] {
+        storage
+            .insert_scheduled_state_change(change)
+            .await
+            .expect("storage failed to insert scheduled state change");
+    }
+
+    // then
+    assert_eq!(
+        storage
+            .find_scheduled_state_changes()
+            .await
+            .expect("storage failed to find scheduled state changes"),
+        vec![opening.clone(), closing.clone()]
+    );
+    assert_eq!(
+        storage
+            .insert_scheduled_state_change(&opening)
+            .await
+            .expect_err("expected error already exists")
+            .type_name(),
+        InsertError::ItemAlreadyExists(String::default()).type_name()
+    );
+
+    // when
+    storage
+        .delete_scheduled_state_change(opening.id.clone())
+        .await
+        .expect("storage failed to delete scheduled state change");
+
+    // then
+    assert_eq!(
+        storage
+            .find_scheduled_state_changes()
+            .await
+            .expect("storage failed to find scheduled state changes"),
+        vec![closing]
+    );
+    assert_eq!(
+        storage
+            .delete_scheduled_state_change(opening.id)
+            .await
+            .expect_err("expected error not found")
+            .type_name(),
+        DeleteError::ItemToDeleteNotFound(String::default()).type_name()
+    );
+}
+
+pub async fn should_insert_and_find_history_page_by_page(storage: &(dyn Storage + Send + Sync)) {
+    // given
+    let entry1 = some_history_entry("Entry1", "some environment", "2021-04-12T22:10:57+02:00");
+    let entry2 = some_history_entry(
+        "Entry2",
+        "some other environment",
+        "2022-04-12T22:10:57+02:00",
+    );
+    let entry3 = some_history_entry("Entry3", "some environment", "2023-04-12T22:10:57+02:00");
+    let entry4 = some_history_entry("Entry4", "some environment", "2024-04-12T22:10:57+02:00");
+
+    for entry in [&entry1, &entry2, &entry3, &entry4] {
+        storage
+            .insert_history_entry(entry)
+            .await
+            .expect("storage failed to insert history entry");
+    }
+
+    // when
+    let first_page = storage
+        .find_history(entry1.key.clone(), 2, None)
+        .await
+        .expect("storage failed to find history");
+    let second_page = storage
+        .find_history(entry1.key.clone(), 2, first_page.next_cursor.clone())
+        .await
+        .expect("storage failed to find history");
+
+    // then
+    assert_eq!(first_page.entries, vec![entry4, entry3]);
+    assert!(first_page.next_cursor.is_some());
+    assert_eq!(second_page.entries, vec![entry1]);
+    assert_eq!(second_page.next_cursor, None);
+}
+
+pub async fn should_fail_to_find_history_with_invalid_cursor(
+    storage: &(dyn Storage + Send + Sync),
+) {
+    // given
+
+    // when
+    let result = storage
+        .find_history(
+            some_history_entry("Entry1", "some environment", "2021-04-12T22:10:57+02:00").key,
+            2,
+            Some("not a cursor".to_owned()),
+        )
+        .await;
+
+    // then
+    assert_eq!(
+        result.expect_err("expected error not found").type_name(),
+        FindError::InvalidCursor(String::default()).type_name()
+    );
+}
+
+pub async fn assert_empty(storage: &(dyn Storage + Send + Sync)) {
+    let count = storage
+        .find_all()
+        .await
+        .expect("storage failed to find gates")
+        .len();
+    assert_eq!(count, 0);
+}
+
+pub fn some_history_entry(id: &str, environment: &str, timestamp: &str) -> HistoryEntry {
+    HistoryEntry {
+        id: id.to_owned(),
+        key: GateKey {
+            group: "some group".to_owned(),
+            service: "some service".to_owned(),
+            environment: environment.to_owned(),
+        },
+        timestamp: some_date_time(timestamp),
+        event: GateEvent::StateChanged,
+        old_value: Some("closed".to_owned()),
+        new_value: Some("open".to_owned()),
+        open_until: Some(some_date_time(timestamp) + TimeDelta::hours(1)),
+        actor: Some("Max".to_owned()),
+    }
+}
+
+pub fn some_date_time(value: &str) -> DateTime<Utc> {
+    DateTime::parse_from_rfc3339(value)
+        .expect("failed creating date")
+        .into()
+}
+
+pub fn some_gate(group: &str, service: &str, environment: &str) -> Gate {
+    Gate {
+        key: GateKey {
+            group: group.to_owned(),
+            service: service.to_owned(),
+            environment: environment.to_owned(),
+        },
+        state: GateState::Open,
+        comments: HashSet::from([
+            Comment {
+                id: "Comment1".to_owned(),
+                message: "Some comment message".to_owned(),
+                created: some_date_time("2021-04-12T22:10:57+02:00"),
+            },
+            Comment {
+                id: "Comment2".to_owned(),
+                message: "Some other comment message".to_owned(),
+                created: some_date_time("2022-04-12T22:10:57+02:00"),
+            },
+        ]),
+        last_updated: some_date_time("2023-04-12T22:10:57+02:00"),
+        display_order: Some(2),
+        version: 0,
+        open_until: None,
+    }
+}
+
+/// Runs every check on a fresh storage from the given future, which returns the storage and whatever
+/// has to live as long as it, e.g. the container of the database.
+macro_rules! storage_conformance_tests {
+    ($fixture:expr) => {
+        $crate::storage::conformance::storage_conformance_tests!(
+            $fixture;
+            should_insert_and_find_one,
+            should_not_insert_if_item_already_exists,
+            should_not_find_one_if_gate_not_exists,
+            should_insert_all,
+            should_insert_none_if_any_item_already_exists,
+            should_insert_each_gate_that_does_not_exist_yet,
+            should_insert_and_find_all,
+            should_find_by_group,
+            should_find_by_group_and_service,
+            should_insert_and_delete,
+            should_restore_deleted_gate,
+            should_not_restore_gate_deleted_before_given_instant,
+            should_fail_to_restore_gate_that_is_not_deleted,
+            should_purge_gates_deleted_before_given_instant,
+            should_replace_deleted_gate_on_insert,
+            should_fail_to_delete_item_if_item_does_not_exist,
+            should_update_state_and_last_modified,
+            should_open_gate_until_instant_and_clear_it_with_next_state_change,
+            should_update_state_and_last_modified_if_expected_version_matches,
+            should_fail_to_update_state_and_last_modified_if_expected_version_does_not_match,
+            should_fail_to_update_state_and_last_modified_of_item_that_does_not_exist,
+            should_update_display_order_and_last_modified,
+            should_update_states_and_add_comment,
+            should_update_no_state_if_any_gate_does_not_exist,
+            should_update_no_state_if_any_gate_was_changed_in_the_meantime,
+            should_add_new_comment_and_update_last_modified,
+            should_update_existing_comment_and_update_last_modified,
+            should_fail_updating_comment_and_update_last_modified_if_item_does_not_exist,
+            should_delete_comment_by_id_and_update_last_modified,
+            should_fail_to_delete_comment_by_id_and_update_last_modified_if_comment_does_not_exist,
+            should_fail_to_delete_comment_by_id_and_update_last_modified_if_item_does_not_exist,
+            should_save_find_and_delete_freeze,
+            should_save_and_find_business_week,
+            should_save_find_and_remove_gate_dependencies,
+            should_save_find_and_delete_business_week_profiles,
+            should_save_find_and_delete_closed_days,
+            should_save_closed_days_at_once,
+            should_insert_find_and_delete_freeze_windows,
+            should_insert_find_and_delete_scheduled_state_changes,
+            should_insert_and_find_history_page_by_page,
+            should_fail_to_find_history_with_invalid_cursor
+        );
+    };
+    ($fixture:expr; $($check:ident),+ $(,)?) => {
+        $(
+            #[tokio::test]
+            async fn $check() {
+                let (storage, _guard) = $fixture.await;
+                $crate::storage::conformance::$check(&storage).await;
+            }
+        )+
+    };
+}
+
+pub(crate) use storage_conformance_tests;
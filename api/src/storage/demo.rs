@@ -1,9 +1,12 @@
 use async_trait::async_trait;
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
 
 use crate::storage;
 use crate::storage::{quote, DeleteError, FindError, InsertError, UpdateError};
-use crate::types::{Comment, Gate, GateKey, GateState};
+use crate::types::{
+    BusinessWeek, BusinessWeekProfile, BusinessWeekScope, ClosedDay, Comment, Freeze, FreezeWindow,
+    Gate, GateDependencies, GateKey, GateState, HistoryEntry, HistoryPage, ScheduledStateChange,
+};
 
 type DynStorage = dyn storage::Storage + Send + Sync;
 
@@ -18,6 +21,14 @@ impl storage::Storage for ReadOnlyStorage {
         Err(InsertError::Other("not allowed in demo mode".to_owned()))
     }
 
+    async fn insert_all(&self, _: &[Gate]) -> Result<(), InsertError> {
+        Err(InsertError::Other("not allowed in demo mode".to_owned()))
+    }
+
+    async fn insert_each(&self, _: &[Gate]) -> Result<Vec<Result<(), InsertError>>, InsertError> {
+        Err(InsertError::Other("not allowed in demo mode".to_owned()))
+    }
+
     async fn find_one(&self, key: GateKey) -> Result<Option<Gate>, FindError> {
         self.proxy.find_one(key).await
     }
@@ -25,7 +36,27 @@ impl storage::Storage for ReadOnlyStorage {
         self.proxy.find_all().await
     }
 
-    async fn delete(&self, _: GateKey) -> Result<(), DeleteError> {
+    async fn find_by_group(&self, group: String) -> Result<Vec<Gate>, FindError> {
+        self.proxy.find_by_group(group).await
+    }
+
+    async fn find_by_group_and_service(
+        &self,
+        group: String,
+        service: String,
+    ) -> Result<Vec<Gate>, FindError> {
+        self.proxy.find_by_group_and_service(group, service).await
+    }
+
+    async fn move_to_trash(&self, _: GateKey, _: DateTime<Utc>) -> Result<(), DeleteError> {
+        Err(DeleteError::Other("not allowed in demo mode".to_owned()))
+    }
+
+    async fn restore(&self, _: GateKey, _: DateTime<Utc>) -> Result<Gate, UpdateError> {
+        Err(UpdateError::Other("not allowed in demo mode".to_owned()))
+    }
+
+    async fn purge_trash(&self, _: DateTime<Utc>) -> Result<u64, DeleteError> {
         Err(DeleteError::Other("not allowed in demo mode".to_owned()))
     }
 
@@ -33,10 +64,36 @@ impl storage::Storage for ReadOnlyStorage {
         &self,
         key: GateKey,
         state: GateState,
+        open_until: Option<DateTime<Utc>>,
         last_updated: DateTime<Utc>,
+        expected_version: Option<u32>,
     ) -> Result<Gate, UpdateError> {
         self.proxy
-            .update_state_and_last_updated(key, state, last_updated)
+            .update_state_and_last_updated(key, state, open_until, last_updated, expected_version)
+            .await
+    }
+
+    async fn update_states_and_last_updated(
+        &self,
+        keys: &[(GateKey, u32)],
+        state: GateState,
+        comment: Option<Comment>,
+        last_updated: DateTime<Utc>,
+    ) -> Result<Vec<Gate>, UpdateError> {
+        let comment = comment
+            .map(|comment| {
+                self.quotes_provider
+                    .random_quote()
+                    .map(|message| Comment {
+                        id: comment.id,
+                        message,
+                        created: last_updated,
+                    })
+                    .map_err(UpdateError::Other)
+            })
+            .transpose()?;
+        self.proxy
+            .update_states_and_last_updated(keys, state, comment, last_updated)
             .await
     }
 
@@ -45,9 +102,15 @@ impl storage::Storage for ReadOnlyStorage {
         key: GateKey,
         display_order: u32,
         last_updated: DateTime<Utc>,
+        expected_version: Option<u32>,
     ) -> Result<Gate, UpdateError> {
         self.proxy
-            .update_display_order_and_last_updated(key, display_order, last_updated)
+            .update_display_order_and_last_updated(
+                key,
+                display_order,
+                last_updated,
+                expected_version,
+            )
             .await
     }
 
@@ -56,6 +119,7 @@ impl storage::Storage for ReadOnlyStorage {
         key: GateKey,
         comment: Comment,
         last_updated: DateTime<Utc>,
+        expected_version: Option<u32>,
     ) -> Result<Gate, UpdateError> {
         self.proxy
             .update_comment_and_last_updated(
@@ -69,6 +133,7 @@ impl storage::Storage for ReadOnlyStorage {
                     created: last_updated,
                 },
                 last_updated,
+                expected_version,
             )
             .await
     }
@@ -78,11 +143,113 @@ impl storage::Storage for ReadOnlyStorage {
         key: GateKey,
         comment_id: String,
         last_updated: DateTime<Utc>,
+        expected_version: Option<u32>,
     ) -> Result<Gate, UpdateError> {
         self.proxy
-            .delete_comment_by_id_and_update_last_updated(key, comment_id, last_updated)
+            .delete_comment_by_id_and_update_last_updated(
+                key,
+                comment_id,
+                last_updated,
+                expected_version,
+            )
             .await
     }
+
+    async fn insert_history_entry(&self, entry: &HistoryEntry) -> Result<(), InsertError> {
+        self.proxy.insert_history_entry(entry).await
+    }
+
+    async fn find_history(
+        &self,
+        key: GateKey,
+        limit: u32,
+        cursor: Option<String>,
+    ) -> Result<HistoryPage, FindError> {
+        self.proxy.find_history(key, limit, cursor).await
+    }
+
+    async fn save_freeze(&self, _: &Freeze) -> Result<(), InsertError> {
+        Err(InsertError::Other("not allowed in demo mode".to_owned()))
+    }
+
+    async fn find_freeze(&self) -> Result<Option<Freeze>, FindError> {
+        self.proxy.find_freeze().await
+    }
+
+    async fn delete_freeze(&self) -> Result<(), DeleteError> {
+        Err(DeleteError::Other("not allowed in demo mode".to_owned()))
+    }
+
+    async fn save_business_week(&self, _: &BusinessWeek) -> Result<(), InsertError> {
+        Err(InsertError::Other("not allowed in demo mode".to_owned()))
+    }
+
+    async fn find_business_week(&self) -> Result<Option<BusinessWeek>, FindError> {
+        self.proxy.find_business_week().await
+    }
+
+    async fn save_business_week_profile(&self, _: &BusinessWeekProfile) -> Result<(), InsertError> {
+        Err(InsertError::Other("not allowed in demo mode".to_owned()))
+    }
+
+    async fn find_business_week_profiles(&self) -> Result<Vec<BusinessWeekProfile>, FindError> {
+        self.proxy.find_business_week_profiles().await
+    }
+
+    async fn delete_business_week_profile(&self, _: BusinessWeekScope) -> Result<(), DeleteError> {
+        Err(DeleteError::Other("not allowed in demo mode".to_owned()))
+    }
+
+    async fn save_closed_day(&self, _: &ClosedDay) -> Result<(), InsertError> {
+        Err(InsertError::Other("not allowed in demo mode".to_owned()))
+    }
+
+    async fn save_closed_days(&self, _: &[ClosedDay]) -> Result<(), InsertError> {
+        Err(InsertError::Other("not allowed in demo mode".to_owned()))
+    }
+
+    async fn find_closed_days(&self) -> Result<Vec<ClosedDay>, FindError> {
+        self.proxy.find_closed_days().await
+    }
+
+    async fn delete_closed_day(&self, _: NaiveDate) -> Result<(), DeleteError> {
+        Err(DeleteError::Other("not allowed in demo mode".to_owned()))
+    }
+
+    async fn insert_freeze_window(&self, _: &FreezeWindow) -> Result<(), InsertError> {
+        Err(InsertError::Other("not allowed in demo mode".to_owned()))
+    }
+
+    async fn find_freeze_windows(&self) -> Result<Vec<FreezeWindow>, FindError> {
+        self.proxy.find_freeze_windows().await
+    }
+
+    async fn delete_freeze_window(&self, _: String) -> Result<(), DeleteError> {
+        Err(DeleteError::Other("not allowed in demo mode".to_owned()))
+    }
+
+    async fn save_gate_dependencies(&self, _: &GateDependencies) -> Result<(), InsertError> {
+        Err(InsertError::Other("not allowed in demo mode".to_owned()))
+    }
+
+    async fn find_gate_dependencies(&self) -> Result<Vec<GateDependencies>, FindError> {
+        self.proxy.find_gate_dependencies().await
+    }
+
+    async fn insert_scheduled_state_change(
+        &self,
+        _: &ScheduledStateChange,
+    ) -> Result<(), InsertError> {
+        Err(InsertError::Other("not allowed in demo mode".to_owned()))
+    }
+
+    async fn find_scheduled_state_changes(&self) -> Result<Vec<ScheduledStateChange>, FindError> {
+        self.proxy.find_scheduled_state_changes().await
+    }
+
+    async fn delete_scheduled_state_change(&self, _: String) -> Result<(), DeleteError> {
+        Err(DeleteError::Other("not allowed in demo mode".to_owned()))
+    }
 }
 
 impl ReadOnlyStorage {
@@ -98,13 +265,17 @@ impl ReadOnlyStorage {
 mod unit_test {
     use std::collections::HashSet;
 
-    use chrono::{DateTime, Utc};
+    use chrono::{DateTime, NaiveDate, Utc};
     use mockall::predicate::eq;
 
     use crate::storage::demo::ReadOnlyStorage;
     use crate::storage::quote::MockQuotesProvider;
     use crate::storage::{MockStorage, Storage, UpdateError};
-    use crate::types::{Comment, Gate, GateKey, GateState};
+    use crate::types::{
+        BusinessWeek, BusinessWeekProfile, BusinessWeekScope, ClosedDay, Comment, Freeze,
+        FreezeScope, FreezeWindow, Gate, GateDependencies, GateKey, GateState,
+        ScheduledStateChange,
+    };
 
     #[tokio::test]
     async fn should_not_insert() {
@@ -125,6 +296,8 @@ mod unit_test {
             comments: HashSet::default(),
             last_updated: DateTime::default(),
             display_order: None,
+            version: 0,
+            open_until: None,
         })
         .await;
         assert!(actual.is_err());
@@ -139,15 +312,202 @@ mod unit_test {
             proxy: Box::new(mock_storage),
             quotes_provider: Box::new(mock_quotes_provider),
         }
-        .delete(GateKey {
-            group: String::new(),
-            service: String::new(),
-            environment: String::new(),
+        .move_to_trash(
+            GateKey {
+                group: String::new(),
+                service: String::new(),
+                environment: String::new(),
+            },
+            Utc::now(),
+        )
+        .await;
+        assert!(actual.is_err());
+    }
+
+    #[tokio::test]
+    async fn should_not_restore() {
+        // when
+        let mock_storage = MockStorage::new();
+        let mock_quotes_provider = MockQuotesProvider::new();
+        let actual = ReadOnlyStorage {
+            proxy: Box::new(mock_storage),
+            quotes_provider: Box::new(mock_quotes_provider),
+        }
+        .restore(
+            GateKey {
+                group: String::new(),
+                service: String::new(),
+                environment: String::new(),
+            },
+            DateTime::<Utc>::MIN_UTC,
+        )
+        .await;
+        assert!(actual.is_err());
+    }
+
+    #[tokio::test]
+    async fn should_not_set_freeze() {
+        // when
+        let mock_storage = MockStorage::new();
+        let mock_quotes_provider = MockQuotesProvider::new();
+        let actual = ReadOnlyStorage {
+            proxy: Box::new(mock_storage),
+            quotes_provider: Box::new(mock_quotes_provider),
+        }
+        .save_freeze(&Freeze {
+            reason: String::new(),
+            actor: None,
+            created: Utc::now(),
+            until: None,
         })
         .await;
         assert!(actual.is_err());
     }
 
+    #[tokio::test]
+    async fn should_not_set_business_week() {
+        // when
+        let mock_storage = MockStorage::new();
+        let mock_quotes_provider = MockQuotesProvider::new();
+        let actual = ReadOnlyStorage {
+            proxy: Box::new(mock_storage),
+            quotes_provider: Box::new(mock_quotes_provider),
+        }
+        .save_business_week(&BusinessWeek::default())
+        .await;
+        assert!(actual.is_err());
+    }
+
+    #[tokio::test]
+    async fn should_not_change_closed_days() {
+        // when
+        let mock_storage = MockStorage::new();
+        let mock_quotes_provider = MockQuotesProvider::new();
+        let storage = ReadOnlyStorage {
+            proxy: Box::new(mock_storage),
+            quotes_provider: Box::new(mock_quotes_provider),
+        };
+        let date = NaiveDate::from_ymd_opt(2025, 12, 24).unwrap();
+
+        // then
+        assert!(storage
+            .save_closed_day(&ClosedDay {
+                date,
+                reason: "Christmas Eve".to_owned(),
+            })
+            .await
+            .is_err());
+        assert!(storage.delete_closed_day(date).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn should_not_change_freeze_windows() {
+        // when
+        let mock_storage = MockStorage::new();
+        let mock_quotes_provider = MockQuotesProvider::new();
+        let storage = ReadOnlyStorage {
+            proxy: Box::new(mock_storage),
+            quotes_provider: Box::new(mock_quotes_provider),
+        };
+        let now = Utc::now();
+
+        // then
+        assert!(storage
+            .insert_freeze_window(&FreezeWindow {
+                id: "some id".to_owned(),
+                name: "Year-end freeze".to_owned(),
+                reason: "annual closing".to_owned(),
+                start: now,
+                end: now,
+                scope: FreezeScope::All,
+                actor: None,
+            })
+            .await
+            .is_err());
+        assert!(storage
+            .delete_freeze_window("some id".to_owned())
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn should_not_change_scheduled_state_changes() {
+        // when
+        let mock_storage = MockStorage::new();
+        let mock_quotes_provider = MockQuotesProvider::new();
+        let storage = ReadOnlyStorage {
+            proxy: Box::new(mock_storage),
+            quotes_provider: Box::new(mock_quotes_provider),
+        };
+
+        // then
+        assert!(storage
+            .insert_scheduled_state_change(&ScheduledStateChange {
+                id: "some id".to_owned(),
+                key: GateKey {
+                    group: "some group".to_owned(),
+                    service: "some service".to_owned(),
+                    environment: "some environment".to_owned(),
+                },
+                state: GateState::Open,
+                at: Utc::now(),
+                actor: None,
+            })
+            .await
+            .is_err());
+        assert!(storage
+            .delete_scheduled_state_change("some id".to_owned())
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn should_not_change_business_week_profiles() {
+        // when
+        let mock_storage = MockStorage::new();
+        let mock_quotes_provider = MockQuotesProvider::new();
+        let storage = ReadOnlyStorage {
+            proxy: Box::new(mock_storage),
+            quotes_provider: Box::new(mock_quotes_provider),
+        };
+        let scope = BusinessWeekScope::new("some group".to_owned(), None, None).unwrap();
+
+        // then
+        assert!(storage
+            .save_business_week_profile(&BusinessWeekProfile {
+                scope: scope.clone(),
+                business_week: BusinessWeek::default(),
+            })
+            .await
+            .is_err());
+        assert!(storage.delete_business_week_profile(scope).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn should_not_change_gate_dependencies() {
+        // when
+        let mock_storage = MockStorage::new();
+        let mock_quotes_provider = MockQuotesProvider::new();
+        let storage = ReadOnlyStorage {
+            proxy: Box::new(mock_storage),
+            quotes_provider: Box::new(mock_quotes_provider),
+        };
+        let key = GateKey {
+            group: "some group".to_owned(),
+            service: "some service".to_owned(),
+            environment: "live".to_owned(),
+        };
+
+        // then
+        assert!(storage
+            .save_gate_dependencies(&GateDependencies {
+                key: key.clone(),
+                depends_on: vec![key],
+            })
+            .await
+            .is_err());
+    }
+
     #[tokio::test]
     async fn should_sanitize_last_updated_comment() {
         // given
@@ -175,8 +535,9 @@ mod unit_test {
                     created: now,
                 }),
                 eq(now),
+                eq(None),
             )
-            .return_once(move |key, _, last_updated| {
+            .return_once(move |key, _, last_updated, _| {
                 Ok(Gate {
                     key,
                     state: GateState::default(),
@@ -187,6 +548,8 @@ mod unit_test {
                     }]),
                     last_updated: now,
                     display_order: None,
+                    version: 0,
+                    open_until: None,
                 })
             });
         let actual = ReadOnlyStorage {
@@ -205,6 +568,7 @@ mod unit_test {
                 created: now,
             },
             now,
+            None,
         )
         .await;
 
@@ -225,6 +589,8 @@ mod unit_test {
                 }]),
                 last_updated: now,
                 display_order: None,
+                version: 0,
+                open_until: None,
             }
         );
     }
@@ -256,6 +622,7 @@ mod unit_test {
                 created: now,
             },
             now,
+            None,
         )
         .await;
 
@@ -283,6 +650,8 @@ mod unit_test {
                     comments: HashSet::default(),
                     last_updated: DateTime::default(),
                     display_order: None,
+                    version: 0,
+                    open_until: None,
                 }))
             });
         let actual = ReadOnlyStorage::new(Box::new(storage))
@@ -305,6 +674,8 @@ mod unit_test {
                 comments: HashSet::default(),
                 last_updated: DateTime::default(),
                 display_order: None,
+                version: 0,
+                open_until: None,
             })
         );
     }
@@ -323,6 +694,8 @@ mod unit_test {
                 comments: HashSet::default(),
                 last_updated: DateTime::default(),
                 display_order: None,
+                version: 0,
+                open_until: None,
             }]))
         });
         let actual = ReadOnlyStorage::new(Box::new(storage)).find_all().await;
@@ -339,6 +712,8 @@ mod unit_test {
                 comments: HashSet::default(),
                 last_updated: DateTime::default(),
                 display_order: None,
+                version: 0,
+                open_until: None,
             }])
         );
     }
@@ -356,17 +731,23 @@ mod unit_test {
                     environment: "input".to_owned(),
                 }),
                 eq(GateState::Closed),
+                eq(None),
                 eq(now),
+                eq(None),
             )
-            .return_once(move |gate_key: GateKey, state: GateState, last_updated| {
-                Ok(Gate {
-                    key: gate_key,
-                    state,
-                    comments: HashSet::default(),
-                    last_updated,
-                    display_order: None,
-                })
-            });
+            .return_once(
+                move |gate_key: GateKey, state: GateState, open_until, last_updated, _| {
+                    Ok(Gate {
+                        key: gate_key,
+                        state,
+                        comments: HashSet::default(),
+                        last_updated,
+                        display_order: None,
+                        version: 0,
+                        open_until,
+                    })
+                },
+            );
         let actual = ReadOnlyStorage::new(Box::new(storage))
             .update_state_and_last_updated(
                 GateKey {
@@ -375,7 +756,9 @@ mod unit_test {
                     environment: "input".to_owned(),
                 },
                 GateState::Closed,
+                None,
                 now,
+                None,
             )
             .await;
         assert!(actual.is_ok());
@@ -391,6 +774,8 @@ mod unit_test {
                 comments: HashSet::default(),
                 last_updated: now,
                 display_order: None,
+                version: 0,
+                open_until: None,
             }
         );
     }
@@ -409,14 +794,17 @@ mod unit_test {
                 }),
                 eq(0),
                 eq(now),
+                eq(None),
             )
-            .return_once(move |key: GateKey, display_order, last_updated| {
+            .return_once(move |key: GateKey, display_order, last_updated, _| {
                 Ok(Gate {
                     key,
                     state: GateState::default(),
                     comments: HashSet::default(),
                     last_updated,
                     display_order: Some(display_order),
+                    version: 0,
+                    open_until: None,
                 })
             });
         let actual = ReadOnlyStorage::new(Box::new(storage))
@@ -428,6 +816,7 @@ mod unit_test {
                 },
                 0,
                 now,
+                None,
             )
             .await;
         assert!(actual.is_ok());
@@ -443,6 +832,8 @@ mod unit_test {
                 comments: HashSet::default(),
                 last_updated: now,
                 display_order: Some(0),
+                version: 0,
+                open_until: None,
             }
         );
     }
@@ -461,14 +852,17 @@ mod unit_test {
                 }),
                 eq(0.to_string()),
                 eq(now),
+                eq(None),
             )
-            .return_once(move |key, _, last_updated| {
+            .return_once(move |key, _, last_updated, _| {
                 Ok(Gate {
                     key,
                     state: GateState::default(),
                     comments: HashSet::default(),
                     last_updated,
                     display_order: None,
+                    version: 0,
+                    open_until: None,
                 })
             });
         let actual = ReadOnlyStorage::new(Box::new(storage))
@@ -480,6 +874,7 @@ mod unit_test {
                 },
                 0.to_string(),
                 now,
+                None,
             )
             .await;
         assert!(actual.is_ok());
@@ -495,6 +890,8 @@ mod unit_test {
                 comments: HashSet::default(),
                 last_updated: now,
                 display_order: None,
+                version: 0,
+                open_until: None,
             }
         );
     }
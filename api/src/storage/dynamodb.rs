@@ -2,19 +2,30 @@ use async_trait::async_trait;
 use aws_config::BehaviorVersion;
 use aws_sdk_dynamodb::config::{Credentials, Region};
 use aws_sdk_dynamodb::error::SdkError;
+use aws_sdk_dynamodb::operation::query::builders::QueryFluentBuilder;
 use aws_sdk_dynamodb::operation::update_item::builders::UpdateItemFluentBuilder;
-use aws_sdk_dynamodb::operation::{delete_item, get_item, put_item, query, scan, update_item};
+use aws_sdk_dynamodb::operation::{
+    delete_item, get_item, put_item, query, scan, transact_write_items, update_item,
+};
 use aws_sdk_dynamodb::types::{
-    AttributeDefinition, AttributeValue, KeySchemaElement, KeyType, ProvisionedThroughput,
-    ReturnValue, ScalarAttributeType,
+    AttributeDefinition, AttributeValue, GlobalSecondaryIndex, KeySchemaElement, KeyType,
+    Projection, ProjectionType, ProvisionedThroughput, Put, ReturnValue,
+    ReturnValuesOnConditionCheckFailure, ScalarAttributeType, TransactWriteItem, Update,
 };
 use aws_sdk_dynamodb::{config, Client};
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, SecondsFormat, Utc};
 use std::collections::hash_map::RandomState;
 use std::collections::{HashMap, HashSet};
 
-use crate::storage::{DeleteError, FindError, InsertError, Storage, UpdateError};
-use crate::types::{Comment, Gate, GateKey, GateState};
+use crate::storage::{
+    check_version, decode_business_week, decode_gate_keys, encode_business_week, encode_gate_keys,
+    DeleteError, FindError, InsertError, Storage, UpdateError,
+};
+use crate::types::{
+    BusinessWeek, BusinessWeekProfile, BusinessWeekScope, ClosedDay, Comment, Freeze, FreezeScope,
+    FreezeWindow, Gate, GateDependencies, GateKey, GateState, HistoryEntry, HistoryPage,
+    ScheduledStateChange,
+};
 
 const GROUP: &str = "group";
 const SERVICE_ENVIRONMENT: &str = "service_environment";
@@ -23,13 +34,66 @@ const ENVIRONMENT: &str = "environment";
 const STATE: &str = "state";
 const LAST_UPDATED: &str = "last_updated";
 const DISPLAY_ORDER: &str = "display_order";
+const VERSION: &str = "version";
 const COMMENTS: &str = "comments";
 const ID: &str = "id";
 const MESSAGE: &str = "message";
 const CREATED: &str = "created";
+const TIMESTAMP: &str = "timestamp";
+const EVENT: &str = "event";
+const OLD_VALUE: &str = "old_value";
+const NEW_VALUE: &str = "new_value";
+const ACTOR: &str = "actor";
+const DELETED_AT: &str = "deleted_at";
+// only set on gates in the trash, so the trash index just holds them
+const TRASH: &str = "trash";
+const OPEN_UNTIL: &str = "open_until";
+const SETTING: &str = "setting";
+const REASON: &str = "reason";
+const UNTIL: &str = "until";
+const WEEK: &str = "week";
+const DATE: &str = "date";
+const NAME: &str = "name";
+const START: &str = "start";
+const END: &str = "end";
+const SCOPE_GROUP: &str = "scope_group";
+const SCOPE_SERVICE: &str = "scope_service";
+const SCOPE_ENVIRONMENT: &str = "scope_environment";
+// the group is the partition key of items, which is the settings group for scheduled state changes
+const GATE_GROUP: &str = "gate_group";
+const AT: &str = "at";
+const DEPENDS_ON: &str = "depends_on";
+// history entries are partitioned by their gate and sorted by their position in its history
+const GATE: &str = "gate";
+const POSITION: &str = "position";
+
+// settings have no '#' in their sort key, so they never collide with gates
+const SETTINGS_GROUP: &str = "settings";
+const FREEZE_SETTING: &str = "freeze";
+const BUSINESS_WEEK_SETTING: &str = "business_week";
+const BUSINESS_WEEK_PROFILE_SETTING: &str = "business_week_profile";
+const CLOSED_DAY_SETTING: &str = "closed_day";
+const FREEZE_WINDOW_SETTING: &str = "freeze_window";
+const SCHEDULED_STATE_CHANGE_SETTING: &str = "scheduled_state_change";
+const GATE_DEPENDENCY_SETTING: &str = "gate_dependency";
+
+// history entries have a table of their own, so reading gates never reads them
+const IS_GATE: &str = "attribute_not_exists(#da) AND attribute_not_exists(#st)";
+// gates moved to the trash keep their item until they are purged
+const GATE_EXISTS: &str = "attribute_exists(#g) AND attribute_not_exists(#da)";
+// a new gate replaces a deleted gate with the same key
+const GATE_NOT_EXISTS: &str = "attribute_not_exists(#g) OR attribute_exists(#da)";
+
+const MAX_TRANSACTION_ITEMS: usize = 100;
+
+const INCREMENT_VERSION: &str = "#v = if_not_exists(#v, :zero) + :one";
+
+const TRASH_INDEX_NAME: &str = "trash";
 
 const LOCAL_GATES_TABLE_NAME: &str = "GatesLocal";
+const LOCAL_HISTORY_TABLE_NAME: &str = "GatesHistoryLocal";
 const ENV_GATES_DYNAMO_DB_TABLE_NAME: &str = "GATES_DYNAMO_DB_TABLE_NAME";
+const ENV_GATES_DYNAMO_DB_HISTORY_TABLE_NAME: &str = "GATES_DYNAMO_DB_HISTORY_TABLE_NAME";
 
 pub(super) const DEFAULT_LOCAL_DYNAMO_DB_PORT: u16 = 8000;
 
@@ -37,6 +101,7 @@ pub(super) const DEFAULT_LOCAL_DYNAMO_DB_PORT: u16 = 8000;
 pub struct DynamoDbStorage {
     pub client: Client,
     pub table: String,
+    pub history_table: String,
 }
 
 #[async_trait]
@@ -46,14 +111,47 @@ impl Storage for DynamoDbStorage {
             .put_item()
             .table_name(&self.table)
             .set_item(Some(gate.into()))
-            .condition_expression("attribute_not_exists(#g)")
+            .condition_expression(GATE_NOT_EXISTS)
             .expression_attribute_names("#g", GROUP)
+            .expression_attribute_names("#da", DELETED_AT)
+            .send()
+            .await?;
+
+        Ok(())
+    }
+
+    async fn insert_all(&self, gates: &[Gate]) -> Result<(), InsertError> {
+        if gates.len() > MAX_TRANSACTION_ITEMS {
+            return Err(InsertError::Other(format!(
+                "at most {MAX_TRANSACTION_ITEMS} gates can be inserted at once"
+            )));
+        }
+
+        self.client
+            .transact_write_items()
+            .set_transact_items(Some(
+                gates
+                    .iter()
+                    .map(|gate| self.put_gate(gate))
+                    .collect::<Result<_, _>>()?,
+            ))
             .send()
             .await?;
 
         Ok(())
     }
 
+    async fn insert_each(
+        &self,
+        gates: &[Gate],
+    ) -> Result<Vec<Result<(), InsertError>>, InsertError> {
+        let mut results = Vec::with_capacity(gates.len());
+        for chunk in gates.chunks(MAX_TRANSACTION_ITEMS) {
+            results.extend(self.insert_chunk(chunk).await?);
+        }
+        Ok(results)
+    }
+
     async fn find_one(
         &self,
         GateKey {
@@ -76,6 +174,7 @@ impl Storage for DynamoDbStorage {
             .send()
             .await?
             .item()
+            .filter(|item| !item.contains_key(DELETED_AT))
             .map(|item| {
                 item.try_into().map_err(|error| {
                     FindError::ItemCouldNotBeDecoded(format!(
@@ -90,6 +189,9 @@ impl Storage for DynamoDbStorage {
         self.client
             .scan()
             .table_name(&self.table)
+            .filter_expression(IS_GATE)
+            .expression_attribute_names("#da", DELETED_AT)
+            .expression_attribute_names("#st", SETTING)
             .into_paginator()
             .items()
             .send()
@@ -110,47 +212,175 @@ impl Storage for DynamoDbStorage {
             })?
     }
 
-    async fn delete(
+    async fn find_by_group(&self, group: String) -> Result<Vec<Gate>, FindError> {
+        self.query(
+            self.client
+                .query()
+                .key_condition_expression("#g = :group")
+                .expression_attribute_names("#g", GROUP)
+                .expression_attribute_values(":group", AttributeValue::S(group)),
+        )
+        .await
+    }
+
+    async fn find_by_group_and_service(
         &self,
-        GateKey {
-            group,
-            service,
-            environment,
-        }: GateKey,
+        group: String,
+        service: String,
+    ) -> Result<Vec<Gate>, FindError> {
+        Ok(self
+            .query(
+                self.client
+                    .query()
+                    .key_condition_expression(
+                        "#g = :group AND begins_with(#se, :serviceEnvironment)",
+                    )
+                    .expression_attribute_names("#g", GROUP)
+                    .expression_attribute_names("#se", SERVICE_ENVIRONMENT)
+                    .expression_attribute_values(":group", AttributeValue::S(group))
+                    .expression_attribute_values(
+                        ":serviceEnvironment",
+                        AttributeValue::S(get_service_environment(service.as_str(), "")),
+                    ),
+            )
+            .await?
+            .into_iter()
+            // the prefix also matches services whose name continues with the separator
+            .filter(|gate| gate.key.service == service)
+            .collect())
+    }
+
+    async fn move_to_trash(
+        &self,
+        key: GateKey,
+        deleted_at: DateTime<Utc>,
     ) -> Result<(), DeleteError> {
         self.client
-            .delete_item()
+            .update_item()
             .table_name(&self.table)
-            .key(GROUP, AttributeValue::S(group))
+            .key(GROUP, AttributeValue::S(key.group.clone()))
             .key(
                 SERVICE_ENVIRONMENT,
-                AttributeValue::S(get_service_environment(
-                    service.as_str(),
-                    environment.as_str(),
-                )),
+                AttributeValue::S(get_service_environment(&key.service, &key.environment)),
             )
-            .condition_expression("attribute_exists(#g)")
+            .update_expression("SET #da = :deletedAt, #tr = :trash")
+            .condition_expression(GATE_EXISTS)
             .expression_attribute_names("#g", GROUP)
+            .expression_attribute_names("#da", DELETED_AT)
+            .expression_attribute_names("#tr", TRASH)
+            .expression_attribute_values(":deletedAt", encode_instant(deleted_at))
+            .expression_attribute_values(":trash", AttributeValue::S(TRASH.to_owned()))
             .send()
-            .await?;
+            .await
+            .map_err(|error| match update_error(error, &key, None) {
+                UpdateError::ItemToUpdateNotFound(error) => {
+                    DeleteError::ItemToDeleteNotFound(error)
+                }
+                UpdateError::VersionConflict(error) | UpdateError::Other(error) => {
+                    DeleteError::Other(error)
+                }
+            })?;
 
         Ok(())
     }
 
+    async fn restore(
+        &self,
+        key: GateKey,
+        deleted_since: DateTime<Utc>,
+    ) -> Result<Gate, UpdateError> {
+        self.client
+            .update_item()
+            .table_name(&self.table)
+            .key(GROUP, AttributeValue::S(key.group.clone()))
+            .key(
+                SERVICE_ENVIRONMENT,
+                AttributeValue::S(get_service_environment(&key.service, &key.environment)),
+            )
+            .update_expression("REMOVE #da, #tr")
+            .condition_expression("#da >= :deletedSince")
+            .expression_attribute_names("#da", DELETED_AT)
+            .expression_attribute_names("#tr", TRASH)
+            .expression_attribute_values(":deletedSince", encode_instant(deleted_since))
+            .return_values(ReturnValue::AllNew)
+            .send()
+            .await
+            .map_err(|error| update_error(error, &key, None))?
+            .attributes()
+            .ok_or_else(|| UpdateError::Other("missing restored gate".to_owned()))?
+            .try_into()
+            .map_err(|error| {
+                UpdateError::Other(format!("could not decode gate (mapping error: {error})"))
+            })
+    }
+
+    async fn purge_trash(&self, deleted_before: DateTime<Utc>) -> Result<u64, DeleteError> {
+        let items = self
+            .client
+            .query()
+            .table_name(&self.table)
+            .index_name(TRASH_INDEX_NAME)
+            .key_condition_expression("#tr = :trash AND #da < :deletedBefore")
+            .projection_expression("#g, #se")
+            .expression_attribute_names("#g", GROUP)
+            .expression_attribute_names("#se", SERVICE_ENVIRONMENT)
+            .expression_attribute_names("#tr", TRASH)
+            .expression_attribute_names("#da", DELETED_AT)
+            .expression_attribute_values(":trash", AttributeValue::S(TRASH.to_owned()))
+            .expression_attribute_values(":deletedBefore", encode_instant(deleted_before))
+            .into_paginator()
+            .items()
+            .send()
+            .collect::<Result<Vec<_>, _>>()
+            .await
+            .map_err(|error| {
+                DeleteError::Other(aws_sdk_dynamodb::error::DisplayErrorContext(error).to_string())
+            })?;
+
+        let mut purged = 0;
+        for item in items {
+            let result = self
+                .client
+                .delete_item()
+                .table_name(&self.table)
+                .set_key(Some(item))
+                // the gate could have been restored in the meantime
+                .condition_expression("#da < :deletedBefore")
+                .expression_attribute_names("#da", DELETED_AT)
+                .expression_attribute_values(":deletedBefore", encode_instant(deleted_before))
+                .send()
+                .await
+                .map_err(DeleteError::from);
+
+            match result {
+                Ok(_) => purged += 1,
+                Err(DeleteError::ItemToDeleteNotFound(_)) => {}
+                Err(error) => return Err(error),
+            }
+        }
+
+        Ok(purged)
+    }
+
     async fn update_state_and_last_updated(
         &self,
-        GateKey {
-            group,
-            service,
-            environment,
-        }: GateKey,
+        key: GateKey,
         state: GateState,
+        open_until: Option<DateTime<Utc>>,
         last_updated: DateTime<Utc>,
+        expected_version: Option<u32>,
     ) -> Result<Gate, UpdateError> {
-        self.prepare_update(group.as_str(), service.as_str(), environment.as_str())
-            .update_expression("SET #s = :newState, #lu = :newLastUpdated")
-            .condition_expression("attribute_exists(#g)")
+        let update_expression = if open_until.is_some() {
+            format!("SET #s = :newState, #ou = :newOpenUntil, #lu = :newLastUpdated, {INCREMENT_VERSION}")
+        } else {
+            format!("SET #s = :newState, #lu = :newLastUpdated, {INCREMENT_VERSION} REMOVE #ou")
+        };
+        let update = self
+            .prepare_update(&key, expected_version)
+            .update_expression(update_expression)
+            .condition_expression(version_condition(GATE_EXISTS, expected_version))
             .expression_attribute_names("#s", STATE)
+            .expression_attribute_names("#ou", OPEN_UNTIL)
             .expression_attribute_names("#lu", LAST_UPDATED)
             .expression_attribute_names("#g", GROUP)
             .expression_attribute_values(
@@ -160,30 +390,133 @@ impl Storage for DynamoDbStorage {
             .expression_attribute_values(
                 ":newLastUpdated",
                 AttributeValue::S(last_updated.to_rfc3339()),
-            )
+            );
+        match open_until {
+            Some(open_until) => update.expression_attribute_values(
+                ":newOpenUntil",
+                AttributeValue::S(open_until.to_rfc3339()),
+            ),
+            None => update,
+        }
+        .send()
+        .await
+        .map_err(|error| update_error(error, &key, expected_version))?
+        .attributes()
+        .ok_or_else(|| UpdateError::Other("missing updated gate".to_owned()))?
+        .try_into()
+        .map_err(|error| {
+            UpdateError::Other(format!("could not decode gate (mapping error: {error})"))
+        })
+    }
+
+    async fn update_states_and_last_updated(
+        &self,
+        keys: &[(GateKey, u32)],
+        state: GateState,
+        comment: Option<Comment>,
+        last_updated: DateTime<Utc>,
+    ) -> Result<Vec<Gate>, UpdateError> {
+        if keys.len() > MAX_TRANSACTION_ITEMS {
+            return Err(UpdateError::Other(format!(
+                "at most {MAX_TRANSACTION_ITEMS} gates can be updated at once"
+            )));
+        }
+
+        let state = AttributeValue::S(state.try_into().map_err(UpdateError::Other)?);
+        let update_expression = if comment.is_some() {
+            format!("SET #s = :newState, #lu = :newLastUpdated, #c.#i = :newComment, {INCREMENT_VERSION} REMOVE #ou")
+        } else {
+            format!("SET #s = :newState, #lu = :newLastUpdated, {INCREMENT_VERSION} REMOVE #ou")
+        };
+
+        self.client
+            .transact_write_items()
+            .set_transact_items(Some(
+                keys.iter()
+                    .map(|(key, expected_version)| {
+                        let update = Update::builder()
+                            .table_name(&self.table)
+                            .key(GROUP, AttributeValue::S(key.group.clone()))
+                            .key(
+                                SERVICE_ENVIRONMENT,
+                                AttributeValue::S(get_service_environment(
+                                    &key.service,
+                                    &key.environment,
+                                )),
+                            )
+                            .update_expression(&update_expression)
+                            .condition_expression(version_condition(
+                                GATE_EXISTS,
+                                Some(*expected_version),
+                            ))
+                            .return_values_on_condition_check_failure(
+                                ReturnValuesOnConditionCheckFailure::AllOld,
+                            )
+                            .expression_attribute_names("#s", STATE)
+                            .expression_attribute_names("#ou", OPEN_UNTIL)
+                            .expression_attribute_names("#lu", LAST_UPDATED)
+                            .expression_attribute_names("#g", GROUP)
+                            .expression_attribute_names("#v", VERSION)
+                            .expression_attribute_names("#da", DELETED_AT)
+                            .expression_attribute_values(":newState", state.clone())
+                            .expression_attribute_values(
+                                ":newLastUpdated",
+                                AttributeValue::S(last_updated.to_rfc3339()),
+                            )
+                            .expression_attribute_values(":zero", AttributeValue::N(0.to_string()))
+                            .expression_attribute_values(":one", AttributeValue::N(1.to_string()))
+                            .expression_attribute_values(
+                                ":expectedVersion",
+                                AttributeValue::N(expected_version.to_string()),
+                            );
+                        match &comment {
+                            Some(comment) => update
+                                .expression_attribute_names("#c", COMMENTS)
+                                .expression_attribute_names("#i", comment.id.clone())
+                                .expression_attribute_values(
+                                    ":newComment",
+                                    AttributeValue::M(HashMap::from(comment)),
+                                ),
+                            None => update,
+                        }
+                        .build()
+                        .map(|update| TransactWriteItem::builder().update(update).build())
+                        .map_err(|error| UpdateError::Other(error.to_string()))
+                    })
+                    .collect::<Result<_, _>>()?,
+            ))
             .send()
-            .await?
-            .attributes()
-            .ok_or_else(|| UpdateError::Other("missing updated gate".to_owned()))?
-            .try_into()
-            .map_err(|error| {
-                UpdateError::Other(format!("could not decode gate (mapping error: {error})"))
-            })
+            .await
+            .map_err(|error| transaction_update_error(error, keys))?;
+
+        let mut gates = Vec::with_capacity(keys.len());
+        for (key, _) in keys {
+            gates.push(
+                self.find_one(key.clone())
+                    .await
+                    .map_err(|error| match error {
+                        FindError::ItemCouldNotBeDecoded(error)
+                        | FindError::InvalidCursor(error)
+                        | FindError::Other(error) => UpdateError::Other(error),
+                    })?
+                    .ok_or_else(|| UpdateError::Other("missing updated gate".to_owned()))?,
+            );
+        }
+        Ok(gates)
     }
 
     async fn update_display_order_and_last_updated(
         &self,
-        GateKey {
-            group,
-            service,
-            environment,
-        }: GateKey,
+        key: GateKey,
         display_order: u32,
         last_updated: DateTime<Utc>,
+        expected_version: Option<u32>,
     ) -> Result<Gate, UpdateError> {
-        self.prepare_update(group.as_str(), service.as_str(), environment.as_str())
-            .update_expression("SET #dp = :display_order, #lu = :newLastUpdated")
-            .condition_expression("attribute_exists(#g)")
+        self.prepare_update(&key, expected_version)
+            .update_expression(format!(
+                "SET #dp = :display_order, #lu = :newLastUpdated, {INCREMENT_VERSION}"
+            ))
+            .condition_expression(version_condition(GATE_EXISTS, expected_version))
             .expression_attribute_names("#dp", DISPLAY_ORDER)
             .expression_attribute_names("#lu", LAST_UPDATED)
             .expression_attribute_names("#g", GROUP)
@@ -196,7 +529,8 @@ impl Storage for DynamoDbStorage {
                 AttributeValue::S(last_updated.to_rfc3339()),
             )
             .send()
-            .await?
+            .await
+            .map_err(|error| update_error(error, &key, expected_version))?
             .attributes()
             .ok_or_else(|| UpdateError::Other("missing updated gate".to_owned()))?
             .try_into()
@@ -207,17 +541,16 @@ impl Storage for DynamoDbStorage {
 
     async fn update_comment_and_last_updated(
         &self,
-        GateKey {
-            group,
-            service,
-            environment,
-        }: GateKey,
+        key: GateKey,
         comment: Comment,
         last_updated: DateTime<Utc>,
+        expected_version: Option<u32>,
     ) -> Result<Gate, UpdateError> {
-        self.prepare_update(group.as_str(), service.as_str(), environment.as_str())
-            .update_expression("SET #c.#i = :newComment, #lu = :newLastUpdated")
-            .condition_expression("attribute_exists(#g)")
+        self.prepare_update(&key, expected_version)
+            .update_expression(format!(
+                "SET #c.#i = :newComment, #lu = :newLastUpdated, {INCREMENT_VERSION}"
+            ))
+            .condition_expression(version_condition(GATE_EXISTS, expected_version))
             .expression_attribute_names("#c", COMMENTS)
             .expression_attribute_names("#i", comment.id.clone())
             .expression_attribute_names("#lu", LAST_UPDATED)
@@ -228,7 +561,8 @@ impl Storage for DynamoDbStorage {
                 AttributeValue::S(last_updated.to_rfc3339()),
             )
             .send()
-            .await?
+            .await
+            .map_err(|error| update_error(error, &key, expected_version))?
             .attributes()
             .ok_or_else(|| UpdateError::Other("missing updated gate".to_owned()))?
             .try_into()
@@ -239,17 +573,19 @@ impl Storage for DynamoDbStorage {
 
     async fn delete_comment_by_id_and_update_last_updated(
         &self,
-        GateKey {
-            group,
-            service,
-            environment,
-        }: GateKey,
+        key: GateKey,
         comment_id: String,
         last_updated: DateTime<Utc>,
+        expected_version: Option<u32>,
     ) -> Result<Gate, UpdateError> {
-        self.prepare_update(group.as_str(), service.as_str(), environment.as_str())
-            .update_expression("REMOVE #c.#i SET #lu = :newLastUpdated")
-            .condition_expression("attribute_exists(#g) AND attribute_exists(#c.#i)")
+        self.prepare_update(&key, expected_version)
+            .update_expression(format!(
+                "REMOVE #c.#i SET #lu = :newLastUpdated, {INCREMENT_VERSION}"
+            ))
+            .condition_expression(version_condition(
+                &format!("{GATE_EXISTS} AND attribute_exists(#c.#i)"),
+                expected_version,
+            ))
             .expression_attribute_names("#c", COMMENTS)
             .expression_attribute_names("#i", comment_id)
             .expression_attribute_names("#lu", LAST_UPDATED)
@@ -259,7 +595,8 @@ impl Storage for DynamoDbStorage {
                 AttributeValue::S(last_updated.to_rfc3339()),
             )
             .send()
-            .await?
+            .await
+            .map_err(|error| update_error(error, &key, expected_version))?
             .attributes()
             .ok_or_else(|| UpdateError::Other("missing updated gate".to_owned()))?
             .try_into()
@@ -267,964 +604,1445 @@ impl Storage for DynamoDbStorage {
                 UpdateError::Other(format!("could not decode gate (mapping error: {error})"))
             })
     }
-}
 
-impl DynamoDbStorage {
-    pub async fn new() -> Self {
-        let aws_config = &aws_config::load_defaults(BehaviorVersion::v2025_08_07()).await;
-        let client = Client::from_conf(config::Builder::from(aws_config).build());
+    async fn insert_history_entry(&self, entry: &HistoryEntry) -> Result<(), InsertError> {
+        self.client
+            .put_item()
+            .table_name(&self.history_table)
+            .set_item(Some(entry.into()))
+            .condition_expression("attribute_not_exists(#g)")
+            .expression_attribute_names("#g", GATE)
+            .send()
+            .await?;
 
-        Self {
-            client,
-            table: std::env::var(ENV_GATES_DYNAMO_DB_TABLE_NAME).unwrap(),
-        }
+        Ok(())
     }
 
-    pub async fn new_local(port: u16) -> Self {
-        let client = Client::from_conf(
-            config::Builder::new()
-                .behavior_version(BehaviorVersion::v2025_08_07())
-                .endpoint_url(format!("http://localhost:{port}/"))
-                .credentials_provider(Credentials::new(
-                    "AccessKeyId",
-                    "SecretAccessKeyId",
-                    None,
-                    None,
-                    "static",
-                ))
-                .region(Region::new("eu-central-1"))
-                .build(),
-        );
+    async fn find_history(
+        &self,
+        key: GateKey,
+        limit: u32,
+        cursor: Option<String>,
+    ) -> Result<HistoryPage, FindError> {
+        let gate = get_history_gate(&key);
+        let output = self
+            .client
+            .query()
+            .table_name(&self.history_table)
+            .key_condition_expression("#g = :gate")
+            .expression_attribute_names("#g", GATE)
+            .expression_attribute_values(":gate", AttributeValue::S(gate.clone()))
+            .scan_index_forward(false)
+            .limit(i32::try_from(limit).unwrap_or(i32::MAX))
+            .set_exclusive_start_key(cursor.map(|cursor| {
+                HashMap::from([
+                    (GATE.to_owned(), AttributeValue::S(gate)),
+                    (POSITION.to_owned(), AttributeValue::S(cursor)),
+                ])
+            }))
+            .send()
+            .await?;
 
-        create_local_table(&client).await;
+        let entries = output
+            .items()
+            .iter()
+            .map(|item| {
+                HistoryEntry::try_from(item).map_err(|error| {
+                    FindError::ItemCouldNotBeDecoded(format!(
+                        "could not decode history entry (mapping error: {error})"
+                    ))
+                })
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(HistoryPage {
+            entries,
+            next_cursor: output
+                .last_evaluated_key()
+                .and_then(|last_key| last_key.get(POSITION))
+                .and_then(|position| position.as_s().ok())
+                .map(ToOwned::to_owned),
+        })
+    }
 
-        Self {
-            client,
-            table: LOCAL_GATES_TABLE_NAME.to_owned(),
-        }
+    async fn save_freeze(&self, freeze: &Freeze) -> Result<(), InsertError> {
+        self.client
+            .put_item()
+            .table_name(&self.table)
+            .set_item(Some(freeze.into()))
+            .send()
+            .await?;
+
+        Ok(())
     }
 
-    fn prepare_update(
-        &self,
-        group: &str,
-        service: &str,
-        environment: &str,
-    ) -> UpdateItemFluentBuilder {
+    async fn find_freeze(&self) -> Result<Option<Freeze>, FindError> {
         self.client
-            .update_item()
+            .get_item()
             .table_name(&self.table)
-            .key(GROUP, AttributeValue::S(group.to_owned()))
+            .key(GROUP, AttributeValue::S(SETTINGS_GROUP.to_owned()))
             .key(
                 SERVICE_ENVIRONMENT,
-                AttributeValue::S(get_service_environment(service, environment)),
+                AttributeValue::S(FREEZE_SETTING.to_owned()),
             )
-            .return_values(ReturnValue::AllNew)
+            .send()
+            .await?
+            .item()
+            .map(|item| {
+                item.try_into().map_err(|error| {
+                    FindError::ItemCouldNotBeDecoded(format!(
+                        "could not decode freeze (mapping error: {error})"
+                    ))
+                })
+            })
+            .transpose()
     }
-}
 
-async fn create_local_table(client: &Client) {
-    _ = client
-        .create_table()
-        .provisioned_throughput(
-            ProvisionedThroughput::builder()
-                .read_capacity_units(1)
-                .write_capacity_units(1)
-                .build()
-                .expect("failed to build ProvisionedThroughput"),
-        )
-        .table_name(LOCAL_GATES_TABLE_NAME.to_owned())
-        .attribute_definitions(
-            AttributeDefinition::builder()
-                .attribute_name(GROUP.to_owned())
-                .attribute_type(ScalarAttributeType::S)
-                .build()
-                .expect("failed to build AttributeDefinition"),
-        )
-        .attribute_definitions(
-            AttributeDefinition::builder()
-                .attribute_name(SERVICE_ENVIRONMENT.to_owned())
-                .attribute_type(ScalarAttributeType::S)
-                .build()
-                .expect("failed to build AttributeDefinition"),
-        )
-        .key_schema(
-            KeySchemaElement::builder()
-                .attribute_name(GROUP.to_owned())
-                .key_type(KeyType::Hash)
-                .build()
-                .expect("failed to build KeySchemaElement"),
-        )
-        .key_schema(
-            KeySchemaElement::builder()
-                .attribute_name(SERVICE_ENVIRONMENT.to_owned())
-                .key_type(KeyType::Range)
-                .build()
-                .expect("failed to build KeySchemaElement"),
-        )
-        .send()
-        .await;
-}
+    async fn delete_freeze(&self) -> Result<(), DeleteError> {
+        self.client
+            .delete_item()
+            .table_name(&self.table)
+            .key(GROUP, AttributeValue::S(SETTINGS_GROUP.to_owned()))
+            .key(
+                SERVICE_ENVIRONMENT,
+                AttributeValue::S(FREEZE_SETTING.to_owned()),
+            )
+            .condition_expression("attribute_exists(#g)")
+            .expression_attribute_names("#g", GROUP)
+            .send()
+            .await?;
 
-fn get_service_environment(service: &str, environment: &str) -> String {
-    format!("{service}#{environment}")
-}
+        Ok(())
+    }
 
-/////////////////////////////////////////////////////////////////////////////
-// Encode
-/////////////////////////////////////////////////////////////////////////////
+    async fn save_business_week(&self, business_week: &BusinessWeek) -> Result<(), InsertError> {
+        self.client
+            .put_item()
+            .table_name(&self.table)
+            .item(GROUP, AttributeValue::S(SETTINGS_GROUP.to_owned()))
+            .item(
+                SERVICE_ENVIRONMENT,
+                AttributeValue::S(BUSINESS_WEEK_SETTING.to_owned()),
+            )
+            .item(SETTING, AttributeValue::S(BUSINESS_WEEK_SETTING.to_owned()))
+            .item(
+                WEEK,
+                AttributeValue::S(encode_business_week(business_week)?),
+            )
+            .send()
+            .await?;
 
-fn encode_string(field: &str, value: String) -> (String, AttributeValue) {
-    (field.to_owned(), AttributeValue::S(value))
-}
+        Ok(())
+    }
 
-fn encode_datetime_utc(field: &str, value: DateTime<Utc>) -> (String, AttributeValue) {
-    (field.to_owned(), AttributeValue::S(value.to_rfc3339()))
-}
+    async fn find_business_week(&self) -> Result<Option<BusinessWeek>, FindError> {
+        self.client
+            .get_item()
+            .table_name(&self.table)
+            .key(GROUP, AttributeValue::S(SETTINGS_GROUP.to_owned()))
+            .key(
+                SERVICE_ENVIRONMENT,
+                AttributeValue::S(BUSINESS_WEEK_SETTING.to_owned()),
+            )
+            .send()
+            .await?
+            .item()
+            .map(|item| {
+                decode_string(WEEK, item)
+                    .map_err(|error| {
+                        FindError::ItemCouldNotBeDecoded(format!(
+                            "could not decode business week (mapping error: {error})"
+                        ))
+                    })
+                    .and_then(|week| decode_business_week(&week))
+            })
+            .transpose()
+    }
 
-fn encode_map(field: &str, value: HashMap<String, AttributeValue>) -> (String, AttributeValue) {
-    (field.to_owned(), AttributeValue::M(value))
-}
+    async fn save_business_week_profile(
+        &self,
+        profile: &BusinessWeekProfile,
+    ) -> Result<(), InsertError> {
+        let mut item: HashMap<String, AttributeValue> = (&profile.scope).into();
+        item.insert(
+            WEEK.to_owned(),
+            AttributeValue::S(encode_business_week(&profile.business_week)?),
+        );
 
-fn encode_u32(field: &str, value: u32) -> (String, AttributeValue) {
-    (field.to_owned(), AttributeValue::N(value.to_string()))
-}
+        self.client
+            .put_item()
+            .table_name(&self.table)
+            .set_item(Some(item))
+            .send()
+            .await?;
 
-impl From<&Gate> for HashMap<String, AttributeValue, RandomState> {
-    fn from(value: &Gate) -> Self {
-        let mut fields = vec![
-            encode_string(GROUP, value.key.group.clone()),
-            encode_string(
+        Ok(())
+    }
+
+    async fn find_business_week_profiles(&self) -> Result<Vec<BusinessWeekProfile>, FindError> {
+        let mut profiles = self
+            .client
+            .query()
+            .table_name(&self.table)
+            .key_condition_expression("#g = :group AND begins_with(#se, :profile)")
+            .expression_attribute_names("#g", GROUP)
+            .expression_attribute_names("#se", SERVICE_ENVIRONMENT)
+            .expression_attribute_values(":group", AttributeValue::S(SETTINGS_GROUP.to_owned()))
+            .expression_attribute_values(
+                ":profile",
+                AttributeValue::S(format!("{BUSINESS_WEEK_PROFILE_SETTING}:")),
+            )
+            .into_paginator()
+            .items()
+            .send()
+            .collect::<Result<Vec<_>, _>>()
+            .await
+            .map_err(FindError::from)?
+            .iter()
+            .map(|item| {
+                let decode_error = |error| {
+                    FindError::ItemCouldNotBeDecoded(format!(
+                        "could not decode business week profile (mapping error: {error})"
+                    ))
+                };
+                Ok(BusinessWeekProfile {
+                    scope: BusinessWeekScope {
+                        group: decode_string(SCOPE_GROUP, item).map_err(decode_error)?,
+                        service: decode_optional_string(SCOPE_SERVICE, item)
+                            .map_err(decode_error)?,
+                        environment: decode_optional_string(SCOPE_ENVIRONMENT, item)
+                            .map_err(decode_error)?,
+                    },
+                    business_week: decode_business_week(
+                        &decode_string(WEEK, item).map_err(decode_error)?,
+                    )?,
+                })
+            })
+            .collect::<Result<Vec<_>, FindError>>()?;
+        profiles.sort_by(|left, right| left.scope.cmp(&right.scope));
+
+        Ok(profiles)
+    }
+
+    async fn delete_business_week_profile(
+        &self,
+        scope: BusinessWeekScope,
+    ) -> Result<(), DeleteError> {
+        self.client
+            .delete_item()
+            .table_name(&self.table)
+            .key(GROUP, AttributeValue::S(SETTINGS_GROUP.to_owned()))
+            .key(
                 SERVICE_ENVIRONMENT,
-                get_service_environment(value.key.service.as_str(), value.key.environment.as_str()),
-            ),
-            encode_string(SERVICE, value.key.service.clone()),
-            encode_string(ENVIRONMENT, value.key.environment.clone()),
-            encode_string(
-                STATE,
-                value
-                    .state
-                    .clone()
-                    .try_into()
-                    .expect("TODO Can not convert value to state"),
-            ),
-            encode_datetime_utc(LAST_UPDATED, value.last_updated),
-            encode_map(
-                COMMENTS,
-                value
-                    .comments
-                    .iter()
-                    .map(|comment| (comment.id.clone(), AttributeValue::M(comment.into())))
-                    .collect(),
-            ),
-        ];
+                AttributeValue::S(get_business_week_profile_setting(&scope)),
+            )
+            .condition_expression("attribute_exists(#g)")
+            .expression_attribute_names("#g", GROUP)
+            .send()
+            .await?;
 
-        if let Some(display_order) = value.display_order {
-            fields.push(encode_u32(DISPLAY_ORDER, display_order));
+        Ok(())
+    }
+
+    async fn save_closed_day(&self, closed_day: &ClosedDay) -> Result<(), InsertError> {
+        self.client
+            .put_item()
+            .table_name(&self.table)
+            .set_item(Some(closed_day.into()))
+            .send()
+            .await?;
+
+        Ok(())
+    }
+
+    async fn save_closed_days(&self, closed_days: &[ClosedDay]) -> Result<(), InsertError> {
+        if closed_days.len() > MAX_TRANSACTION_ITEMS {
+            return Err(InsertError::Other(format!(
+                "at most {MAX_TRANSACTION_ITEMS} closed days can be saved at once"
+            )));
         }
 
-        Self::from_iter(fields)
+        self.client
+            .transact_write_items()
+            .set_transact_items(Some(
+                closed_days
+                    .iter()
+                    .map(|closed_day| {
+                        Put::builder()
+                            .table_name(&self.table)
+                            .set_item(Some(closed_day.into()))
+                            .build()
+                            .map(|put| TransactWriteItem::builder().put(put).build())
+                            .map_err(|error| InsertError::Other(error.to_string()))
+                    })
+                    .collect::<Result<_, _>>()?,
+            ))
+            .send()
+            .await?;
+
+        Ok(())
     }
-}
 
-impl From<&Comment> for HashMap<String, AttributeValue, RandomState> {
-    fn from(value: &Comment) -> Self {
-        Self::from([
-            (ID.to_owned(), AttributeValue::S(value.id.clone())),
-            (MESSAGE.to_owned(), AttributeValue::S(value.message.clone())),
-            (
-                CREATED.to_owned(),
-                AttributeValue::S(value.created.to_rfc3339()),
-            ),
-        ])
+    async fn find_closed_days(&self) -> Result<Vec<ClosedDay>, FindError> {
+        self.client
+            .query()
+            .table_name(&self.table)
+            .key_condition_expression("#g = :group AND begins_with(#se, :closedDay)")
+            .expression_attribute_names("#g", GROUP)
+            .expression_attribute_names("#se", SERVICE_ENVIRONMENT)
+            .expression_attribute_values(":group", AttributeValue::S(SETTINGS_GROUP.to_owned()))
+            .expression_attribute_values(
+                ":closedDay",
+                AttributeValue::S(get_closed_day_setting("")),
+            )
+            .into_paginator()
+            .items()
+            .send()
+            .collect::<Result<Vec<_>, _>>()
+            .await
+            .map_err(FindError::from)?
+            .iter()
+            .map(|item| {
+                item.try_into().map_err(|error| {
+                    FindError::ItemCouldNotBeDecoded(format!(
+                        "could not decode closed day (mapping error: {error})"
+                    ))
+                })
+            })
+            .collect()
     }
-}
 
-/////////////////////////////////////////////////////////////////////////////
-// Decode
-/////////////////////////////////////////////////////////////////////////////
+    async fn delete_closed_day(&self, date: NaiveDate) -> Result<(), DeleteError> {
+        self.client
+            .delete_item()
+            .table_name(&self.table)
+            .key(GROUP, AttributeValue::S(SETTINGS_GROUP.to_owned()))
+            .key(
+                SERVICE_ENVIRONMENT,
+                AttributeValue::S(get_closed_day_setting(&date.to_string())),
+            )
+            .condition_expression("attribute_exists(#g)")
+            .expression_attribute_names("#g", GROUP)
+            .send()
+            .await?;
 
-type DecodeError = String;
+        Ok(())
+    }
 
-fn decode_string(
-    field: &str,
-    input: &HashMap<String, AttributeValue>,
-) -> Result<String, DecodeError> {
-    input
-        .get(field)
-        .ok_or_else(|| format!("field {field} could not be found"))?
-        .as_s()
-        .map_err(|_| format!("field {field} could not be parsed as string"))
-        .cloned()
-}
+    async fn insert_freeze_window(&self, freeze_window: &FreezeWindow) -> Result<(), InsertError> {
+        self.client
+            .put_item()
+            .table_name(&self.table)
+            .set_item(Some(freeze_window.into()))
+            .condition_expression("attribute_not_exists(#g)")
+            .expression_attribute_names("#g", GROUP)
+            .send()
+            .await?;
 
-fn decode_datetime_utc(
-    field: &str,
-    input: &HashMap<String, AttributeValue>,
-) -> Result<DateTime<Utc>, DecodeError> {
-    DateTime::parse_from_rfc3339(&decode_string(field, input)?)
-        .map_err(|_| format!("field {field} could not be parsed as datetime"))
-        .map(std::convert::Into::into)
-}
+        Ok(())
+    }
 
-fn decode_optional_u32(
-    field: &str,
-    input: &HashMap<String, AttributeValue>,
-) -> Result<Option<u32>, DecodeError> {
-    input
-        .get(field)
-        .map(|value| {
-            value
-                .as_n()
-                .map_err(|_| format!("field {field} could not be parsed as string"))
-                .and_then(|value| {
-                    value.parse().map_err(|error| {
-                        format!("field {field} could not be parsed as u32: {error}")
-                    })
+    async fn find_freeze_windows(&self) -> Result<Vec<FreezeWindow>, FindError> {
+        let mut freeze_windows = self
+            .client
+            .query()
+            .table_name(&self.table)
+            .key_condition_expression("#g = :group AND begins_with(#se, :freezeWindow)")
+            .expression_attribute_names("#g", GROUP)
+            .expression_attribute_names("#se", SERVICE_ENVIRONMENT)
+            .expression_attribute_values(":group", AttributeValue::S(SETTINGS_GROUP.to_owned()))
+            .expression_attribute_values(
+                ":freezeWindow",
+                AttributeValue::S(get_freeze_window_setting("")),
+            )
+            .into_paginator()
+            .items()
+            .send()
+            .collect::<Result<Vec<_>, _>>()
+            .await
+            .map_err(FindError::from)?
+            .iter()
+            .map(|item| {
+                item.try_into().map_err(|error| {
+                    FindError::ItemCouldNotBeDecoded(format!(
+                        "could not decode freeze window (mapping error: {error})"
+                    ))
                 })
-        })
-        .transpose()
-}
+            })
+            .collect::<Result<Vec<FreezeWindow>, _>>()?;
+        freeze_windows.sort_by_key(|freeze_window| freeze_window.start);
 
-fn decode_map<'a>(
-    field: &str,
-    input: &'a HashMap<String, AttributeValue>,
-) -> Result<&'a HashMap<String, AttributeValue>, DecodeError> {
-    input
-        .get(field)
-        .ok_or_else(|| format!("field {field} could not be found"))?
-        .as_m()
-        .map_err(|_| format!("field {field} could not be parsed as map"))
-}
+        Ok(freeze_windows)
+    }
 
-impl TryFrom<&HashMap<String, AttributeValue>> for Gate {
-    type Error = String;
+    async fn delete_freeze_window(&self, id: String) -> Result<(), DeleteError> {
+        self.client
+            .delete_item()
+            .table_name(&self.table)
+            .key(GROUP, AttributeValue::S(SETTINGS_GROUP.to_owned()))
+            .key(
+                SERVICE_ENVIRONMENT,
+                AttributeValue::S(get_freeze_window_setting(&id)),
+            )
+            .condition_expression("attribute_exists(#g)")
+            .expression_attribute_names("#g", GROUP)
+            .send()
+            .await?;
 
-    fn try_from(value: &HashMap<String, AttributeValue>) -> Result<Self, Self::Error> {
-        Ok(Self {
-            key: GateKey {
-                group: decode_string(GROUP, value)?,
-                service: decode_string(SERVICE, value)?,
-                environment: decode_string(ENVIRONMENT, value)?,
-            },
-            state: decode_string(STATE, value)?.try_into()?,
-            comments: decode_map(COMMENTS, value)?
-                .iter()
-                .map(|(id, value)| {
-                    value
-                        .as_m()
-                        .map_err(|_| format!("comment {id} could not be parsed"))
-                        .and_then(std::convert::TryInto::try_into)
+        Ok(())
+    }
+
+    async fn save_gate_dependencies(
+        &self,
+        dependencies: &GateDependencies,
+    ) -> Result<(), InsertError> {
+        if dependencies.depends_on.is_empty() {
+            self.client
+                .delete_item()
+                .table_name(&self.table)
+                .key(GROUP, AttributeValue::S(SETTINGS_GROUP.to_owned()))
+                .key(
+                    SERVICE_ENVIRONMENT,
+                    AttributeValue::S(get_gate_dependency_setting(&dependencies.key)),
+                )
+                .send()
+                .await
+                .map_err(|error| {
+                    InsertError::Other(
+                        aws_sdk_dynamodb::error::DisplayErrorContext(error).to_string(),
+                    )
+                })?;
+            return Ok(());
+        }
+
+        self.client
+            .put_item()
+            .table_name(&self.table)
+            .set_item(Some(HashMap::from_iter([
+                encode_string(GROUP, SETTINGS_GROUP.to_owned()),
+                encode_string(
+                    SERVICE_ENVIRONMENT,
+                    get_gate_dependency_setting(&dependencies.key),
+                ),
+                encode_string(SETTING, GATE_DEPENDENCY_SETTING.to_owned()),
+                encode_string(GATE_GROUP, dependencies.key.group.clone()),
+                encode_string(SERVICE, dependencies.key.service.clone()),
+                encode_string(ENVIRONMENT, dependencies.key.environment.clone()),
+                encode_string(DEPENDS_ON, encode_gate_keys(&dependencies.depends_on)?),
+            ])))
+            .send()
+            .await?;
+
+        Ok(())
+    }
+
+    async fn find_gate_dependencies(&self) -> Result<Vec<GateDependencies>, FindError> {
+        let mut dependencies = self
+            .client
+            .query()
+            .table_name(&self.table)
+            .key_condition_expression("#g = :group AND begins_with(#se, :gateDependency)")
+            .expression_attribute_names("#g", GROUP)
+            .expression_attribute_names("#se", SERVICE_ENVIRONMENT)
+            .expression_attribute_values(":group", AttributeValue::S(SETTINGS_GROUP.to_owned()))
+            .expression_attribute_values(
+                ":gateDependency",
+                AttributeValue::S(format!("{GATE_DEPENDENCY_SETTING}:")),
+            )
+            .into_paginator()
+            .items()
+            .send()
+            .collect::<Result<Vec<_>, _>>()
+            .await
+            .map_err(FindError::from)?
+            .iter()
+            .map(|item| {
+                let decode_error = |error| {
+                    FindError::ItemCouldNotBeDecoded(format!(
+                        "could not decode gate dependencies (mapping error: {error})"
+                    ))
+                };
+                Ok(GateDependencies {
+                    key: GateKey {
+                        group: decode_string(GATE_GROUP, item).map_err(decode_error)?,
+                        service: decode_string(SERVICE, item).map_err(decode_error)?,
+                        environment: decode_string(ENVIRONMENT, item).map_err(decode_error)?,
+                    },
+                    depends_on: decode_gate_keys(
+                        &decode_string(DEPENDS_ON, item).map_err(decode_error)?,
+                    )?,
+                })
+            })
+            .collect::<Result<Vec<_>, FindError>>()?;
+        dependencies.sort_by(|left, right| left.key.cmp(&right.key));
+
+        Ok(dependencies)
+    }
+
+    async fn insert_scheduled_state_change(
+        &self,
+        change: &ScheduledStateChange,
+    ) -> Result<(), InsertError> {
+        self.client
+            .put_item()
+            .table_name(&self.table)
+            .set_item(Some(change.try_into().map_err(InsertError::Other)?))
+            .condition_expression("attribute_not_exists(#g)")
+            .expression_attribute_names("#g", GROUP)
+            .send()
+            .await?;
+
+        Ok(())
+    }
+
+    async fn find_scheduled_state_changes(&self) -> Result<Vec<ScheduledStateChange>, FindError> {
+        let mut changes = self
+            .client
+            .query()
+            .table_name(&self.table)
+            .key_condition_expression("#g = :group AND begins_with(#se, :scheduledStateChange)")
+            .expression_attribute_names("#g", GROUP)
+            .expression_attribute_names("#se", SERVICE_ENVIRONMENT)
+            .expression_attribute_values(":group", AttributeValue::S(SETTINGS_GROUP.to_owned()))
+            .expression_attribute_values(
+                ":scheduledStateChange",
+                AttributeValue::S(get_scheduled_state_change_setting("")),
+            )
+            .into_paginator()
+            .items()
+            .send()
+            .collect::<Result<Vec<_>, _>>()
+            .await
+            .map_err(FindError::from)?
+            .iter()
+            .map(|item| {
+                item.try_into().map_err(|error| {
+                    FindError::ItemCouldNotBeDecoded(format!(
+                        "could not decode scheduled state change (mapping error: {error})"
+                    ))
                 })
-                .collect::<Result<HashSet<Comment>, String>>()?,
-            last_updated: decode_datetime_utc(LAST_UPDATED, value)?,
-            display_order: decode_optional_u32(DISPLAY_ORDER, value)?,
-        })
+            })
+            .collect::<Result<Vec<ScheduledStateChange>, _>>()?;
+        changes.sort_by_key(|change| change.at);
+
+        Ok(changes)
     }
-}
 
-impl TryFrom<&HashMap<String, AttributeValue>> for Comment {
-    type Error = String;
+    async fn delete_scheduled_state_change(&self, id: String) -> Result<(), DeleteError> {
+        self.client
+            .delete_item()
+            .table_name(&self.table)
+            .key(GROUP, AttributeValue::S(SETTINGS_GROUP.to_owned()))
+            .key(
+                SERVICE_ENVIRONMENT,
+                AttributeValue::S(get_scheduled_state_change_setting(&id)),
+            )
+            .condition_expression("attribute_exists(#g)")
+            .expression_attribute_names("#g", GROUP)
+            .send()
+            .await?;
 
-    fn try_from(value: &HashMap<String, AttributeValue>) -> Result<Self, Self::Error> {
-        Ok(Self {
-            id: decode_string(ID, value)?,
-            message: decode_string(MESSAGE, value)?,
-            created: decode_datetime_utc(CREATED, value)?,
-        })
+        Ok(())
     }
 }
 
-/////////////////////////////////////////////////////////////////////////////
-// Converting SdkError<_> to Storage Errors
-/////////////////////////////////////////////////////////////////////////////
+// the ISO date keeps closed days ordered by their sort key
+fn get_closed_day_setting(date: &str) -> String {
+    format!("{CLOSED_DAY_SETTING}:{date}")
+}
 
-impl From<SdkError<get_item::GetItemError>> for FindError {
-    fn from(value: SdkError<get_item::GetItemError>) -> Self {
-        Self::Other(aws_sdk_dynamodb::error::DisplayErrorContext(value).to_string())
-    }
+// encoded as JSON, as the names of groups, services and environments may contain any character
+fn get_business_week_profile_setting(scope: &BusinessWeekScope) -> String {
+    format!(
+        "{BUSINESS_WEEK_PROFILE_SETTING}:{}",
+        serde_json::json!([scope.group, scope.service, scope.environment])
+    )
 }
 
-impl From<SdkError<put_item::PutItemError>> for InsertError {
-    fn from(value: SdkError<put_item::PutItemError>) -> Self {
-        match value.into_service_error() {
-            put_item::PutItemError::ConditionalCheckFailedException(exception) => {
-                Self::ItemAlreadyExists(
-                    aws_sdk_dynamodb::error::DisplayErrorContext(exception).to_string(),
-                )
-            }
-            error => Self::Other(aws_sdk_dynamodb::error::DisplayErrorContext(error).to_string()),
+// encoded as JSON, as the names of groups, services and environments may contain any character
+fn get_gate_dependency_setting(key: &GateKey) -> String {
+    format!(
+        "{GATE_DEPENDENCY_SETTING}:{}",
+        serde_json::json!([key.group, key.service, key.environment])
+    )
+}
+
+fn get_freeze_window_setting(id: &str) -> String {
+    format!("{FREEZE_WINDOW_SETTING}:{id}")
+}
+
+fn get_scheduled_state_change_setting(id: &str) -> String {
+    format!("{SCHEDULED_STATE_CHANGE_SETTING}:{id}")
+}
+
+impl DynamoDbStorage {
+    pub async fn new() -> Self {
+        let aws_config = &aws_config::load_defaults(BehaviorVersion::v2025_08_07()).await;
+        let client = Client::from_conf(config::Builder::from(aws_config).build());
+
+        Self {
+            client,
+            table: std::env::var(ENV_GATES_DYNAMO_DB_TABLE_NAME).unwrap(),
+            history_table: std::env::var(ENV_GATES_DYNAMO_DB_HISTORY_TABLE_NAME).unwrap(),
         }
     }
-}
 
-impl From<SdkError<update_item::UpdateItemError>> for UpdateError {
-    fn from(value: SdkError<update_item::UpdateItemError>) -> Self {
-        match value.into_service_error() {
-            update_item::UpdateItemError::ConditionalCheckFailedException(exception) => {
-                Self::ItemToUpdateNotFound(
-                    aws_sdk_dynamodb::error::DisplayErrorContext(exception).to_string(),
-                )
-            }
-            error => Self::Other(aws_sdk_dynamodb::error::DisplayErrorContext(error).to_string()),
+    pub async fn new_local(port: u16) -> Self {
+        let client = Client::from_conf(
+            config::Builder::new()
+                .behavior_version(BehaviorVersion::v2025_08_07())
+                .endpoint_url(format!("http://localhost:{port}/"))
+                .credentials_provider(Credentials::new(
+                    "AccessKeyId",
+                    "SecretAccessKeyId",
+                    None,
+                    None,
+                    "static",
+                ))
+                .region(Region::new("eu-central-1"))
+                .build(),
+        );
+
+        create_local_table(
+            &client,
+            LOCAL_GATES_TABLE_NAME,
+            GROUP,
+            SERVICE_ENVIRONMENT,
+            Some((TRASH_INDEX_NAME, TRASH, DELETED_AT)),
+        )
+        .await;
+        create_local_table(&client, LOCAL_HISTORY_TABLE_NAME, GATE, POSITION, None).await;
+
+        Self {
+            client,
+            table: LOCAL_GATES_TABLE_NAME.to_owned(),
+            history_table: LOCAL_HISTORY_TABLE_NAME.to_owned(),
         }
     }
-}
 
-impl From<SdkError<scan::ScanError>> for FindError {
-    fn from(value: SdkError<scan::ScanError>) -> Self {
-        Self::Other(aws_sdk_dynamodb::error::DisplayErrorContext(value).to_string())
+    async fn query(&self, query: QueryFluentBuilder) -> Result<Vec<Gate>, FindError> {
+        query
+            .table_name(&self.table)
+            .filter_expression(IS_GATE)
+            .expression_attribute_names("#da", DELETED_AT)
+            .expression_attribute_names("#st", SETTING)
+            .into_paginator()
+            .items()
+            .send()
+            .collect::<Result<Vec<_>, _>>()
+            .await
+            .map_err(FindError::from)
+            .map(|result| {
+                result
+                    .into_iter()
+                    .map(|item| {
+                        Gate::try_from(&item).map_err(|error| {
+                            FindError::ItemCouldNotBeDecoded(format!(
+                                "could not decode gate (mapping error: {error})"
+                            ))
+                        })
+                    })
+                    .collect()
+            })?
     }
-}
 
-impl From<SdkError<query::QueryError>> for FindError {
-    fn from(value: SdkError<query::QueryError>) -> Self {
-        Self::Other(aws_sdk_dynamodb::error::DisplayErrorContext(value).to_string())
+    fn put_gate(&self, gate: &Gate) -> Result<TransactWriteItem, InsertError> {
+        Put::builder()
+            .table_name(&self.table)
+            .set_item(Some(gate.into()))
+            .condition_expression(GATE_NOT_EXISTS)
+            .expression_attribute_names("#g", GROUP)
+            .expression_attribute_names("#da", DELETED_AT)
+            .build()
+            .map(|put| TransactWriteItem::builder().put(put).build())
+            .map_err(|error| InsertError::Other(error.to_string()))
     }
-}
 
-impl From<SdkError<delete_item::DeleteItemError>> for DeleteError {
-    fn from(value: SdkError<delete_item::DeleteItemError>) -> Self {
-        match value.into_service_error() {
-            delete_item::DeleteItemError::ConditionalCheckFailedException(exception) => {
-                Self::ItemToDeleteNotFound(
-                    aws_sdk_dynamodb::error::DisplayErrorContext(exception).to_string(),
-                )
+    /// Retries the transaction without the gates that already exist until the others are inserted.
+    async fn insert_chunk(
+        &self,
+        gates: &[Gate],
+    ) -> Result<Vec<Result<(), InsertError>>, InsertError> {
+        let mut results: Vec<Option<Result<(), InsertError>>> =
+            gates.iter().map(|_| None).collect();
+        loop {
+            let pending: Vec<usize> = (0..gates.len())
+                .filter(|index| results[*index].is_none())
+                .collect();
+            if pending.is_empty() {
+                return Ok(results.into_iter().flatten().collect());
             }
 
-            error => Self::Other(aws_sdk_dynamodb::error::DisplayErrorContext(error).to_string()),
+            let items = pending
+                .iter()
+                .map(|index| self.put_gate(&gates[*index]))
+                .collect::<Result<_, _>>()?;
+            match self
+                .client
+                .transact_write_items()
+                .set_transact_items(Some(items))
+                .send()
+                .await
+            {
+                Ok(_) => {
+                    for index in pending {
+                        results[index] = Some(Ok(()));
+                    }
+                }
+                Err(error) => {
+                    let existing = existing_items(error, &pending)?;
+                    for index in existing {
+                        results[index] = Some(Err(InsertError::ItemAlreadyExists(format!(
+                            "gate {} already exists",
+                            gates[index].key
+                        ))));
+                    }
+                }
+            }
         }
     }
-}
 
-#[cfg(test)]
-mod integration_tests {
-    use chrono::DateTime;
-    use itertools::concat;
-    use mockall::Any;
-    use similar_asserts::assert_eq;
-    use testcontainers::runners::AsyncRunner;
-    use testcontainers_modules::dynamodb_local::DynamoDb;
+    fn prepare_update(
+        &self,
+        key: &GateKey,
+        expected_version: Option<u32>,
+    ) -> UpdateItemFluentBuilder {
+        let update = self
+            .client
+            .update_item()
+            .table_name(&self.table)
+            .key(GROUP, AttributeValue::S(key.group.clone()))
+            .key(
+                SERVICE_ENVIRONMENT,
+                AttributeValue::S(get_service_environment(&key.service, &key.environment)),
+            )
+            .expression_attribute_names("#v", VERSION)
+            .expression_attribute_names("#da", DELETED_AT)
+            .expression_attribute_values(":zero", AttributeValue::N(0.to_string()))
+            .expression_attribute_values(":one", AttributeValue::N(1.to_string()))
+            .return_values(ReturnValue::AllNew)
+            .return_values_on_condition_check_failure(ReturnValuesOnConditionCheckFailure::AllOld);
 
-    use crate::types::Gate;
+        match expected_version {
+            Some(expected_version) => update.expression_attribute_values(
+                ":expectedVersion",
+                AttributeValue::N(expected_version.to_string()),
+            ),
+            None => update,
+        }
+    }
+}
 
-    use super::*;
+/// Creates the table, if given with a global secondary index of name, partition key and sort key.
+async fn create_local_table(
+    client: &Client,
+    table_name: &str,
+    partition_key: &str,
+    sort_key: &str,
+    index: Option<(&str, &str, &str)>,
+) {
+    let throughput = ProvisionedThroughput::builder()
+        .read_capacity_units(1)
+        .write_capacity_units(1)
+        .build()
+        .expect("failed to build ProvisionedThroughput");
+    let mut create_table = client
+        .create_table()
+        .provisioned_throughput(throughput.clone())
+        .table_name(table_name)
+        .attribute_definitions(attribute_definition(partition_key))
+        .attribute_definitions(attribute_definition(sort_key))
+        .set_key_schema(Some(key_schema(partition_key, sort_key)));
+    if let Some((index_name, index_partition_key, index_sort_key)) = index {
+        create_table = create_table
+            .attribute_definitions(attribute_definition(index_partition_key))
+            .attribute_definitions(attribute_definition(index_sort_key))
+            .global_secondary_indexes(
+                GlobalSecondaryIndex::builder()
+                    .index_name(index_name)
+                    .set_key_schema(Some(key_schema(index_partition_key, index_sort_key)))
+                    .projection(
+                        Projection::builder()
+                            .projection_type(ProjectionType::KeysOnly)
+                            .build(),
+                    )
+                    .provisioned_throughput(throughput)
+                    .build()
+                    .expect("failed to build GlobalSecondaryIndex"),
+            );
+    }
+    _ = create_table.send().await;
+}
 
-    #[tokio::test]
-    async fn should_insert_and_find_one() {
-        // given
-        let dynamodb_container = DynamoDb::default()
-            .start()
-            .await
-            .expect("dynamoDb docker container to be started");
-        let port = dynamodb_container
-            .get_host_port_ipv4(8000)
-            .await
-            .expect("dynamoDb docker container host port go be found");
+fn attribute_definition(name: &str) -> AttributeDefinition {
+    AttributeDefinition::builder()
+        .attribute_name(name)
+        .attribute_type(ScalarAttributeType::S)
+        .build()
+        .expect("failed to build AttributeDefinition")
+}
 
-        let dynamodb_storage = DynamoDbStorage::new_local(port).await;
-        assert_empty(&dynamodb_storage).await;
+fn key_schema(partition_key: &str, sort_key: &str) -> Vec<KeySchemaElement> {
+    vec![
+        KeySchemaElement::builder()
+            .attribute_name(partition_key)
+            .key_type(KeyType::Hash)
+            .build()
+            .expect("failed to build KeySchemaElement"),
+        KeySchemaElement::builder()
+            .attribute_name(sort_key)
+            .key_type(KeyType::Range)
+            .build()
+            .expect("failed to build KeySchemaElement"),
+    ]
+}
 
-        let gate = some_gate("some group", "some service", "some environment");
+fn get_service_environment(service: &str, environment: &str) -> String {
+    format!("{service}#{environment}")
+}
 
-        dynamodb_storage
-            .insert(&gate)
-            .await
-            .expect("storage failed to insert gate");
+// encoded as JSON, as the names of groups, services and environments may contain any character
+fn get_history_gate(key: &GateKey) -> String {
+    serde_json::json!([key.group, key.service, key.environment]).to_string()
+}
 
-        // when
-        let result = dynamodb_storage.find_one(gate.key.clone()).await;
+fn get_history_cursor(entry: &HistoryEntry) -> String {
+    format!("{:020}_{}", entry.timestamp.timestamp_micros(), entry.id)
+}
 
-        // then
-        let stored_gate = result
-            .expect("storage failed to find gate")
-            .expect("gate not found");
-        assert_eq!(stored_gate, gate);
+fn version_condition(condition: &str, expected_version: Option<u32>) -> String {
+    match expected_version {
+        // gates stored before versioning was introduced have no version attribute
+        Some(0) => format!("{condition} AND (attribute_not_exists(#v) OR #v = :expectedVersion)"),
+        Some(_) => format!("{condition} AND #v = :expectedVersion"),
+        None => condition.to_owned(),
     }
+}
 
-    #[tokio::test]
-    async fn should_not_insert_if_item_already_exists() {
-        // given
-        let dynamodb_container = DynamoDb::default()
-            .start()
-            .await
-            .expect("dynamoDb docker container to be started");
-        let port = dynamodb_container
-            .get_host_port_ipv4(8000)
-            .await
-            .expect("dynamoDb docker container host port go be found");
-
-        let dynamodb_storage = DynamoDbStorage::new_local(port).await;
-        assert_empty(&dynamodb_storage).await;
+/////////////////////////////////////////////////////////////////////////////
+// Encode
+/////////////////////////////////////////////////////////////////////////////
 
-        let gate = some_gate("some group", "some service", "some environment");
+fn encode_string(field: &str, value: String) -> (String, AttributeValue) {
+    (field.to_owned(), AttributeValue::S(value))
+}
 
-        dynamodb_storage
-            .insert(&gate)
-            .await
-            .expect("storage failed to insert gate");
+fn encode_datetime_utc(field: &str, value: DateTime<Utc>) -> (String, AttributeValue) {
+    (field.to_owned(), AttributeValue::S(value.to_rfc3339()))
+}
 
-        // when
-        let result = dynamodb_storage.insert(&gate).await;
+/// Instants compared inside of condition expressions need a fixed length representation.
+fn encode_instant(value: DateTime<Utc>) -> AttributeValue {
+    AttributeValue::S(value.to_rfc3339_opts(SecondsFormat::Micros, true))
+}
 
-        // then
-        assert_eq!(result.is_err(), true);
-        assert_eq!(
-            result.expect_err("expected error not found").type_name(),
-            InsertError::ItemAlreadyExists(String::default()).type_name()
-        );
-    }
+fn encode_map(field: &str, value: HashMap<String, AttributeValue>) -> (String, AttributeValue) {
+    (field.to_owned(), AttributeValue::M(value))
+}
 
-    #[tokio::test]
-    async fn should_not_find_one_if_gate_not_exists() {
-        // given
-        let dynamodb_container = DynamoDb::default()
-            .start()
-            .await
-            .expect("dynamoDb docker container to be started");
-        let port = dynamodb_container
-            .get_host_port_ipv4(8000)
-            .await
-            .expect("dynamoDb docker container host port go be found");
+fn encode_u32(field: &str, value: u32) -> (String, AttributeValue) {
+    (field.to_owned(), AttributeValue::N(value.to_string()))
+}
 
-        let dynamodb_storage = DynamoDbStorage::new_local(port).await;
-        assert_empty(&dynamodb_storage).await;
+impl From<&Gate> for HashMap<String, AttributeValue, RandomState> {
+    fn from(value: &Gate) -> Self {
+        let mut fields = vec![
+            encode_string(GROUP, value.key.group.clone()),
+            encode_string(
+                SERVICE_ENVIRONMENT,
+                get_service_environment(value.key.service.as_str(), value.key.environment.as_str()),
+            ),
+            encode_string(SERVICE, value.key.service.clone()),
+            encode_string(ENVIRONMENT, value.key.environment.clone()),
+            encode_string(
+                STATE,
+                value
+                    .state
+                    .clone()
+                    .try_into()
+                    .expect("TODO Can not convert value to state"),
+            ),
+            encode_datetime_utc(LAST_UPDATED, value.last_updated),
+            encode_map(
+                COMMENTS,
+                value
+                    .comments
+                    .iter()
+                    .map(|comment| (comment.id.clone(), AttributeValue::M(comment.into())))
+                    .collect(),
+            ),
+        ];
 
-        // when
-        let result = dynamodb_storage
-            .find_one(GateKey {
-                group: "some group".to_owned(),
-                service: "some service".to_owned(),
-                environment: "some environment".to_owned(),
-            })
-            .await;
+        if let Some(display_order) = value.display_order {
+            fields.push(encode_u32(DISPLAY_ORDER, display_order));
+        }
+        fields.push(encode_u32(VERSION, value.version));
+        if let Some(open_until) = value.open_until {
+            fields.push(encode_datetime_utc(OPEN_UNTIL, open_until));
+        }
 
-        // then
-        let stored_gate = result.expect("storage failed to find gate");
-        assert_eq!(stored_gate.is_none(), true);
+        Self::from_iter(fields)
     }
+}
 
-    #[tokio::test]
-    async fn should_insert_and_find_all() {
-        // given
-        let dynamodb_container = DynamoDb::default()
-            .start()
-            .await
-            .expect("dynamoDb docker container to be started");
-        let port = dynamodb_container
-            .get_host_port_ipv4(8000)
-            .await
-            .expect("dynamoDb docker container host port go be found");
-
-        let dynamodb_storage = DynamoDbStorage::new_local(port).await;
-        assert_empty(&dynamodb_storage).await;
-
-        let gate1 = some_gate("some group", "some service", "some environment");
-        let gate2 = some_gate(
-            "some other group",
-            "some other service",
-            "some other environment",
-        );
-
-        dynamodb_storage
-            .insert(&gate1)
-            .await
-            .expect("storage failed to insert gate");
-        dynamodb_storage
-            .insert(&gate2)
-            .await
-            .expect("storage failed to insert gate");
+impl From<&HistoryEntry> for HashMap<String, AttributeValue, RandomState> {
+    fn from(value: &HistoryEntry) -> Self {
+        let mut fields = vec![
+            encode_string(GATE, get_history_gate(&value.key)),
+            encode_string(POSITION, get_history_cursor(value)),
+            encode_string(GROUP, value.key.group.clone()),
+            encode_string(SERVICE, value.key.service.clone()),
+            encode_string(ENVIRONMENT, value.key.environment.clone()),
+            encode_string(ID, value.id.clone()),
+            encode_datetime_utc(TIMESTAMP, value.timestamp),
+            encode_string(EVENT, value.event.into()),
+        ];
 
-        // when
-        let result = dynamodb_storage.find_all().await;
+        for (field, value) in [
+            (OLD_VALUE, &value.old_value),
+            (NEW_VALUE, &value.new_value),
+            (ACTOR, &value.actor),
+        ] {
+            if let Some(value) = value {
+                fields.push(encode_string(field, value.clone()));
+            }
+        }
+        if let Some(open_until) = value.open_until {
+            fields.push(encode_datetime_utc(OPEN_UNTIL, open_until));
+        }
 
-        // then
-        let stored_gates = result.expect("storage failed to find gates");
-        assert_eq!(stored_gates.len(), 2);
-        assert_eq!(stored_gates, vec![gate1, gate2]);
+        Self::from_iter(fields)
     }
+}
 
-    #[tokio::test]
-    async fn should_insert_and_delete() {
-        // given
-        let dynamodb_container = DynamoDb::default()
-            .start()
-            .await
-            .expect("dynamoDb docker container to be started");
-        let port = dynamodb_container
-            .get_host_port_ipv4(8000)
-            .await
-            .expect("dynamoDb docker container host port go be found");
-
-        let dynamodb_storage = DynamoDbStorage::new_local(port).await;
-        assert_empty(&dynamodb_storage).await;
-
-        let gate = some_gate("some group", "some service", "some environment");
+impl From<&Freeze> for HashMap<String, AttributeValue, RandomState> {
+    fn from(value: &Freeze) -> Self {
+        let mut fields = vec![
+            encode_string(GROUP, SETTINGS_GROUP.to_owned()),
+            encode_string(SERVICE_ENVIRONMENT, FREEZE_SETTING.to_owned()),
+            encode_string(SETTING, FREEZE_SETTING.to_owned()),
+            encode_string(REASON, value.reason.clone()),
+            encode_datetime_utc(CREATED, value.created),
+        ];
+        if let Some(actor) = &value.actor {
+            fields.push(encode_string(ACTOR, actor.clone()));
+        }
+        if let Some(until) = value.until {
+            fields.push(encode_datetime_utc(UNTIL, until));
+        }
 
-        dynamodb_storage
-            .insert(&gate)
-            .await
-            .expect("storage failed to insert gate");
+        Self::from_iter(fields)
+    }
+}
 
-        let stored_gates = dynamodb_storage
-            .find_all()
-            .await
-            .expect("storage failed to find gates");
-        assert_eq!(stored_gates.len(), 1);
+impl From<&ClosedDay> for HashMap<String, AttributeValue, RandomState> {
+    fn from(value: &ClosedDay) -> Self {
+        Self::from_iter(vec![
+            encode_string(GROUP, SETTINGS_GROUP.to_owned()),
+            encode_string(
+                SERVICE_ENVIRONMENT,
+                get_closed_day_setting(&value.date.to_string()),
+            ),
+            encode_string(SETTING, CLOSED_DAY_SETTING.to_owned()),
+            encode_string(DATE, value.date.to_string()),
+            encode_string(REASON, value.reason.clone()),
+        ])
+    }
+}
 
-        // when
-        let result = dynamodb_storage.delete(gate.key).await;
+impl From<&BusinessWeekScope> for HashMap<String, AttributeValue, RandomState> {
+    fn from(value: &BusinessWeekScope) -> Self {
+        let mut fields = vec![
+            encode_string(GROUP, SETTINGS_GROUP.to_owned()),
+            encode_string(
+                SERVICE_ENVIRONMENT,
+                get_business_week_profile_setting(value),
+            ),
+            encode_string(SETTING, BUSINESS_WEEK_PROFILE_SETTING.to_owned()),
+            encode_string(SCOPE_GROUP, value.group.clone()),
+        ];
+        if let Some(service) = &value.service {
+            fields.push(encode_string(SCOPE_SERVICE, service.clone()));
+        }
+        if let Some(environment) = &value.environment {
+            fields.push(encode_string(SCOPE_ENVIRONMENT, environment.clone()));
+        }
 
-        // then
-        result.expect("storage failed to delete gate");
-        let count = dynamodb_storage
-            .find_all()
-            .await
-            .expect("storage failed to find gates")
-            .len();
-        assert_eq!(count, 0);
+        Self::from_iter(fields)
     }
+}
 
-    #[tokio::test]
-    async fn should_fail_to_delete_item_if_item_does_not_exist() {
-        // given
-        let dynamodb_container = DynamoDb::default()
-            .start()
-            .await
-            .expect("dynamoDb docker container to be started");
-        let port = dynamodb_container
-            .get_host_port_ipv4(8000)
-            .await
-            .expect("dynamoDb docker container host port go be found");
-
-        let dynamodb_storage = DynamoDbStorage::new_local(port).await;
-        assert_empty(&dynamodb_storage).await;
+impl From<&FreezeWindow> for HashMap<String, AttributeValue, RandomState> {
+    fn from(value: &FreezeWindow) -> Self {
+        let mut fields = vec![
+            encode_string(GROUP, SETTINGS_GROUP.to_owned()),
+            encode_string(SERVICE_ENVIRONMENT, get_freeze_window_setting(&value.id)),
+            encode_string(SETTING, FREEZE_WINDOW_SETTING.to_owned()),
+            encode_string(ID, value.id.clone()),
+            encode_string(NAME, value.name.clone()),
+            encode_string(REASON, value.reason.clone()),
+            encode_datetime_utc(START, value.start),
+            encode_datetime_utc(END, value.end),
+        ];
+        if let Some(group) = value.scope.group() {
+            fields.push(encode_string(SCOPE_GROUP, group.to_owned()));
+        }
+        if let Some(environment) = value.scope.environment() {
+            fields.push(encode_string(SCOPE_ENVIRONMENT, environment.to_owned()));
+        }
+        if let Some(actor) = &value.actor {
+            fields.push(encode_string(ACTOR, actor.clone()));
+        }
 
-        let gate = some_gate("some group", "some service", "some environment");
+        Self::from_iter(fields)
+    }
+}
 
-        dynamodb_storage
-            .insert(&gate)
-            .await
-            .expect("storage failed to insert gate");
+impl TryFrom<&ScheduledStateChange> for HashMap<String, AttributeValue, RandomState> {
+    type Error = String;
 
-        let stored_gates = dynamodb_storage
-            .find_all()
-            .await
-            .expect("storage failed to find gates");
-        assert_eq!(stored_gates.len(), 1);
+    fn try_from(value: &ScheduledStateChange) -> Result<Self, Self::Error> {
+        let mut fields = vec![
+            encode_string(GROUP, SETTINGS_GROUP.to_owned()),
+            encode_string(
+                SERVICE_ENVIRONMENT,
+                get_scheduled_state_change_setting(&value.id),
+            ),
+            encode_string(SETTING, SCHEDULED_STATE_CHANGE_SETTING.to_owned()),
+            encode_string(ID, value.id.clone()),
+            encode_string(GATE_GROUP, value.key.group.clone()),
+            encode_string(SERVICE, value.key.service.clone()),
+            encode_string(ENVIRONMENT, value.key.environment.clone()),
+            encode_string(STATE, value.state.clone().try_into()?),
+            encode_datetime_utc(AT, value.at),
+        ];
+        if let Some(actor) = &value.actor {
+            fields.push(encode_string(ACTOR, actor.clone()));
+        }
 
-        // when
-        let result = dynamodb_storage
-            .delete(GateKey {
-                group: "some group".to_owned(),
-                service: "some service".to_owned(),
-                environment: "some wrong environment".to_owned(),
-            })
-            .await;
+        Ok(Self::from_iter(fields))
+    }
+}
 
-        // then
-        assert_eq!(result.is_err(), true);
-        let stored_gates = dynamodb_storage
-            .find_all()
-            .await
-            .expect("storage failed to find gates");
-        assert_eq!(stored_gates.len(), 1);
-        assert_eq!(stored_gates, vec![gate]);
+impl From<&Comment> for HashMap<String, AttributeValue, RandomState> {
+    fn from(value: &Comment) -> Self {
+        Self::from([
+            (ID.to_owned(), AttributeValue::S(value.id.clone())),
+            (MESSAGE.to_owned(), AttributeValue::S(value.message.clone())),
+            (
+                CREATED.to_owned(),
+                AttributeValue::S(value.created.to_rfc3339()),
+            ),
+        ])
     }
+}
 
-    #[tokio::test]
-    async fn should_update_state_and_last_modified() {
-        // given
-        let dynamodb_container = DynamoDb::default()
-            .start()
-            .await
-            .expect("dynamoDb docker container to be started");
-        let port = dynamodb_container
-            .get_host_port_ipv4(8000)
-            .await
-            .expect("dynamoDb docker container host port go be found");
+/////////////////////////////////////////////////////////////////////////////
+// Decode
+/////////////////////////////////////////////////////////////////////////////
 
-        let dynamodb_storage = DynamoDbStorage::new_local(port).await;
-        assert_empty(&dynamodb_storage).await;
+type DecodeError = String;
 
-        let gate = some_gate("some group", "some service", "some environment");
-        assert_eq!(gate.state, GateState::Open);
-        assert_eq!(
-            gate.last_updated,
-            DateTime::parse_from_rfc3339("2023-04-12T22:10:57+02:00")
-                .expect("failed creating date")
-        );
+fn decode_string(
+    field: &str,
+    input: &HashMap<String, AttributeValue>,
+) -> Result<String, DecodeError> {
+    input
+        .get(field)
+        .ok_or_else(|| format!("field {field} could not be found"))?
+        .as_s()
+        .map_err(|_| format!("field {field} could not be parsed as string"))
+        .cloned()
+}
 
-        dynamodb_storage
-            .insert(&gate)
-            .await
-            .expect("storage failed to insert gate");
+fn decode_optional_string(
+    field: &str,
+    input: &HashMap<String, AttributeValue>,
+) -> Result<Option<String>, DecodeError> {
+    input
+        .get(field)
+        .map(|value| {
+            value
+                .as_s()
+                .map_err(|_| format!("field {field} could not be parsed as string"))
+                .cloned()
+        })
+        .transpose()
+}
 
-        // when
-        let new_last_updated = DateTime::parse_from_rfc3339("2025-04-12T22:10:57+02:00")
-            .expect("failed creating date")
-            .into();
-        let new_state = GateState::Closed;
-
-        let result = dynamodb_storage
-            .update_state_and_last_updated(
-                GateKey {
-                    group: "some group".to_owned(),
-                    service: "some service".to_owned(),
-                    environment: "some environment".to_owned(),
-                },
-                new_state.clone(),
-                new_last_updated,
-            )
-            .await;
+fn decode_datetime_utc(
+    field: &str,
+    input: &HashMap<String, AttributeValue>,
+) -> Result<DateTime<Utc>, DecodeError> {
+    DateTime::parse_from_rfc3339(&decode_string(field, input)?)
+        .map_err(|_| format!("field {field} could not be parsed as datetime"))
+        .map(std::convert::Into::into)
+}
 
-        // then
-        result.expect("storage failed to update gate state");
-        let stored_gates = dynamodb_storage
-            .find_all()
-            .await
-            .expect("storage failed to find gates");
-        assert_eq!(stored_gates.len(), 1);
-        assert_eq!(
-            *stored_gates.first().expect("failed to get stored gate"),
-            Gate {
-                key: gate.key,
-                state: new_state,
-                comments: gate.comments,
-                last_updated: new_last_updated,
-                display_order: gate.display_order,
-            }
-        );
-    }
+fn decode_optional_u32(
+    field: &str,
+    input: &HashMap<String, AttributeValue>,
+) -> Result<Option<u32>, DecodeError> {
+    input
+        .get(field)
+        .map(|value| {
+            value
+                .as_n()
+                .map_err(|_| format!("field {field} could not be parsed as string"))
+                .and_then(|value| {
+                    value.parse().map_err(|error| {
+                        format!("field {field} could not be parsed as u32: {error}")
+                    })
+                })
+        })
+        .transpose()
+}
 
-    #[tokio::test]
-    async fn should_fail_to_update_state_and_last_modified_of_item_that_does_not_exist() {
-        // given
-        let dynamodb_container = DynamoDb::default()
-            .start()
-            .await
-            .expect("dynamoDb docker container to be started");
-        let port = dynamodb_container
-            .get_host_port_ipv4(8000)
-            .await
-            .expect("dynamoDb docker container host port go be found");
+fn decode_version(input: &HashMap<String, AttributeValue>) -> Result<u32, DecodeError> {
+    decode_optional_u32(VERSION, input).map(Option::unwrap_or_default)
+}
+
+fn decode_map<'a>(
+    field: &str,
+    input: &'a HashMap<String, AttributeValue>,
+) -> Result<&'a HashMap<String, AttributeValue>, DecodeError> {
+    input
+        .get(field)
+        .ok_or_else(|| format!("field {field} could not be found"))?
+        .as_m()
+        .map_err(|_| format!("field {field} could not be parsed as map"))
+}
 
-        let dynamodb_storage = DynamoDbStorage::new_local(port).await;
-        assert_empty(&dynamodb_storage).await;
+impl TryFrom<&HashMap<String, AttributeValue>> for Gate {
+    type Error = String;
 
-        let gate = some_gate("some group", "some service", "some environment");
-        dynamodb_storage
-            .insert(&gate)
-            .await
-            .expect("storage failed to insert gate");
+    fn try_from(value: &HashMap<String, AttributeValue>) -> Result<Self, Self::Error> {
+        Ok(Self {
+            key: GateKey {
+                group: decode_string(GROUP, value)?,
+                service: decode_string(SERVICE, value)?,
+                environment: decode_string(ENVIRONMENT, value)?,
+            },
+            state: decode_string(STATE, value)?.try_into()?,
+            comments: decode_map(COMMENTS, value)?
+                .iter()
+                .map(|(id, value)| {
+                    value
+                        .as_m()
+                        .map_err(|_| format!("comment {id} could not be parsed"))
+                        .and_then(std::convert::TryInto::try_into)
+                })
+                .collect::<Result<HashSet<Comment>, String>>()?,
+            last_updated: decode_datetime_utc(LAST_UPDATED, value)?,
+            display_order: decode_optional_u32(DISPLAY_ORDER, value)?,
+            version: decode_version(value)?,
+            open_until: value
+                .contains_key(OPEN_UNTIL)
+                .then(|| decode_datetime_utc(OPEN_UNTIL, value))
+                .transpose()?,
+        })
+    }
+}
 
-        // when
-        let result = dynamodb_storage
-            .update_state_and_last_updated(
-                GateKey {
-                    group: "some group".to_owned(),
-                    service: "some service".to_owned(),
-                    environment: "some wrong environment".to_owned(),
-                },
-                GateState::Closed,
-                DateTime::parse_from_rfc3339("2025-04-12T22:10:57+02:00")
-                    .expect("failed creating date")
-                    .into(),
-            )
-            .await;
+impl TryFrom<&HashMap<String, AttributeValue>> for HistoryEntry {
+    type Error = String;
 
-        // then
-        assert_eq!(result.is_err(), true);
-        let stored_gates = dynamodb_storage
-            .find_all()
-            .await
-            .expect("storage failed to find gates");
-        assert_eq!(stored_gates.len(), 1);
-        assert_eq!(
-            *stored_gates.first().expect("failed to get stored gate"),
-            gate
-        );
+    fn try_from(value: &HashMap<String, AttributeValue>) -> Result<Self, Self::Error> {
+        Ok(Self {
+            id: decode_string(ID, value)?,
+            key: GateKey {
+                group: decode_string(GROUP, value)?,
+                service: decode_string(SERVICE, value)?,
+                environment: decode_string(ENVIRONMENT, value)?,
+            },
+            timestamp: decode_datetime_utc(TIMESTAMP, value)?,
+            event: decode_string(EVENT, value)?.try_into()?,
+            old_value: decode_optional_string(OLD_VALUE, value)?,
+            new_value: decode_optional_string(NEW_VALUE, value)?,
+            open_until: value
+                .contains_key(OPEN_UNTIL)
+                .then(|| decode_datetime_utc(OPEN_UNTIL, value))
+                .transpose()?,
+            actor: decode_optional_string(ACTOR, value)?,
+        })
     }
+}
 
-    #[tokio::test]
-    async fn should_add_new_comment_and_update_last_modified() {
-        // given
-        let dynamodb_container = DynamoDb::default()
-            .start()
-            .await
-            .expect("dynamoDb docker container to be started");
-        let port = dynamodb_container
-            .get_host_port_ipv4(8000)
-            .await
-            .expect("dynamoDb docker container host port go be found");
-
-        let dynamodb_storage = DynamoDbStorage::new_local(port).await;
-        assert_empty(&dynamodb_storage).await;
+impl TryFrom<&HashMap<String, AttributeValue>> for Freeze {
+    type Error = String;
 
-        let gate = some_gate("some group", "some service", "some environment");
+    fn try_from(value: &HashMap<String, AttributeValue>) -> Result<Self, Self::Error> {
+        Ok(Self {
+            reason: decode_string(REASON, value)?,
+            actor: decode_optional_string(ACTOR, value)?,
+            created: decode_datetime_utc(CREATED, value)?,
+            until: value
+                .contains_key(UNTIL)
+                .then(|| decode_datetime_utc(UNTIL, value))
+                .transpose()?,
+        })
+    }
+}
 
-        dynamodb_storage
-            .insert(&gate)
-            .await
-            .expect("storage failed to insert gate");
+impl TryFrom<&HashMap<String, AttributeValue>> for ClosedDay {
+    type Error = String;
 
-        let now = DateTime::parse_from_rfc3339("2025-04-12T22:10:57+02:00")
-            .expect("failed creating date")
-            .into();
-        let new_comment = Comment {
-            id: "NewCommentId".to_owned(),
-            message: "Some new comment message".to_owned(),
-            created: now,
-        };
+    fn try_from(value: &HashMap<String, AttributeValue>) -> Result<Self, Self::Error> {
+        Ok(Self {
+            date: decode_string(DATE, value)?
+                .parse()
+                .map_err(|_| format!("field {DATE} could not be parsed as date"))?,
+            reason: decode_string(REASON, value)?,
+        })
+    }
+}
 
-        // when
-        let result = dynamodb_storage
-            .update_comment_and_last_updated(gate.key.clone(), new_comment.clone(), now)
-            .await;
+impl TryFrom<&HashMap<String, AttributeValue>> for FreezeWindow {
+    type Error = String;
 
-        // then
-        result.expect("storage failed to update gate comment");
-        let stored_gates = dynamodb_storage
-            .find_all()
-            .await
-            .expect("storage failed to find gates");
-        assert_eq!(stored_gates.len(), 1);
-        assert_eq!(
-            *stored_gates.first().expect("failed to get stored gate"),
-            Gate {
-                key: gate.key,
-                state: gate.state,
-                comments: concat(vec![gate.comments, HashSet::from([new_comment])]),
-                last_updated: now,
-                display_order: gate.display_order,
-            }
-        );
+    fn try_from(value: &HashMap<String, AttributeValue>) -> Result<Self, Self::Error> {
+        Ok(Self {
+            id: decode_string(ID, value)?,
+            name: decode_string(NAME, value)?,
+            reason: decode_string(REASON, value)?,
+            start: decode_datetime_utc(START, value)?,
+            end: decode_datetime_utc(END, value)?,
+            scope: FreezeScope::new(
+                decode_optional_string(SCOPE_GROUP, value)?,
+                decode_optional_string(SCOPE_ENVIRONMENT, value)?,
+            )?,
+            actor: decode_optional_string(ACTOR, value)?,
+        })
     }
+}
 
-    #[tokio::test]
-    async fn should_update_existing_comment_and_update_last_modified() {
-        // given
-        let dynamodb_container = DynamoDb::default()
-            .start()
-            .await
-            .expect("dynamoDb docker container to be started");
-        let port = dynamodb_container
-            .get_host_port_ipv4(8000)
-            .await
-            .expect("dynamoDb docker container host port go be found");
+impl TryFrom<&HashMap<String, AttributeValue>> for ScheduledStateChange {
+    type Error = String;
 
-        let dynamodb_storage = DynamoDbStorage::new_local(port).await;
-        assert_empty(&dynamodb_storage).await;
+    fn try_from(value: &HashMap<String, AttributeValue>) -> Result<Self, Self::Error> {
+        Ok(Self {
+            id: decode_string(ID, value)?,
+            key: GateKey {
+                group: decode_string(GATE_GROUP, value)?,
+                service: decode_string(SERVICE, value)?,
+                environment: decode_string(ENVIRONMENT, value)?,
+            },
+            state: decode_string(STATE, value)?.try_into()?,
+            at: decode_datetime_utc(AT, value)?,
+            actor: decode_optional_string(ACTOR, value)?,
+        })
+    }
+}
 
-        let gate = some_gate("some group", "some service", "some environment");
+impl TryFrom<&HashMap<String, AttributeValue>> for Comment {
+    type Error = String;
 
-        dynamodb_storage
-            .insert(&gate)
-            .await
-            .expect("storage failed to insert gate");
+    fn try_from(value: &HashMap<String, AttributeValue>) -> Result<Self, Self::Error> {
+        Ok(Self {
+            id: decode_string(ID, value)?,
+            message: decode_string(MESSAGE, value)?,
+            created: decode_datetime_utc(CREATED, value)?,
+        })
+    }
+}
 
-        let now = DateTime::parse_from_rfc3339("2025-04-12T22:10:57+02:00")
-            .expect("failed creating date")
-            .into();
-        let changed_comment = Comment {
-            id: "Comment1".to_owned(),
-            message: "Some changed comment message".to_owned(),
-            created: now,
-        };
+/////////////////////////////////////////////////////////////////////////////
+// Converting SdkError<_> to Storage Errors
+/////////////////////////////////////////////////////////////////////////////
 
-        // when
-        let result = dynamodb_storage
-            .update_comment_and_last_updated(gate.key.clone(), changed_comment.clone(), now)
-            .await;
+impl From<SdkError<get_item::GetItemError>> for FindError {
+    fn from(value: SdkError<get_item::GetItemError>) -> Self {
+        Self::Other(aws_sdk_dynamodb::error::DisplayErrorContext(value).to_string())
+    }
+}
 
-        // then
-        result.expect("storage failed to update gate comment");
-        let stored_gates = dynamodb_storage
-            .find_all()
-            .await
-            .expect("storage failed to find gates");
-        assert_eq!(stored_gates.len(), 1);
-        assert_eq!(
-            *stored_gates.first().expect("failed to get stored gate"),
-            Gate {
-                key: gate.key,
-                state: gate.state,
-                comments: HashSet::from([
-                    changed_comment,
-                    Comment {
-                        id: "Comment2".to_owned(),
-                        message: "Some other comment message".to_owned(),
-                        created: DateTime::parse_from_rfc3339("2022-04-12T22:10:57+02:00")
-                            .expect("failed creating date")
-                            .into(),
-                    },
-                ]),
-                last_updated: now,
-                display_order: gate.display_order,
+impl From<SdkError<put_item::PutItemError>> for InsertError {
+    fn from(value: SdkError<put_item::PutItemError>) -> Self {
+        match value.into_service_error() {
+            put_item::PutItemError::ConditionalCheckFailedException(exception) => {
+                Self::ItemAlreadyExists(
+                    aws_sdk_dynamodb::error::DisplayErrorContext(exception).to_string(),
+                )
             }
-        );
+            error => Self::Other(aws_sdk_dynamodb::error::DisplayErrorContext(error).to_string()),
+        }
     }
+}
 
-    #[tokio::test]
-    async fn should_fail_updating_comment_and_update_last_modified_if_item_does_not_exist() {
-        // given
-        let dynamodb_container = DynamoDb::default()
-            .start()
-            .await
-            .expect("dynamoDb docker container to be started");
-        let port = dynamodb_container
-            .get_host_port_ipv4(8000)
-            .await
-            .expect("dynamoDb docker container host port go be found");
+impl From<SdkError<transact_write_items::TransactWriteItemsError>> for InsertError {
+    fn from(value: SdkError<transact_write_items::TransactWriteItemsError>) -> Self {
+        match value.into_service_error() {
+            transact_write_items::TransactWriteItemsError::TransactionCanceledException(
+                exception,
+            ) if exception
+                .cancellation_reasons()
+                .iter()
+                .any(|reason| reason.code() == Some("ConditionalCheckFailed")) =>
+            {
+                Self::ItemAlreadyExists(
+                    aws_sdk_dynamodb::error::DisplayErrorContext(exception).to_string(),
+                )
+            }
+            error => Self::Other(aws_sdk_dynamodb::error::DisplayErrorContext(error).to_string()),
+        }
+    }
+}
 
-        let dynamodb_storage = DynamoDbStorage::new_local(port).await;
-        assert_empty(&dynamodb_storage).await;
+/// Returns the pending items of a canceled transaction whose gates already exist, or the error if
+/// the transaction failed for any other reason.
+fn existing_items(
+    value: SdkError<transact_write_items::TransactWriteItemsError>,
+    pending: &[usize],
+) -> Result<Vec<usize>, InsertError> {
+    match value.into_service_error() {
+        transact_write_items::TransactWriteItemsError::TransactionCanceledException(exception) => {
+            let existing: Vec<usize> = exception
+                .cancellation_reasons()
+                .iter()
+                // the reasons are given in the order of the transaction items
+                .zip(pending)
+                .filter(|(reason, _)| reason.code() == Some("ConditionalCheckFailed"))
+                .map(|(_, index)| *index)
+                .collect();
+            if existing.is_empty() {
+                Err(InsertError::Other(
+                    aws_sdk_dynamodb::error::DisplayErrorContext(exception).to_string(),
+                ))
+            } else {
+                Ok(existing)
+            }
+        }
+        error => Err(InsertError::Other(
+            aws_sdk_dynamodb::error::DisplayErrorContext(error).to_string(),
+        )),
+    }
+}
 
-        let now = DateTime::parse_from_rfc3339("2025-04-12T22:10:57+02:00")
-            .expect("failed creating date")
-            .into();
-        let changed_comment = Comment {
-            id: "Comment1".to_owned(),
-            message: "Some changed comment message".to_owned(),
-            created: now,
-        };
+/// A missing gate takes precedence over one changed in the meantime.
+fn transaction_update_error(
+    value: SdkError<transact_write_items::TransactWriteItemsError>,
+    keys: &[(GateKey, u32)],
+) -> UpdateError {
+    match value.into_service_error() {
+        transact_write_items::TransactWriteItemsError::TransactionCanceledException(exception) => {
+            exception
+                .cancellation_reasons()
+                .iter()
+                // the reasons are given in the order of the transaction items
+                .zip(keys)
+                .filter(|(reason, _)| reason.code() == Some("ConditionalCheckFailed"))
+                .map(|(reason, (key, expected_version))| {
+                    reason
+                        .item()
+                        .filter(|item| !item.contains_key(DELETED_AT))
+                        .and_then(|item| decode_version(item).ok())
+                        .and_then(|version| {
+                            check_version(key, version, Some(*expected_version)).err()
+                        })
+                        .unwrap_or_else(|| {
+                            UpdateError::ItemToUpdateNotFound(format!("gate {key} does not exist"))
+                        })
+                })
+                .min_by_key(|error| !matches!(error, UpdateError::ItemToUpdateNotFound(_)))
+                .unwrap_or_else(|| {
+                    UpdateError::Other(
+                        aws_sdk_dynamodb::error::DisplayErrorContext(exception).to_string(),
+                    )
+                })
+        }
+        error => {
+            UpdateError::Other(aws_sdk_dynamodb::error::DisplayErrorContext(error).to_string())
+        }
+    }
+}
 
-        // when
-        let result = dynamodb_storage
-            .update_comment_and_last_updated(
-                GateKey {
-                    group: "some group".to_owned(),
-                    service: "some service".to_owned(),
-                    environment: "some environment".to_owned(),
-                },
-                changed_comment.clone(),
-                now,
-            )
-            .await;
+fn update_error(
+    value: SdkError<update_item::UpdateItemError>,
+    key: &GateKey,
+    expected_version: Option<u32>,
+) -> UpdateError {
+    match value.into_service_error() {
+        update_item::UpdateItemError::ConditionalCheckFailedException(exception) => exception
+            .item()
+            .filter(|item| !item.contains_key(DELETED_AT))
+            .and_then(|item| decode_version(item).ok())
+            .and_then(|version| check_version(key, version, expected_version).err())
+            .unwrap_or_else(|| {
+                UpdateError::ItemToUpdateNotFound(
+                    aws_sdk_dynamodb::error::DisplayErrorContext(exception).to_string(),
+                )
+            }),
+        error => {
+            UpdateError::Other(aws_sdk_dynamodb::error::DisplayErrorContext(error).to_string())
+        }
+    }
+}
 
-        // then
-        assert_eq!(result.is_err(), true);
-        let stored_gates = dynamodb_storage
-            .find_all()
-            .await
-            .expect("storage failed to find gates");
-        assert_eq!(stored_gates.len(), 0);
+impl From<SdkError<scan::ScanError>> for FindError {
+    fn from(value: SdkError<scan::ScanError>) -> Self {
+        Self::Other(aws_sdk_dynamodb::error::DisplayErrorContext(value).to_string())
     }
+}
 
-    #[tokio::test]
-    async fn should_delete_comment_by_id_and_update_last_modified() {
-        // given
-        let dynamodb_container = DynamoDb::default()
-            .start()
-            .await
-            .expect("dynamoDb docker container to be started");
-        let port = dynamodb_container
-            .get_host_port_ipv4(8000)
-            .await
-            .expect("dynamoDb docker container host port go be found");
+impl From<SdkError<query::QueryError>> for FindError {
+    fn from(value: SdkError<query::QueryError>) -> Self {
+        Self::Other(aws_sdk_dynamodb::error::DisplayErrorContext(value).to_string())
+    }
+}
 
-        let dynamodb_storage = DynamoDbStorage::new_local(port).await;
-        assert_empty(&dynamodb_storage).await;
+impl From<SdkError<delete_item::DeleteItemError>> for DeleteError {
+    fn from(value: SdkError<delete_item::DeleteItemError>) -> Self {
+        match value.into_service_error() {
+            delete_item::DeleteItemError::ConditionalCheckFailedException(exception) => {
+                Self::ItemToDeleteNotFound(
+                    aws_sdk_dynamodb::error::DisplayErrorContext(exception).to_string(),
+                )
+            }
 
-        let gate = some_gate("some group", "some service", "some environment");
+            error => Self::Other(aws_sdk_dynamodb::error::DisplayErrorContext(error).to_string()),
+        }
+    }
+}
 
-        dynamodb_storage
-            .insert(&gate)
-            .await
-            .expect("storage failed to insert gate");
+#[cfg(test)]
+mod integration_tests {
+    use similar_asserts::assert_eq;
+    use testcontainers::runners::AsyncRunner;
+    use testcontainers::ContainerAsync;
+    use testcontainers_modules::dynamodb_local::DynamoDb;
 
-        let now = DateTime::parse_from_rfc3339("2025-04-12T22:10:57+02:00")
-            .expect("failed creating date")
-            .into();
+    use crate::storage::conformance::{some_gate, some_history_entry, storage_conformance_tests};
 
-        // when
-        let result = dynamodb_storage
-            .delete_comment_by_id_and_update_last_updated(
-                gate.key.clone(),
-                "Comment1".to_owned(),
-                now,
-            )
-            .await;
+    use super::*;
 
-        // then
-        result.expect("storage failed to delete gate comment");
-        let stored_gates = dynamodb_storage
-            .find_all()
-            .await
-            .expect("storage failed to find gates");
-        assert_eq!(stored_gates.len(), 1);
-        assert_eq!(
-            *stored_gates.first().expect("failed to get stored gate"),
-            Gate {
-                key: gate.key,
-                state: gate.state,
-                comments: HashSet::from([
-                    // Comment1 removed
-                    Comment {
-                        id: "Comment2".to_owned(),
-                        message: "Some other comment message".to_owned(),
-                        created: DateTime::parse_from_rfc3339("2022-04-12T22:10:57+02:00")
-                            .expect("failed creating date")
-                            .into(),
-                    },
-                ]),
-                last_updated: now,
-                display_order: gate.display_order,
-            }
-        );
-    }
+    storage_conformance_tests!(start_dynamodb());
 
     #[tokio::test]
-    async fn should_fail_to_delete_comment_by_id_and_update_last_modified_if_comment_does_not_exist(
-    ) {
+    async fn should_not_find_history_entries_as_gates() {
         // given
-        let dynamodb_container = DynamoDb::default()
-            .start()
-            .await
-            .expect("dynamoDb docker container to be started");
-        let port = dynamodb_container
-            .get_host_port_ipv4(8000)
-            .await
-            .expect("dynamoDb docker container host port go be found");
-
-        let dynamodb_storage = DynamoDbStorage::new_local(port).await;
-        assert_empty(&dynamodb_storage).await;
-
+        let (dynamodb_storage, _dynamodb_container) = start_dynamodb().await;
         let gate = some_gate("some group", "some service", "some environment");
-
         dynamodb_storage
             .insert(&gate)
             .await
             .expect("storage failed to insert gate");
-
-        let now = DateTime::parse_from_rfc3339("2025-04-12T22:10:57+02:00")
-            .expect("failed creating date")
-            .into();
+        dynamodb_storage
+            .insert_history_entry(&some_history_entry(
+                "Entry1",
+                "some environment",
+                "2021-04-12T22:10:57+02:00",
+            ))
+            .await
+            .expect("storage failed to insert history entry");
 
         // when
-        let result = dynamodb_storage
-            .delete_comment_by_id_and_update_last_updated(
-                gate.key.clone(),
-                "NonExistentCommentId".to_owned(),
-                now,
-            )
-            .await;
+        let result = dynamodb_storage.find_all().await;
 
         // then
-        assert_eq!(result.is_err(), true);
-        let stored_gates = dynamodb_storage
-            .find_all()
-            .await
-            .expect("storage failed to find gates");
-        assert_eq!(stored_gates.len(), 1);
-        assert_eq!(
-            *stored_gates.first().expect("failed to get stored gate"),
-            gate
-        );
+        assert_eq!(result.expect("storage failed to find gates"), vec![gate]);
     }
 
-    #[tokio::test]
-    async fn should_fail_to_delete_comment_by_id_and_update_last_modified_if_item_does_not_exist() {
-        // given
+    async fn start_dynamodb() -> (DynamoDbStorage, ContainerAsync<DynamoDb>) {
         let dynamodb_container = DynamoDb::default()
             .start()
             .await
@@ -1234,72 +2052,6 @@ mod integration_tests {
             .await
             .expect("dynamoDb docker container host port go be found");
 
-        let dynamodb_storage = DynamoDbStorage::new_local(port).await;
-        assert_empty(&dynamodb_storage).await;
-
-        let now = DateTime::parse_from_rfc3339("2025-04-12T22:10:57+02:00")
-            .expect("failed creating date")
-            .into();
-
-        // when
-        let result = dynamodb_storage
-            .delete_comment_by_id_and_update_last_updated(
-                GateKey {
-                    group: "some group".to_owned(),
-                    service: "some service".to_owned(),
-                    environment: "some environment".to_owned(),
-                },
-                "Comment1".to_owned(),
-                now,
-            )
-            .await;
-
-        // then
-        assert_eq!(result.is_err(), true);
-        let stored_gates = dynamodb_storage
-            .find_all()
-            .await
-            .expect("storage failed to find gates");
-        assert_eq!(stored_gates.len(), 0);
-    }
-
-    async fn assert_empty(dynamodb_storage: &DynamoDbStorage) {
-        let count = dynamodb_storage
-            .find_all()
-            .await
-            .expect("storage failed to find gates")
-            .len();
-        assert_eq!(count, 0);
-    }
-
-    fn some_gate(group: &str, service: &str, environment: &str) -> Gate {
-        Gate {
-            key: GateKey {
-                group: group.to_owned(),
-                service: service.to_owned(),
-                environment: environment.to_owned(),
-            },
-            state: GateState::Open,
-            comments: HashSet::from([
-                Comment {
-                    id: "Comment1".to_owned(),
-                    message: "Some comment message".to_owned(),
-                    created: DateTime::parse_from_rfc3339("2021-04-12T22:10:57+02:00")
-                        .expect("failed creating date")
-                        .into(),
-                },
-                Comment {
-                    id: "Comment2".to_owned(),
-                    message: "Some other comment message".to_owned(),
-                    created: DateTime::parse_from_rfc3339("2022-04-12T22:10:57+02:00")
-                        .expect("failed creating date")
-                        .into(),
-                },
-            ]),
-            last_updated: DateTime::parse_from_rfc3339("2023-04-12T22:10:57+02:00")
-                .expect("failed creating date")
-                .into(),
-            display_order: Some(2),
-        }
+        (DynamoDbStorage::new_local(port).await, dynamodb_container)
     }
 }
@@ -0,0 +1,629 @@
+use async_trait::async_trait;
+use chrono::{DateTime, NaiveDate, Utc};
+use std::collections::BTreeMap;
+use std::sync::RwLock;
+
+use crate::storage::{
+    check_version, history_page, parse_position_cursor, DeleteError, FindError, InsertError,
+    Storage, UpdateError,
+};
+use crate::types::{
+    BusinessWeek, BusinessWeekProfile, BusinessWeekScope, ClosedDay, Comment, Freeze, FreezeWindow,
+    Gate, GateDependencies, GateKey, GateState, HistoryEntry, HistoryPage, ScheduledStateChange,
+};
+
+#[derive(Default)]
+pub struct InMemoryStorage {
+    gates: RwLock<BTreeMap<GateKey, Gate>>,
+    trash: RwLock<BTreeMap<GateKey, (Gate, DateTime<Utc>)>>,
+    history: RwLock<Vec<HistoryEntry>>,
+    freeze: RwLock<Option<Freeze>>,
+    business_week: RwLock<Option<BusinessWeek>>,
+    business_week_profiles: RwLock<BTreeMap<BusinessWeekScope, BusinessWeek>>,
+    closed_days: RwLock<BTreeMap<NaiveDate, ClosedDay>>,
+    freeze_windows: RwLock<BTreeMap<String, FreezeWindow>>,
+    scheduled_state_changes: RwLock<BTreeMap<String, ScheduledStateChange>>,
+    gate_dependencies: RwLock<BTreeMap<GateKey, Vec<GateKey>>>,
+}
+
+#[async_trait]
+impl Storage for InMemoryStorage {
+    async fn insert(&self, gate: &Gate) -> Result<(), InsertError> {
+        let mut gates = self
+            .gates
+            .write()
+            .map_err(|error| InsertError::Other(error.to_string()))?;
+
+        if gates.contains_key(&gate.key) {
+            return Err(InsertError::ItemAlreadyExists(format!(
+                "gate {} already exists",
+                gate.key
+            )));
+        }
+
+        gates.insert(gate.key.clone(), gate.clone());
+        drop(gates);
+
+        // a new gate replaces a deleted gate with the same key
+        self.trash
+            .write()
+            .map_err(|error| InsertError::Other(error.to_string()))?
+            .remove(&gate.key);
+
+        Ok(())
+    }
+
+    async fn insert_all(&self, new_gates: &[Gate]) -> Result<(), InsertError> {
+        let mut gates = self
+            .gates
+            .write()
+            .map_err(|error| InsertError::Other(error.to_string()))?;
+        let mut trash = self
+            .trash
+            .write()
+            .map_err(|error| InsertError::Other(error.to_string()))?;
+
+        if let Some(gate) = new_gates.iter().find(|gate| gates.contains_key(&gate.key)) {
+            return Err(InsertError::ItemAlreadyExists(format!(
+                "gate {} already exists",
+                gate.key
+            )));
+        }
+
+        for gate in new_gates {
+            trash.remove(&gate.key);
+            gates.insert(gate.key.clone(), gate.clone());
+        }
+        drop(trash);
+        drop(gates);
+
+        Ok(())
+    }
+
+    async fn insert_each(
+        &self,
+        gates: &[Gate],
+    ) -> Result<Vec<Result<(), InsertError>>, InsertError> {
+        let mut results = Vec::with_capacity(gates.len());
+        for gate in gates {
+            results.push(self.insert(gate).await);
+        }
+        Ok(results)
+    }
+
+    async fn find_one(&self, key: GateKey) -> Result<Option<Gate>, FindError> {
+        Ok(self
+            .gates
+            .read()
+            .map_err(|error| FindError::Other(error.to_string()))?
+            .get(&key)
+            .cloned())
+    }
+
+    async fn find_all(&self) -> Result<Vec<Gate>, FindError> {
+        Ok(self
+            .gates
+            .read()
+            .map_err(|error| FindError::Other(error.to_string()))?
+            .values()
+            .cloned()
+            .collect())
+    }
+
+    async fn find_by_group(&self, group: String) -> Result<Vec<Gate>, FindError> {
+        Ok(self
+            .gates
+            .read()
+            .map_err(|error| FindError::Other(error.to_string()))?
+            .values()
+            .filter(|gate| gate.key.group == group)
+            .cloned()
+            .collect())
+    }
+
+    async fn find_by_group_and_service(
+        &self,
+        group: String,
+        service: String,
+    ) -> Result<Vec<Gate>, FindError> {
+        Ok(self
+            .gates
+            .read()
+            .map_err(|error| FindError::Other(error.to_string()))?
+            .values()
+            .filter(|gate| gate.key.group == group && gate.key.service == service)
+            .cloned()
+            .collect())
+    }
+
+    async fn move_to_trash(
+        &self,
+        key: GateKey,
+        deleted_at: DateTime<Utc>,
+    ) -> Result<(), DeleteError> {
+        let mut gates = self
+            .gates
+            .write()
+            .map_err(|error| DeleteError::Other(error.to_string()))?;
+        let mut trash = self
+            .trash
+            .write()
+            .map_err(|error| DeleteError::Other(error.to_string()))?;
+
+        let gate = gates.remove(&key).ok_or_else(|| {
+            DeleteError::ItemToDeleteNotFound(format!("gate {key} does not exist"))
+        })?;
+        trash.insert(key, (gate, deleted_at));
+        drop(trash);
+        drop(gates);
+
+        Ok(())
+    }
+
+    async fn restore(
+        &self,
+        key: GateKey,
+        deleted_since: DateTime<Utc>,
+    ) -> Result<Gate, UpdateError> {
+        let mut gates = self
+            .gates
+            .write()
+            .map_err(|error| UpdateError::Other(error.to_string()))?;
+        let mut trash = self
+            .trash
+            .write()
+            .map_err(|error| UpdateError::Other(error.to_string()))?;
+
+        let restorable = trash
+            .get(&key)
+            .is_some_and(|(_, deleted_at)| deleted_since <= *deleted_at);
+        let Some((gate, _)) = restorable.then(|| trash.remove(&key)).flatten() else {
+            return Err(UpdateError::ItemToUpdateNotFound(format!(
+                "deleted gate {key} does not exist"
+            )));
+        };
+        gates.insert(key, gate.clone());
+        drop(trash);
+        drop(gates);
+
+        Ok(gate)
+    }
+
+    async fn purge_trash(&self, deleted_before: DateTime<Utc>) -> Result<u64, DeleteError> {
+        let mut trash = self
+            .trash
+            .write()
+            .map_err(|error| DeleteError::Other(error.to_string()))?;
+
+        let count = trash.len();
+        trash.retain(|_, (_, deleted_at)| *deleted_at >= deleted_before);
+        let purged = count - trash.len();
+        drop(trash);
+
+        Ok(purged as u64)
+    }
+
+    async fn update_state_and_last_updated(
+        &self,
+        key: GateKey,
+        state: GateState,
+        open_until: Option<DateTime<Utc>>,
+        last_updated: DateTime<Utc>,
+        expected_version: Option<u32>,
+    ) -> Result<Gate, UpdateError> {
+        self.update(&key, expected_version, |gate| {
+            gate.state = state;
+            gate.open_until = open_until;
+            gate.last_updated = last_updated;
+            Ok(())
+        })
+    }
+
+    async fn update_states_and_last_updated(
+        &self,
+        keys: &[(GateKey, u32)],
+        state: GateState,
+        comment: Option<Comment>,
+        last_updated: DateTime<Utc>,
+    ) -> Result<Vec<Gate>, UpdateError> {
+        let mut gates = self
+            .gates
+            .write()
+            .map_err(|error| UpdateError::Other(error.to_string()))?;
+
+        if let Some((key, _)) = keys.iter().find(|(key, _)| !gates.contains_key(key)) {
+            return Err(UpdateError::ItemToUpdateNotFound(format!(
+                "gate {key} does not exist"
+            )));
+        }
+        for (key, expected_version) in keys {
+            if let Some(gate) = gates.get(key) {
+                check_version(key, gate.version, Some(*expected_version))?;
+            }
+        }
+
+        let mut updated = Vec::with_capacity(keys.len());
+        for (key, _) in keys {
+            if let Some(gate) = gates.get_mut(key) {
+                gate.state = state.clone();
+                gate.open_until = None;
+                if let Some(comment) = &comment {
+                    gate.comments.retain(|existing| existing.id != comment.id);
+                    gate.comments.insert(comment.clone());
+                }
+                gate.last_updated = last_updated;
+                gate.version += 1;
+                updated.push(gate.clone());
+            }
+        }
+        drop(gates);
+
+        Ok(updated)
+    }
+
+    async fn update_display_order_and_last_updated(
+        &self,
+        key: GateKey,
+        display_order: u32,
+        last_updated: DateTime<Utc>,
+        expected_version: Option<u32>,
+    ) -> Result<Gate, UpdateError> {
+        self.update(&key, expected_version, |gate| {
+            gate.display_order = Some(display_order);
+            gate.last_updated = last_updated;
+            Ok(())
+        })
+    }
+
+    async fn update_comment_and_last_updated(
+        &self,
+        key: GateKey,
+        comment: Comment,
+        last_updated: DateTime<Utc>,
+        expected_version: Option<u32>,
+    ) -> Result<Gate, UpdateError> {
+        self.update(&key, expected_version, |gate| {
+            gate.comments.retain(|existing| existing.id != comment.id);
+            gate.comments.insert(comment);
+            gate.last_updated = last_updated;
+            Ok(())
+        })
+    }
+
+    async fn delete_comment_by_id_and_update_last_updated(
+        &self,
+        key: GateKey,
+        comment_id: String,
+        last_updated: DateTime<Utc>,
+        expected_version: Option<u32>,
+    ) -> Result<Gate, UpdateError> {
+        self.update(&key, expected_version, |gate| {
+            if !gate.comments.iter().any(|comment| comment.id == comment_id) {
+                return Err(UpdateError::ItemToUpdateNotFound(format!(
+                    "comment {comment_id} does not exist"
+                )));
+            }
+            gate.comments.retain(|comment| comment.id != comment_id);
+            gate.last_updated = last_updated;
+            Ok(())
+        })
+    }
+
+    async fn insert_history_entry(&self, entry: &HistoryEntry) -> Result<(), InsertError> {
+        self.history
+            .write()
+            .map_err(|error| InsertError::Other(error.to_string()))?
+            .push(entry.clone());
+
+        Ok(())
+    }
+
+    async fn find_history(
+        &self,
+        key: GateKey,
+        limit: u32,
+        cursor: Option<String>,
+    ) -> Result<HistoryPage, FindError> {
+        let before = parse_position_cursor(cursor)?;
+        let history = self
+            .history
+            .read()
+            .map_err(|error| FindError::Other(error.to_string()))?;
+
+        let end = before.map_or(history.len(), |before| {
+            usize::try_from(before)
+                .unwrap_or_default()
+                .min(history.len())
+        });
+        let entries = history[..end]
+            .iter()
+            .enumerate()
+            .rev()
+            .filter(|(_, entry)| entry.key == key)
+            .take(limit as usize + 1)
+            .map(|(position, entry)| (position.to_string(), entry.clone()))
+            .collect();
+        drop(history);
+
+        Ok(history_page(entries, limit))
+    }
+
+    async fn save_freeze(&self, freeze: &Freeze) -> Result<(), InsertError> {
+        *self
+            .freeze
+            .write()
+            .map_err(|error| InsertError::Other(error.to_string()))? = Some(freeze.clone());
+
+        Ok(())
+    }
+
+    async fn find_freeze(&self) -> Result<Option<Freeze>, FindError> {
+        Ok(self
+            .freeze
+            .read()
+            .map_err(|error| FindError::Other(error.to_string()))?
+            .clone())
+    }
+
+    async fn delete_freeze(&self) -> Result<(), DeleteError> {
+        self.freeze
+            .write()
+            .map_err(|error| DeleteError::Other(error.to_string()))?
+            .take()
+            .map(|_| ())
+            .ok_or_else(|| DeleteError::ItemToDeleteNotFound("freeze does not exist".to_owned()))
+    }
+
+    async fn save_business_week(&self, business_week: &BusinessWeek) -> Result<(), InsertError> {
+        *self
+            .business_week
+            .write()
+            .map_err(|error| InsertError::Other(error.to_string()))? = Some(business_week.clone());
+
+        Ok(())
+    }
+
+    async fn find_business_week(&self) -> Result<Option<BusinessWeek>, FindError> {
+        Ok(self
+            .business_week
+            .read()
+            .map_err(|error| FindError::Other(error.to_string()))?
+            .clone())
+    }
+
+    async fn save_business_week_profile(
+        &self,
+        profile: &BusinessWeekProfile,
+    ) -> Result<(), InsertError> {
+        self.business_week_profiles
+            .write()
+            .map_err(|error| InsertError::Other(error.to_string()))?
+            .insert(profile.scope.clone(), profile.business_week.clone());
+
+        Ok(())
+    }
+
+    async fn find_business_week_profiles(&self) -> Result<Vec<BusinessWeekProfile>, FindError> {
+        Ok(self
+            .business_week_profiles
+            .read()
+            .map_err(|error| FindError::Other(error.to_string()))?
+            .iter()
+            .map(|(scope, business_week)| BusinessWeekProfile {
+                scope: scope.clone(),
+                business_week: business_week.clone(),
+            })
+            .collect())
+    }
+
+    async fn delete_business_week_profile(
+        &self,
+        scope: BusinessWeekScope,
+    ) -> Result<(), DeleteError> {
+        self.business_week_profiles
+            .write()
+            .map_err(|error| DeleteError::Other(error.to_string()))?
+            .remove(&scope)
+            .map(|_| ())
+            .ok_or_else(|| {
+                DeleteError::ItemToDeleteNotFound("business week profile does not exist".to_owned())
+            })
+    }
+
+    async fn save_closed_day(&self, closed_day: &ClosedDay) -> Result<(), InsertError> {
+        self.closed_days
+            .write()
+            .map_err(|error| InsertError::Other(error.to_string()))?
+            .insert(closed_day.date, closed_day.clone());
+
+        Ok(())
+    }
+
+    async fn save_closed_days(&self, closed_days: &[ClosedDay]) -> Result<(), InsertError> {
+        self.closed_days
+            .write()
+            .map_err(|error| InsertError::Other(error.to_string()))?
+            .extend(
+                closed_days
+                    .iter()
+                    .map(|closed_day| (closed_day.date, closed_day.clone())),
+            );
+
+        Ok(())
+    }
+
+    async fn find_closed_days(&self) -> Result<Vec<ClosedDay>, FindError> {
+        Ok(self
+            .closed_days
+            .read()
+            .map_err(|error| FindError::Other(error.to_string()))?
+            .values()
+            .cloned()
+            .collect())
+    }
+
+    async fn delete_closed_day(&self, date: NaiveDate) -> Result<(), DeleteError> {
+        self.closed_days
+            .write()
+            .map_err(|error| DeleteError::Other(error.to_string()))?
+            .remove(&date)
+            .map(|_| ())
+            .ok_or_else(|| {
+                DeleteError::ItemToDeleteNotFound(format!("closed day {date} does not exist"))
+            })
+    }
+
+    async fn insert_freeze_window(&self, freeze_window: &FreezeWindow) -> Result<(), InsertError> {
+        let mut freeze_windows = self
+            .freeze_windows
+            .write()
+            .map_err(|error| InsertError::Other(error.to_string()))?;
+
+        if freeze_windows.contains_key(&freeze_window.id) {
+            return Err(InsertError::ItemAlreadyExists(format!(
+                "freeze window {} already exists",
+                freeze_window.id
+            )));
+        }
+        freeze_windows.insert(freeze_window.id.clone(), freeze_window.clone());
+        drop(freeze_windows);
+
+        Ok(())
+    }
+
+    async fn find_freeze_windows(&self) -> Result<Vec<FreezeWindow>, FindError> {
+        let mut freeze_windows: Vec<FreezeWindow> = self
+            .freeze_windows
+            .read()
+            .map_err(|error| FindError::Other(error.to_string()))?
+            .values()
+            .cloned()
+            .collect();
+        freeze_windows.sort_by_key(|freeze_window| freeze_window.start);
+
+        Ok(freeze_windows)
+    }
+
+    async fn delete_freeze_window(&self, id: String) -> Result<(), DeleteError> {
+        self.freeze_windows
+            .write()
+            .map_err(|error| DeleteError::Other(error.to_string()))?
+            .remove(&id)
+            .map(|_| ())
+            .ok_or_else(|| {
+                DeleteError::ItemToDeleteNotFound(format!("freeze window {id} does not exist"))
+            })
+    }
+
+    async fn save_gate_dependencies(
+        &self,
+        dependencies: &GateDependencies,
+    ) -> Result<(), InsertError> {
+        let mut gate_dependencies = self
+            .gate_dependencies
+            .write()
+            .map_err(|error| InsertError::Other(error.to_string()))?;
+        if dependencies.depends_on.is_empty() {
+            gate_dependencies.remove(&dependencies.key);
+        } else {
+            gate_dependencies.insert(dependencies.key.clone(), dependencies.depends_on.clone());
+        }
+        drop(gate_dependencies);
+
+        Ok(())
+    }
+
+    async fn find_gate_dependencies(&self) -> Result<Vec<GateDependencies>, FindError> {
+        Ok(self
+            .gate_dependencies
+            .read()
+            .map_err(|error| FindError::Other(error.to_string()))?
+            .iter()
+            .map(|(key, depends_on)| GateDependencies {
+                key: key.clone(),
+                depends_on: depends_on.clone(),
+            })
+            .collect())
+    }
+
+    async fn insert_scheduled_state_change(
+        &self,
+        change: &ScheduledStateChange,
+    ) -> Result<(), InsertError> {
+        let mut changes = self
+            .scheduled_state_changes
+            .write()
+            .map_err(|error| InsertError::Other(error.to_string()))?;
+
+        if changes.contains_key(&change.id) {
+            return Err(InsertError::ItemAlreadyExists(format!(
+                "scheduled state change {} already exists",
+                change.id
+            )));
+        }
+        changes.insert(change.id.clone(), change.clone());
+        drop(changes);
+
+        Ok(())
+    }
+
+    async fn find_scheduled_state_changes(&self) -> Result<Vec<ScheduledStateChange>, FindError> {
+        let mut changes: Vec<ScheduledStateChange> = self
+            .scheduled_state_changes
+            .read()
+            .map_err(|error| FindError::Other(error.to_string()))?
+            .values()
+            .cloned()
+            .collect();
+        changes.sort_by_key(|change| change.at);
+
+        Ok(changes)
+    }
+
+    async fn delete_scheduled_state_change(&self, id: String) -> Result<(), DeleteError> {
+        self.scheduled_state_changes
+            .write()
+            .map_err(|error| DeleteError::Other(error.to_string()))?
+            .remove(&id)
+            .map(|_| ())
+            .ok_or_else(|| {
+                DeleteError::ItemToDeleteNotFound(format!(
+                    "scheduled state change {id} does not exist"
+                ))
+            })
+    }
+}
+
+impl InMemoryStorage {
+    fn update(
+        &self,
+        key: &GateKey,
+        expected_version: Option<u32>,
+        update: impl FnOnce(&mut Gate) -> Result<(), UpdateError>,
+    ) -> Result<Gate, UpdateError> {
+        let mut gates = self
+            .gates
+            .write()
+            .map_err(|error| UpdateError::Other(error.to_string()))?;
+
+        let mut gate = gates.get(key).cloned().ok_or_else(|| {
+            UpdateError::ItemToUpdateNotFound(format!("gate {key} does not exist"))
+        })?;
+        check_version(key, gate.version, expected_version)?;
+        update(&mut gate)?;
+        gate.version += 1;
+        gates.insert(key.clone(), gate.clone());
+        drop(gates);
+
+        Ok(gate)
+    }
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use crate::storage::conformance::storage_conformance_tests;
+
+    use super::*;
+
+    storage_conformance_tests!(async { (InMemoryStorage::default(), ()) });
+}
@@ -0,0 +1,30 @@
+use crate::storage::{DeleteError, FindError, InsertError, UpdateError};
+
+impl From<sqlx::Error> for FindError {
+    fn from(value: sqlx::Error) -> Self {
+        Self::Other(value.to_string())
+    }
+}
+
+impl From<sqlx::Error> for InsertError {
+    fn from(value: sqlx::Error) -> Self {
+        match value {
+            sqlx::Error::Database(error) if error.is_unique_violation() => {
+                Self::ItemAlreadyExists(error.to_string())
+            }
+            error => Self::Other(error.to_string()),
+        }
+    }
+}
+
+impl From<sqlx::Error> for UpdateError {
+    fn from(value: sqlx::Error) -> Self {
+        Self::Other(value.to_string())
+    }
+}
+
+impl From<sqlx::Error> for DeleteError {
+    fn from(value: sqlx::Error) -> Self {
+        Self::Other(value.to_string())
+    }
+}
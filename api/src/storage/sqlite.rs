@@ -0,0 +1,1236 @@
+use async_trait::async_trait;
+use chrono::{DateTime, NaiveDate, SecondsFormat, Utc};
+use sqlx::migrate::Migrator;
+use sqlx::sqlite::{SqliteConnectOptions, SqlitePoolOptions};
+use sqlx::{Connection, FromRow, SqliteConnection, SqlitePool};
+use std::collections::{HashMap, HashSet};
+
+use crate::storage::{
+    check_version, decode_business_week, decode_gate_keys, encode_business_week, encode_gate_keys,
+    history_page, parse_position_cursor, DeleteError, FindError, InsertError, Storage, UpdateError,
+};
+use crate::types::{
+    BusinessWeek, BusinessWeekProfile, BusinessWeekScope, ClosedDay, Comment, Freeze, FreezeScope,
+    FreezeWindow, Gate, GateDependencies, GateKey, GateState, HistoryEntry, HistoryPage,
+    ScheduledStateChange,
+};
+
+const ENV_GATES_SQLITE_DATABASE_PATH: &str = "GATES_SQLITE_DATABASE_PATH";
+
+static MIGRATOR: Migrator = sqlx::migrate!("./migrations/sqlite");
+
+#[derive(Clone)]
+pub struct SqliteStorage {
+    pub pool: SqlitePool,
+}
+
+#[async_trait]
+impl Storage for SqliteStorage {
+    async fn insert(&self, gate: &Gate) -> Result<(), InsertError> {
+        let mut transaction = self.pool.begin().await?;
+        insert_gate(&mut transaction, gate).await?;
+        transaction.commit().await?;
+        Ok(())
+    }
+
+    async fn insert_all(&self, gates: &[Gate]) -> Result<(), InsertError> {
+        let mut transaction = self.pool.begin().await?;
+        for gate in gates {
+            insert_gate(&mut transaction, gate).await?;
+        }
+        transaction.commit().await?;
+        Ok(())
+    }
+
+    async fn insert_each(
+        &self,
+        gates: &[Gate],
+    ) -> Result<Vec<Result<(), InsertError>>, InsertError> {
+        let mut transaction = self.pool.begin().await?;
+        let mut results = Vec::with_capacity(gates.len());
+        for gate in gates {
+            // a gate that could not be inserted only rolls back to its own savepoint
+            let mut savepoint = Connection::begin(&mut *transaction).await?;
+            let result = insert_gate(&mut savepoint, gate).await;
+            if result.is_ok() {
+                savepoint.commit().await?;
+            } else {
+                savepoint.rollback().await?;
+            }
+            results.push(result);
+        }
+        transaction.commit().await?;
+        Ok(results)
+    }
+
+    async fn find_one(&self, key: GateKey) -> Result<Option<Gate>, FindError> {
+        let mut connection = self.pool.acquire().await?;
+        fetch_gate(&mut connection, &key).await
+    }
+
+    async fn find_all(&self) -> Result<Vec<Gate>, FindError> {
+        self.find_gates(None, None).await
+    }
+
+    async fn find_by_group(&self, group: String) -> Result<Vec<Gate>, FindError> {
+        self.find_gates(Some(&group), None).await
+    }
+
+    async fn find_by_group_and_service(
+        &self,
+        group: String,
+        service: String,
+    ) -> Result<Vec<Gate>, FindError> {
+        self.find_gates(Some(&group), Some(&service)).await
+    }
+
+    async fn move_to_trash(
+        &self,
+        key: GateKey,
+        deleted_at: DateTime<Utc>,
+    ) -> Result<(), DeleteError> {
+        let deleted = sqlx::query(
+            r#"UPDATE gates SET deleted_at = ?
+               WHERE "group" = ? AND service = ? AND environment = ?
+                 AND deleted_at IS NULL"#,
+        )
+        .bind(deleted_at.to_rfc3339_opts(SecondsFormat::Micros, true))
+        .bind(&key.group)
+        .bind(&key.service)
+        .bind(&key.environment)
+        .execute(&self.pool)
+        .await?
+        .rows_affected();
+
+        if deleted == 0 {
+            return Err(DeleteError::ItemToDeleteNotFound(format!(
+                "gate {key} does not exist"
+            )));
+        }
+        Ok(())
+    }
+
+    async fn restore(
+        &self,
+        key: GateKey,
+        deleted_since: DateTime<Utc>,
+    ) -> Result<Gate, UpdateError> {
+        let mut transaction = self.pool.begin().await?;
+
+        let restored = sqlx::query(
+            r#"UPDATE gates SET deleted_at = NULL
+               WHERE "group" = ? AND service = ? AND environment = ?
+                 AND deleted_at >= ?"#,
+        )
+        .bind(&key.group)
+        .bind(&key.service)
+        .bind(&key.environment)
+        .bind(deleted_since.to_rfc3339_opts(SecondsFormat::Micros, true))
+        .execute(&mut *transaction)
+        .await?
+        .rows_affected();
+
+        if restored == 0 {
+            return Err(UpdateError::ItemToUpdateNotFound(format!(
+                "deleted gate {key} does not exist"
+            )));
+        }
+
+        let gate = fetch_updated_gate(&mut transaction, &key).await?;
+        transaction.commit().await?;
+        Ok(gate)
+    }
+
+    async fn purge_trash(&self, deleted_before: DateTime<Utc>) -> Result<u64, DeleteError> {
+        Ok(sqlx::query("DELETE FROM gates WHERE deleted_at < ?")
+            .bind(deleted_before.to_rfc3339_opts(SecondsFormat::Micros, true))
+            .execute(&self.pool)
+            .await?
+            .rows_affected())
+    }
+
+    async fn update_state_and_last_updated(
+        &self,
+        key: GateKey,
+        state: GateState,
+        open_until: Option<DateTime<Utc>>,
+        last_updated: DateTime<Utc>,
+        expected_version: Option<u32>,
+    ) -> Result<Gate, UpdateError> {
+        let mut transaction = self.pool.begin().await?;
+        check_gate_version(&mut transaction, &key, expected_version).await?;
+
+        sqlx::query(
+            r#"UPDATE gates SET state = ?, open_until = ?, last_updated = ?, version = version + 1
+               WHERE "group" = ? AND service = ? AND environment = ?"#,
+        )
+        .bind(String::try_from(state).map_err(UpdateError::Other)?)
+        .bind(open_until.map(|open_until| open_until.to_rfc3339()))
+        .bind(last_updated.to_rfc3339())
+        .bind(&key.group)
+        .bind(&key.service)
+        .bind(&key.environment)
+        .execute(&mut *transaction)
+        .await?;
+
+        let gate = fetch_updated_gate(&mut transaction, &key).await?;
+        transaction.commit().await?;
+        Ok(gate)
+    }
+
+    async fn update_states_and_last_updated(
+        &self,
+        keys: &[(GateKey, u32)],
+        state: GateState,
+        comment: Option<Comment>,
+        last_updated: DateTime<Utc>,
+    ) -> Result<Vec<Gate>, UpdateError> {
+        let state = String::try_from(state).map_err(UpdateError::Other)?;
+        let mut transaction = self.pool.begin().await?;
+        let mut gates = Vec::with_capacity(keys.len());
+
+        for (key, expected_version) in keys {
+            check_gate_version(&mut transaction, key, Some(*expected_version)).await?;
+
+            sqlx::query(
+                r#"UPDATE gates SET state = ?, open_until = NULL, last_updated = ?, version = version + 1
+                   WHERE "group" = ? AND service = ? AND environment = ?"#,
+            )
+            .bind(&state)
+            .bind(last_updated.to_rfc3339())
+            .bind(&key.group)
+            .bind(&key.service)
+            .bind(&key.environment)
+            .execute(&mut *transaction)
+            .await?;
+
+            if let Some(comment) = &comment {
+                upsert_comment(&mut transaction, key, comment).await?;
+            }
+
+            gates.push(fetch_updated_gate(&mut transaction, key).await?);
+        }
+
+        transaction.commit().await?;
+        Ok(gates)
+    }
+
+    async fn update_display_order_and_last_updated(
+        &self,
+        key: GateKey,
+        display_order: u32,
+        last_updated: DateTime<Utc>,
+        expected_version: Option<u32>,
+    ) -> Result<Gate, UpdateError> {
+        let mut transaction = self.pool.begin().await?;
+        check_gate_version(&mut transaction, &key, expected_version).await?;
+
+        sqlx::query(
+            r#"UPDATE gates SET display_order = ?, last_updated = ?, version = version + 1
+               WHERE "group" = ? AND service = ? AND environment = ?"#,
+        )
+        .bind(display_order)
+        .bind(last_updated.to_rfc3339())
+        .bind(&key.group)
+        .bind(&key.service)
+        .bind(&key.environment)
+        .execute(&mut *transaction)
+        .await?;
+
+        let gate = fetch_updated_gate(&mut transaction, &key).await?;
+        transaction.commit().await?;
+        Ok(gate)
+    }
+
+    async fn update_comment_and_last_updated(
+        &self,
+        key: GateKey,
+        comment: Comment,
+        last_updated: DateTime<Utc>,
+        expected_version: Option<u32>,
+    ) -> Result<Gate, UpdateError> {
+        let mut transaction = self.pool.begin().await?;
+        check_gate_version(&mut transaction, &key, expected_version).await?;
+
+        update_last_updated(&mut transaction, &key, last_updated).await?;
+        upsert_comment(&mut transaction, &key, &comment).await?;
+
+        let gate = fetch_updated_gate(&mut transaction, &key).await?;
+        transaction.commit().await?;
+        Ok(gate)
+    }
+
+    async fn delete_comment_by_id_and_update_last_updated(
+        &self,
+        key: GateKey,
+        comment_id: String,
+        last_updated: DateTime<Utc>,
+        expected_version: Option<u32>,
+    ) -> Result<Gate, UpdateError> {
+        let mut transaction = self.pool.begin().await?;
+        check_gate_version(&mut transaction, &key, expected_version).await?;
+
+        let deleted = sqlx::query(
+            r#"DELETE FROM comments
+               WHERE "group" = ? AND service = ? AND environment = ? AND id = ?"#,
+        )
+        .bind(&key.group)
+        .bind(&key.service)
+        .bind(&key.environment)
+        .bind(&comment_id)
+        .execute(&mut *transaction)
+        .await?
+        .rows_affected();
+
+        if deleted == 0 {
+            return Err(UpdateError::ItemToUpdateNotFound(format!(
+                "comment {comment_id} of gate {key} does not exist"
+            )));
+        }
+
+        update_last_updated(&mut transaction, &key, last_updated).await?;
+        let gate = fetch_updated_gate(&mut transaction, &key).await?;
+        transaction.commit().await?;
+        Ok(gate)
+    }
+
+    async fn insert_history_entry(&self, entry: &HistoryEntry) -> Result<(), InsertError> {
+        sqlx::query(
+            r#"INSERT INTO gate_history
+                 (id, "group", service, environment, timestamp, event, old_value, new_value,
+                  open_until, actor)
+               VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"#,
+        )
+        .bind(&entry.id)
+        .bind(&entry.key.group)
+        .bind(&entry.key.service)
+        .bind(&entry.key.environment)
+        .bind(entry.timestamp.to_rfc3339())
+        .bind(String::from(entry.event))
+        .bind(&entry.old_value)
+        .bind(&entry.new_value)
+        .bind(entry.open_until.map(|open_until| open_until.to_rfc3339()))
+        .bind(&entry.actor)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn find_history(
+        &self,
+        key: GateKey,
+        limit: u32,
+        cursor: Option<String>,
+    ) -> Result<HistoryPage, FindError> {
+        let before = parse_position_cursor(cursor)?;
+        let entries = sqlx::query_as::<_, HistoryEntryRow>(
+            r#"SELECT position, id, "group", service, environment, timestamp, event,
+                      old_value, new_value, open_until, actor
+               FROM gate_history
+               WHERE "group" = ?1 AND service = ?2 AND environment = ?3
+                 AND (?4 IS NULL OR position < ?4)
+               ORDER BY position DESC LIMIT ?5"#,
+        )
+        .bind(&key.group)
+        .bind(&key.service)
+        .bind(&key.environment)
+        .bind(before)
+        .bind(i64::from(limit) + 1)
+        .fetch_all(&self.pool)
+        .await?
+        .into_iter()
+        .map(|row| {
+            let position = row.position.to_string();
+            row.try_into()
+                .map(|entry| (position, entry))
+                .map_err(|error| {
+                    FindError::ItemCouldNotBeDecoded(format!(
+                        "could not decode history entry (mapping error: {error})"
+                    ))
+                })
+        })
+        .collect::<Result<_, _>>()?;
+
+        Ok(history_page(entries, limit))
+    }
+
+    async fn save_freeze(&self, freeze: &Freeze) -> Result<(), InsertError> {
+        sqlx::query(
+            r"INSERT INTO freeze (id, reason, actor, created, until)
+              VALUES (1, ?, ?, ?, ?)
+              ON CONFLICT (id)
+              DO UPDATE SET reason = excluded.reason, actor = excluded.actor,
+                            created = excluded.created, until = excluded.until",
+        )
+        .bind(&freeze.reason)
+        .bind(&freeze.actor)
+        .bind(freeze.created.to_rfc3339())
+        .bind(freeze.until.map(|until| until.to_rfc3339()))
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn find_freeze(&self) -> Result<Option<Freeze>, FindError> {
+        sqlx::query_as::<_, FreezeRow>("SELECT reason, actor, created, until FROM freeze")
+            .fetch_optional(&self.pool)
+            .await?
+            .map(|row| {
+                row.try_into().map_err(|error| {
+                    FindError::ItemCouldNotBeDecoded(format!(
+                        "could not decode freeze (mapping error: {error})"
+                    ))
+                })
+            })
+            .transpose()
+    }
+
+    async fn delete_freeze(&self) -> Result<(), DeleteError> {
+        let deleted = sqlx::query("DELETE FROM freeze")
+            .execute(&self.pool)
+            .await?
+            .rows_affected();
+
+        if deleted == 0 {
+            return Err(DeleteError::ItemToDeleteNotFound(
+                "freeze does not exist".to_owned(),
+            ));
+        }
+        Ok(())
+    }
+
+    async fn save_business_week(&self, business_week: &BusinessWeek) -> Result<(), InsertError> {
+        sqlx::query(
+            r"INSERT INTO business_week (id, week)
+              VALUES (1, ?)
+              ON CONFLICT (id)
+              DO UPDATE SET week = excluded.week",
+        )
+        .bind(encode_business_week(business_week)?)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn find_business_week(&self) -> Result<Option<BusinessWeek>, FindError> {
+        sqlx::query_scalar::<_, String>("SELECT week FROM business_week")
+            .fetch_optional(&self.pool)
+            .await?
+            .map(|week| decode_business_week(&week))
+            .transpose()
+    }
+
+    async fn save_business_week_profile(
+        &self,
+        profile: &BusinessWeekProfile,
+    ) -> Result<(), InsertError> {
+        sqlx::query(
+            r#"INSERT INTO business_week_profile ("group", service, environment, week)
+              VALUES (?, ?, ?, ?)
+              ON CONFLICT ("group", service, environment)
+              DO UPDATE SET week = excluded.week"#,
+        )
+        .bind(&profile.scope.group)
+        .bind(profile.scope.service.as_deref().unwrap_or_default())
+        .bind(profile.scope.environment.as_deref().unwrap_or_default())
+        .bind(encode_business_week(&profile.business_week)?)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn find_business_week_profiles(&self) -> Result<Vec<BusinessWeekProfile>, FindError> {
+        sqlx::query_as::<_, BusinessWeekProfileRow>(
+            r#"SELECT "group", service, environment, week FROM business_week_profile
+               ORDER BY "group", service, environment"#,
+        )
+        .fetch_all(&self.pool)
+        .await?
+        .into_iter()
+        .map(|row| {
+            Ok(BusinessWeekProfile {
+                scope: BusinessWeekScope {
+                    group: row.group,
+                    service: Some(row.service).filter(|service| !service.is_empty()),
+                    environment: Some(row.environment)
+                        .filter(|environment| !environment.is_empty()),
+                },
+                business_week: decode_business_week(&row.week)?,
+            })
+        })
+        .collect()
+    }
+
+    async fn delete_business_week_profile(
+        &self,
+        scope: BusinessWeekScope,
+    ) -> Result<(), DeleteError> {
+        let deleted = sqlx::query(
+            r#"DELETE FROM business_week_profile
+               WHERE "group" = ? AND service = ? AND environment = ?"#,
+        )
+        .bind(&scope.group)
+        .bind(scope.service.unwrap_or_default())
+        .bind(scope.environment.unwrap_or_default())
+        .execute(&self.pool)
+        .await?
+        .rows_affected();
+
+        if deleted == 0 {
+            return Err(DeleteError::ItemToDeleteNotFound(
+                "business week profile does not exist".to_owned(),
+            ));
+        }
+        Ok(())
+    }
+
+    async fn save_closed_day(&self, closed_day: &ClosedDay) -> Result<(), InsertError> {
+        upsert_closed_day(&mut *self.pool.acquire().await?, closed_day).await
+    }
+
+    async fn save_closed_days(&self, closed_days: &[ClosedDay]) -> Result<(), InsertError> {
+        let mut transaction = self.pool.begin().await?;
+        for closed_day in closed_days {
+            upsert_closed_day(&mut transaction, closed_day).await?;
+        }
+        transaction.commit().await?;
+        Ok(())
+    }
+
+    async fn find_closed_days(&self) -> Result<Vec<ClosedDay>, FindError> {
+        sqlx::query_as::<_, ClosedDayRow>("SELECT date, reason FROM closed_day ORDER BY date")
+            .fetch_all(&self.pool)
+            .await?
+            .into_iter()
+            .map(|row| {
+                row.try_into().map_err(|error| {
+                    FindError::ItemCouldNotBeDecoded(format!(
+                        "could not decode closed day (mapping error: {error})"
+                    ))
+                })
+            })
+            .collect()
+    }
+
+    async fn delete_closed_day(&self, date: NaiveDate) -> Result<(), DeleteError> {
+        let deleted = sqlx::query("DELETE FROM closed_day WHERE date = ?")
+            .bind(date.to_string())
+            .execute(&self.pool)
+            .await?
+            .rows_affected();
+
+        if deleted == 0 {
+            return Err(DeleteError::ItemToDeleteNotFound(format!(
+                "closed day {date} does not exist"
+            )));
+        }
+        Ok(())
+    }
+
+    async fn insert_freeze_window(&self, freeze_window: &FreezeWindow) -> Result<(), InsertError> {
+        sqlx::query(
+            r"INSERT INTO freeze_window (id, name, reason, starts_at, ends_at, scope_group, scope_environment, actor)
+              VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(&freeze_window.id)
+        .bind(&freeze_window.name)
+        .bind(&freeze_window.reason)
+        .bind(freeze_window.start.to_rfc3339())
+        .bind(freeze_window.end.to_rfc3339())
+        .bind(freeze_window.scope.group())
+        .bind(freeze_window.scope.environment())
+        .bind(&freeze_window.actor)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn find_freeze_windows(&self) -> Result<Vec<FreezeWindow>, FindError> {
+        let mut freeze_windows = sqlx::query_as::<_, FreezeWindowRow>(
+            r"SELECT id, name, reason, starts_at, ends_at, scope_group, scope_environment, actor
+              FROM freeze_window",
+        )
+        .fetch_all(&self.pool)
+        .await?
+        .into_iter()
+        .map(|row| {
+            row.try_into().map_err(|error| {
+                FindError::ItemCouldNotBeDecoded(format!(
+                    "could not decode freeze window (mapping error: {error})"
+                ))
+            })
+        })
+        .collect::<Result<Vec<FreezeWindow>, _>>()?;
+        // the instants are stored as text, which does not order them reliably
+        freeze_windows.sort_by_key(|freeze_window| freeze_window.start);
+
+        Ok(freeze_windows)
+    }
+
+    async fn delete_freeze_window(&self, id: String) -> Result<(), DeleteError> {
+        let deleted = sqlx::query("DELETE FROM freeze_window WHERE id = ?")
+            .bind(&id)
+            .execute(&self.pool)
+            .await?
+            .rows_affected();
+
+        if deleted == 0 {
+            return Err(DeleteError::ItemToDeleteNotFound(format!(
+                "freeze window {id} does not exist"
+            )));
+        }
+        Ok(())
+    }
+    async fn save_gate_dependencies(
+        &self,
+        dependencies: &GateDependencies,
+    ) -> Result<(), InsertError> {
+        if dependencies.depends_on.is_empty() {
+            sqlx::query(
+                r#"DELETE FROM gate_dependency
+                   WHERE "group" = ? AND service = ? AND environment = ?"#,
+            )
+            .bind(&dependencies.key.group)
+            .bind(&dependencies.key.service)
+            .bind(&dependencies.key.environment)
+            .execute(&self.pool)
+            .await?;
+            return Ok(());
+        }
+
+        sqlx::query(
+            r#"INSERT INTO gate_dependency ("group", service, environment, depends_on)
+              VALUES (?, ?, ?, ?)
+              ON CONFLICT ("group", service, environment)
+              DO UPDATE SET depends_on = excluded.depends_on"#,
+        )
+        .bind(&dependencies.key.group)
+        .bind(&dependencies.key.service)
+        .bind(&dependencies.key.environment)
+        .bind(encode_gate_keys(&dependencies.depends_on)?)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn find_gate_dependencies(&self) -> Result<Vec<GateDependencies>, FindError> {
+        sqlx::query_as::<_, GateDependencyRow>(
+            r#"SELECT "group", service, environment, depends_on FROM gate_dependency
+               ORDER BY "group", service, environment"#,
+        )
+        .fetch_all(&self.pool)
+        .await?
+        .into_iter()
+        .map(|row| {
+            Ok(GateDependencies {
+                key: GateKey {
+                    group: row.group,
+                    service: row.service,
+                    environment: row.environment,
+                },
+                depends_on: decode_gate_keys(&row.depends_on)?,
+            })
+        })
+        .collect()
+    }
+
+    async fn insert_scheduled_state_change(
+        &self,
+        change: &ScheduledStateChange,
+    ) -> Result<(), InsertError> {
+        sqlx::query(
+            r#"INSERT INTO scheduled_state_change (id, "group", service, environment, state, at, actor)
+              VALUES (?, ?, ?, ?, ?, ?, ?)"#,
+        )
+        .bind(&change.id)
+        .bind(&change.key.group)
+        .bind(&change.key.service)
+        .bind(&change.key.environment)
+        .bind(String::try_from(change.state.clone()).map_err(InsertError::Other)?)
+        .bind(change.at.to_rfc3339())
+        .bind(&change.actor)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn find_scheduled_state_changes(&self) -> Result<Vec<ScheduledStateChange>, FindError> {
+        let mut changes = sqlx::query_as::<_, ScheduledStateChangeRow>(
+            r#"SELECT id, "group", service, environment, state, at, actor
+              FROM scheduled_state_change"#,
+        )
+        .fetch_all(&self.pool)
+        .await?
+        .into_iter()
+        .map(|row| {
+            row.try_into().map_err(|error| {
+                FindError::ItemCouldNotBeDecoded(format!(
+                    "could not decode scheduled state change (mapping error: {error})"
+                ))
+            })
+        })
+        .collect::<Result<Vec<ScheduledStateChange>, _>>()?;
+        // the instants are stored as text, which does not order them reliably
+        changes.sort_by_key(|change| change.at);
+
+        Ok(changes)
+    }
+
+    async fn delete_scheduled_state_change(&self, id: String) -> Result<(), DeleteError> {
+        let deleted = sqlx::query("DELETE FROM scheduled_state_change WHERE id = ?")
+            .bind(&id)
+            .execute(&self.pool)
+            .await?
+            .rows_affected();
+
+        if deleted == 0 {
+            return Err(DeleteError::ItemToDeleteNotFound(format!(
+                "scheduled state change {id} does not exist"
+            )));
+        }
+        Ok(())
+    }
+}
+
+impl SqliteStorage {
+    pub async fn new() -> Self {
+        Self::new_with_path(
+            &std::env::var(ENV_GATES_SQLITE_DATABASE_PATH).unwrap_or_else(|_| {
+                panic!("{ENV_GATES_SQLITE_DATABASE_PATH} must be set to use the sqlite storage")
+            }),
+        )
+        .await
+    }
+
+    pub async fn new_with_path(path: &str) -> Self {
+        Self::connect(SqliteConnectOptions::new().filename(path)).await
+    }
+
+    #[allow(dead_code)]
+    pub async fn new_in_memory() -> Self {
+        Self::connect(SqliteConnectOptions::new().in_memory(true)).await
+    }
+
+    async fn connect(options: SqliteConnectOptions) -> Self {
+        // sqlite allows a single writer only, a single connection also keeps in-memory databases alive
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .min_connections(1)
+            .idle_timeout(None)
+            .max_lifetime(None)
+            .connect_with(options.create_if_missing(true).foreign_keys(true))
+            .await
+            .expect("failed to open sqlite database");
+
+        MIGRATOR
+            .run(&pool)
+            .await
+            .expect("failed to migrate sqlite database");
+
+        Self { pool }
+    }
+
+    async fn find_gates(
+        &self,
+        group: Option<&str>,
+        service: Option<&str>,
+    ) -> Result<Vec<Gate>, FindError> {
+        let mut comments_by_key = sqlx::query_as::<_, CommentRow>(
+            r#"SELECT "group", service, environment, id, message, created FROM comments
+               WHERE (?1 IS NULL OR "group" = ?1) AND (?2 IS NULL OR service = ?2)"#,
+        )
+        .bind(group)
+        .bind(service)
+        .fetch_all(&self.pool)
+        .await?
+        .into_iter()
+        .try_fold(
+            HashMap::<GateKey, HashSet<Comment>>::new(),
+            |mut comments_by_key, row| {
+                let (key, comment) = row.try_into().map_err(|error| {
+                    FindError::ItemCouldNotBeDecoded(format!(
+                        "could not decode comment (mapping error: {error})"
+                    ))
+                })?;
+                comments_by_key.entry(key).or_default().insert(comment);
+                Ok::<_, FindError>(comments_by_key)
+            },
+        )?;
+
+        sqlx::query_as::<_, GateRow>(
+            r#"SELECT "group", service, environment, state, last_updated, display_order, version,
+                      open_until
+               FROM gates
+               WHERE (?1 IS NULL OR "group" = ?1) AND (?2 IS NULL OR service = ?2)
+                 AND deleted_at IS NULL
+               ORDER BY "group", service, environment"#,
+        )
+        .bind(group)
+        .bind(service)
+        .fetch_all(&self.pool)
+        .await?
+        .into_iter()
+        .map(|row| {
+            let comments = comments_by_key.remove(&row.key()).unwrap_or_default();
+            row.into_gate(comments).map_err(|error| {
+                FindError::ItemCouldNotBeDecoded(format!(
+                    "could not decode gate (mapping error: {error})"
+                ))
+            })
+        })
+        .collect()
+    }
+}
+
+async fn insert_gate(connection: &mut SqliteConnection, gate: &Gate) -> Result<(), InsertError> {
+    // a new gate replaces a deleted gate with the same key
+    sqlx::query(
+        r#"DELETE FROM gates
+           WHERE "group" = ? AND service = ? AND environment = ?
+             AND deleted_at IS NOT NULL"#,
+    )
+    .bind(&gate.key.group)
+    .bind(&gate.key.service)
+    .bind(&gate.key.environment)
+    .execute(&mut *connection)
+    .await?;
+
+    sqlx::query(
+        r#"INSERT INTO gates
+             ("group", service, environment, state, last_updated, display_order, version,
+              open_until)
+           VALUES (?, ?, ?, ?, ?, ?, ?, ?)"#,
+    )
+    .bind(&gate.key.group)
+    .bind(&gate.key.service)
+    .bind(&gate.key.environment)
+    .bind(String::try_from(gate.state.clone()).map_err(InsertError::Other)?)
+    .bind(gate.last_updated.to_rfc3339())
+    .bind(gate.display_order)
+    .bind(gate.version)
+    .bind(gate.open_until.map(|open_until| open_until.to_rfc3339()))
+    .execute(&mut *connection)
+    .await?;
+
+    for comment in &gate.comments {
+        upsert_comment(connection, &gate.key, comment).await?;
+    }
+    Ok(())
+}
+
+async fn upsert_closed_day(
+    connection: &mut SqliteConnection,
+    closed_day: &ClosedDay,
+) -> Result<(), InsertError> {
+    sqlx::query(
+        r"INSERT INTO closed_day (date, reason)
+          VALUES (?, ?)
+          ON CONFLICT (date)
+          DO UPDATE SET reason = excluded.reason",
+    )
+    .bind(closed_day.date.to_string())
+    .bind(&closed_day.reason)
+    .execute(connection)
+    .await?;
+    Ok(())
+}
+
+async fn fetch_gate(
+    connection: &mut SqliteConnection,
+    key: &GateKey,
+) -> Result<Option<Gate>, FindError> {
+    let Some(gate) = sqlx::query_as::<_, GateRow>(
+        r#"SELECT "group", service, environment, state, last_updated, display_order, version,
+                  open_until
+           FROM gates
+           WHERE "group" = ? AND service = ? AND environment = ? AND deleted_at IS NULL"#,
+    )
+    .bind(&key.group)
+    .bind(&key.service)
+    .bind(&key.environment)
+    .fetch_optional(&mut *connection)
+    .await?
+    else {
+        return Ok(None);
+    };
+
+    let comments = sqlx::query_as::<_, CommentRow>(
+        r#"SELECT "group", service, environment, id, message, created
+           FROM comments WHERE "group" = ? AND service = ? AND environment = ?"#,
+    )
+    .bind(&key.group)
+    .bind(&key.service)
+    .bind(&key.environment)
+    .fetch_all(&mut *connection)
+    .await?
+    .into_iter()
+    .map(|row| row.try_into().map(|(_, comment)| comment))
+    .collect::<Result<HashSet<Comment>, DecodeError>>()
+    .map_err(|error| {
+        FindError::ItemCouldNotBeDecoded(format!(
+            "could not decode comment (mapping error: {error})"
+        ))
+    })?;
+
+    gate.into_gate(comments).map(Some).map_err(|error| {
+        FindError::ItemCouldNotBeDecoded(format!("could not decode gate (mapping error: {error})"))
+    })
+}
+
+async fn fetch_updated_gate(
+    connection: &mut SqliteConnection,
+    key: &GateKey,
+) -> Result<Gate, UpdateError> {
+    fetch_gate(connection, key)
+        .await
+        .map_err(|error| match error {
+            FindError::ItemCouldNotBeDecoded(error)
+            | FindError::InvalidCursor(error)
+            | FindError::Other(error) => UpdateError::Other(error),
+        })?
+        .ok_or_else(|| UpdateError::Other("missing updated gate".to_owned()))
+}
+
+async fn update_last_updated(
+    connection: &mut SqliteConnection,
+    key: &GateKey,
+    last_updated: DateTime<Utc>,
+) -> Result<(), UpdateError> {
+    sqlx::query(
+        r#"UPDATE gates SET last_updated = ?, version = version + 1
+           WHERE "group" = ? AND service = ? AND environment = ?"#,
+    )
+    .bind(last_updated.to_rfc3339())
+    .bind(&key.group)
+    .bind(&key.service)
+    .bind(&key.environment)
+    .execute(connection)
+    .await?;
+    Ok(())
+}
+
+async fn upsert_comment(
+    connection: &mut SqliteConnection,
+    key: &GateKey,
+    comment: &Comment,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        r#"INSERT INTO comments ("group", service, environment, id, message, created)
+           VALUES (?, ?, ?, ?, ?, ?)
+           ON CONFLICT ("group", service, environment, id)
+           DO UPDATE SET message = excluded.message, created = excluded.created"#,
+    )
+    .bind(&key.group)
+    .bind(&key.service)
+    .bind(&key.environment)
+    .bind(&comment.id)
+    .bind(&comment.message)
+    .bind(comment.created.to_rfc3339())
+    .execute(connection)
+    .await?;
+    Ok(())
+}
+
+async fn check_gate_version(
+    connection: &mut SqliteConnection,
+    key: &GateKey,
+    expected_version: Option<u32>,
+) -> Result<(), UpdateError> {
+    let version = sqlx::query_scalar::<_, i64>(
+        r#"SELECT version FROM gates
+           WHERE "group" = ? AND service = ? AND environment = ? AND deleted_at IS NULL"#,
+    )
+    .bind(&key.group)
+    .bind(&key.service)
+    .bind(&key.environment)
+    .fetch_optional(connection)
+    .await?
+    .ok_or_else(|| UpdateError::ItemToUpdateNotFound(format!("gate {key} does not exist")))?;
+
+    check_version(
+        key,
+        u32::try_from(version).map_err(|error| {
+            UpdateError::Other(format!(
+                "version of gate {key} could not be parsed as u32: {error}"
+            ))
+        })?,
+        expected_version,
+    )
+}
+
+/////////////////////////////////////////////////////////////////////////////
+// Decode
+/////////////////////////////////////////////////////////////////////////////
+
+type DecodeError = String;
+
+#[derive(FromRow)]
+struct GateRow {
+    group: String,
+    service: String,
+    environment: String,
+    state: String,
+    last_updated: String,
+    display_order: Option<i64>,
+    version: i64,
+    open_until: Option<String>,
+}
+
+#[derive(FromRow)]
+struct CommentRow {
+    group: String,
+    service: String,
+    environment: String,
+    id: String,
+    message: String,
+    created: String,
+}
+
+#[derive(FromRow)]
+struct FreezeRow {
+    reason: String,
+    actor: Option<String>,
+    created: String,
+    until: Option<String>,
+}
+
+#[derive(FromRow)]
+struct BusinessWeekProfileRow {
+    group: String,
+    service: String,
+    environment: String,
+    week: String,
+}
+
+#[derive(FromRow)]
+struct GateDependencyRow {
+    group: String,
+    service: String,
+    environment: String,
+    depends_on: String,
+}
+
+#[derive(FromRow)]
+struct ClosedDayRow {
+    date: String,
+    reason: String,
+}
+
+#[derive(FromRow)]
+struct FreezeWindowRow {
+    id: String,
+    name: String,
+    reason: String,
+    starts_at: String,
+    ends_at: String,
+    scope_group: Option<String>,
+    scope_environment: Option<String>,
+    actor: Option<String>,
+}
+
+#[derive(FromRow)]
+struct ScheduledStateChangeRow {
+    id: String,
+    group: String,
+    service: String,
+    environment: String,
+    state: String,
+    at: String,
+    actor: Option<String>,
+}
+
+#[derive(FromRow)]
+struct HistoryEntryRow {
+    position: i64,
+    id: String,
+    group: String,
+    service: String,
+    environment: String,
+    timestamp: String,
+    event: String,
+    old_value: Option<String>,
+    new_value: Option<String>,
+    open_until: Option<String>,
+    actor: Option<String>,
+}
+
+fn decode_datetime_utc(field: &str, value: &str) -> Result<DateTime<Utc>, DecodeError> {
+    DateTime::parse_from_rfc3339(value)
+        .map_err(|_| format!("field {field} could not be parsed as datetime"))
+        .map(Into::into)
+}
+
+impl GateRow {
+    fn key(&self) -> GateKey {
+        GateKey {
+            group: self.group.clone(),
+            service: self.service.clone(),
+            environment: self.environment.clone(),
+        }
+    }
+
+    fn into_gate(self, comments: HashSet<Comment>) -> Result<Gate, DecodeError> {
+        Ok(Gate {
+            key: self.key(),
+            state: self.state.try_into()?,
+            comments,
+            last_updated: decode_datetime_utc("last_updated", &self.last_updated)?,
+            display_order: self
+                .display_order
+                .map(|display_order| {
+                    u32::try_from(display_order).map_err(|error| {
+                        format!("field display_order could not be parsed as u32: {error}")
+                    })
+                })
+                .transpose()?,
+            version: u32::try_from(self.version)
+                .map_err(|error| format!("field version could not be parsed as u32: {error}"))?,
+            open_until: self
+                .open_until
+                .map(|open_until| decode_datetime_utc("open_until", &open_until))
+                .transpose()?,
+        })
+    }
+}
+
+impl TryFrom<CommentRow> for (GateKey, Comment) {
+    type Error = DecodeError;
+
+    fn try_from(value: CommentRow) -> Result<Self, Self::Error> {
+        Ok((
+            GateKey {
+                group: value.group,
+                service: value.service,
+                environment: value.environment,
+            },
+            Comment {
+                created: decode_datetime_utc("created", &value.created)?,
+                id: value.id,
+                message: value.message,
+            },
+        ))
+    }
+}
+
+impl TryFrom<HistoryEntryRow> for HistoryEntry {
+    type Error = DecodeError;
+
+    fn try_from(value: HistoryEntryRow) -> Result<Self, Self::Error> {
+        Ok(Self {
+            id: value.id,
+            key: GateKey {
+                group: value.group,
+                service: value.service,
+                environment: value.environment,
+            },
+            timestamp: decode_datetime_utc("timestamp", &value.timestamp)?,
+            event: value.event.try_into()?,
+            old_value: value.old_value,
+            new_value: value.new_value,
+            open_until: value
+                .open_until
+                .map(|open_until| decode_datetime_utc("open_until", &open_until))
+                .transpose()?,
+            actor: value.actor,
+        })
+    }
+}
+
+impl TryFrom<FreezeRow> for Freeze {
+    type Error = DecodeError;
+
+    fn try_from(value: FreezeRow) -> Result<Self, Self::Error> {
+        Ok(Self {
+            reason: value.reason,
+            actor: value.actor,
+            created: decode_datetime_utc("created", &value.created)?,
+            until: value
+                .until
+                .map(|until| decode_datetime_utc("until", &until))
+                .transpose()?,
+        })
+    }
+}
+
+impl TryFrom<ClosedDayRow> for ClosedDay {
+    type Error = DecodeError;
+
+    fn try_from(value: ClosedDayRow) -> Result<Self, Self::Error> {
+        Ok(Self {
+            date: value
+                .date
+                .parse()
+                .map_err(|_| "field date could not be parsed as date".to_owned())?,
+            reason: value.reason,
+        })
+    }
+}
+
+impl TryFrom<FreezeWindowRow> for FreezeWindow {
+    type Error = DecodeError;
+
+    fn try_from(value: FreezeWindowRow) -> Result<Self, Self::Error> {
+        Ok(Self {
+            id: value.id,
+            name: value.name,
+            reason: value.reason,
+            start: decode_datetime_utc("starts_at", &value.starts_at)?,
+            end: decode_datetime_utc("ends_at", &value.ends_at)?,
+            scope: FreezeScope::new(value.scope_group, value.scope_environment)?,
+            actor: value.actor,
+        })
+    }
+}
+
+impl TryFrom<ScheduledStateChangeRow> for ScheduledStateChange {
+    type Error = DecodeError;
+
+    fn try_from(value: ScheduledStateChangeRow) -> Result<Self, Self::Error> {
+        Ok(Self {
+            id: value.id,
+            key: GateKey {
+                group: value.group,
+                service: value.service,
+                environment: value.environment,
+            },
+            state: value.state.try_into()?,
+            at: decode_datetime_utc("at", &value.at)?,
+            actor: value.actor,
+        })
+    }
+}
+
+#[cfg(test)]
+mod integration_tests {
+    use similar_asserts::assert_eq;
+
+    use crate::storage::conformance::{some_gate, storage_conformance_tests};
+
+    use super::*;
+
+    storage_conformance_tests!(async { (SqliteStorage::new_in_memory().await, ()) });
+
+    #[tokio::test]
+    async fn should_keep_gates_after_reopening_database_file() {
+        // given
+        let path = std::env::temp_dir().join(format!("gates-{}.sqlite", cuid2::create_id()));
+        let path = path.to_str().expect("failed to build database path");
+        let gate = some_gate("some group", "some service", "some environment");
+
+        let sqlite_storage = SqliteStorage::new_with_path(path).await;
+        sqlite_storage
+            .insert(&gate)
+            .await
+            .expect("storage failed to insert gate");
+        sqlite_storage.pool.close().await;
+
+        // when
+        let sqlite_storage = SqliteStorage::new_with_path(path).await;
+        let result = sqlite_storage.find_all().await;
+
+        // then
+        assert_eq!(result.expect("storage failed to find gates"), vec![gate]);
+        sqlite_storage.pool.close().await;
+        std::fs::remove_file(path).expect("failed to remove database file");
+    }
+}
@@ -0,0 +1,48 @@
+use chrono::TimeDelta;
+use std::env;
+
+pub const ENV_GATES_TRASH_RETENTION_DAYS: &str = "GATES_TRASH_RETENTION_DAYS";
+
+const DEFAULT_RETENTION_DAYS: i64 = 30;
+
+/// How long deleted gates can be restored before they are purged.
+pub fn retention() -> TimeDelta {
+    parse_retention(env::var(ENV_GATES_TRASH_RETENTION_DAYS).ok().as_deref())
+}
+
+fn parse_retention(days: Option<&str>) -> TimeDelta {
+    let days = days.map_or(DEFAULT_RETENTION_DAYS, |days| {
+        days.trim()
+            .parse()
+            .ok()
+            .filter(|days| *days >= 0)
+            .unwrap_or_else(|| {
+                panic!("unsupported value '{days}' for {ENV_GATES_TRASH_RETENTION_DAYS}")
+            })
+    });
+    TimeDelta::days(days)
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use rstest::rstest;
+    use similar_asserts::assert_eq;
+
+    use super::*;
+
+    #[rstest]
+    #[case(None, TimeDelta::days(30))]
+    #[case(Some("7"), TimeDelta::days(7))]
+    #[case(Some(" 0 "), TimeDelta::zero())]
+    fn should_parse_retention(#[case] days: Option<&str>, #[case] expected: TimeDelta) {
+        assert_eq!(parse_retention(days), expected);
+    }
+
+    #[rstest]
+    #[case("-1")]
+    #[case("a week")]
+    #[should_panic(expected = "GATES_TRASH_RETENTION_DAYS")]
+    fn should_reject_invalid_retention(#[case] days: &str) {
+        parse_retention(Some(days));
+    }
+}
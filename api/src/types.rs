@@ -1,84 +1,303 @@
-use chrono::{DateTime, NaiveTime, Utc, Weekday};
+use chrono::{DateTime, Datelike, NaiveDate, NaiveTime, TimeZone, Utc, Weekday};
+use chrono_tz::Tz;
 use itertools::Itertools;
 use openapi::models;
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
 use std::collections::HashSet;
+use std::fmt;
 
 pub mod app_state;
 pub mod use_cases;
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct BusinessTimes {
     pub start: NaiveTime,
     pub end: NaiveTime,
 }
 
 impl BusinessTimes {
-    pub fn is_outside_of_business_times(&self, date_to_check: DateTime<Utc>) -> bool {
-        let time_to_check = date_to_check.time();
-        time_to_check < self.start || time_to_check > self.end
+    /// Business times ending before they start span midnight and end on the next day.
+    pub fn spans_midnight(&self) -> bool {
+        self.end < self.start
+    }
+
+    /// Whether the time is within the business times on the day they start.
+    pub fn contains_on_first_day(&self, time: NaiveTime) -> bool {
+        self.start <= time && (self.spans_midnight() || time <= self.end)
+    }
+
+    /// Whether the time is within the business times on the day after they start.
+    pub fn contains_on_next_day(&self, time: NaiveTime) -> bool {
+        self.spans_midnight() && time <= self.end
     }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct BusinessWeek {
-    pub monday: Option<BusinessTimes>,
-    pub tuesday: Option<BusinessTimes>,
-    pub wednesday: Option<BusinessTimes>,
-    pub thursday: Option<BusinessTimes>,
-    pub friday: Option<BusinessTimes>,
-    pub saturday: Option<BusinessTimes>,
-    pub sunday: Option<BusinessTimes>,
+    #[serde(default, deserialize_with = "deserialize_business_times")]
+    pub monday: Vec<BusinessTimes>,
+    #[serde(default, deserialize_with = "deserialize_business_times")]
+    pub tuesday: Vec<BusinessTimes>,
+    #[serde(default, deserialize_with = "deserialize_business_times")]
+    pub wednesday: Vec<BusinessTimes>,
+    #[serde(default, deserialize_with = "deserialize_business_times")]
+    pub thursday: Vec<BusinessTimes>,
+    #[serde(default, deserialize_with = "deserialize_business_times")]
+    pub friday: Vec<BusinessTimes>,
+    #[serde(default, deserialize_with = "deserialize_business_times")]
+    pub saturday: Vec<BusinessTimes>,
+    #[serde(default, deserialize_with = "deserialize_business_times")]
+    pub sunday: Vec<BusinessTimes>,
+    #[serde(default = "default_time_zone")]
+    pub time_zone: Tz,
+}
+
+const fn default_time_zone() -> Tz {
+    chrono_tz::Europe::Berlin
 }
 
+/// Besides a list, accepts a single business times object or null, as business weeks were written
+/// before a day could have several business times.
+fn deserialize_business_times<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Vec<BusinessTimes>, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum OneOrMany {
+        One(BusinessTimes),
+        Many(Vec<BusinessTimes>),
+    }
+
+    Ok(match Option::<OneOrMany>::deserialize(deserializer)? {
+        None => vec![],
+        Some(OneOrMany::One(business_times)) => vec![business_times],
+        Some(OneOrMany::Many(business_times)) => business_times,
+    })
+}
+
+const WEEKDAYS: [Weekday; 7] = [
+    Weekday::Mon,
+    Weekday::Tue,
+    Weekday::Wed,
+    Weekday::Thu,
+    Weekday::Fri,
+    Weekday::Sat,
+    Weekday::Sun,
+];
+
 impl BusinessWeek {
-    pub const fn business_times_by_weekday(&self, weekday: Weekday) -> Option<&BusinessTimes> {
+    pub fn business_times_by_weekday(&self, weekday: Weekday) -> &[BusinessTimes] {
         match weekday {
-            Weekday::Mon => self.monday.as_ref(),
-            Weekday::Tue => self.tuesday.as_ref(),
-            Weekday::Wed => self.wednesday.as_ref(),
-            Weekday::Thu => self.thursday.as_ref(),
-            Weekday::Fri => self.friday.as_ref(),
-            Weekday::Sat => self.saturday.as_ref(),
-            Weekday::Sun => self.sunday.as_ref(),
+            Weekday::Mon => &self.monday,
+            Weekday::Tue => &self.tuesday,
+            Weekday::Wed => &self.wednesday,
+            Weekday::Thu => &self.thursday,
+            Weekday::Fri => &self.friday,
+            Weekday::Sat => &self.saturday,
+            Weekday::Sun => &self.sunday,
         }
     }
 
-    pub const fn default() -> Self {
+    /// Business times spanning midnight keep the day after they start open until they end.
+    pub fn is_outside_of_business_times<T: TimeZone>(&self, date_to_check: &DateTime<T>) -> bool {
+        let time_to_check = date_to_check.time();
+        let weekday = date_to_check.weekday();
+        let started_today = self
+            .business_times_by_weekday(weekday)
+            .iter()
+            .any(|business_times| business_times.contains_on_first_day(time_to_check));
+        let started_yesterday = self
+            .business_times_by_weekday(weekday.pred())
+            .iter()
+            .any(|business_times| business_times.contains_on_next_day(time_to_check));
+        !started_today && !started_yesterday
+    }
+
+    pub fn parse(business_week: models::BusinessWeek, time_zone: &str) -> Result<Self, String> {
+        let parse = |business_times: Option<Vec<models::BusinessTimes>>| {
+            business_times
+                .unwrap_or_default()
+                .into_iter()
+                .map(TryInto::try_into)
+                .collect::<Result<Vec<_>, _>>()
+        };
+        let business_week = Self {
+            monday: parse(business_week.monday)?,
+            tuesday: parse(business_week.tuesday)?,
+            wednesday: parse(business_week.wednesday)?,
+            thursday: parse(business_week.thursday)?,
+            friday: parse(business_week.friday)?,
+            saturday: parse(business_week.saturday)?,
+            sunday: parse(business_week.sunday)?,
+            time_zone: time_zone
+                .parse()
+                .map_err(|_| format!("{time_zone} is not a known time zone"))?,
+        };
+        business_week.validate()?;
+        Ok(business_week)
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        WEEKDAYS
+            .into_iter()
+            .flat_map(|weekday| {
+                self.business_times_by_weekday(weekday)
+                    .iter()
+                    .map(move |times| (weekday, times))
+            })
+            .find(|(_, times)| times.start == times.end)
+            .map_or(Ok(()), |(weekday, times)| {
+                Err(format!(
+                    "business times on {weekday} must not end when they start, but start and end at {}",
+                    times.start
+                ))
+            })
+    }
+}
+
+impl Default for BusinessWeek {
+    fn default() -> Self {
+        let business_times = |start_hour, end_hour, end_minute| {
+            vec![BusinessTimes {
+                start: NaiveTime::from_hms_opt(start_hour, 0, 0).unwrap(),
+                end: NaiveTime::from_hms_opt(end_hour, end_minute, 0).unwrap(),
+            }]
+        };
         Self {
-            monday: Some(BusinessTimes {
-                start: NaiveTime::from_hms_opt(7, 0, 0).unwrap(),
-                end: NaiveTime::from_hms_opt(18, 30, 0).unwrap(),
-            }),
-            tuesday: Some(BusinessTimes {
-                start: NaiveTime::from_hms_opt(8, 0, 0).unwrap(),
-                end: NaiveTime::from_hms_opt(18, 0, 0).unwrap(),
-            }),
-            wednesday: Some(BusinessTimes {
-                start: NaiveTime::from_hms_opt(8, 0, 0).unwrap(),
-                end: NaiveTime::from_hms_opt(17, 0, 0).unwrap(),
-            }),
-            thursday: Some(BusinessTimes {
-                start: NaiveTime::from_hms_opt(8, 0, 0).unwrap(),
-                end: NaiveTime::from_hms_opt(18, 0, 0).unwrap(),
-            }),
-            friday: Some(BusinessTimes {
-                start: NaiveTime::from_hms_opt(10, 0, 0).unwrap(),
-                end: NaiveTime::from_hms_opt(16, 0, 0).unwrap(),
-            }),
-            saturday: None,
-            sunday: None,
+            monday: business_times(7, 18, 30),
+            tuesday: business_times(8, 18, 0),
+            wednesday: business_times(8, 17, 0),
+            thursday: business_times(8, 18, 0),
+            friday: business_times(10, 16, 0),
+            saturday: vec![],
+            sunday: vec![],
+            time_zone: default_time_zone(),
+        }
+    }
+}
+
+/// The gates a business week profile applies to: all gates of a group, of a service or a single gate.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct BusinessWeekScope {
+    pub group: String,
+    pub service: Option<String>,
+    pub environment: Option<String>,
+}
+
+impl BusinessWeekScope {
+    pub fn new(
+        group: String,
+        service: Option<String>,
+        environment: Option<String>,
+    ) -> Result<Self, String> {
+        if group.is_empty()
+            || service.as_ref().is_some_and(String::is_empty)
+            || environment.as_ref().is_some_and(String::is_empty)
+        {
+            return Err("group, service and environment must not be empty".to_owned());
+        }
+        if service.is_none() && environment.is_some() {
+            return Err("an environment can only be given together with a service".to_owned());
         }
+        Ok(Self {
+            group,
+            service,
+            environment,
+        })
+    }
+
+    /// The scopes containing the gate, from the most to the least specific one.
+    pub fn of_gate(key: &GateKey) -> [Self; 3] {
+        [
+            Self {
+                group: key.group.clone(),
+                service: Some(key.service.clone()),
+                environment: Some(key.environment.clone()),
+            },
+            Self {
+                group: key.group.clone(),
+                service: Some(key.service.clone()),
+                environment: None,
+            },
+            Self {
+                group: key.group.clone(),
+                service: None,
+                environment: None,
+            },
+        ]
     }
 }
 
+/// Business hours differing from the global business week, e.g. for internal tools.
 #[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BusinessWeekProfile {
+    pub scope: BusinessWeekScope,
+    pub business_week: BusinessWeek,
+}
+
+/// The environment names the business hours apply to, all environments if there are none.
+/// A `*` in a name matches any number of characters, e.g. `prod*`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EnvironmentPatterns(Vec<String>);
+
+impl EnvironmentPatterns {
+    pub fn new(patterns: Vec<String>) -> Result<Self, String> {
+        if patterns.is_empty() || patterns.iter().any(String::is_empty) {
+            return Err("environment patterns must not be empty".to_owned());
+        }
+        Ok(Self(patterns))
+    }
+
+    pub fn matches(&self, environment: &str) -> bool {
+        self.0.is_empty()
+            || self
+                .0
+                .iter()
+                .any(|pattern| matches_pattern(pattern, environment))
+    }
+}
+
+fn matches_pattern(pattern: &str, value: &str) -> bool {
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or_default();
+    let Some(mut rest) = value.strip_prefix(first) else {
+        return false;
+    };
+    let mut parts = parts.collect::<Vec<_>>();
+    let Some(last) = parts.pop() else {
+        return rest.is_empty();
+    };
+    for part in parts {
+        match rest.find(part) {
+            Some(index) => rest = &rest[index + part.len()..],
+            None => return false,
+        }
+    }
+    rest.len() >= last.len() && rest.ends_with(last)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub struct GateKey {
     pub group: String,
     pub service: String,
     pub environment: String,
 }
 
+impl fmt::Display for GateKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}/{}", self.group, self.service, self.environment)
+    }
+}
+
+/// The gates a gate depends on, it is closed as long as one of them is closed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GateDependencies {
+    pub key: GateKey,
+    pub depends_on: Vec<GateKey>,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Gate {
     pub key: GateKey,
@@ -86,6 +305,40 @@ pub struct Gate {
     pub comments: HashSet<Comment>,
     pub last_updated: DateTime<Utc>,
     pub display_order: Option<u32>,
+    pub version: u32,
+    /// When the open gate is closed again automatically.
+    pub open_until: Option<DateTime<Utc>>,
+}
+
+impl Gate {
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        self.state == GateState::Open && self.open_until.is_some_and(|open_until| open_until <= now)
+    }
+
+    /// Reports the gate closed once its open state has expired.
+    pub fn close_if_expired(self, now: DateTime<Utc>) -> Self {
+        if self.is_expired(now) {
+            Self {
+                state: GateState::Closed,
+                open_until: None,
+                ..self
+            }
+        } else {
+            self
+        }
+    }
+
+    /// The representation of the gate, including how long it stays open at the given instant.
+    pub fn into_rep(self, now: DateTime<Utc>) -> models::Gate {
+        let open_remaining_seconds = self
+            .open_until
+            .filter(|_| self.state == GateState::Open)
+            .map(|open_until| (open_until - now).num_seconds().max(0));
+        models::Gate {
+            open_remaining_seconds,
+            ..self.into()
+        }
+    }
 }
 
 #[derive(Debug, Clone, Hash, PartialEq, Eq)]
@@ -95,6 +348,136 @@ pub struct Comment {
     pub created: DateTime<Utc>,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GateEvent {
+    Created,
+    Deleted,
+    Restored,
+    StateChanged,
+    DisplayOrderChanged,
+    CommentAdded,
+    CommentDeleted,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HistoryEntry {
+    pub id: String,
+    pub key: GateKey,
+    pub timestamp: DateTime<Utc>,
+    pub event: GateEvent,
+    pub old_value: Option<String>,
+    pub new_value: Option<String>,
+    /// When the gate opened by the change is closed again automatically.
+    pub open_until: Option<DateTime<Utc>>,
+    pub actor: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HistoryPage {
+    pub entries: Vec<HistoryEntry>,
+    pub next_cursor: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Freeze {
+    pub reason: String,
+    pub actor: Option<String>,
+    pub created: DateTime<Utc>,
+    pub until: Option<DateTime<Utc>>,
+}
+
+impl Freeze {
+    pub fn is_active(&self, now: DateTime<Utc>) -> bool {
+        self.created <= now && self.until.is_none_or(|until| now < until)
+    }
+}
+
+/// The gates a freeze window applies to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FreezeScope {
+    All,
+    Group(String),
+    Environment(String),
+}
+
+impl FreezeScope {
+    /// At most one of group and environment may be given, none means all gates.
+    pub fn new(group: Option<String>, environment: Option<String>) -> Result<Self, String> {
+        match (group, environment) {
+            (None, None) => Ok(Self::All),
+            (Some(group), None) => Ok(Self::Group(group)),
+            (None, Some(environment)) => Ok(Self::Environment(environment)),
+            (Some(_), Some(_)) => {
+                Err("a freeze window is either scoped to a group or an environment".to_owned())
+            }
+        }
+    }
+
+    pub fn group(&self) -> Option<&str> {
+        match self {
+            Self::Group(group) => Some(group),
+            Self::All | Self::Environment(_) => None,
+        }
+    }
+
+    pub fn environment(&self) -> Option<&str> {
+        match self {
+            Self::Environment(environment) => Some(environment),
+            Self::All | Self::Group(_) => None,
+        }
+    }
+
+    pub fn contains(&self, key: &GateKey) -> bool {
+        match self {
+            Self::All => true,
+            Self::Group(group) => key.group == *group,
+            Self::Environment(environment) => key.environment == *environment,
+        }
+    }
+}
+
+/// A named freeze between two instants, e.g. for a release or the holiday season.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FreezeWindow {
+    pub id: String,
+    pub name: String,
+    pub reason: String,
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+    pub scope: FreezeScope,
+    pub actor: Option<String>,
+}
+
+impl FreezeWindow {
+    pub fn is_active(&self, now: DateTime<Utc>) -> bool {
+        self.start <= now && now < self.end
+    }
+}
+
+/// A change of the state of a gate at a future instant, applied by the scheduler once it is due.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScheduledStateChange {
+    pub id: String,
+    pub key: GateKey,
+    pub state: GateState,
+    pub at: DateTime<Utc>,
+    pub actor: Option<String>,
+}
+
+impl ScheduledStateChange {
+    pub fn is_due(&self, now: DateTime<Utc>) -> bool {
+        self.at <= now
+    }
+}
+
+/// A day on which all gates are closed, e.g. a public holiday, in the time zone of the business week.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClosedDay {
+    pub date: NaiveDate,
+    pub reason: String,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 #[derive(Default)]
@@ -107,17 +490,64 @@ pub enum GateState {
 impl From<BusinessWeek> for models::BusinessWeek {
     fn from(value: BusinessWeek) -> Self {
         Self {
-            monday: value.monday.map(Into::into),
-            tuesday: value.tuesday.map(Into::into),
-            wednesday: value.wednesday.map(Into::into),
-            thursday: value.thursday.map(Into::into),
-            friday: value.friday.map(Into::into),
-            saturday: value.saturday.map(Into::into),
-            sunday: value.sunday.map(Into::into),
+            monday: business_times_to_models(value.monday),
+            tuesday: business_times_to_models(value.tuesday),
+            wednesday: business_times_to_models(value.wednesday),
+            thursday: business_times_to_models(value.thursday),
+            friday: business_times_to_models(value.friday),
+            saturday: business_times_to_models(value.saturday),
+            sunday: business_times_to_models(value.sunday),
+        }
+    }
+}
+
+impl From<GateKey> for models::GateKey {
+    fn from(value: GateKey) -> Self {
+        Self {
+            group: value.group,
+            service: value.service,
+            environment: value.environment,
+        }
+    }
+}
+
+impl From<models::GateKey> for GateKey {
+    fn from(value: models::GateKey) -> Self {
+        Self {
+            group: value.group,
+            service: value.service,
+            environment: value.environment,
+        }
+    }
+}
+
+impl From<GateDependencies> for models::GateDependencies {
+    fn from(value: GateDependencies) -> Self {
+        Self {
+            depends_on: value.depends_on.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl From<BusinessWeekProfile> for models::BusinessWeekProfile {
+    fn from(value: BusinessWeekProfile) -> Self {
+        Self {
+            group: value.scope.group,
+            service: value.scope.service,
+            environment: value.scope.environment,
+            time_zone: value.business_week.time_zone.name().to_owned(),
+            business_week: value.business_week.into(),
         }
     }
 }
 
+// days without business times are left out
+fn business_times_to_models(
+    business_times: Vec<BusinessTimes>,
+) -> Option<Vec<models::BusinessTimes>> {
+    (!business_times.is_empty()).then(|| business_times.into_iter().map(Into::into).collect())
+}
+
 impl From<BusinessTimes> for models::BusinessTimes {
     fn from(value: BusinessTimes) -> Self {
         Self {
@@ -127,6 +557,22 @@ impl From<BusinessTimes> for models::BusinessTimes {
     }
 }
 
+impl TryFrom<models::BusinessTimes> for BusinessTimes {
+    type Error = String;
+
+    fn try_from(value: models::BusinessTimes) -> Result<Self, Self::Error> {
+        Ok(Self {
+            start: parse_rfc3339_time(&value.start)?,
+            end: parse_rfc3339_time(&value.end)?,
+        })
+    }
+}
+
+fn parse_rfc3339_time(value: &str) -> Result<NaiveTime, String> {
+    NaiveTime::parse_from_str(value.strip_suffix('Z').unwrap_or(value), "%H:%M:%S")
+        .map_err(|_| format!("{value} is not a valid time"))
+}
+
 trait ToRfc339Time {
     fn to_rfc3339(&self) -> String;
 }
@@ -173,6 +619,34 @@ impl From<GateState> for models::GateStateRep {
     fn from(value: GateState) -> Self {
         Self {
             state: value.into(),
+            freeze: None,
+            reason: None,
+            closure_reason: None,
+            latest_comment: None,
+            next_transition: None,
+            transitions: None,
+        }
+    }
+}
+
+/// Why a gate is closed, in the order of precedence.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClosureReason {
+    Freeze,
+    BusinessHours,
+    Expired,
+    Manual,
+    Dependency,
+}
+
+impl From<ClosureReason> for models::ClosureReason {
+    fn from(value: ClosureReason) -> Self {
+        match value {
+            ClosureReason::Freeze => Self::Freeze,
+            ClosureReason::BusinessHours => Self::BusinessHours,
+            ClosureReason::Manual => Self::Manual,
+            ClosureReason::Expired => Self::Expired,
+            ClosureReason::Dependency => Self::Dependency,
         }
     }
 }
@@ -187,6 +661,8 @@ impl From<GateState> for models::GateState {
 }
 impl From<Gate> for models::Gate {
     fn from(value: Gate) -> Self {
+        let closure_reason =
+            (value.state == GateState::Closed).then_some(models::ClosureReason::Manual);
         Self {
             group: value.key.group,
             service: value.key.service,
@@ -200,6 +676,120 @@ impl From<Gate> for models::Gate {
                 .collect(),
             last_updated: value.last_updated.to_rfc3339(),
             display_order: value.display_order.map(f64::from),
+            version: i64::from(value.version),
+            open_until: value.open_until.map(|open_until| open_until.to_rfc3339()),
+            open_remaining_seconds: None,
+            closure_reason,
+        }
+    }
+}
+
+impl TryFrom<String> for GateEvent {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        serde_json::from_str(&format!("\"{value}\""))
+            .map_err(|serde_error| format!("cannot convert {value} to GateEvent: {serde_error}"))
+    }
+}
+
+impl From<GateEvent> for String {
+    fn from(value: GateEvent) -> Self {
+        match value {
+            GateEvent::Created => "created",
+            GateEvent::Deleted => "deleted",
+            GateEvent::Restored => "restored",
+            GateEvent::StateChanged => "state_changed",
+            GateEvent::DisplayOrderChanged => "display_order_changed",
+            GateEvent::CommentAdded => "comment_added",
+            GateEvent::CommentDeleted => "comment_deleted",
+        }
+        .to_owned()
+    }
+}
+
+impl From<GateEvent> for models::GateEvent {
+    fn from(value: GateEvent) -> Self {
+        match value {
+            GateEvent::Created => Self::Created,
+            GateEvent::Deleted => Self::Deleted,
+            GateEvent::Restored => Self::Restored,
+            GateEvent::StateChanged => Self::StateChanged,
+            GateEvent::DisplayOrderChanged => Self::DisplayOrderChanged,
+            GateEvent::CommentAdded => Self::CommentAdded,
+            GateEvent::CommentDeleted => Self::CommentDeleted,
+        }
+    }
+}
+
+impl From<Freeze> for models::Freeze {
+    fn from(value: Freeze) -> Self {
+        Self {
+            reason: value.reason,
+            actor: value.actor,
+            created: value.created.to_rfc3339(),
+            until: value.until.map(|until| until.to_rfc3339()),
+        }
+    }
+}
+
+impl From<FreezeWindow> for models::FreezeWindow {
+    fn from(value: FreezeWindow) -> Self {
+        Self {
+            id: value.id,
+            name: value.name,
+            reason: value.reason,
+            start: value.start.to_rfc3339(),
+            end: value.end.to_rfc3339(),
+            group: value.scope.group().map(ToOwned::to_owned),
+            environment: value.scope.environment().map(ToOwned::to_owned),
+            actor: value.actor,
+        }
+    }
+}
+
+impl From<ScheduledStateChange> for models::ScheduledStateChange {
+    fn from(value: ScheduledStateChange) -> Self {
+        Self {
+            id: value.id,
+            group: value.key.group,
+            service: value.key.service,
+            environment: value.key.environment,
+            state: value.state.into(),
+            at: value.at.to_rfc3339(),
+            actor: value.actor,
+        }
+    }
+}
+
+impl From<ClosedDay> for models::ClosedDay {
+    fn from(value: ClosedDay) -> Self {
+        Self {
+            date: value.date.to_string(),
+            reason: value.reason,
+        }
+    }
+}
+
+impl From<HistoryEntry> for models::HistoryEntry {
+    fn from(value: HistoryEntry) -> Self {
+        Self {
+            id: value.id,
+            timestamp: value.timestamp.to_rfc3339(),
+            event: value.event.into(),
+            old_value: value.old_value,
+            new_value: value.new_value,
+            open_until: value.open_until.map(|open_until| open_until.to_rfc3339()),
+            actor: value.actor,
+        }
+    }
+}
+
+impl From<HistoryPage> for models::GateHistory {
+    fn from(value: HistoryPage) -> Self {
+        Self {
+            entries: value.entries.into_iter().map_into().collect(),
+            next_cursor: value.next_cursor,
         }
     }
 }
@@ -222,6 +812,7 @@ mod unit_test {
     use crate::types;
     use chrono::{DateTime, NaiveTime, Utc};
     use openapi::models;
+    use rstest::rstest;
 
     use crate::types::BusinessTimes;
 
@@ -246,7 +837,7 @@ mod unit_test {
         let expected = true;
 
         // when
-        let actual = business_times.is_outside_of_business_times(given_date_time);
+        let actual = !business_times.contains_on_first_day(given_date_time.time());
 
         // then
         assert_eq!(expected, actual);
@@ -260,7 +851,7 @@ mod unit_test {
         let expected = false;
 
         // when
-        let actual = business_times.is_outside_of_business_times(given_date_time);
+        let actual = !business_times.contains_on_first_day(given_date_time.time());
 
         // then
         assert_eq!(expected, actual);
@@ -274,7 +865,7 @@ mod unit_test {
         let expected = false;
 
         // when
-        let actual = business_times.is_outside_of_business_times(given_date_time);
+        let actual = !business_times.contains_on_first_day(given_date_time.time());
 
         // then
         assert_eq!(expected, actual);
@@ -288,7 +879,7 @@ mod unit_test {
         let expected = false;
 
         // when
-        let actual = business_times.is_outside_of_business_times(given_date_time);
+        let actual = !business_times.contains_on_first_day(given_date_time.time());
 
         // then
         assert_eq!(expected, actual);
@@ -302,12 +893,41 @@ mod unit_test {
         let expected = true;
 
         // when
-        let actual = business_times.is_outside_of_business_times(given_date_time);
+        let actual = !business_times.contains_on_first_day(given_date_time.time());
 
         // then
         assert_eq!(expected, actual);
     }
 
+    #[rstest]
+    #[case(&[], "develop", true)]
+    #[case(&["live"], "live", true)]
+    #[case(&["live"], "live-eu", false)]
+    #[case(&["live", "prod"], "prod", true)]
+    #[case(&["prod*"], "prod", true)]
+    #[case(&["prod*"], "production", true)]
+    #[case(&["prod*"], "preprod", false)]
+    #[case(&["*-live"], "eu-live", true)]
+    #[case(&["*-live"], "eu-live-2", false)]
+    #[case(&["eu-*-live"], "eu-central-live", true)]
+    #[case(&["eu-*-live"], "eu-live", false)]
+    #[case(&["*"], "develop", true)]
+    fn should_match_environment_patterns(
+        #[case] patterns: &[&str],
+        #[case] environment: &str,
+        #[case] expected: bool,
+    ) {
+        // given
+        let environment_patterns =
+            types::EnvironmentPatterns(patterns.iter().map(|&it| it.to_owned()).collect());
+
+        // when
+        let actual = environment_patterns.matches(environment);
+
+        // then
+        assert_eq!(actual, expected);
+    }
+
     #[test]
     fn should_convert_domain_gate_to_open_api_gate() {
         let gate = some_gate("some-group", "some-service", "some-environment");
@@ -338,10 +958,74 @@ mod unit_test {
                 .to_utc()
                 .to_rfc3339(),
             display_order: Option::default(),
+            version: 0,
+            open_until: None,
+            open_remaining_seconds: None,
+            closure_reason: None,
         };
         assert_eq!(actual, expected);
     }
 
+    #[rstest]
+    #[case(
+        types::GateState::Open,
+        Some("2023-04-12T11:00:00Z"),
+        types::GateState::Closed
+    )]
+    #[case(
+        types::GateState::Open,
+        Some("2023-04-12T12:00:00Z"),
+        types::GateState::Closed
+    )]
+    #[case(
+        types::GateState::Open,
+        Some("2023-04-12T13:00:00Z"),
+        types::GateState::Open
+    )]
+    #[case(types::GateState::Open, None, types::GateState::Open)]
+    #[case(
+        types::GateState::Closed,
+        Some("2023-04-12T11:00:00Z"),
+        types::GateState::Closed
+    )]
+    fn should_close_gate_if_expired(
+        #[case] state: types::GateState,
+        #[case] open_until: Option<&str>,
+        #[case] expected: types::GateState,
+    ) {
+        // given
+        let gate = types::Gate {
+            state,
+            open_until: open_until.map(instant),
+            ..some_gate("some-group", "some-service", "some-environment")
+        };
+
+        // when
+        let actual = gate.close_if_expired(instant("2023-04-12T12:00:00Z"));
+
+        // then
+        assert_eq!(actual.state, expected);
+    }
+
+    #[test]
+    fn should_convert_gate_with_remaining_open_time() {
+        // given
+        let gate = types::Gate {
+            open_until: Some(instant("2023-04-12T13:00:00Z")),
+            ..some_gate("some-group", "some-service", "some-environment")
+        };
+
+        // when
+        let actual = gate.into_rep(instant("2023-04-12T12:30:00Z"));
+
+        // then
+        assert_eq!(
+            actual.open_until,
+            Some("2023-04-12T13:00:00+00:00".to_owned())
+        );
+        assert_eq!(actual.open_remaining_seconds, Some(1800));
+    }
+
     #[test]
     fn should_convert_comment() {
         let actual: models::Comment = types::Comment {
@@ -364,6 +1048,12 @@ mod unit_test {
         assert_eq!(actual, expected);
     }
 
+    fn instant(value: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(value)
+            .expect("failed to parse date")
+            .to_utc()
+    }
+
     fn some_gate(group: &str, service: &str, environment: &str) -> types::Gate {
         types::Gate {
             key: types::GateKey {
@@ -394,6 +1084,8 @@ mod unit_test {
                     .expect("failed creating date"),
             ),
             display_order: Option::default(),
+            version: 0,
+            open_until: None,
         }
     }
 }
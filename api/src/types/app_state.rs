@@ -1,4 +1,5 @@
-use crate::{clock, date_time_switch, id_provider, storage, types};
+use crate::{clock, date_time_switch, id_provider, storage, trash, types};
+use chrono::TimeDelta;
 use std::sync::Arc;
 type Storage = dyn storage::Storage + Send + Sync;
 type Clock = dyn clock::Clock + Send + Sync;
@@ -12,7 +13,7 @@ pub struct AppState {
     pub(crate) id_provider: Arc<IdProvider>,
     pub(crate) use_cases: types::use_cases::UseCases,
     pub(crate) date_time_switch: Arc<DateTimeSwitch>,
-    pub(crate) business_week: types::BusinessWeek,
+    pub(crate) trash_retention: TimeDelta,
 }
 impl AppState {
     pub(crate) fn new(
@@ -27,7 +28,7 @@ impl AppState {
             id_provider,
             use_cases: types::use_cases::UseCases::new(),
             date_time_switch,
-            business_week: types::BusinessWeek::default(),
+            trash_retention: trash::retention(),
         }
     }
 }
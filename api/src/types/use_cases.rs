@@ -1,36 +1,105 @@
 use crate::use_cases::{
-    add_comment, create_gate, delete_comment, delete_gate, get_config, get_gate, get_gate_state,
-    list_gates, update_display_order, update_gate_state,
+    add_comment, apply_scheduled_state_changes, cancel_scheduled_state_change, clear_freeze,
+    close_expired_gates, close_gates_after_business_hours, create_freeze_window, create_gate,
+    create_gates, delete_business_week_profile, delete_closed_day, delete_comment,
+    delete_freeze_window, delete_gate, get_config, get_gate, get_gate_dependencies,
+    get_gate_history, get_gate_state, import_closed_days, list_business_week_profiles,
+    list_closed_days, list_freeze_windows, list_gates, list_gates_by_group, list_gates_by_service,
+    list_scheduled_state_changes, purge_trash, restore_gate, schedule_state_change,
+    set_business_week, set_business_week_profile, set_closed_day, set_freeze,
+    set_gate_dependencies, update_display_order, update_gate_state, update_gate_states,
 };
 use std::sync::Arc;
 
 #[derive(Clone)]
 pub struct UseCases {
     pub(crate) list_gates: Arc<list_gates::DynType>,
+    pub(crate) list_gates_by_group: Arc<list_gates_by_group::DynType>,
+    pub(crate) list_gates_by_service: Arc<list_gates_by_service::DynType>,
     pub(crate) create_gate: Arc<create_gate::DynType>,
+    pub(crate) create_gates: Arc<create_gates::DynType>,
     pub(crate) delete_gates: Arc<delete_gate::DynType>,
+    pub(crate) restore_gate: Arc<restore_gate::DynType>,
     pub(crate) get_gate: Arc<get_gate::DynType>,
+    pub(crate) get_gate_history: Arc<get_gate_history::DynType>,
     pub(crate) get_config: Arc<get_config::DynType>,
+    pub(crate) set_business_week: Arc<set_business_week::DynType>,
+    pub(crate) list_business_week_profiles: Arc<list_business_week_profiles::DynType>,
+    pub(crate) set_business_week_profile: Arc<set_business_week_profile::DynType>,
+    pub(crate) delete_business_week_profile: Arc<delete_business_week_profile::DynType>,
+    pub(crate) list_closed_days: Arc<list_closed_days::DynType>,
+    pub(crate) set_closed_day: Arc<set_closed_day::DynType>,
+    pub(crate) delete_closed_day: Arc<delete_closed_day::DynType>,
+    pub(crate) import_closed_days: Arc<import_closed_days::DynType>,
+    pub(crate) set_freeze: Arc<set_freeze::DynType>,
+    pub(crate) clear_freeze: Arc<clear_freeze::DynType>,
+    pub(crate) list_freeze_windows: Arc<list_freeze_windows::DynType>,
+    pub(crate) create_freeze_window: Arc<create_freeze_window::DynType>,
+    pub(crate) delete_freeze_window: Arc<delete_freeze_window::DynType>,
     pub(crate) get_gate_state: Arc<get_gate_state::DynType>,
     pub(crate) update_gate_state: Arc<update_gate_state::DynType>,
+    pub(crate) update_gate_states: Arc<update_gate_states::DynType>,
     pub(crate) add_comment: Arc<add_comment::DynType>,
     pub(crate) delete_comment: Arc<delete_comment::DynType>,
     pub(crate) update_display_order: Arc<update_display_order::DynType>,
+    pub(crate) get_gate_dependencies: Arc<get_gate_dependencies::DynType>,
+    pub(crate) set_gate_dependencies: Arc<set_gate_dependencies::DynType>,
+    pub(crate) list_scheduled_state_changes: Arc<list_scheduled_state_changes::DynType>,
+    pub(crate) schedule_state_change: Arc<schedule_state_change::DynType>,
+    pub(crate) cancel_scheduled_state_change: Arc<cancel_scheduled_state_change::DynType>,
+    pub(crate) apply_scheduled_state_changes: Arc<apply_scheduled_state_changes::DynType>,
+    pub(crate) close_expired_gates: Arc<close_expired_gates::DynType>,
+    pub(crate) close_gates_after_business_hours: Arc<close_gates_after_business_hours::DynType>,
+    pub(crate) purge_trash: Arc<purge_trash::DynType>,
 }
 
 impl UseCases {
     pub(crate) fn new() -> Self {
         Self {
             list_gates: Arc::new(list_gates::use_case::create()),
+            list_gates_by_group: Arc::new(list_gates_by_group::use_case::create()),
+            list_gates_by_service: Arc::new(list_gates_by_service::use_case::create()),
             create_gate: Arc::new(create_gate::use_case::create()),
+            create_gates: Arc::new(create_gates::use_case::create()),
             delete_gates: Arc::new(delete_gate::use_case::create()),
+            restore_gate: Arc::new(restore_gate::use_case::create()),
             get_gate: Arc::new(get_gate::use_case::create()),
+            get_gate_history: Arc::new(get_gate_history::use_case::create()),
             get_config: Arc::new(get_config::use_case::create()),
+            set_business_week: Arc::new(set_business_week::use_case::create()),
+            list_business_week_profiles: Arc::new(list_business_week_profiles::use_case::create()),
+            set_business_week_profile: Arc::new(set_business_week_profile::use_case::create()),
+            delete_business_week_profile: Arc::new(delete_business_week_profile::use_case::create()),
+            list_closed_days: Arc::new(list_closed_days::use_case::create()),
+            set_closed_day: Arc::new(set_closed_day::use_case::create()),
+            delete_closed_day: Arc::new(delete_closed_day::use_case::create()),
+            import_closed_days: Arc::new(import_closed_days::use_case::create()),
+            set_freeze: Arc::new(set_freeze::use_case::create()),
+            clear_freeze: Arc::new(clear_freeze::use_case::create()),
+            list_freeze_windows: Arc::new(list_freeze_windows::use_case::create()),
+            create_freeze_window: Arc::new(create_freeze_window::use_case::create()),
+            delete_freeze_window: Arc::new(delete_freeze_window::use_case::create()),
             get_gate_state: Arc::new(get_gate_state::use_case::create()),
             update_gate_state: Arc::new(update_gate_state::use_case::create()),
+            update_gate_states: Arc::new(update_gate_states::use_case::create()),
             add_comment: Arc::new(add_comment::use_case::create()),
             delete_comment: Arc::new(delete_comment::use_case::create()),
             update_display_order: Arc::new(update_display_order::use_case::create()),
+            get_gate_dependencies: Arc::new(get_gate_dependencies::use_case::create()),
+            set_gate_dependencies: Arc::new(set_gate_dependencies::use_case::create()),
+            list_scheduled_state_changes: Arc::new(list_scheduled_state_changes::use_case::create()),
+            schedule_state_change: Arc::new(schedule_state_change::use_case::create()),
+            cancel_scheduled_state_change: Arc::new(
+                cancel_scheduled_state_change::use_case::create(),
+            ),
+            apply_scheduled_state_changes: Arc::new(
+                apply_scheduled_state_changes::use_case::create(),
+            ),
+            close_expired_gates: Arc::new(close_expired_gates::use_case::create()),
+            close_gates_after_business_hours: Arc::new(
+                close_gates_after_business_hours::use_case::create(),
+            ),
+            purge_trash: Arc::new(purge_trash::use_case::create()),
         }
     }
 }
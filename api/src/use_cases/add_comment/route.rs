@@ -1,7 +1,10 @@
+use crate::actor;
+use crate::etag;
 use crate::types::app_state::AppState;
 use crate::use_cases::add_comment::use_case::{Error, Input};
 use axum::extract::{Path, State};
-use axum::http::StatusCode;
+use axum::http::header::ETAG;
+use axum::http::{HeaderMap, StatusCode};
 use axum::response::IntoResponse;
 use axum::Json;
 use serde::{Deserialize, Serialize};
@@ -16,6 +19,7 @@ pub struct PathParams {
 #[derive(Serialize, Deserialize)]
 pub struct Payload {
     pub message: String,
+    pub expected_version: Option<u32>,
 }
 
 pub async fn handler(
@@ -25,8 +29,16 @@ pub async fn handler(
         environment,
     }): Path<PathParams>,
     State(app_state): State<AppState>,
-    Json(Payload { message }): Json<Payload>,
+    headers: HeaderMap,
+    Json(Payload {
+        message,
+        expected_version,
+    }): Json<Payload>,
 ) -> impl IntoResponse {
+    let expected_version = match etag::expected_version(&headers, expected_version) {
+        Ok(expected_version) => expected_version,
+        Err(error) => return (StatusCode::BAD_REQUEST, Json(error)).into_response(),
+    };
     match app_state
         .use_cases
         .add_comment
@@ -36,6 +48,8 @@ pub async fn handler(
                 service,
                 environment,
                 message,
+                expected_version,
+                actor: actor::from_headers(&headers),
             },
             app_state.storage.as_ref(),
             app_state.clock.as_ref(),
@@ -43,9 +57,12 @@ pub async fn handler(
         )
         .await
     {
-        Ok(gate) => Json(gate).into_response(),
+        Ok(gate) => ([(ETAG, etag::from_version(gate.version))], Json(gate)).into_response(),
         Err(error) => match error {
             Error::GateNotFound => StatusCode::NO_CONTENT.into_response(),
+            Error::VersionConflict(error) => {
+                (StatusCode::PRECONDITION_FAILED, Json(error)).into_response()
+            }
             Error::InvalidInputMessage(error) => {
                 (StatusCode::BAD_REQUEST, Json(error)).into_response()
             }
@@ -1,7 +1,8 @@
 use crate::clock::Clock;
+use crate::history;
 use crate::id_provider::IdProvider;
 use crate::storage::{Storage, UpdateError};
-use crate::types::{Comment, GateKey};
+use crate::types::{Comment, GateEvent, GateKey, HistoryEntry};
 use async_trait::async_trait;
 use openapi::models;
 
@@ -11,12 +12,15 @@ pub struct Input {
     pub service: String,
     pub environment: String,
     pub message: String,
+    pub expected_version: Option<u32>,
+    pub actor: Option<String>,
 }
 
 #[derive(Debug, PartialEq, Eq)]
 pub enum Error {
     GateNotFound,
     InvalidInputMessage(String),
+    VersionConflict(String),
     Internal(String),
 }
 
@@ -24,6 +28,7 @@ impl From<UpdateError> for Error {
     fn from(value: UpdateError) -> Self {
         match value {
             UpdateError::ItemToUpdateNotFound(_) => Self::GateNotFound,
+            UpdateError::VersionConflict(error) => Self::VersionConflict(error),
             UpdateError::Other(error) => Self::Internal(error),
         }
     }
@@ -56,33 +61,59 @@ impl UseCase for UseCaseImpl {
             service,
             environment,
             message,
+            expected_version,
+            actor,
         }: Input,
         storage: &(dyn Storage + Send + Sync),
         clock: &(dyn Clock + Send + Sync),
         id_provider: &(dyn IdProvider + Send + Sync),
     ) -> Result<models::Gate, Error> {
         let now = clock.now();
-        match message.as_str().trim() {
-            "" => Err(Error::InvalidInputMessage(
+        if message.trim().is_empty() {
+            return Err(Error::InvalidInputMessage(
                 "cannot add comment without message".to_owned(),
-            )),
-            _ => Ok(storage
-                .update_comment_and_last_updated(
-                    GateKey {
-                        group,
-                        service,
-                        environment,
-                    },
-                    Comment {
-                        id: id_provider.get(),
-                        message: message.trim().to_owned(),
-                        created: now,
-                    },
-                    now,
-                )
-                .await?
-                .into()),
+            ));
         }
+
+        let comment_id = id_provider.get();
+        let gate = storage
+            .update_comment_and_last_updated(
+                GateKey {
+                    group,
+                    service,
+                    environment,
+                },
+                Comment {
+                    id: comment_id.clone(),
+                    message: message.trim().to_owned(),
+                    created: now,
+                },
+                now,
+                expected_version,
+            )
+            .await?;
+
+        history::record(
+            storage,
+            &HistoryEntry {
+                id: id_provider.get(),
+                key: gate.key.clone(),
+                timestamp: now,
+                event: GateEvent::CommentAdded,
+                old_value: None,
+                // the stored message, it is replaced in demo mode
+                new_value: gate
+                    .comments
+                    .iter()
+                    .find(|comment| comment.id == comment_id)
+                    .map(|comment| comment.message.clone()),
+                open_until: None,
+                actor,
+            },
+        )
+        .await;
+
+        Ok(gate.into())
     }
 }
 
@@ -92,6 +123,7 @@ mod unit_tests {
 
     use chrono::DateTime;
     use itertools::concat;
+    use mockall::predicate::eq;
     use similar_asserts::assert_eq;
 
     use crate::clock::MockClock;
@@ -119,15 +151,30 @@ mod unit_tests {
 
         mock_storage
             .expect_update_comment_and_last_updated()
-            .return_once(move |key, comment, last_updated| {
+            .return_once(move |key, comment, last_updated, _| {
                 Ok(Gate {
                     key,
                     state: gate.state.clone(),
                     comments: concat(vec![gate.comments, HashSet::from([comment])]),
                     last_updated,
                     display_order: Option::default(),
+                    version: 0,
+                    open_until: None,
                 })
             });
+        mock_storage
+            .expect_insert_history_entry()
+            .with(eq(HistoryEntry {
+                id: "id".to_owned(),
+                key: gate.key.clone(),
+                timestamp: now,
+                event: GateEvent::CommentAdded,
+                old_value: None,
+                new_value: Some("some new comment".to_owned()),
+                open_until: None,
+                actor: Some("Max".to_owned()),
+            }))
+            .return_once(|_| Ok(()));
 
         // when
         let gate_with_comment = UseCaseImpl {}
@@ -137,6 +184,8 @@ mod unit_tests {
                     service: "some service".to_owned(),
                     environment: "some environment".to_owned(),
                     message: "    some new comment".to_owned(),
+                    expected_version: None,
+                    actor: Some("Max".to_owned()),
                 },
                 &mock_storage,
                 &mock_clock,
@@ -178,6 +227,10 @@ mod unit_tests {
                 ],
                 last_updated: now.to_rfc3339(),
                 display_order: Option::default(),
+                version: 0,
+                open_until: None,
+                open_remaining_seconds: None,
+                closure_reason: None,
             }
         );
     }
@@ -199,13 +252,15 @@ mod unit_tests {
 
         mock_storage
             .expect_update_comment_and_last_updated()
-            .return_once(move |key, comment, last_updated| {
+            .return_once(move |key, comment, last_updated, _| {
                 Ok(Gate {
                     key,
                     state: gate.state.clone(),
                     comments: concat(vec![gate.comments, HashSet::from([comment])]),
                     last_updated,
                     display_order: Option::default(),
+                    version: 0,
+                    open_until: None,
                 })
             });
 
@@ -217,6 +272,8 @@ mod unit_tests {
                     service: "some service".to_owned(),
                     environment: "some environment".to_owned(),
                     message: String::default(),
+                    expected_version: None,
+                    actor: None,
                 },
                 &mock_storage,
                 &mock_clock,
@@ -249,13 +306,15 @@ mod unit_tests {
 
         mock_storage
             .expect_update_comment_and_last_updated()
-            .return_once(move |key, comment, last_updated| {
+            .return_once(move |key, comment, last_updated, _| {
                 Ok(Gate {
                     key,
                     state: gate.state.clone(),
                     comments: concat(vec![gate.comments, HashSet::from([comment])]),
                     last_updated,
                     display_order: Option::default(),
+                    version: 0,
+                    open_until: None,
                 })
             });
 
@@ -267,6 +326,8 @@ mod unit_tests {
                     service: "some service".to_owned(),
                     environment: "some environment".to_owned(),
                     message: " ".to_owned(),
+                    expected_version: None,
+                    actor: None,
                 },
                 &mock_storage,
                 &mock_clock,
@@ -296,7 +357,7 @@ mod unit_tests {
 
         mock_storage
             .expect_update_comment_and_last_updated()
-            .returning(move |_, _, _| {
+            .returning(move |_, _, _, _| {
                 Err(UpdateError::ItemToUpdateNotFound(
                     "ConditionalCheckFailedException".to_owned(),
                 ))
@@ -309,6 +370,8 @@ mod unit_tests {
                     service: "some service".to_owned(),
                     environment: "some environment".to_owned(),
                     message: "some message".to_owned(),
+                    expected_version: None,
+                    actor: None,
                 },
                 &mock_storage,
                 &mock_clock,
@@ -334,7 +397,7 @@ mod unit_tests {
 
         mock_storage
             .expect_update_comment_and_last_updated()
-            .returning(move |_, _, _| Err(UpdateError::Other("some error".to_owned())));
+            .returning(move |_, _, _, _| Err(UpdateError::Other("some error".to_owned())));
 
         let left = UseCaseImpl {}
             .execute(
@@ -343,6 +406,8 @@ mod unit_tests {
                     service: "some service".to_owned(),
                     environment: "some environment".to_owned(),
                     message: "some message".to_owned(),
+                    expected_version: None,
+                    actor: None,
                 },
                 &mock_storage,
                 &mock_clock,
@@ -385,6 +450,8 @@ mod unit_tests {
                 .expect("failed creating date")
                 .into(),
             display_order: Option::default(),
+            version: 0,
+            open_until: None,
         }
     }
 }
@@ -0,0 +1,597 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+use crate::clock::Clock;
+use crate::date_time_switch::DateTimeSwitch;
+use crate::history;
+use crate::id_provider::IdProvider;
+use crate::storage;
+use crate::storage::Storage;
+use crate::types::{Comment, GateEvent, GateState, HistoryEntry, ScheduledStateChange};
+
+// a scheduled change is retried if the gate was changed in the meantime
+const MAX_ATTEMPTS: u32 = 3;
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Error {
+    Internal(String),
+}
+
+impl From<storage::FindError> for Error {
+    fn from(value: storage::FindError) -> Self {
+        match value {
+            storage::FindError::ItemCouldNotBeDecoded(error)
+            | storage::FindError::InvalidCursor(error)
+            | storage::FindError::Other(error) => Self::Internal(error),
+        }
+    }
+}
+
+impl From<storage::UpdateError> for Error {
+    fn from(value: storage::UpdateError) -> Self {
+        match value {
+            storage::UpdateError::ItemToUpdateNotFound(error)
+            | storage::UpdateError::VersionConflict(error)
+            | storage::UpdateError::Other(error) => Self::Internal(error),
+        }
+    }
+}
+
+#[async_trait]
+pub trait UseCase {
+    /// Applies all scheduled state changes that are due and returns how many were applied. A change
+    /// that could not be applied is kept, so it is tried again on the next run.
+    async fn execute(
+        &self,
+        storage: &(dyn Storage + Send + Sync),
+        clock: &(dyn Clock + Send + Sync),
+        date_time_switch: &(dyn DateTimeSwitch + Send + Sync),
+        id_provider: &(dyn IdProvider + Send + Sync),
+    ) -> Result<usize, Error>;
+}
+
+pub fn create() -> impl UseCase {
+    UseCaseImpl {}
+}
+
+#[derive(Clone)]
+struct UseCaseImpl {}
+
+#[async_trait]
+impl UseCase for UseCaseImpl {
+    async fn execute(
+        &self,
+        storage: &(dyn Storage + Send + Sync),
+        clock: &(dyn Clock + Send + Sync),
+        date_time_switch: &(dyn DateTimeSwitch + Send + Sync),
+        id_provider: &(dyn IdProvider + Send + Sync),
+    ) -> Result<usize, Error> {
+        let now = clock.now();
+        let mut applied = 0;
+        for change in storage
+            .find_scheduled_state_changes()
+            .await?
+            .into_iter()
+            .filter(|change| change.is_due(now))
+        {
+            match apply(&change, now, storage, date_time_switch, id_provider).await {
+                Ok(true) => applied += 1,
+                Ok(false) => {}
+                Err(error) => {
+                    tracing::error!(
+                        "could not apply scheduled state change {} of gate {}: {error:?}",
+                        change.id,
+                        change.key
+                    );
+                    continue;
+                }
+            }
+            if let Err(error) = storage
+                .delete_scheduled_state_change(change.id.clone())
+                .await
+            {
+                tracing::error!(
+                    "could not delete applied scheduled state change {} of gate {}: {error:?}",
+                    change.id,
+                    change.key
+                );
+            }
+        }
+        Ok(applied)
+    }
+}
+
+/// Changes the state like a person would, so a gate is not opened after business hours, and
+/// comments on the change. A gate that already has the scheduled state is left untouched.
+async fn apply(
+    change: &ScheduledStateChange,
+    now: DateTime<Utc>,
+    storage: &(dyn Storage + Send + Sync),
+    date_time_switch: &(dyn DateTimeSwitch + Send + Sync),
+    id_provider: &(dyn IdProvider + Send + Sync),
+) -> Result<bool, Error> {
+    let state = String::try_from(change.state.clone()).map_err(Error::Internal)?;
+    let mut attempts = 1;
+    loop {
+        let Some(previous) = storage.find_one(change.key.clone()).await? else {
+            tracing::warn!(
+                "dropping scheduled state change {} of deleted gate {}",
+                change.id,
+                change.key
+            );
+            return Ok(false);
+        };
+        if previous.state == change.state {
+            tracing::info!(
+                "skipping scheduled state change {} of gate {}, it is already {:?}",
+                change.id,
+                change.key,
+                change.state
+            );
+            return Ok(false);
+        }
+        if change.state == GateState::Open && date_time_switch.is_closed(now, &change.key).await {
+            add_comment(
+                change,
+                format!(
+                    "Scheduled change to {state} was not applied, already after business hours"
+                ),
+                now,
+                storage,
+                id_provider,
+            )
+            .await?;
+            return Ok(false);
+        }
+
+        match storage
+            .update_state_and_last_updated(
+                change.key.clone(),
+                change.state.clone(),
+                None,
+                now,
+                Some(previous.version),
+            )
+            .await
+        {
+            Ok(_) => {
+                let previous_state = String::try_from(previous.state).map_err(Error::Internal)?;
+                record_applied(change, previous_state, state, now, storage, id_provider).await?;
+                return Ok(true);
+            }
+            Err(storage::UpdateError::VersionConflict(_)) if attempts < MAX_ATTEMPTS => {
+                attempts += 1;
+            }
+            Err(storage::UpdateError::ItemToUpdateNotFound(_)) => {
+                tracing::warn!(
+                    "dropping scheduled state change {} of deleted gate {}",
+                    change.id,
+                    change.key
+                );
+                return Ok(false);
+            }
+            Err(error) => return Err(error.into()),
+        }
+    }
+}
+
+async fn record_applied(
+    change: &ScheduledStateChange,
+    previous_state: String,
+    state: String,
+    now: DateTime<Utc>,
+    storage: &(dyn Storage + Send + Sync),
+    id_provider: &(dyn IdProvider + Send + Sync),
+) -> Result<(), Error> {
+    history::record(
+        storage,
+        &HistoryEntry {
+            id: id_provider.get(),
+            key: change.key.clone(),
+            timestamp: now,
+            event: GateEvent::StateChanged,
+            old_value: Some(previous_state),
+            new_value: Some(state.clone()),
+            open_until: None,
+            actor: change.actor.clone(),
+        },
+    )
+    .await;
+    let scheduled_by = change
+        .actor
+        .as_ref()
+        .map(|actor| format!(" by {actor}"))
+        .unwrap_or_default();
+    add_comment(
+        change,
+        format!("Changed to {state} as scheduled{scheduled_by}"),
+        now,
+        storage,
+        id_provider,
+    )
+    .await
+}
+
+async fn add_comment(
+    change: &ScheduledStateChange,
+    message: String,
+    now: DateTime<Utc>,
+    storage: &(dyn Storage + Send + Sync),
+    id_provider: &(dyn IdProvider + Send + Sync),
+) -> Result<(), Error> {
+    storage
+        .update_comment_and_last_updated(
+            change.key.clone(),
+            Comment {
+                id: id_provider.get(),
+                message: message.clone(),
+                created: now,
+            },
+            now,
+            None,
+        )
+        .await?;
+    history::record(
+        storage,
+        &HistoryEntry {
+            id: id_provider.get(),
+            key: change.key.clone(),
+            timestamp: now,
+            event: GateEvent::CommentAdded,
+            old_value: None,
+            new_value: Some(message),
+            open_until: None,
+            actor: change.actor.clone(),
+        },
+    )
+    .await;
+    Ok(())
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use std::collections::HashSet;
+
+    use mockall::predicate::eq;
+    use rstest::rstest;
+    use similar_asserts::assert_eq;
+
+    use crate::clock::MockClock;
+    use crate::date_time_switch::MockDateTimeSwitch;
+    use crate::id_provider::MockIdProvider;
+    use crate::storage::{FindError, MockStorage, UpdateError};
+    use crate::types::{Gate, GateKey};
+
+    use super::*;
+
+    fn instant(value: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(value)
+            .expect("failed to parse date")
+            .to_utc()
+    }
+
+    fn some_key() -> GateKey {
+        GateKey {
+            group: "some group".to_owned(),
+            service: "some service".to_owned(),
+            environment: "live".to_owned(),
+        }
+    }
+
+    fn some_gate(state: GateState) -> Gate {
+        Gate {
+            key: some_key(),
+            state,
+            comments: HashSet::default(),
+            last_updated: instant("2023-06-05T06:00:00Z"),
+            display_order: None,
+            version: 0,
+            open_until: None,
+        }
+    }
+
+    fn some_change(id: &str, state: GateState, at: &str) -> ScheduledStateChange {
+        ScheduledStateChange {
+            id: id.to_owned(),
+            key: some_key(),
+            state,
+            at: instant(at),
+            actor: Some("Max".to_owned()),
+        }
+    }
+
+    fn given_storage(changes: Vec<ScheduledStateChange>, gate: Option<Gate>) -> MockStorage {
+        let mut mock_storage = MockStorage::new();
+        mock_storage
+            .expect_find_scheduled_state_changes()
+            .return_once(|| Ok(changes));
+        mock_storage
+            .expect_find_one()
+            .with(eq(some_key()))
+            .return_once(|_| Ok(gate));
+        mock_storage
+            .expect_delete_scheduled_state_change()
+            .with(eq("due".to_owned()))
+            .times(1)
+            .return_once(|_| Ok(()));
+        mock_storage
+    }
+
+    fn given_clock() -> MockClock {
+        let mut mock_clock = MockClock::new();
+        mock_clock
+            .expect_now()
+            .return_const(instant("2023-06-05T13:00:00Z"));
+        mock_clock
+    }
+
+    fn given_id_provider() -> MockIdProvider {
+        let mut mock_id_provider = MockIdProvider::new();
+        mock_id_provider.expect_get().return_const("some id");
+        mock_id_provider
+    }
+
+    #[tokio::test]
+    async fn should_apply_due_scheduled_state_changes() {
+        // given
+        let now = instant("2023-06-05T13:00:00Z");
+        let mut mock_storage = given_storage(
+            vec![
+                some_change("due", GateState::Open, "2023-06-05T12:00:00Z"),
+                some_change("upcoming", GateState::Closed, "2023-06-05T15:00:00Z"),
+            ],
+            Some(some_gate(GateState::Closed)),
+        );
+        mock_storage
+            .expect_update_state_and_last_updated()
+            .with(
+                eq(some_key()),
+                eq(GateState::Open),
+                eq(None),
+                eq(now),
+                eq(Some(0)),
+            )
+            .times(1)
+            .return_once(|_, _, _, _, _| Ok(some_gate(GateState::Open)));
+        mock_storage
+            .expect_update_comment_and_last_updated()
+            .withf(|key, comment, _, _| {
+                *key == some_key() && comment.message == "Changed to open as scheduled by Max"
+            })
+            .times(1)
+            .return_once(|_, _, _, _| Ok(some_gate(GateState::Open)));
+        mock_storage
+            .expect_insert_history_entry()
+            .times(2)
+            .returning(|_| Ok(()));
+        let mut mock_date_time_switch = MockDateTimeSwitch::new();
+        mock_date_time_switch.expect_is_closed().return_const(false);
+
+        // when
+        let result = UseCaseImpl {}
+            .execute(
+                &mock_storage,
+                &given_clock(),
+                &mock_date_time_switch,
+                &given_id_provider(),
+            )
+            .await;
+
+        // then
+        assert_eq!(result, Ok(1));
+    }
+
+    #[tokio::test]
+    async fn should_not_open_gate_after_business_hours() {
+        // given
+        let mut mock_storage = given_storage(
+            vec![some_change("due", GateState::Open, "2023-06-05T12:00:00Z")],
+            Some(some_gate(GateState::Closed)),
+        );
+        mock_storage.expect_update_state_and_last_updated().never();
+        mock_storage
+            .expect_update_comment_and_last_updated()
+            .withf(|_, comment, _, _| comment.message.contains("not applied"))
+            .times(1)
+            .return_once(|_, _, _, _| Ok(some_gate(GateState::Closed)));
+        mock_storage
+            .expect_insert_history_entry()
+            .times(1)
+            .returning(|_| Ok(()));
+        let mut mock_date_time_switch = MockDateTimeSwitch::new();
+        mock_date_time_switch.expect_is_closed().return_const(true);
+
+        // when
+        let result = UseCaseImpl {}
+            .execute(
+                &mock_storage,
+                &given_clock(),
+                &mock_date_time_switch,
+                &given_id_provider(),
+            )
+            .await;
+
+        // then
+        assert_eq!(result, Ok(0));
+    }
+
+    #[rstest]
+    #[case(GateState::Open)]
+    #[case(GateState::Closed)]
+    #[tokio::test]
+    async fn should_skip_scheduled_state_change_to_current_state(#[case] state: GateState) {
+        // given
+        let mut mock_storage = given_storage(
+            vec![some_change("due", state.clone(), "2023-06-05T12:00:00Z")],
+            Some(some_gate(state)),
+        );
+        mock_storage.expect_update_state_and_last_updated().never();
+        mock_storage
+            .expect_update_comment_and_last_updated()
+            .never();
+        mock_storage.expect_insert_history_entry().never();
+        let mut mock_date_time_switch = MockDateTimeSwitch::new();
+        mock_date_time_switch.expect_is_closed().return_const(false);
+
+        // when
+        let result = UseCaseImpl {}
+            .execute(
+                &mock_storage,
+                &given_clock(),
+                &mock_date_time_switch,
+                &given_id_provider(),
+            )
+            .await;
+
+        // then
+        assert_eq!(result, Ok(0));
+    }
+
+    #[tokio::test]
+    async fn should_drop_scheduled_state_change_of_deleted_gate() {
+        // given
+        let mut mock_storage = given_storage(
+            vec![some_change(
+                "due",
+                GateState::Closed,
+                "2023-06-05T12:00:00Z",
+            )],
+            None,
+        );
+        mock_storage.expect_update_state_and_last_updated().never();
+
+        // when
+        let result = UseCaseImpl {}
+            .execute(
+                &mock_storage,
+                &given_clock(),
+                &MockDateTimeSwitch::new(),
+                &given_id_provider(),
+            )
+            .await;
+
+        // then
+        assert_eq!(result, Ok(0));
+    }
+
+    #[tokio::test]
+    async fn should_retry_scheduled_state_change_if_gate_was_changed_in_the_meantime() {
+        // given
+        let mut mock_storage = MockStorage::new();
+        mock_storage
+            .expect_find_scheduled_state_changes()
+            .return_once(|| {
+                Ok(vec![some_change(
+                    "due",
+                    GateState::Open,
+                    "2023-06-05T12:00:00Z",
+                )])
+            });
+        mock_storage
+            .expect_find_one()
+            .times(1)
+            .return_once(|_| Ok(Some(some_gate(GateState::Closed))));
+        mock_storage.expect_find_one().times(1).return_once(|_| {
+            Ok(Some(Gate {
+                version: 1,
+                ..some_gate(GateState::Closed)
+            }))
+        });
+        mock_storage
+            .expect_update_state_and_last_updated()
+            .withf(|_, _, _, _, expected_version| *expected_version == Some(0))
+            .times(1)
+            .return_once(|_, _, _, _, _| {
+                Err(UpdateError::VersionConflict(
+                    "some version conflict".to_owned(),
+                ))
+            });
+        mock_storage
+            .expect_update_state_and_last_updated()
+            .withf(|_, _, _, _, expected_version| *expected_version == Some(1))
+            .times(1)
+            .return_once(|_, _, _, _, _| Ok(some_gate(GateState::Open)));
+        mock_storage
+            .expect_update_comment_and_last_updated()
+            .times(1)
+            .return_once(|_, _, _, _| Ok(some_gate(GateState::Open)));
+        mock_storage
+            .expect_insert_history_entry()
+            .times(2)
+            .returning(|_| Ok(()));
+        mock_storage
+            .expect_delete_scheduled_state_change()
+            .with(eq("due".to_owned()))
+            .times(1)
+            .return_once(|_| Ok(()));
+        let mut mock_date_time_switch = MockDateTimeSwitch::new();
+        mock_date_time_switch.expect_is_closed().return_const(false);
+
+        // when
+        let result = UseCaseImpl {}
+            .execute(
+                &mock_storage,
+                &given_clock(),
+                &mock_date_time_switch,
+                &given_id_provider(),
+            )
+            .await;
+
+        // then
+        assert_eq!(result, Ok(1));
+    }
+
+    #[tokio::test]
+    async fn should_keep_scheduled_state_change_that_could_not_be_applied_and_apply_the_others() {
+        // given
+        let mut mock_storage = MockStorage::new();
+        mock_storage
+            .expect_find_scheduled_state_changes()
+            .return_once(|| {
+                Ok(vec![
+                    some_change("failing", GateState::Open, "2023-06-05T11:00:00Z"),
+                    some_change("due", GateState::Open, "2023-06-05T12:00:00Z"),
+                ])
+            });
+        mock_storage
+            .expect_find_one()
+            .times(1)
+            .return_once(|_| Err(FindError::Other("some error".to_owned())));
+        mock_storage
+            .expect_find_one()
+            .times(1)
+            .return_once(|_| Ok(Some(some_gate(GateState::Closed))));
+        mock_storage
+            .expect_update_state_and_last_updated()
+            .times(1)
+            .return_once(|_, _, _, _, _| Ok(some_gate(GateState::Open)));
+        mock_storage
+            .expect_update_comment_and_last_updated()
+            .times(1)
+            .return_once(|_, _, _, _| Ok(some_gate(GateState::Open)));
+        mock_storage
+            .expect_insert_history_entry()
+            .times(2)
+            .returning(|_| Ok(()));
+        mock_storage
+            .expect_delete_scheduled_state_change()
+            .with(eq("due".to_owned()))
+            .times(1)
+            .return_once(|_| Ok(()));
+        let mut mock_date_time_switch = MockDateTimeSwitch::new();
+        mock_date_time_switch.expect_is_closed().return_const(false);
+
+        // when
+        let result = UseCaseImpl {}
+            .execute(
+                &mock_storage,
+                &given_clock(),
+                &mock_date_time_switch,
+                &given_id_provider(),
+            )
+            .await;
+
+        // then
+        assert_eq!(result, Ok(1));
+    }
+}
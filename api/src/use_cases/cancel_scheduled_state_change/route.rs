@@ -0,0 +1,50 @@
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::Json;
+use serde::{Deserialize, Serialize};
+
+use crate::types::app_state::AppState;
+use crate::use_cases::cancel_scheduled_state_change::use_case;
+use crate::use_cases::cancel_scheduled_state_change::use_case::Error;
+
+#[derive(Serialize, Deserialize)]
+pub struct PathParams {
+    group: String,
+    service: String,
+    environment: String,
+    scheduled_state_change_id: String,
+}
+
+pub async fn handler(
+    Path(PathParams {
+        group,
+        service,
+        environment,
+        scheduled_state_change_id,
+    }): Path<PathParams>,
+    State(app_state): State<AppState>,
+) -> impl IntoResponse {
+    match app_state
+        .use_cases
+        .cancel_scheduled_state_change
+        .execute(
+            use_case::Input {
+                group,
+                service,
+                environment,
+                id: scheduled_state_change_id,
+            },
+            app_state.storage.as_ref(),
+        )
+        .await
+    {
+        Ok(()) => StatusCode::OK.into_response(),
+        Err(error) => match error {
+            Error::ScheduledStateChangeNotFound => StatusCode::NO_CONTENT.into_response(),
+            Error::Internal(error) => {
+                (StatusCode::INTERNAL_SERVER_ERROR, Json(error)).into_response()
+            }
+        },
+    }
+}
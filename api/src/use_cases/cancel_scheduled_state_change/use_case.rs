@@ -0,0 +1,158 @@
+use async_trait::async_trait;
+
+use crate::storage;
+use crate::storage::Storage;
+use crate::types::GateKey;
+
+#[derive(Debug)]
+pub struct Input {
+    pub group: String,
+    pub service: String,
+    pub environment: String,
+    pub id: String,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Error {
+    ScheduledStateChangeNotFound,
+    Internal(String),
+}
+
+impl From<storage::FindError> for Error {
+    fn from(value: storage::FindError) -> Self {
+        match value {
+            storage::FindError::ItemCouldNotBeDecoded(error)
+            | storage::FindError::InvalidCursor(error)
+            | storage::FindError::Other(error) => Self::Internal(error),
+        }
+    }
+}
+
+impl From<storage::DeleteError> for Error {
+    fn from(value: storage::DeleteError) -> Self {
+        match value {
+            storage::DeleteError::ItemToDeleteNotFound(_) => Self::ScheduledStateChangeNotFound,
+            storage::DeleteError::Other(error) => Self::Internal(error),
+        }
+    }
+}
+
+#[async_trait]
+pub trait UseCase {
+    async fn execute(
+        &self,
+        input: Input,
+        storage: &(dyn Storage + Send + Sync),
+    ) -> Result<(), Error>;
+}
+
+pub fn create() -> impl UseCase {
+    UseCaseImpl {}
+}
+
+#[derive(Clone)]
+struct UseCaseImpl {}
+
+#[async_trait]
+impl UseCase for UseCaseImpl {
+    async fn execute(
+        &self,
+        Input {
+            group,
+            service,
+            environment,
+            id,
+        }: Input,
+        storage: &(dyn Storage + Send + Sync),
+    ) -> Result<(), Error> {
+        let key = GateKey {
+            group,
+            service,
+            environment,
+        };
+        // the id alone would allow cancelling changes of other gates
+        if !storage
+            .find_scheduled_state_changes()
+            .await?
+            .iter()
+            .any(|change| change.id == id && change.key == key)
+        {
+            return Err(Error::ScheduledStateChangeNotFound);
+        }
+        storage.delete_scheduled_state_change(id).await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use chrono::DateTime;
+    use mockall::predicate::eq;
+    use similar_asserts::assert_eq;
+
+    use crate::storage::MockStorage;
+    use crate::types::{GateState, ScheduledStateChange};
+
+    use super::*;
+
+    fn some_input(environment: &str) -> Input {
+        Input {
+            group: "some group".to_owned(),
+            service: "some service".to_owned(),
+            environment: environment.to_owned(),
+            id: "some id".to_owned(),
+        }
+    }
+
+    fn given_storage() -> MockStorage {
+        let mut mock_storage = MockStorage::new();
+        mock_storage
+            .expect_find_scheduled_state_changes()
+            .return_once(|| {
+                Ok(vec![ScheduledStateChange {
+                    id: "some id".to_owned(),
+                    key: GateKey {
+                        group: "some group".to_owned(),
+                        service: "some service".to_owned(),
+                        environment: "live".to_owned(),
+                    },
+                    state: GateState::Closed,
+                    at: DateTime::default(),
+                    actor: None,
+                }])
+            });
+        mock_storage
+    }
+
+    #[tokio::test]
+    async fn should_cancel_scheduled_state_change() {
+        // given
+        let mut mock_storage = given_storage();
+        mock_storage
+            .expect_delete_scheduled_state_change()
+            .with(eq("some id".to_owned()))
+            .return_once(|_| Ok(()));
+
+        // when
+        let result = UseCaseImpl {}
+            .execute(some_input("live"), &mock_storage)
+            .await;
+
+        // then
+        assert_eq!(result, Ok(()));
+    }
+
+    #[tokio::test]
+    async fn should_not_cancel_scheduled_state_change_of_other_gate() {
+        // given
+        let mock_storage = given_storage();
+
+        // when
+        let result = UseCaseImpl {}
+            .execute(some_input("develop"), &mock_storage)
+            .await;
+
+        // then
+        assert_eq!(result, Err(Error::ScheduledStateChangeNotFound));
+    }
+}
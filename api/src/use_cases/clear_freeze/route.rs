@@ -0,0 +1,24 @@
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::Json;
+
+use crate::types::app_state::AppState;
+use crate::use_cases::clear_freeze::use_case::Error;
+
+pub async fn handler(State(app_state): State<AppState>) -> impl IntoResponse {
+    match app_state
+        .use_cases
+        .clear_freeze
+        .execute(app_state.storage.as_ref())
+        .await
+    {
+        Ok(()) => StatusCode::OK.into_response(),
+        Err(error) => match error {
+            Error::FreezeNotFound => StatusCode::NO_CONTENT.into_response(),
+            Error::Internal(error) => {
+                (StatusCode::INTERNAL_SERVER_ERROR, Json(error)).into_response()
+            }
+        },
+    }
+}
@@ -0,0 +1,75 @@
+use async_trait::async_trait;
+
+use crate::storage;
+use crate::storage::Storage;
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Error {
+    FreezeNotFound,
+    Internal(String),
+}
+
+impl From<storage::DeleteError> for Error {
+    fn from(value: storage::DeleteError) -> Self {
+        match value {
+            storage::DeleteError::ItemToDeleteNotFound(_) => Self::FreezeNotFound,
+            storage::DeleteError::Other(error) => Self::Internal(error),
+        }
+    }
+}
+
+#[async_trait]
+pub trait UseCase {
+    async fn execute(&self, storage: &(dyn Storage + Send + Sync)) -> Result<(), Error>;
+}
+
+pub fn create() -> impl UseCase {
+    UseCaseImpl {}
+}
+
+#[derive(Clone)]
+struct UseCaseImpl {}
+
+#[async_trait]
+impl UseCase for UseCaseImpl {
+    async fn execute(&self, storage: &(dyn Storage + Send + Sync)) -> Result<(), Error> {
+        Ok(storage.delete_freeze().await?)
+    }
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use crate::storage::{DeleteError, MockStorage};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn should_clear_freeze() {
+        // given
+        let mut mock_storage = MockStorage::new();
+        mock_storage.expect_delete_freeze().return_once(|| Ok(()));
+
+        // when
+        let result = UseCaseImpl {}.execute(&mock_storage).await;
+
+        // then
+        assert_eq!(result, Ok(()));
+    }
+
+    #[tokio::test]
+    async fn should_return_freeze_not_found_error() {
+        // given
+        let mut mock_storage = MockStorage::new();
+        mock_storage.expect_delete_freeze().return_once(|| {
+            Err(DeleteError::ItemToDeleteNotFound(
+                "freeze does not exist".to_owned(),
+            ))
+        });
+
+        // when
+        let result = UseCaseImpl {}.execute(&mock_storage).await;
+
+        // then
+        assert_eq!(result, Err(Error::FreezeNotFound));
+    }
+}
@@ -0,0 +1,2 @@
+pub mod use_case;
+pub type DynType = dyn use_case::UseCase + Send + Sync;
@@ -0,0 +1,213 @@
+use async_trait::async_trait;
+
+use crate::auto_close;
+use crate::clock::Clock;
+use crate::id_provider::IdProvider;
+use crate::storage::Storage;
+use crate::types::Gate;
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Error {
+    Internal(String),
+}
+
+impl From<auto_close::Error> for Error {
+    fn from(value: auto_close::Error) -> Self {
+        match value {
+            auto_close::Error::Internal(error) => Self::Internal(error),
+        }
+    }
+}
+
+#[async_trait]
+pub trait UseCase {
+    /// Closes the given gates whose open state has expired and returns how many were closed.
+    async fn execute(
+        &self,
+        gates: &[Gate],
+        storage: &(dyn Storage + Send + Sync),
+        clock: &(dyn Clock + Send + Sync),
+        id_provider: &(dyn IdProvider + Send + Sync),
+    ) -> Result<usize, Error>;
+}
+
+pub fn create() -> impl UseCase {
+    UseCaseImpl {}
+}
+
+#[derive(Clone)]
+struct UseCaseImpl {}
+
+#[async_trait]
+impl UseCase for UseCaseImpl {
+    async fn execute(
+        &self,
+        gates: &[Gate],
+        storage: &(dyn Storage + Send + Sync),
+        clock: &(dyn Clock + Send + Sync),
+        id_provider: &(dyn IdProvider + Send + Sync),
+    ) -> Result<usize, Error> {
+        let now = clock.now();
+        let mut closed = 0;
+        for gate in gates.iter().filter(|gate| gate.is_expired(now)) {
+            let message = format!(
+                "Closed automatically, the gate was only opened until {}",
+                gate.open_until.unwrap_or(now).to_rfc3339()
+            );
+            if auto_close::close(gate, message, now, storage, id_provider).await? {
+                closed += 1;
+            }
+        }
+        Ok(closed)
+    }
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use std::collections::HashSet;
+
+    use mockall::predicate::eq;
+    use similar_asserts::assert_eq;
+
+    use chrono::{DateTime, Utc};
+
+    use crate::clock::MockClock;
+    use crate::id_provider::MockIdProvider;
+    use crate::storage::{MockStorage, UpdateError};
+    use crate::types::{Gate, GateEvent, GateKey, GateState, HistoryEntry};
+
+    use super::*;
+
+    fn instant(value: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(value)
+            .expect("failed to parse date")
+            .to_utc()
+    }
+
+    fn some_gate(environment: &str, state: GateState, open_until: Option<&str>) -> Gate {
+        Gate {
+            key: GateKey {
+                group: "some group".to_owned(),
+                service: "some service".to_owned(),
+                environment: environment.to_owned(),
+            },
+            state,
+            comments: HashSet::default(),
+            last_updated: instant("2023-06-05T06:00:00Z"),
+            display_order: None,
+            version: 3,
+            open_until: open_until.map(instant),
+        }
+    }
+
+    fn given_clock() -> MockClock {
+        let mut mock_clock = MockClock::new();
+        mock_clock
+            .expect_now()
+            .return_const(instant("2023-06-05T13:00:00Z"));
+        mock_clock
+    }
+
+    fn given_id_provider() -> MockIdProvider {
+        let mut mock_id_provider = MockIdProvider::new();
+        mock_id_provider.expect_get().return_const("some id");
+        mock_id_provider
+    }
+
+    #[tokio::test]
+    async fn should_close_gates_whose_open_state_expired() {
+        // given
+        let now = instant("2023-06-05T13:00:00Z");
+        let expired = some_gate("expired", GateState::Open, Some("2023-06-05T12:00:00Z"));
+        let gates = vec![
+            expired.clone(),
+            some_gate("open", GateState::Open, Some("2023-06-05T14:00:00Z")),
+            some_gate("unlimited", GateState::Open, None),
+            some_gate("closed", GateState::Closed, Some("2023-06-05T12:00:00Z")),
+        ];
+        let mut mock_storage = MockStorage::new();
+        mock_storage
+            .expect_update_state_and_last_updated()
+            .with(
+                eq(expired.key.clone()),
+                eq(GateState::Closed),
+                eq(None),
+                eq(now),
+                eq(Some(3)),
+            )
+            .times(1)
+            .return_once(|key, _, _, _, _| {
+                Ok(Gate {
+                    key,
+                    ..some_gate("expired", GateState::Closed, None)
+                })
+            });
+        mock_storage
+            .expect_insert_history_entry()
+            .with(eq(HistoryEntry {
+                id: "some id".to_owned(),
+                key: expired.key.clone(),
+                timestamp: now,
+                event: GateEvent::StateChanged,
+                old_value: Some("open".to_owned()),
+                new_value: Some("closed".to_owned()),
+                open_until: None,
+                actor: None,
+            }))
+            .times(1)
+            .return_once(|_| Ok(()));
+        mock_storage
+            .expect_update_comment_and_last_updated()
+            .withf(|key, comment, _, _| {
+                key.environment == "expired"
+                    && comment.message
+                        == "Closed automatically, the gate was only opened until 2023-06-05T12:00:00+00:00"
+            })
+            .times(1)
+            .return_once(|_, _, _, _| Ok(some_gate("expired", GateState::Closed, None)));
+        mock_storage
+            .expect_insert_history_entry()
+            .withf(|entry| entry.event == GateEvent::CommentAdded && entry.actor.is_none())
+            .times(1)
+            .return_once(|_| Ok(()));
+
+        // when
+        let result = UseCaseImpl {}
+            .execute(&gates, &mock_storage, &given_clock(), &given_id_provider())
+            .await;
+
+        // then
+        assert_eq!(result, Ok(1));
+    }
+
+    #[tokio::test]
+    async fn should_skip_gate_changed_in_the_meantime() {
+        // given
+        let gates = vec![some_gate(
+            "expired",
+            GateState::Open,
+            Some("2023-06-05T12:00:00Z"),
+        )];
+        let mut mock_storage = MockStorage::new();
+        mock_storage
+            .expect_update_state_and_last_updated()
+            .times(1)
+            .return_once(|_, _, _, _, _| {
+                Err(UpdateError::VersionConflict(
+                    "some version conflict".to_owned(),
+                ))
+            });
+        mock_storage.expect_insert_history_entry().never();
+        mock_storage
+            .expect_update_comment_and_last_updated()
+            .never();
+
+        // when
+        let result = UseCaseImpl {}
+            .execute(&gates, &mock_storage, &given_clock(), &given_id_provider())
+            .await;
+
+        // then
+        assert_eq!(result, Ok(0));
+    }
+}
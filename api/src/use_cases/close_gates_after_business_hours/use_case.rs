@@ -0,0 +1,448 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+use crate::auto_close;
+use crate::clock::Clock;
+use crate::date_time_switch::DateTimeSwitch;
+use crate::id_provider::IdProvider;
+use crate::storage;
+use crate::storage::Storage;
+use crate::types::{Gate, GateEvent, GateState};
+
+const COMMENT: &str = "Closed automatically at the end of business hours";
+
+const HISTORY_PAGE_SIZE: u32 = 20;
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Error {
+    Internal(String),
+}
+
+impl From<storage::FindError> for Error {
+    fn from(value: storage::FindError) -> Self {
+        match value {
+            storage::FindError::ItemCouldNotBeDecoded(error)
+            | storage::FindError::InvalidCursor(error)
+            | storage::FindError::Other(error) => Self::Internal(error),
+        }
+    }
+}
+
+impl From<auto_close::Error> for Error {
+    fn from(value: auto_close::Error) -> Self {
+        match value {
+            auto_close::Error::Internal(error) => Self::Internal(error),
+        }
+    }
+}
+
+#[async_trait]
+pub trait UseCase {
+    /// Closes the given open gates outside of their business hours, so they stay closed until someone
+    /// opens them again, and returns how many were closed. Gates that were opened after business
+    /// hours closed are left open.
+    async fn execute(
+        &self,
+        gates: &[Gate],
+        storage: &(dyn Storage + Send + Sync),
+        clock: &(dyn Clock + Send + Sync),
+        date_time_switch: &(dyn DateTimeSwitch + Send + Sync),
+        id_provider: &(dyn IdProvider + Send + Sync),
+    ) -> Result<usize, Error>;
+}
+
+pub fn create() -> impl UseCase {
+    UseCaseImpl {}
+}
+
+#[derive(Clone)]
+struct UseCaseImpl {}
+
+#[async_trait]
+impl UseCase for UseCaseImpl {
+    async fn execute(
+        &self,
+        gates: &[Gate],
+        storage: &(dyn Storage + Send + Sync),
+        clock: &(dyn Clock + Send + Sync),
+        date_time_switch: &(dyn DateTimeSwitch + Send + Sync),
+        id_provider: &(dyn IdProvider + Send + Sync),
+    ) -> Result<usize, Error> {
+        let now = clock.now();
+        let mut closed = 0;
+        for gate in gates.iter().filter(|gate| gate.state == GateState::Open) {
+            if date_time_switch.is_closed(now, &gate.key).await
+                && was_open_at_close(gate, now, storage, date_time_switch).await?
+                && auto_close::close(gate, COMMENT.to_owned(), now, storage, id_provider).await?
+            {
+                closed += 1;
+            }
+        }
+        Ok(closed)
+    }
+}
+
+/// Whether business hours closed since the gate got its state, so it was not opened after hours on
+/// purpose, e.g. by restoring it from the trash. Comments and display order changes don't count,
+/// they also update the gate. Without history, the gate is taken as open at close.
+async fn was_open_at_close(
+    gate: &Gate,
+    now: DateTime<Utc>,
+    storage: &(dyn Storage + Send + Sync),
+    date_time_switch: &(dyn DateTimeSwitch + Send + Sync),
+) -> Result<bool, Error> {
+    let Some(state_changed) = find_state_changed(gate, storage).await? else {
+        return Ok(true);
+    };
+    Ok(date_time_switch
+        .next_transitions(state_changed, &gate.key)
+        .await
+        .and_then(|transitions| transitions.next_close)
+        .is_some_and(|next_close| next_close <= now))
+}
+
+/// When the gate got its current state, according to the newest history entry that set it.
+async fn find_state_changed(
+    gate: &Gate,
+    storage: &(dyn Storage + Send + Sync),
+) -> Result<Option<DateTime<Utc>>, Error> {
+    let mut cursor = None;
+    loop {
+        let page = storage
+            .find_history(gate.key.clone(), HISTORY_PAGE_SIZE, cursor)
+            .await?;
+        if let Some(entry) = page.entries.iter().find(|entry| {
+            matches!(
+                entry.event,
+                GateEvent::Created | GateEvent::Restored | GateEvent::StateChanged
+            )
+        }) {
+            return Ok(Some(entry.timestamp));
+        }
+        match page.next_cursor {
+            Some(next_cursor) => cursor = Some(next_cursor),
+            None => return Ok(None),
+        }
+    }
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use std::collections::HashSet;
+
+    use mockall::predicate::eq;
+    use similar_asserts::assert_eq;
+
+    use crate::clock::MockClock;
+    use crate::date_time_switch::MockDateTimeSwitch;
+    use crate::date_time_switch::Transitions;
+    use crate::id_provider::MockIdProvider;
+    use crate::storage::MockStorage;
+    use crate::types::{GateKey, HistoryEntry, HistoryPage};
+
+    use super::*;
+
+    fn instant(value: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(value)
+            .expect("failed to parse date")
+            .to_utc()
+    }
+
+    fn some_key(environment: &str) -> GateKey {
+        GateKey {
+            group: "some group".to_owned(),
+            service: "some service".to_owned(),
+            environment: environment.to_owned(),
+        }
+    }
+
+    fn some_gate(environment: &str, state: GateState) -> Gate {
+        Gate {
+            key: some_key(environment),
+            state,
+            comments: HashSet::default(),
+            last_updated: instant("2023-06-05T06:00:00Z"),
+            display_order: None,
+            version: 3,
+            open_until: None,
+        }
+    }
+
+    fn some_history_entry(event: GateEvent, timestamp: &str) -> HistoryEntry {
+        HistoryEntry {
+            id: "some id".to_owned(),
+            key: some_key("live"),
+            timestamp: instant(timestamp),
+            event,
+            old_value: None,
+            new_value: None,
+            open_until: None,
+            actor: Some("Max".to_owned()),
+        }
+    }
+
+    #[tokio::test]
+    async fn should_close_open_gates_outside_of_business_hours() {
+        // given
+        let now = instant("2023-06-05T17:00:00Z");
+        let mut mock_clock = MockClock::new();
+        mock_clock.expect_now().return_const(now);
+
+        let mut mock_date_time_switch = MockDateTimeSwitch::new();
+        mock_date_time_switch
+            .expect_is_closed()
+            .withf(|_, key| key.environment == "live")
+            .return_const(true);
+        mock_date_time_switch
+            .expect_is_closed()
+            .withf(|_, key| key.environment == "develop")
+            .return_const(false);
+        mock_date_time_switch
+            .expect_next_transitions()
+            .with(eq(instant("2023-06-05T06:00:00Z")), eq(some_key("live")))
+            .return_const(Some(Transitions {
+                next_open: Some(instant("2023-06-06T06:00:00Z")),
+                next_close: Some(instant("2023-06-05T16:00:00Z")),
+            }));
+
+        let gates = vec![
+            some_gate("live", GateState::Open),
+            some_gate("develop", GateState::Open),
+            some_gate("closed", GateState::Closed),
+        ];
+        let mut mock_storage = MockStorage::new();
+        mock_storage
+            .expect_find_history()
+            .with(eq(some_key("live")), eq(HISTORY_PAGE_SIZE), eq(None))
+            .return_once(|_, _, _| {
+                Ok(HistoryPage {
+                    entries: vec![some_history_entry(
+                        GateEvent::StateChanged,
+                        "2023-06-05T06:00:00Z",
+                    )],
+                    next_cursor: None,
+                })
+            });
+        mock_storage
+            .expect_update_state_and_last_updated()
+            .with(
+                eq(some_key("live")),
+                eq(GateState::Closed),
+                eq(None),
+                eq(now),
+                eq(Some(3)),
+            )
+            .times(1)
+            .return_once(|_, _, _, _, _| Ok(some_gate("live", GateState::Closed)));
+        mock_storage
+            .expect_insert_history_entry()
+            .with(eq(HistoryEntry {
+                id: "some id".to_owned(),
+                key: some_key("live"),
+                timestamp: now,
+                event: GateEvent::StateChanged,
+                old_value: Some("open".to_owned()),
+                new_value: Some("closed".to_owned()),
+                open_until: None,
+                actor: None,
+            }))
+            .times(1)
+            .return_once(|_| Ok(()));
+        mock_storage
+            .expect_update_comment_and_last_updated()
+            .withf(|key, comment, _, _| {
+                key.environment == "live"
+                    && comment.message == "Closed automatically at the end of business hours"
+            })
+            .times(1)
+            .return_once(|_, _, _, _| Ok(some_gate("live", GateState::Closed)));
+        mock_storage
+            .expect_insert_history_entry()
+            .withf(|entry| entry.event == GateEvent::CommentAdded && entry.actor.is_none())
+            .times(1)
+            .return_once(|_| Ok(()));
+
+        let mut mock_id_provider = MockIdProvider::new();
+        mock_id_provider.expect_get().return_const("some id");
+
+        // when
+        let result = UseCaseImpl {}
+            .execute(
+                &gates,
+                &mock_storage,
+                &mock_clock,
+                &mock_date_time_switch,
+                &mock_id_provider,
+            )
+            .await;
+
+        // then
+        assert_eq!(result, Ok(1));
+    }
+
+    #[tokio::test]
+    async fn should_keep_gates_open_that_were_restored_after_business_hours() {
+        // given
+        let now = instant("2023-06-05T17:00:00Z");
+        let mut mock_clock = MockClock::new();
+        mock_clock.expect_now().return_const(now);
+
+        let mut mock_date_time_switch = MockDateTimeSwitch::new();
+        mock_date_time_switch.expect_is_closed().return_const(true);
+        mock_date_time_switch
+            .expect_next_transitions()
+            .with(eq(instant("2023-06-05T16:30:00Z")), eq(some_key("live")))
+            .return_const(Some(Transitions {
+                next_open: Some(instant("2023-06-06T06:00:00Z")),
+                next_close: Some(instant("2023-06-06T16:00:00Z")),
+            }));
+
+        let gates = vec![Gate {
+            last_updated: instant("2023-06-05T16:30:00Z"),
+            ..some_gate("live", GateState::Open)
+        }];
+        let mut mock_storage = MockStorage::new();
+        mock_storage.expect_find_history().return_once(|_, _, _| {
+            Ok(HistoryPage {
+                entries: vec![some_history_entry(
+                    GateEvent::Restored,
+                    "2023-06-05T16:30:00Z",
+                )],
+                next_cursor: None,
+            })
+        });
+        mock_storage.expect_update_state_and_last_updated().never();
+        mock_storage
+            .expect_update_comment_and_last_updated()
+            .never();
+        mock_storage.expect_insert_history_entry().never();
+
+        // when
+        let result = UseCaseImpl {}
+            .execute(
+                &gates,
+                &mock_storage,
+                &mock_clock,
+                &mock_date_time_switch,
+                &MockIdProvider::new(),
+            )
+            .await;
+
+        // then
+        assert_eq!(result, Ok(0));
+    }
+
+    #[tokio::test]
+    async fn should_close_gates_that_were_only_commented_on_after_business_hours() {
+        // given
+        let now = instant("2023-06-05T17:00:00Z");
+        let mut mock_clock = MockClock::new();
+        mock_clock.expect_now().return_const(now);
+
+        let mut mock_date_time_switch = MockDateTimeSwitch::new();
+        mock_date_time_switch.expect_is_closed().return_const(true);
+        mock_date_time_switch
+            .expect_next_transitions()
+            .with(eq(instant("2023-06-05T06:00:00Z")), eq(some_key("live")))
+            .return_const(Some(Transitions {
+                next_open: Some(instant("2023-06-06T06:00:00Z")),
+                next_close: Some(instant("2023-06-05T16:00:00Z")),
+            }));
+
+        let gates = vec![Gate {
+            last_updated: instant("2023-06-05T16:30:00Z"),
+            ..some_gate("live", GateState::Open)
+        }];
+        let mut mock_storage = MockStorage::new();
+        mock_storage
+            .expect_find_history()
+            .with(eq(some_key("live")), eq(HISTORY_PAGE_SIZE), eq(None))
+            .return_once(|_, _, _| {
+                Ok(HistoryPage {
+                    entries: vec![some_history_entry(
+                        GateEvent::CommentAdded,
+                        "2023-06-05T16:30:00Z",
+                    )],
+                    next_cursor: Some("some cursor".to_owned()),
+                })
+            });
+        mock_storage
+            .expect_find_history()
+            .with(
+                eq(some_key("live")),
+                eq(HISTORY_PAGE_SIZE),
+                eq(Some("some cursor".to_owned())),
+            )
+            .return_once(|_, _, _| {
+                Ok(HistoryPage {
+                    entries: vec![
+                        some_history_entry(GateEvent::DisplayOrderChanged, "2023-06-05T09:00:00Z"),
+                        some_history_entry(GateEvent::StateChanged, "2023-06-05T06:00:00Z"),
+                    ],
+                    next_cursor: None,
+                })
+            });
+        mock_storage
+            .expect_update_state_and_last_updated()
+            .times(1)
+            .return_once(|_, _, _, _, _| Ok(some_gate("live", GateState::Closed)));
+        mock_storage
+            .expect_update_comment_and_last_updated()
+            .times(1)
+            .return_once(|_, _, _, _| Ok(some_gate("live", GateState::Closed)));
+        mock_storage
+            .expect_insert_history_entry()
+            .times(2)
+            .returning(|_| Ok(()));
+
+        let mut mock_id_provider = MockIdProvider::new();
+        mock_id_provider.expect_get().return_const("some id");
+
+        // when
+        let result = UseCaseImpl {}
+            .execute(
+                &gates,
+                &mock_storage,
+                &mock_clock,
+                &mock_date_time_switch,
+                &mock_id_provider,
+            )
+            .await;
+
+        // then
+        assert_eq!(result, Ok(1));
+    }
+
+    #[tokio::test]
+    async fn should_return_storage_error() {
+        // given
+        let mut mock_clock = MockClock::new();
+        mock_clock
+            .expect_now()
+            .return_const(instant("2023-06-05T17:00:00Z"));
+        let mut mock_date_time_switch = MockDateTimeSwitch::new();
+        mock_date_time_switch.expect_is_closed().return_const(true);
+        let gates = vec![some_gate("live", GateState::Open)];
+        let mut mock_storage = MockStorage::new();
+        mock_storage
+            .expect_find_history()
+            .return_once(|_, _, _| Err(storage::FindError::Other("some storage error".to_owned())));
+
+        // when
+        let result = UseCaseImpl {}
+            .execute(
+                &gates,
+                &mock_storage,
+                &mock_clock,
+                &mock_date_time_switch,
+                &MockIdProvider::new(),
+            )
+            .await;
+
+        // then
+        assert_eq!(
+            result,
+            Err(Error::Internal("some storage error".to_owned()))
+        );
+    }
+}
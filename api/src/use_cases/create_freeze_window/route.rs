@@ -0,0 +1,62 @@
+use axum::extract::State;
+use axum::http::{HeaderMap, StatusCode};
+use axum::response::IntoResponse;
+use axum::Json;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::actor;
+use crate::types::app_state::AppState;
+use crate::use_cases::create_freeze_window::use_case;
+use crate::use_cases::create_freeze_window::use_case::Error;
+
+#[derive(Serialize, Deserialize)]
+pub struct Payload {
+    pub name: String,
+    pub reason: String,
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+    pub group: Option<String>,
+    pub environment: Option<String>,
+}
+
+pub async fn handler(
+    State(app_state): State<AppState>,
+    headers: HeaderMap,
+    Json(Payload {
+        name,
+        reason,
+        start,
+        end,
+        group,
+        environment,
+    }): Json<Payload>,
+) -> impl IntoResponse {
+    match app_state
+        .use_cases
+        .create_freeze_window
+        .execute(
+            use_case::Input {
+                name,
+                reason,
+                start,
+                end,
+                group,
+                environment,
+                actor: actor::from_headers(&headers),
+            },
+            app_state.storage.as_ref(),
+            app_state.clock.as_ref(),
+            app_state.id_provider.as_ref(),
+        )
+        .await
+    {
+        Ok(freeze_window) => Json(freeze_window).into_response(),
+        Err(error) => match error {
+            Error::InvalidInput(error) => (StatusCode::BAD_REQUEST, Json(error)).into_response(),
+            Error::Internal(error) => {
+                (StatusCode::INTERNAL_SERVER_ERROR, Json(error)).into_response()
+            }
+        },
+    }
+}
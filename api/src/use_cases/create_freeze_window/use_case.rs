@@ -0,0 +1,208 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use openapi::models;
+
+use crate::clock::Clock;
+use crate::id_provider::IdProvider;
+use crate::storage;
+use crate::storage::Storage;
+use crate::types::{FreezeScope, FreezeWindow};
+
+#[derive(Debug)]
+pub struct Input {
+    pub name: String,
+    pub reason: String,
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+    pub group: Option<String>,
+    pub environment: Option<String>,
+    pub actor: Option<String>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Error {
+    InvalidInput(String),
+    Internal(String),
+}
+
+impl From<storage::InsertError> for Error {
+    fn from(value: storage::InsertError) -> Self {
+        match value {
+            storage::InsertError::ItemAlreadyExists(error) | storage::InsertError::Other(error) => {
+                Self::Internal(error)
+            }
+        }
+    }
+}
+
+#[async_trait]
+pub trait UseCase {
+    async fn execute(
+        &self,
+        input: Input,
+        storage: &(dyn Storage + Send + Sync),
+        clock: &(dyn Clock + Send + Sync),
+        id_provider: &(dyn IdProvider + Send + Sync),
+    ) -> Result<models::FreezeWindow, Error>;
+}
+
+pub fn create() -> impl UseCase {
+    UseCaseImpl {}
+}
+
+#[derive(Clone)]
+struct UseCaseImpl {}
+
+#[async_trait]
+impl UseCase for UseCaseImpl {
+    async fn execute(
+        &self,
+        Input {
+            name,
+            reason,
+            start,
+            end,
+            group,
+            environment,
+            actor,
+        }: Input,
+        storage: &(dyn Storage + Send + Sync),
+        clock: &(dyn Clock + Send + Sync),
+        id_provider: &(dyn IdProvider + Send + Sync),
+    ) -> Result<models::FreezeWindow, Error> {
+        let name = not_empty("name", &name)?;
+        let reason = not_empty("reason", &reason)?;
+        let group = group.map(|group| not_empty("group", &group)).transpose()?;
+        let environment = environment
+            .map(|environment| not_empty("environment", &environment))
+            .transpose()?;
+
+        if start >= end {
+            return Err(Error::InvalidInput(
+                "the start of the freeze window must be before its end".to_owned(),
+            ));
+        }
+        if end <= clock.now() {
+            return Err(Error::InvalidInput(
+                "the end of the freeze window must be in the future".to_owned(),
+            ));
+        }
+
+        let freeze_window = FreezeWindow {
+            id: id_provider.get(),
+            name,
+            reason,
+            start,
+            end,
+            scope: FreezeScope::new(group, environment).map_err(Error::InvalidInput)?,
+            actor,
+        };
+        storage.insert_freeze_window(&freeze_window).await?;
+
+        Ok(freeze_window.into())
+    }
+}
+
+fn not_empty(field: &str, value: &str) -> Result<String, Error> {
+    let value = value.trim();
+    if value.is_empty() {
+        return Err(Error::InvalidInput(format!("{field} must not be empty")));
+    }
+    Ok(value.to_owned())
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use mockall::predicate::eq;
+    use rstest::rstest;
+    use similar_asserts::assert_eq;
+
+    use crate::clock::MockClock;
+    use crate::id_provider::MockIdProvider;
+    use crate::storage::MockStorage;
+
+    use super::*;
+
+    fn instant(value: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(value)
+            .expect("failed to parse date")
+            .to_utc()
+    }
+
+    fn given_input() -> Input {
+        Input {
+            name: " Year-end freeze ".to_owned(),
+            reason: "annual closing".to_owned(),
+            start: instant("2025-12-19T17:00:00Z"),
+            end: instant("2026-01-05T06:00:00Z"),
+            group: None,
+            environment: Some("live".to_owned()),
+            actor: Some("Max".to_owned()),
+        }
+    }
+
+    #[tokio::test]
+    async fn should_create_freeze_window() {
+        // given
+        let mut mock_clock = MockClock::new();
+        mock_clock
+            .expect_now()
+            .return_const(instant("2025-12-01T12:00:00Z"));
+        let mut mock_id_provider = MockIdProvider::new();
+        mock_id_provider.expect_get().return_const("some id");
+
+        let freeze_window = FreezeWindow {
+            id: "some id".to_owned(),
+            name: "Year-end freeze".to_owned(),
+            reason: "annual closing".to_owned(),
+            start: instant("2025-12-19T17:00:00Z"),
+            end: instant("2026-01-05T06:00:00Z"),
+            scope: FreezeScope::Environment("live".to_owned()),
+            actor: Some("Max".to_owned()),
+        };
+        let mut mock_storage = MockStorage::new();
+        mock_storage
+            .expect_insert_freeze_window()
+            .with(eq(freeze_window.clone()))
+            .return_once(|_| Ok(()));
+
+        // when
+        let result = UseCaseImpl {}
+            .execute(given_input(), &mock_storage, &mock_clock, &mock_id_provider)
+            .await;
+
+        // then
+        assert_eq!(result, Ok(freeze_window.into()));
+    }
+
+    #[rstest]
+    #[case(Input { name: " ".to_owned(), ..given_input() })]
+    #[case(Input { reason: String::new(), ..given_input() })]
+    #[case(Input { group: Some(String::new()), environment: None, ..given_input() })]
+    #[case(Input { group: Some("some group".to_owned()), ..given_input() })]
+    #[case(Input { end: instant("2025-12-19T17:00:00Z"), ..given_input() })]
+    #[case(Input {
+        start: instant("2025-11-01T12:00:00Z"),
+        end: instant("2025-11-30T12:00:00Z"),
+        ..given_input()
+    })]
+    #[tokio::test]
+    async fn should_reject_invalid_freeze_window(#[case] input: Input) {
+        // given
+        let mut mock_clock = MockClock::new();
+        mock_clock
+            .expect_now()
+            .return_const(instant("2025-12-01T12:00:00Z"));
+        let mut mock_id_provider = MockIdProvider::new();
+        mock_id_provider.expect_get().return_const("some id");
+        let mock_storage = MockStorage::new();
+
+        // when
+        let result = UseCaseImpl {}
+            .execute(input, &mock_storage, &mock_clock, &mock_id_provider)
+            .await;
+
+        // then
+        assert!(matches!(result, Err(Error::InvalidInput(_))));
+    }
+}
@@ -1,8 +1,11 @@
+use crate::actor;
+use crate::etag;
 use crate::types::app_state::AppState;
 use crate::use_cases::create_gate::use_case;
 use crate::use_cases::create_gate::use_case::Error;
 use axum::extract::State;
-use axum::http::StatusCode;
+use axum::http::header::ETAG;
+use axum::http::{HeaderMap, StatusCode};
 use axum::response::IntoResponse;
 use axum::Json;
 use serde::{Deserialize, Serialize};
@@ -17,6 +20,7 @@ pub struct Payload {
 
 pub async fn handler(
     State(app_state): State<AppState>,
+    headers: HeaderMap,
     Json(Payload {
         group,
         service,
@@ -34,13 +38,15 @@ pub async fn handler(
                 service,
                 environment,
                 display_order,
+                actor: actor::from_headers(&headers),
             },
             app_state.storage.as_ref(),
             app_state.clock.as_ref(),
+            app_state.id_provider.as_ref(),
         )
         .await
     {
-        Ok(gate) => Json(gate).into_response(),
+        Ok(gate) => ([(ETAG, etag::from_version(gate.version))], Json(gate)).into_response(),
         Err(error) => match error {
             Error::InvalidInput(error) => (StatusCode::BAD_REQUEST, Json(error)).into_response(),
             Error::GateAlreadyExists => StatusCode::CONFLICT.into_response(),
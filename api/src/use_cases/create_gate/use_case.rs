@@ -3,9 +3,11 @@ use openapi::models;
 use std::collections::HashSet;
 
 use crate::clock::Clock;
+use crate::history;
+use crate::id_provider::IdProvider;
 use crate::storage;
 use crate::storage::Storage;
-use crate::types::{Gate, GateKey, GateState};
+use crate::types::{Gate, GateEvent, GateKey, GateState, HistoryEntry};
 
 #[derive(Debug)]
 pub struct Input {
@@ -13,6 +15,7 @@ pub struct Input {
     pub service: String,
     pub environment: String,
     pub display_order: Option<u32>,
+    pub actor: Option<String>,
 }
 
 #[derive(Debug, PartialEq, Eq)]
@@ -38,6 +41,7 @@ pub trait UseCase {
         input: Input,
         storage: &(dyn Storage + Send + Sync),
         clock: &(dyn Clock + Send + Sync),
+        id_provider: &(dyn IdProvider + Send + Sync),
     ) -> Result<models::Gate, Error>;
 }
 
@@ -57,9 +61,11 @@ impl UseCase for UseCaseImpl {
             service,
             environment,
             display_order,
+            actor,
         }: Input,
         storage: &(dyn Storage + Send + Sync),
         clock: &(dyn Clock + Send + Sync),
+        id_provider: &(dyn IdProvider + Send + Sync),
     ) -> Result<models::Gate, Error> {
         if group.is_empty() || service.is_empty() || environment.is_empty() {
             return Err(Error::InvalidInput(
@@ -77,9 +83,25 @@ impl UseCase for UseCaseImpl {
             comments: HashSet::default(),
             last_updated: clock.now(),
             display_order,
+            version: 0,
+            open_until: None,
         };
 
         storage.insert(&gate).await?;
+        history::record(
+            storage,
+            &HistoryEntry {
+                id: id_provider.get(),
+                key: gate.key.clone(),
+                timestamp: gate.last_updated,
+                event: GateEvent::Created,
+                old_value: None,
+                new_value: Some(gate.state.clone().try_into().map_err(Error::Internal)?),
+                open_until: None,
+                actor,
+            },
+        )
+        .await;
 
         Ok(gate.into())
     }
@@ -93,9 +115,10 @@ mod unit_tests {
     use mockall::predicate::eq;
 
     use crate::clock::MockClock;
+    use crate::id_provider::MockIdProvider;
     use crate::storage;
     use crate::storage::MockStorage;
-    use crate::types::{Gate, GateKey, GateState};
+    use crate::types::{Gate, GateEvent, GateKey, GateState, HistoryEntry};
     use crate::use_cases::create_gate::use_case::{Error, Input, UseCase, UseCaseImpl};
 
     #[tokio::test]
@@ -117,12 +140,32 @@ mod unit_tests {
             comments: HashSet::default(),
             last_updated: DateTime::from(now),
             display_order: Some(123),
+            version: 0,
+            open_until: None,
         };
 
         mock_storage
             .expect_insert()
-            .with(eq(gate1))
+            .with(eq(gate1.clone()))
             .returning(move |_| Ok(()));
+        mock_storage
+            .expect_insert_history_entry()
+            .with(eq(HistoryEntry {
+                id: "some id".to_owned(),
+                key: gate1.key,
+                timestamp: now.into(),
+                event: GateEvent::Created,
+                old_value: None,
+                new_value: Some("closed".to_owned()),
+                open_until: None,
+                actor: Some("Max".to_owned()),
+            }))
+            .returning(|_| Ok(()));
+
+        let mut mock_id_provider = MockIdProvider::new();
+        mock_id_provider
+            .expect_get()
+            .return_const("some id".to_owned());
 
         UseCaseImpl {}
             .execute(
@@ -131,9 +174,11 @@ mod unit_tests {
                     service: "some service".to_owned(),
                     environment: "some environment".to_owned(),
                     display_order: Some(123),
+                    actor: Some("Max".to_owned()),
                 },
                 &mock_storage,
                 &mock_clock,
+                &mock_id_provider,
             )
             .await
             .expect("There is no error expected here!");
@@ -151,9 +196,11 @@ mod unit_tests {
                     service: "some service".to_owned(),
                     environment: "some environment".to_owned(),
                     display_order: None,
+                    actor: None,
                 },
                 &mock_storage,
                 &mock_clock,
+                &MockIdProvider::new(),
             )
             .await;
 
@@ -176,9 +223,11 @@ mod unit_tests {
                     service: String::default(),
                     environment: "some environment".to_owned(),
                     display_order: None,
+                    actor: None,
                 },
                 &mock_storage,
                 &mock_clock,
+                &MockIdProvider::new(),
             )
             .await;
 
@@ -201,9 +250,11 @@ mod unit_tests {
                     service: "some service".to_owned(),
                     environment: String::default(),
                     display_order: None,
+                    actor: None,
                 },
                 &mock_storage,
                 &mock_clock,
+                &MockIdProvider::new(),
             )
             .await;
 
@@ -233,6 +284,8 @@ mod unit_tests {
             comments: HashSet::default(),
             last_updated: DateTime::from(now),
             display_order: Option::default(),
+            version: 0,
+            open_until: None,
         };
 
         mock_storage
@@ -251,9 +304,11 @@ mod unit_tests {
                     service: "some service".to_owned(),
                     environment: "some environment".to_owned(),
                     display_order: None,
+                    actor: None,
                 },
                 &mock_storage,
                 &mock_clock,
+                &MockIdProvider::new(),
             )
             .await;
 
@@ -283,6 +338,8 @@ mod unit_tests {
             comments: HashSet::default(),
             last_updated: DateTime::from(now),
             display_order: Option::default(),
+            version: 0,
+            open_until: None,
         };
 
         mock_storage
@@ -297,9 +354,11 @@ mod unit_tests {
                     service: "some service".to_owned(),
                     environment: "some environment".to_owned(),
                     display_order: None,
+                    actor: None,
                 },
                 &mock_storage,
                 &mock_clock,
+                &MockIdProvider::new(),
             )
             .await;
 
@@ -0,0 +1,77 @@
+use crate::actor;
+use crate::types::app_state::AppState;
+use crate::use_cases::create_gates::use_case;
+use crate::use_cases::create_gates::use_case::Error;
+use axum::extract::State;
+use axum::http::{HeaderMap, StatusCode};
+use axum::response::IntoResponse;
+use axum::Json;
+use openapi::models;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize)]
+pub struct GatePayload {
+    pub group: String,
+    pub service: String,
+    pub environment: String,
+    pub display_order: Option<u32>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Payload {
+    pub gates: Vec<GatePayload>,
+    #[serde(default)]
+    pub all_or_nothing: bool,
+}
+
+pub async fn handler(
+    State(app_state): State<AppState>,
+    headers: HeaderMap,
+    Json(Payload {
+        gates,
+        all_or_nothing,
+    }): Json<Payload>,
+) -> impl IntoResponse {
+    match app_state
+        .use_cases
+        .create_gates
+        .execute(
+            use_case::Input {
+                gates: gates
+                    .into_iter()
+                    .map(|gate| use_case::GateInput {
+                        group: gate.group,
+                        service: gate.service,
+                        environment: gate.environment,
+                        display_order: gate.display_order,
+                    })
+                    .collect(),
+                all_or_nothing,
+                actor: actor::from_headers(&headers),
+            },
+            app_state.storage.as_ref(),
+            app_state.clock.as_ref(),
+            app_state.id_provider.as_ref(),
+        )
+        .await
+    {
+        Ok(result) => {
+            if all_or_nothing
+                && result
+                    .results
+                    .iter()
+                    .any(|result| result.status != models::CreateGateStatus::Created)
+            {
+                (StatusCode::CONFLICT, Json(result)).into_response()
+            } else {
+                Json(result).into_response()
+            }
+        }
+        Err(error) => match error {
+            Error::InvalidInput(error) => (StatusCode::BAD_REQUEST, Json(error)).into_response(),
+            Error::Internal(error) => {
+                (StatusCode::INTERNAL_SERVER_ERROR, Json(error)).into_response()
+            }
+        },
+    }
+}
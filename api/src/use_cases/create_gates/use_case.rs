@@ -0,0 +1,592 @@
+use async_trait::async_trait;
+use openapi::models;
+use std::collections::HashSet;
+
+use crate::clock::Clock;
+use crate::history;
+use crate::id_provider::IdProvider;
+use crate::storage;
+use crate::storage::Storage;
+use crate::types::{Gate, GateEvent, GateKey, GateState, HistoryEntry};
+
+pub const MAX_GATES: usize = 100;
+
+#[derive(Debug, Clone)]
+pub struct GateInput {
+    pub group: String,
+    pub service: String,
+    pub environment: String,
+    pub display_order: Option<u32>,
+}
+
+#[derive(Debug)]
+pub struct Input {
+    pub gates: Vec<GateInput>,
+    pub all_or_nothing: bool,
+    pub actor: Option<String>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Error {
+    InvalidInput(String),
+    Internal(String),
+}
+
+impl From<storage::FindError> for Error {
+    fn from(value: storage::FindError) -> Self {
+        match value {
+            storage::FindError::ItemCouldNotBeDecoded(error)
+            | storage::FindError::InvalidCursor(error)
+            | storage::FindError::Other(error) => Self::Internal(error),
+        }
+    }
+}
+
+#[async_trait]
+pub trait UseCase {
+    async fn execute(
+        &self,
+        input: Input,
+        storage: &(dyn Storage + Send + Sync),
+        clock: &(dyn Clock + Send + Sync),
+        id_provider: &(dyn IdProvider + Send + Sync),
+    ) -> Result<models::CreateGatesResult, Error>;
+}
+
+pub fn create() -> impl UseCase {
+    UseCaseImpl {}
+}
+
+#[derive(Clone)]
+struct UseCaseImpl {}
+
+#[async_trait]
+impl UseCase for UseCaseImpl {
+    async fn execute(
+        &self,
+        Input {
+            gates,
+            all_or_nothing,
+            actor,
+        }: Input,
+        storage: &(dyn Storage + Send + Sync),
+        clock: &(dyn Clock + Send + Sync),
+        id_provider: &(dyn IdProvider + Send + Sync),
+    ) -> Result<models::CreateGatesResult, Error> {
+        if gates.is_empty() || gates.len() > MAX_GATES {
+            return Err(Error::InvalidInput(format!(
+                "between 1 and {MAX_GATES} gates must be given"
+            )));
+        }
+
+        let now = clock.now();
+        let mut results = Vec::with_capacity(gates.len());
+        let mut candidates = Vec::new();
+        let mut seen = HashSet::new();
+
+        for gate in gates {
+            let status = validate(&gate, &mut seen);
+            results.push(result(&gate, status.clone()));
+            if status.is_ok() {
+                candidates.push((results.len() - 1, new_gate(gate, now)));
+            }
+        }
+
+        if all_or_nothing {
+            create_all(&mut results, candidates, actor, storage, id_provider).await?;
+        } else {
+            create_each(&mut results, candidates, actor, storage, id_provider).await?;
+        }
+
+        Ok(models::CreateGatesResult { results })
+    }
+}
+
+/// Relies on the storage to insert either all gates or none of them, and only looks up which ones
+/// already exist once it refused to.
+async fn create_all(
+    results: &mut [models::CreateGateResult],
+    candidates: Vec<(usize, Gate)>,
+    actor: Option<String>,
+    storage: &(dyn Storage + Send + Sync),
+    id_provider: &(dyn IdProvider + Send + Sync),
+) -> Result<(), Error> {
+    if has_failures(results) {
+        skip_candidates(results, &candidates);
+        return Ok(());
+    }
+
+    let gates: Vec<Gate> = candidates.iter().map(|(_, gate)| gate.clone()).collect();
+    match storage.insert_all(&gates).await {
+        Ok(()) => {}
+        Err(storage::InsertError::ItemAlreadyExists(_)) => {
+            for (index, gate) in &candidates {
+                if storage.find_one(gate.key.clone()).await?.is_some() {
+                    results[*index] = already_exists(&results[*index]);
+                }
+            }
+            skip_candidates(results, &candidates);
+            return Ok(());
+        }
+        Err(storage::InsertError::Other(error)) => return Err(Error::Internal(error)),
+    }
+
+    for (index, gate) in candidates {
+        insert_created_history_entry(&gate, actor.clone(), storage, id_provider).await?;
+        results[index] = created(&results[index], gate);
+    }
+    Ok(())
+}
+
+async fn create_each(
+    results: &mut [models::CreateGateResult],
+    candidates: Vec<(usize, Gate)>,
+    actor: Option<String>,
+    storage: &(dyn Storage + Send + Sync),
+    id_provider: &(dyn IdProvider + Send + Sync),
+) -> Result<(), Error> {
+    let gates: Vec<Gate> = candidates.iter().map(|(_, gate)| gate.clone()).collect();
+    let inserted = storage
+        .insert_each(&gates)
+        .await
+        .map_err(|error| match error {
+            storage::InsertError::ItemAlreadyExists(error) | storage::InsertError::Other(error) => {
+                Error::Internal(error)
+            }
+        })?;
+
+    for ((index, gate), result) in candidates.into_iter().zip(inserted) {
+        match result {
+            Ok(()) => {
+                insert_created_history_entry(&gate, actor.clone(), storage, id_provider).await?;
+                results[index] = created(&results[index], gate);
+            }
+            Err(storage::InsertError::ItemAlreadyExists(_)) => {
+                results[index] = already_exists(&results[index]);
+            }
+            Err(storage::InsertError::Other(error)) => {
+                results[index] = result_with_status(
+                    &results[index],
+                    models::CreateGateStatus::Failed,
+                    Some(error),
+                );
+            }
+        }
+    }
+    Ok(())
+}
+
+fn validate(gate: &GateInput, seen: &mut HashSet<GateKey>) -> Result<(), String> {
+    if gate.group.is_empty() || gate.service.is_empty() || gate.environment.is_empty() {
+        return Err("group, service and environment must not be empty".to_owned());
+    }
+    if !seen.insert(GateKey {
+        group: gate.group.clone(),
+        service: gate.service.clone(),
+        environment: gate.environment.clone(),
+    }) {
+        return Err("gate is contained more than once".to_owned());
+    }
+    Ok(())
+}
+
+fn new_gate(
+    GateInput {
+        group,
+        service,
+        environment,
+        display_order,
+    }: GateInput,
+    now: chrono::DateTime<chrono::Utc>,
+) -> Gate {
+    Gate {
+        key: GateKey {
+            group,
+            service,
+            environment,
+        },
+        state: GateState::default(),
+        comments: HashSet::default(),
+        last_updated: now,
+        display_order,
+        version: 0,
+        open_until: None,
+    }
+}
+
+fn result(gate: &GateInput, status: Result<(), String>) -> models::CreateGateResult {
+    let (status, message) = match status {
+        // the final status of valid entries is determined once they have been stored
+        Ok(()) => (models::CreateGateStatus::Skipped, None),
+        Err(message) => (models::CreateGateStatus::Invalid, Some(message)),
+    };
+    models::CreateGateResult {
+        group: gate.group.clone(),
+        service: gate.service.clone(),
+        environment: gate.environment.clone(),
+        status,
+        message,
+        gate: None,
+    }
+}
+
+fn result_with_status(
+    result: &models::CreateGateResult,
+    status: models::CreateGateStatus,
+    message: Option<String>,
+) -> models::CreateGateResult {
+    models::CreateGateResult {
+        status,
+        message,
+        ..result.clone()
+    }
+}
+
+fn already_exists(result: &models::CreateGateResult) -> models::CreateGateResult {
+    result_with_status(
+        result,
+        models::CreateGateStatus::AlreadyExists,
+        Some("gate already exists".to_owned()),
+    )
+}
+
+fn created(result: &models::CreateGateResult, gate: Gate) -> models::CreateGateResult {
+    models::CreateGateResult {
+        status: models::CreateGateStatus::Created,
+        message: None,
+        gate: Some(gate.into()),
+        ..result.clone()
+    }
+}
+
+fn has_failures(results: &[models::CreateGateResult]) -> bool {
+    results.iter().any(|result| {
+        matches!(
+            result.status,
+            models::CreateGateStatus::Invalid | models::CreateGateStatus::AlreadyExists
+        )
+    })
+}
+
+fn skip_candidates(results: &mut [models::CreateGateResult], candidates: &[(usize, Gate)]) {
+    for (index, _) in candidates {
+        if results[*index].status == models::CreateGateStatus::Skipped {
+            results[*index].message = Some("another gate could not be created".to_owned());
+        }
+    }
+}
+
+async fn insert_created_history_entry(
+    gate: &Gate,
+    actor: Option<String>,
+    storage: &(dyn Storage + Send + Sync),
+    id_provider: &(dyn IdProvider + Send + Sync),
+) -> Result<(), Error> {
+    history::record(
+        storage,
+        &HistoryEntry {
+            id: id_provider.get(),
+            key: gate.key.clone(),
+            timestamp: gate.last_updated,
+            event: GateEvent::Created,
+            old_value: None,
+            new_value: Some(gate.state.clone().try_into().map_err(Error::Internal)?),
+            open_until: None,
+            actor,
+        },
+    )
+    .await;
+    Ok(())
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use std::collections::HashSet;
+
+    use chrono::DateTime;
+    use mockall::predicate::eq;
+    use openapi::models;
+    use similar_asserts::assert_eq;
+
+    use crate::clock::MockClock;
+    use crate::id_provider::MockIdProvider;
+    use crate::storage;
+    use crate::storage::MockStorage;
+    use crate::types::{Gate, GateKey, GateState};
+    use crate::use_cases::create_gates::use_case::{Error, GateInput, Input, UseCase, UseCaseImpl};
+
+    #[tokio::test]
+    async fn should_create_gates_and_report_result_per_gate() {
+        // given
+        let mut mock_storage = MockStorage::new();
+        let mut mock_clock = MockClock::new();
+
+        let now = DateTime::parse_from_rfc3339("2023-04-12T22:10:57+02:00")
+            .expect("failed to parse date");
+        mock_clock.expect_now().return_const(now);
+
+        mock_storage
+            .expect_insert_each()
+            .with(eq(vec![
+                some_gate("dev", DateTime::from(now)),
+                some_gate("test", DateTime::from(now)),
+                some_gate("prod", DateTime::from(now)),
+            ]))
+            .times(1)
+            .returning(|_| {
+                Ok(vec![
+                    Ok(()),
+                    Err(storage::InsertError::ItemAlreadyExists(
+                        "item already exists".to_owned(),
+                    )),
+                    Err(storage::InsertError::Other("some error".to_owned())),
+                ])
+            });
+        mock_storage
+            .expect_insert_history_entry()
+            .times(1)
+            .returning(|_| Ok(()));
+
+        let mut mock_id_provider = MockIdProvider::new();
+        mock_id_provider
+            .expect_get()
+            .return_const("some id".to_owned());
+
+        // when
+        let result = UseCaseImpl {}
+            .execute(
+                Input {
+                    gates: vec![
+                        some_gate_input("dev"),
+                        some_gate_input("test"),
+                        some_gate_input("prod"),
+                        some_gate_input("dev"),
+                        some_gate_input(""),
+                    ],
+                    all_or_nothing: false,
+                    actor: None,
+                },
+                &mock_storage,
+                &mock_clock,
+                &mock_id_provider,
+            )
+            .await
+            .expect("There is no error expected here!");
+
+        // then
+        assert_eq!(
+            statuses(&result),
+            vec![
+                models::CreateGateStatus::Created,
+                models::CreateGateStatus::AlreadyExists,
+                models::CreateGateStatus::Failed,
+                models::CreateGateStatus::Invalid,
+                models::CreateGateStatus::Invalid,
+            ]
+        );
+        assert_eq!(
+            result.results[0].gate,
+            Some(some_gate("dev", DateTime::from(now)).into())
+        );
+        assert_eq!(result.results[2].message, Some("some error".to_owned()));
+        assert_eq!(
+            result.results[3].message,
+            Some("gate is contained more than once".to_owned())
+        );
+    }
+
+    #[tokio::test]
+    async fn should_create_all_gates_in_all_or_nothing_mode() {
+        // given
+        let mut mock_storage = MockStorage::new();
+        let mut mock_clock = MockClock::new();
+
+        let now = DateTime::parse_from_rfc3339("2023-04-12T22:10:57+02:00")
+            .expect("failed to parse date");
+        mock_clock.expect_now().return_const(now);
+
+        mock_storage.expect_find_one().never();
+        mock_storage
+            .expect_insert_all()
+            .with(eq(vec![
+                some_gate("dev", DateTime::from(now)),
+                some_gate("prod", DateTime::from(now)),
+            ]))
+            .returning(|_| Ok(()));
+        mock_storage
+            .expect_insert_history_entry()
+            .times(2)
+            .returning(|_| Ok(()));
+
+        let mut mock_id_provider = MockIdProvider::new();
+        mock_id_provider
+            .expect_get()
+            .return_const("some id".to_owned());
+
+        // when
+        let result = UseCaseImpl {}
+            .execute(
+                Input {
+                    gates: vec![some_gate_input("dev"), some_gate_input("prod")],
+                    all_or_nothing: true,
+                    actor: None,
+                },
+                &mock_storage,
+                &mock_clock,
+                &mock_id_provider,
+            )
+            .await
+            .expect("There is no error expected here!");
+
+        // then
+        assert_eq!(
+            statuses(&result),
+            vec![
+                models::CreateGateStatus::Created,
+                models::CreateGateStatus::Created,
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn should_create_no_gate_in_all_or_nothing_mode_if_one_already_exists() {
+        // given
+        let mut mock_storage = MockStorage::new();
+        let mut mock_clock = MockClock::new();
+
+        let now = DateTime::parse_from_rfc3339("2023-04-12T22:10:57+02:00")
+            .expect("failed to parse date");
+        mock_clock.expect_now().return_const(now);
+
+        mock_storage
+            .expect_find_one()
+            .with(eq(some_gate("dev", DateTime::from(now)).key))
+            .returning(|_| Ok(None));
+        mock_storage
+            .expect_find_one()
+            .with(eq(some_gate("prod", DateTime::from(now)).key))
+            .returning(move |_| Ok(Some(some_gate("prod", DateTime::from(now)))));
+        mock_storage.expect_insert_all().times(1).returning(|_| {
+            Err(storage::InsertError::ItemAlreadyExists(
+                "item already exists".to_owned(),
+            ))
+        });
+        mock_storage.expect_insert_history_entry().never();
+
+        // when
+        let result = UseCaseImpl {}
+            .execute(
+                Input {
+                    gates: vec![some_gate_input("dev"), some_gate_input("prod")],
+                    all_or_nothing: true,
+                    actor: None,
+                },
+                &mock_storage,
+                &mock_clock,
+                &MockIdProvider::new(),
+            )
+            .await
+            .expect("There is no error expected here!");
+
+        // then
+        assert_eq!(
+            statuses(&result),
+            vec![
+                models::CreateGateStatus::Skipped,
+                models::CreateGateStatus::AlreadyExists,
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn should_fail_in_all_or_nothing_mode_if_gates_could_not_be_inserted() {
+        // given
+        let mut mock_storage = MockStorage::new();
+        let mut mock_clock = MockClock::new();
+
+        let now = DateTime::parse_from_rfc3339("2023-04-12T22:10:57+02:00")
+            .expect("failed to parse date");
+        mock_clock.expect_now().return_const(now);
+
+        mock_storage
+            .expect_insert_all()
+            .returning(|_| Err(storage::InsertError::Other("some error".to_owned())));
+        mock_storage.expect_insert_history_entry().never();
+
+        // when
+        let result = UseCaseImpl {}
+            .execute(
+                Input {
+                    gates: vec![some_gate_input("dev"), some_gate_input("prod")],
+                    all_or_nothing: true,
+                    actor: None,
+                },
+                &mock_storage,
+                &mock_clock,
+                &MockIdProvider::new(),
+            )
+            .await;
+
+        // then
+        assert_eq!(
+            result.expect_err("Error expected here"),
+            Error::Internal("some error".to_owned())
+        );
+    }
+
+    #[tokio::test]
+    async fn should_fail_when_no_gates_are_given() {
+        // given
+        let mock_storage = MockStorage::new();
+        let mock_clock = MockClock::new();
+
+        // when
+        let result = UseCaseImpl {}
+            .execute(
+                Input {
+                    gates: Vec::default(),
+                    all_or_nothing: false,
+                    actor: None,
+                },
+                &mock_storage,
+                &mock_clock,
+                &MockIdProvider::new(),
+            )
+            .await;
+
+        // then
+        assert_eq!(
+            result.expect_err("Error expected here"),
+            Error::InvalidInput("between 1 and 100 gates must be given".to_owned())
+        );
+    }
+
+    fn statuses(result: &models::CreateGatesResult) -> Vec<models::CreateGateStatus> {
+        result.results.iter().map(|result| result.status).collect()
+    }
+
+    fn some_gate_input(environment: &str) -> GateInput {
+        GateInput {
+            group: "some group".to_owned(),
+            service: "some service".to_owned(),
+            environment: environment.to_owned(),
+            display_order: None,
+        }
+    }
+
+    fn some_gate(environment: &str, now: DateTime<chrono::Utc>) -> Gate {
+        Gate {
+            key: GateKey {
+                group: "some group".to_owned(),
+                service: "some service".to_owned(),
+                environment: environment.to_owned(),
+            },
+            state: GateState::Closed,
+            comments: HashSet::default(),
+            last_updated: now,
+            display_order: None,
+            version: 0,
+            open_until: None,
+        }
+    }
+}
@@ -0,0 +1,49 @@
+use axum::extract::{Query, State};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::Json;
+use serde::{Deserialize, Serialize};
+
+use crate::types::app_state::AppState;
+use crate::use_cases::delete_business_week_profile::use_case;
+use crate::use_cases::delete_business_week_profile::use_case::Error;
+
+#[derive(Serialize, Deserialize)]
+pub struct QueryParams {
+    group: String,
+    service: Option<String>,
+    environment: Option<String>,
+}
+
+pub async fn handler(
+    Query(QueryParams {
+        group,
+        service,
+        environment,
+    }): Query<QueryParams>,
+    State(app_state): State<AppState>,
+) -> impl IntoResponse {
+    match app_state
+        .use_cases
+        .delete_business_week_profile
+        .execute(
+            use_case::Input {
+                group,
+                service,
+                environment,
+            },
+            app_state.storage.as_ref(),
+            app_state.date_time_switch.as_ref(),
+        )
+        .await
+    {
+        Ok(()) => StatusCode::OK.into_response(),
+        Err(error) => match error {
+            Error::BusinessWeekProfileNotFound => StatusCode::NO_CONTENT.into_response(),
+            Error::InvalidInput(error) => (StatusCode::BAD_REQUEST, Json(error)).into_response(),
+            Error::Internal(error) => {
+                (StatusCode::INTERNAL_SERVER_ERROR, Json(error)).into_response()
+            }
+        },
+    }
+}
@@ -0,0 +1,156 @@
+use async_trait::async_trait;
+
+use crate::date_time_switch::DateTimeSwitch;
+use crate::storage;
+use crate::storage::Storage;
+use crate::types::BusinessWeekScope;
+
+#[derive(Debug)]
+pub struct Input {
+    pub group: String,
+    pub service: Option<String>,
+    pub environment: Option<String>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Error {
+    BusinessWeekProfileNotFound,
+    InvalidInput(String),
+    Internal(String),
+}
+
+impl From<storage::DeleteError> for Error {
+    fn from(value: storage::DeleteError) -> Self {
+        match value {
+            storage::DeleteError::ItemToDeleteNotFound(_) => Self::BusinessWeekProfileNotFound,
+            storage::DeleteError::Other(error) => Self::Internal(error),
+        }
+    }
+}
+
+#[async_trait]
+pub trait UseCase {
+    async fn execute(
+        &self,
+        input: Input,
+        storage: &(dyn Storage + Send + Sync),
+        date_time_switch: &(dyn DateTimeSwitch + Send + Sync),
+    ) -> Result<(), Error>;
+}
+
+pub fn create() -> impl UseCase {
+    UseCaseImpl {}
+}
+
+#[derive(Clone)]
+struct UseCaseImpl {}
+
+#[async_trait]
+impl UseCase for UseCaseImpl {
+    async fn execute(
+        &self,
+        Input {
+            group,
+            service,
+            environment,
+        }: Input,
+        storage: &(dyn Storage + Send + Sync),
+        date_time_switch: &(dyn DateTimeSwitch + Send + Sync),
+    ) -> Result<(), Error> {
+        let scope =
+            BusinessWeekScope::new(group, service, environment).map_err(Error::InvalidInput)?;
+
+        storage.delete_business_week_profile(scope).await?;
+        date_time_switch.clear_cache();
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use mockall::predicate::eq;
+
+    use crate::date_time_switch::MockDateTimeSwitch;
+    use crate::storage::{DeleteError, MockStorage};
+
+    use super::*;
+
+    fn some_input() -> Input {
+        Input {
+            group: "some group".to_owned(),
+            service: Some("some service".to_owned()),
+            environment: Some("live".to_owned()),
+        }
+    }
+
+    #[tokio::test]
+    async fn should_delete_business_week_profile() {
+        // given
+        let mut mock_storage = MockStorage::new();
+        mock_storage
+            .expect_delete_business_week_profile()
+            .with(eq(BusinessWeekScope {
+                group: "some group".to_owned(),
+                service: Some("some service".to_owned()),
+                environment: Some("live".to_owned()),
+            }))
+            .return_once(|_| Ok(()));
+
+        let mut mock_date_time_switch = MockDateTimeSwitch::new();
+        mock_date_time_switch
+            .expect_clear_cache()
+            .times(1)
+            .return_const(());
+
+        // when
+        let result = UseCaseImpl {}
+            .execute(some_input(), &mock_storage, &mock_date_time_switch)
+            .await;
+
+        // then
+        assert_eq!(result, Ok(()));
+    }
+
+    #[tokio::test]
+    async fn should_return_business_week_profile_not_found_error() {
+        // given
+        let mut mock_storage = MockStorage::new();
+        mock_storage
+            .expect_delete_business_week_profile()
+            .return_once(|_| {
+                Err(DeleteError::ItemToDeleteNotFound(
+                    "business week profile does not exist".to_owned(),
+                ))
+            });
+
+        // when
+        let result = UseCaseImpl {}
+            .execute(some_input(), &mock_storage, &MockDateTimeSwitch::new())
+            .await;
+
+        // then
+        assert_eq!(result, Err(Error::BusinessWeekProfileNotFound));
+    }
+
+    #[tokio::test]
+    async fn should_fail_for_environment_without_service() {
+        // given
+        let mock_storage = MockStorage::new();
+
+        // when
+        let result = UseCaseImpl {}
+            .execute(
+                Input {
+                    service: None,
+                    ..some_input()
+                },
+                &mock_storage,
+                &MockDateTimeSwitch::new(),
+            )
+            .await;
+
+        // then
+        assert!(matches!(result, Err(Error::InvalidInput(_))));
+    }
+}
@@ -0,0 +1,39 @@
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::Json;
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+use crate::types::app_state::AppState;
+use crate::use_cases::delete_closed_day::use_case;
+use crate::use_cases::delete_closed_day::use_case::Error;
+
+#[derive(Serialize, Deserialize)]
+pub struct PathParams {
+    date: NaiveDate,
+}
+
+pub async fn handler(
+    Path(PathParams { date }): Path<PathParams>,
+    State(app_state): State<AppState>,
+) -> impl IntoResponse {
+    match app_state
+        .use_cases
+        .delete_closed_day
+        .execute(
+            use_case::Input { date },
+            app_state.storage.as_ref(),
+            app_state.date_time_switch.as_ref(),
+        )
+        .await
+    {
+        Ok(()) => StatusCode::OK.into_response(),
+        Err(error) => match error {
+            Error::ClosedDayNotFound => StatusCode::NO_CONTENT.into_response(),
+            Error::Internal(error) => {
+                (StatusCode::INTERNAL_SERVER_ERROR, Json(error)).into_response()
+            }
+        },
+    }
+}
@@ -0,0 +1,118 @@
+use async_trait::async_trait;
+use chrono::NaiveDate;
+
+use crate::date_time_switch::DateTimeSwitch;
+use crate::storage;
+use crate::storage::Storage;
+
+#[derive(Debug)]
+pub struct Input {
+    pub date: NaiveDate,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Error {
+    ClosedDayNotFound,
+    Internal(String),
+}
+
+impl From<storage::DeleteError> for Error {
+    fn from(value: storage::DeleteError) -> Self {
+        match value {
+            storage::DeleteError::ItemToDeleteNotFound(_) => Self::ClosedDayNotFound,
+            storage::DeleteError::Other(error) => Self::Internal(error),
+        }
+    }
+}
+
+#[async_trait]
+pub trait UseCase {
+    async fn execute(
+        &self,
+        input: Input,
+        storage: &(dyn Storage + Send + Sync),
+        date_time_switch: &(dyn DateTimeSwitch + Send + Sync),
+    ) -> Result<(), Error>;
+}
+
+pub fn create() -> impl UseCase {
+    UseCaseImpl {}
+}
+
+#[derive(Clone)]
+struct UseCaseImpl {}
+
+#[async_trait]
+impl UseCase for UseCaseImpl {
+    async fn execute(
+        &self,
+        Input { date }: Input,
+        storage: &(dyn Storage + Send + Sync),
+        date_time_switch: &(dyn DateTimeSwitch + Send + Sync),
+    ) -> Result<(), Error> {
+        storage.delete_closed_day(date).await?;
+        date_time_switch.clear_cache();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use mockall::predicate::eq;
+
+    use crate::date_time_switch::MockDateTimeSwitch;
+    use crate::storage::{DeleteError, MockStorage};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn should_delete_closed_day() {
+        // given
+        let date = NaiveDate::from_ymd_opt(2025, 12, 24).unwrap();
+        let mut mock_storage = MockStorage::new();
+        mock_storage
+            .expect_delete_closed_day()
+            .with(eq(date))
+            .return_once(|_| Ok(()));
+
+        let mut mock_date_time_switch = MockDateTimeSwitch::new();
+        mock_date_time_switch
+            .expect_clear_cache()
+            .times(1)
+            .return_const(());
+
+        // when
+        let result = UseCaseImpl {}
+            .execute(Input { date }, &mock_storage, &mock_date_time_switch)
+            .await;
+
+        // then
+        assert_eq!(result, Ok(()));
+    }
+
+    #[tokio::test]
+    async fn should_return_closed_day_not_found_error() {
+        // given
+        let mut mock_storage = MockStorage::new();
+        mock_storage.expect_delete_closed_day().return_once(|_| {
+            Err(DeleteError::ItemToDeleteNotFound(
+                "closed day does not exist".to_owned(),
+            ))
+        });
+        let mock_date_time_switch = MockDateTimeSwitch::new();
+
+        // when
+        let result = UseCaseImpl {}
+            .execute(
+                Input {
+                    date: NaiveDate::from_ymd_opt(2025, 12, 24).unwrap(),
+                },
+                &mock_storage,
+                &mock_date_time_switch,
+            )
+            .await;
+
+        // then
+        assert_eq!(result, Err(Error::ClosedDayNotFound));
+    }
+}
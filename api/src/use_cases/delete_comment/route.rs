@@ -1,9 +1,12 @@
 use axum::extract::{Path, State};
-use axum::http::StatusCode;
+use axum::http::header::ETAG;
+use axum::http::{HeaderMap, StatusCode};
 use axum::response::IntoResponse;
 use axum::Json;
 use serde::{Deserialize, Serialize};
 
+use crate::actor;
+use crate::etag;
 use crate::types::app_state::AppState;
 use crate::use_cases::delete_comment::use_case;
 use crate::use_cases::delete_comment::use_case::Error;
@@ -24,7 +27,12 @@ pub async fn handler(
         comment_id,
     }): Path<PathParams>,
     State(app_state): State<AppState>,
+    headers: HeaderMap,
 ) -> impl IntoResponse {
+    let if_match = match etag::if_match(&headers) {
+        Ok(if_match) => if_match,
+        Err(error) => return (StatusCode::BAD_REQUEST, Json(error)).into_response(),
+    };
     match app_state
         .use_cases
         .delete_comment
@@ -34,15 +42,21 @@ pub async fn handler(
                 service,
                 environment,
                 comment_id,
+                expected_version: if_match,
+                actor: actor::from_headers(&headers),
             },
             app_state.storage.as_ref(),
             app_state.clock.as_ref(),
+            app_state.id_provider.as_ref(),
         )
         .await
     {
-        Ok(gate) => Json(gate).into_response(),
+        Ok(gate) => ([(ETAG, etag::from_version(gate.version))], Json(gate)).into_response(),
         Err(error) => match error {
             Error::GateOrCommentNotFound => StatusCode::NO_CONTENT.into_response(),
+            Error::VersionConflict(error) => {
+                (StatusCode::PRECONDITION_FAILED, Json(error)).into_response()
+            }
             Error::Internal(error) => {
                 (StatusCode::INTERNAL_SERVER_ERROR, Json(error)).into_response()
             }
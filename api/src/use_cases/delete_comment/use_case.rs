@@ -1,7 +1,9 @@
 use crate::clock::Clock;
+use crate::history;
+use crate::id_provider::IdProvider;
 use crate::storage;
 use crate::storage::Storage;
-use crate::types::GateKey;
+use crate::types::{GateEvent, GateKey, HistoryEntry};
 use async_trait::async_trait;
 use openapi::models;
 
@@ -11,11 +13,14 @@ pub struct Input {
     pub service: String,
     pub environment: String,
     pub comment_id: String,
+    pub expected_version: Option<u32>,
+    pub actor: Option<String>,
 }
 
 #[derive(Debug, PartialEq, Eq)]
 pub enum Error {
     GateOrCommentNotFound,
+    VersionConflict(String),
     Internal(String),
 }
 
@@ -23,11 +28,22 @@ impl From<storage::UpdateError> for Error {
     fn from(value: storage::UpdateError) -> Self {
         match value {
             storage::UpdateError::ItemToUpdateNotFound(_) => Self::GateOrCommentNotFound,
+            storage::UpdateError::VersionConflict(error) => Self::VersionConflict(error),
             storage::UpdateError::Other(error) => Self::Internal(error),
         }
     }
 }
 
+impl From<storage::FindError> for Error {
+    fn from(value: storage::FindError) -> Self {
+        match value {
+            storage::FindError::ItemCouldNotBeDecoded(error)
+            | storage::FindError::InvalidCursor(error)
+            | storage::FindError::Other(error) => Self::Internal(error),
+        }
+    }
+}
+
 #[async_trait]
 pub trait UseCase {
     async fn execute(
@@ -35,6 +51,7 @@ pub trait UseCase {
         input: Input,
         storage: &(dyn Storage + Send + Sync),
         clock: &(dyn Clock + Send + Sync),
+        id_provider: &(dyn IdProvider + Send + Sync),
     ) -> Result<models::Gate, Error>;
 }
 
@@ -54,35 +71,64 @@ impl UseCase for UseCaseImpl {
             service,
             environment,
             comment_id,
+            expected_version,
+            actor,
         }: Input,
         storage: &(dyn Storage + Send + Sync),
         clock: &(dyn Clock + Send + Sync),
+        id_provider: &(dyn IdProvider + Send + Sync),
     ) -> Result<models::Gate, Error> {
-        Ok(storage
-            .delete_comment_by_id_and_update_last_updated(
-                GateKey {
-                    group,
-                    service,
-                    environment,
-                },
-                comment_id,
-                clock.now(),
-            )
+        let now = clock.now();
+        let key = GateKey {
+            group,
+            service,
+            environment,
+        };
+        let message = storage
+            .find_one(key.clone())
             .await?
-            .into())
+            .and_then(|gate| {
+                gate.comments
+                    .into_iter()
+                    .find(|comment| comment.id == comment_id)
+            })
+            .map(|comment| comment.message);
+
+        let gate = storage
+            .delete_comment_by_id_and_update_last_updated(key, comment_id, now, expected_version)
+            .await?;
+
+        history::record(
+            storage,
+            &HistoryEntry {
+                id: id_provider.get(),
+                key: gate.key.clone(),
+                timestamp: now,
+                event: GateEvent::CommentDeleted,
+                old_value: message,
+                new_value: None,
+                open_until: None,
+                actor,
+            },
+        )
+        .await;
+
+        Ok(gate.into())
     }
 }
 
 #[cfg(test)]
 mod unit_tests {
     use chrono::DateTime;
+    use mockall::predicate::eq;
     use similar_asserts::assert_eq;
     use std::collections::HashSet;
 
     use crate::clock::MockClock;
+    use crate::id_provider::MockIdProvider;
     use crate::storage;
     use crate::storage::MockStorage;
-    use crate::types::{Gate, GateState};
+    use crate::types::{Comment, Gate, GateState};
 
     use super::*;
 
@@ -95,17 +141,56 @@ mod unit_tests {
             .to_utc();
         mock_clock.expect_now().return_const(now);
 
+        mock_storage.expect_find_one().return_once(move |key| {
+            Ok(Some(Gate {
+                key,
+                state: GateState::Open,
+                comments: HashSet::from([Comment {
+                    id: "comment_id".to_owned(),
+                    message: "some comment".to_owned(),
+                    created: now,
+                }]),
+                last_updated: now,
+                display_order: Option::default(),
+                version: 0,
+                open_until: None,
+            }))
+        });
         mock_storage
             .expect_delete_comment_by_id_and_update_last_updated()
-            .return_once(|key, _, now| {
+            .return_once(|key, _, now, _| {
                 Ok(Gate {
                     key,
                     state: GateState::Open,
                     comments: HashSet::default(),
                     last_updated: now,
                     display_order: Option::default(),
+                    version: 0,
+                    open_until: None,
                 })
             });
+        mock_storage
+            .expect_insert_history_entry()
+            .with(eq(HistoryEntry {
+                id: "some id".to_owned(),
+                key: GateKey {
+                    group: "group".to_string(),
+                    service: "service".to_string(),
+                    environment: "environment".to_string(),
+                },
+                timestamp: now,
+                event: GateEvent::CommentDeleted,
+                old_value: Some("some comment".to_owned()),
+                new_value: None,
+                open_until: None,
+                actor: None,
+            }))
+            .return_once(|_| Ok(()));
+
+        let mut mock_id_provider = MockIdProvider::new();
+        mock_id_provider
+            .expect_get()
+            .return_const("some id".to_owned());
 
         let left = UseCaseImpl {}
             .execute(
@@ -114,9 +199,12 @@ mod unit_tests {
                     service: "service".to_string(),
                     environment: "environment".to_string(),
                     comment_id: "comment_id".to_string(),
+                    expected_version: None,
+                    actor: None,
                 },
                 &mock_storage,
                 &mock_clock,
+                &mock_id_provider,
             )
             .await;
 
@@ -128,6 +216,10 @@ mod unit_tests {
             comments: vec![],
             last_updated: now.to_rfc3339(),
             display_order: Option::default(),
+            version: 0,
+            open_until: None,
+            open_remaining_seconds: None,
+            closure_reason: None,
         };
         assert_eq!(left.unwrap(), expected);
     }
@@ -140,9 +232,10 @@ mod unit_tests {
             .expect("failed to parse date");
         mock_clock.expect_now().return_const(now);
 
+        mock_storage.expect_find_one().return_once(|_| Ok(None));
         mock_storage
             .expect_delete_comment_by_id_and_update_last_updated()
-            .return_once(|_, _, _| {
+            .return_once(|_, _, _, _| {
                 Err(storage::UpdateError::ItemToUpdateNotFound(
                     "ConditionalCheckFailedException".to_string(),
                 ))
@@ -155,9 +248,12 @@ mod unit_tests {
                     service: "service".to_string(),
                     environment: "environment".to_string(),
                     comment_id: "comment_id".to_string(),
+                    expected_version: None,
+                    actor: None,
                 },
                 &mock_storage,
                 &mock_clock,
+                &MockIdProvider::new(),
             )
             .await;
 
@@ -176,9 +272,10 @@ mod unit_tests {
             .expect("failed to parse date");
         mock_clock.expect_now().return_const(now);
 
+        mock_storage.expect_find_one().return_once(|_| Ok(None));
         mock_storage
             .expect_delete_comment_by_id_and_update_last_updated()
-            .return_once(|_, _, _| Err(storage::UpdateError::Other("some error".to_string())));
+            .return_once(|_, _, _, _| Err(storage::UpdateError::Other("some error".to_string())));
 
         let left = UseCaseImpl {}
             .execute(
@@ -187,9 +284,12 @@ mod unit_tests {
                     service: "service".to_string(),
                     environment: "environment".to_string(),
                     comment_id: "comment_id".to_string(),
+                    expected_version: None,
+                    actor: None,
                 },
                 &mock_storage,
                 &mock_clock,
+                &MockIdProvider::new(),
             )
             .await;
 
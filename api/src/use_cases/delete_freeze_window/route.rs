@@ -0,0 +1,39 @@
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::Json;
+use serde::{Deserialize, Serialize};
+
+use crate::types::app_state::AppState;
+use crate::use_cases::delete_freeze_window::use_case;
+use crate::use_cases::delete_freeze_window::use_case::Error;
+
+#[derive(Serialize, Deserialize)]
+pub struct PathParams {
+    freeze_window_id: String,
+}
+
+pub async fn handler(
+    Path(PathParams { freeze_window_id }): Path<PathParams>,
+    State(app_state): State<AppState>,
+) -> impl IntoResponse {
+    match app_state
+        .use_cases
+        .delete_freeze_window
+        .execute(
+            use_case::Input {
+                id: freeze_window_id,
+            },
+            app_state.storage.as_ref(),
+        )
+        .await
+    {
+        Ok(()) => StatusCode::OK.into_response(),
+        Err(error) => match error {
+            Error::FreezeWindowNotFound => StatusCode::NO_CONTENT.into_response(),
+            Error::Internal(error) => {
+                (StatusCode::INTERNAL_SERVER_ERROR, Json(error)).into_response()
+            }
+        },
+    }
+}
@@ -0,0 +1,108 @@
+use async_trait::async_trait;
+
+use crate::storage;
+use crate::storage::Storage;
+
+#[derive(Debug)]
+pub struct Input {
+    pub id: String,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Error {
+    FreezeWindowNotFound,
+    Internal(String),
+}
+
+impl From<storage::DeleteError> for Error {
+    fn from(value: storage::DeleteError) -> Self {
+        match value {
+            storage::DeleteError::ItemToDeleteNotFound(_) => Self::FreezeWindowNotFound,
+            storage::DeleteError::Other(error) => Self::Internal(error),
+        }
+    }
+}
+
+#[async_trait]
+pub trait UseCase {
+    async fn execute(
+        &self,
+        input: Input,
+        storage: &(dyn Storage + Send + Sync),
+    ) -> Result<(), Error>;
+}
+
+pub fn create() -> impl UseCase {
+    UseCaseImpl {}
+}
+
+#[derive(Clone)]
+struct UseCaseImpl {}
+
+#[async_trait]
+impl UseCase for UseCaseImpl {
+    async fn execute(
+        &self,
+        Input { id }: Input,
+        storage: &(dyn Storage + Send + Sync),
+    ) -> Result<(), Error> {
+        storage.delete_freeze_window(id).await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use mockall::predicate::eq;
+
+    use crate::storage::{DeleteError, MockStorage};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn should_delete_freeze_window() {
+        // given
+        let mut mock_storage = MockStorage::new();
+        mock_storage
+            .expect_delete_freeze_window()
+            .with(eq("some id".to_owned()))
+            .return_once(|_| Ok(()));
+
+        // when
+        let result = UseCaseImpl {}
+            .execute(
+                Input {
+                    id: "some id".to_owned(),
+                },
+                &mock_storage,
+            )
+            .await;
+
+        // then
+        assert_eq!(result, Ok(()));
+    }
+
+    #[tokio::test]
+    async fn should_return_freeze_window_not_found_error() {
+        // given
+        let mut mock_storage = MockStorage::new();
+        mock_storage.expect_delete_freeze_window().return_once(|_| {
+            Err(DeleteError::ItemToDeleteNotFound(
+                "freeze window does not exist".to_owned(),
+            ))
+        });
+
+        // when
+        let result = UseCaseImpl {}
+            .execute(
+                Input {
+                    id: "some id".to_owned(),
+                },
+                &mock_storage,
+            )
+            .await;
+
+        // then
+        assert_eq!(result, Err(Error::FreezeWindowNotFound));
+    }
+}
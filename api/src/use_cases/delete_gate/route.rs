@@ -1,8 +1,9 @@
+use crate::actor;
 use crate::types::app_state::AppState;
 use crate::use_cases::delete_gate::use_case;
 use crate::use_cases::delete_gate::use_case::Error;
 use axum::extract::{Path, State};
-use axum::http::StatusCode;
+use axum::http::{HeaderMap, StatusCode};
 use axum::response::IntoResponse;
 use axum::Json;
 use serde::{Deserialize, Serialize};
@@ -21,6 +22,7 @@ pub async fn handler(
         environment,
     }): Path<PathParams>,
     State(app_state): State<AppState>,
+    headers: HeaderMap,
 ) -> impl IntoResponse {
     match app_state
         .use_cases
@@ -30,8 +32,11 @@ pub async fn handler(
                 group,
                 service,
                 environment,
+                actor: actor::from_headers(&headers),
             },
             app_state.storage.as_ref(),
+            app_state.clock.as_ref(),
+            app_state.id_provider.as_ref(),
         )
         .await
     {
@@ -1,6 +1,9 @@
+use crate::clock::Clock;
+use crate::history;
+use crate::id_provider::IdProvider;
 use crate::storage;
 use crate::storage::Storage;
-use crate::types::GateKey;
+use crate::types::{GateEvent, GateKey, HistoryEntry};
 use async_trait::async_trait;
 
 #[derive(Debug)]
@@ -8,6 +11,7 @@ pub struct Input {
     pub group: String,
     pub service: String,
     pub environment: String,
+    pub actor: Option<String>,
 }
 
 #[derive(Debug, PartialEq, Eq)]
@@ -31,6 +35,8 @@ pub trait UseCase {
         &self,
         input: Input,
         storage: &(dyn Storage + Send + Sync),
+        clock: &(dyn Clock + Send + Sync),
+        id_provider: &(dyn IdProvider + Send + Sync),
     ) -> Result<(), Error>;
 }
 
@@ -49,26 +55,46 @@ impl UseCase for UseCaseImpl {
             group,
             service,
             environment,
+            actor,
         }: Input,
         storage: &(dyn Storage + Send + Sync),
+        clock: &(dyn Clock + Send + Sync),
+        id_provider: &(dyn IdProvider + Send + Sync),
     ) -> Result<(), Error> {
-        Ok(storage
-            .delete(GateKey {
-                group,
-                service,
-                environment,
-            })
-            .await?)
+        let key = GateKey {
+            group,
+            service,
+            environment,
+        };
+        let now = clock.now();
+        storage.move_to_trash(key.clone(), now).await?;
+
+        history::record(
+            storage,
+            &HistoryEntry {
+                id: id_provider.get(),
+                key,
+                timestamp: now,
+                event: GateEvent::Deleted,
+                old_value: None,
+                new_value: None,
+                open_until: None,
+                actor,
+            },
+        )
+        .await;
+        Ok(())
     }
 }
 
 #[cfg(test)]
 mod unit_tests {
-    use chrono::DateTime;
+    use chrono::{DateTime, Utc};
     use mockall::predicate::eq;
 
     use crate::clock::MockClock;
-    use crate::storage::{DeleteError, MockStorage};
+    use crate::id_provider::MockIdProvider;
+    use crate::storage::{DeleteError, InsertError, MockStorage};
     use crate::types::GateKey;
 
     use super::*;
@@ -82,28 +108,93 @@ mod unit_tests {
         mock_clock.expect_now().return_const(now);
 
         mock_storage
-            .expect_delete()
-            .with(eq(GateKey {
-                group: "group".to_string(),
-                service: "service".to_string(),
-                environment: "develop".to_string(),
+            .expect_move_to_trash()
+            .with(
+                eq(GateKey {
+                    group: "group".to_string(),
+                    service: "service".to_string(),
+                    environment: "develop".to_string(),
+                }),
+                eq(DateTime::<Utc>::from(now)),
+            )
+            .return_once(|_, _| Ok(()));
+        mock_storage
+            .expect_insert_history_entry()
+            .with(eq(HistoryEntry {
+                id: "some id".to_owned(),
+                key: GateKey {
+                    group: "group".to_string(),
+                    service: "service".to_string(),
+                    environment: "develop".to_string(),
+                },
+                timestamp: now.into(),
+                event: GateEvent::Deleted,
+                old_value: None,
+                new_value: None,
+                open_until: None,
+                actor: Some("Max".to_owned()),
             }))
             .return_once(|_| Ok(()));
 
+        let mut mock_id_provider = MockIdProvider::new();
+        mock_id_provider
+            .expect_get()
+            .return_const("some id".to_owned());
+
         let left = UseCaseImpl {}
             .execute(
                 Input {
                     group: "group".to_owned(),
                     service: "service".to_owned(),
                     environment: "develop".to_owned(),
+                    actor: Some("Max".to_owned()),
                 },
                 &mock_storage,
+                &mock_clock,
+                &mock_id_provider,
             )
             .await;
 
         assert!(left.is_ok());
     }
 
+    #[tokio::test]
+    async fn should_delete_gate_even_if_history_could_not_be_recorded() {
+        let mut mock_storage = MockStorage::new();
+        let mut mock_clock = MockClock::new();
+        let now = DateTime::parse_from_rfc3339("2023-04-12T22:10:57+02:00")
+            .expect("failed to parse date");
+        mock_clock.expect_now().return_const(now);
+
+        mock_storage
+            .expect_move_to_trash()
+            .return_once(|_, _| Ok(()));
+        mock_storage
+            .expect_insert_history_entry()
+            .return_once(|_| Err(InsertError::Other("some error".to_owned())));
+
+        let mut mock_id_provider = MockIdProvider::new();
+        mock_id_provider
+            .expect_get()
+            .return_const("some id".to_owned());
+
+        let left = UseCaseImpl {}
+            .execute(
+                Input {
+                    group: "group".to_owned(),
+                    service: "service".to_owned(),
+                    environment: "develop".to_owned(),
+                    actor: None,
+                },
+                &mock_storage,
+                &mock_clock,
+                &mock_id_provider,
+            )
+            .await;
+
+        assert_eq!(left, Ok(()));
+    }
+
     #[tokio::test]
     async fn should_return_gate_not_found_error_if_storage_could_not_find_item_to_delete() {
         let mut mock_storage = MockStorage::new();
@@ -113,7 +204,7 @@ mod unit_tests {
             .expect("failed to parse date");
         mock_clock.expect_now().return_const(now);
 
-        mock_storage.expect_delete().returning(move |_| {
+        mock_storage.expect_move_to_trash().returning(move |_, _| {
             Err(DeleteError::ItemToDeleteNotFound(
                 "ConditionalCheckFailedException".to_owned(),
             ))
@@ -125,8 +216,11 @@ mod unit_tests {
                     group: "some group".to_owned(),
                     service: "some service".to_owned(),
                     environment: "some environment".to_owned(),
+                    actor: None,
                 },
                 &mock_storage,
+                &mock_clock,
+                &MockIdProvider::new(),
             )
             .await;
 
@@ -144,8 +238,8 @@ mod unit_tests {
         mock_clock.expect_now().return_const(now);
 
         mock_storage
-            .expect_delete()
-            .returning(move |_| Err(DeleteError::Other("some error".to_owned())));
+            .expect_move_to_trash()
+            .returning(move |_, _| Err(DeleteError::Other("some error".to_owned())));
 
         let left = UseCaseImpl {}
             .execute(
@@ -153,8 +247,11 @@ mod unit_tests {
                     group: "some group".to_owned(),
                     service: "some service".to_owned(),
                     environment: "some environment".to_owned(),
+                    actor: None,
                 },
                 &mock_storage,
+                &mock_clock,
+                &MockIdProvider::new(),
             )
             .await;
 
@@ -4,15 +4,22 @@ use axum::response::IntoResponse;
 use axum::Json;
 
 use crate::types::app_state::AppState;
+use crate::use_cases::get_config::use_case::Error;
 
 pub async fn handler(State(app_state): State<AppState>) -> impl IntoResponse {
     match app_state
         .use_cases
         .get_config
-        .execute(app_state.clock.as_ref(), app_state.business_week)
+        .execute(
+            app_state.storage.as_ref(),
+            app_state.clock.as_ref(),
+            app_state.date_time_switch.as_ref(),
+        )
         .await
     {
         Ok(config) => Json(config).into_response(),
-        _err => StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+        Err(Error::Internal(error)) => {
+            (StatusCode::INTERNAL_SERVER_ERROR, Json(error)).into_response()
+        }
     }
 }
@@ -3,17 +3,33 @@ use openapi::models;
 use openapi::models::Config;
 
 use crate::clock::Clock;
-use crate::types::BusinessWeek;
+use crate::date_time_switch::DateTimeSwitch;
+use crate::freeze;
+use crate::storage;
+use crate::storage::Storage;
 
 #[derive(Debug, PartialEq, Eq)]
-pub enum Error {}
+pub enum Error {
+    Internal(String),
+}
+
+impl From<storage::FindError> for Error {
+    fn from(value: storage::FindError) -> Self {
+        match value {
+            storage::FindError::ItemCouldNotBeDecoded(error)
+            | storage::FindError::InvalidCursor(error)
+            | storage::FindError::Other(error) => Self::Internal(error),
+        }
+    }
+}
 
 #[async_trait]
 pub trait UseCase {
     async fn execute(
         &self,
+        storage: &(dyn Storage + Send + Sync),
         clock: &(dyn Clock + Send + Sync),
-        business_week: BusinessWeek,
+        date_time_switch: &(dyn DateTimeSwitch + Send + Sync),
     ) -> Result<Config, Error>;
 }
 
@@ -28,53 +44,117 @@ struct UseCaseImpl {}
 impl UseCase for UseCaseImpl {
     async fn execute(
         &self,
+        storage: &(dyn Storage + Send + Sync),
         clock: &(dyn Clock + Send + Sync),
-        business_week: BusinessWeek,
+        date_time_switch: &(dyn DateTimeSwitch + Send + Sync),
     ) -> Result<Config, Error> {
+        let now = clock.now();
+        let business_week = date_time_switch.business_week().await;
+        let today = now.with_timezone(&business_week.time_zone).date_naive();
+        let closed_days = storage
+            .find_closed_days()
+            .await?
+            .into_iter()
+            .filter(|closed_day| closed_day.date >= today)
+            .map(Into::into)
+            .collect();
+        let enabled = date_time_switch.is_enabled();
+        let transitions = if enabled {
+            Some(date_time_switch.next_global_transitions(now).await.into())
+        } else {
+            None
+        };
+        let time_zone = business_week.time_zone.name().to_owned();
         let openapi_business_week: models::BusinessWeek = business_week.into();
-        Ok(Config::new(clock.now().to_rfc3339(), openapi_business_week))
+        Ok(Config {
+            freeze: freeze::active(storage, now).await?.freeze.map(Into::into),
+            transitions,
+            ..Config::new(
+                now.to_rfc3339(),
+                enabled,
+                openapi_business_week,
+                time_zone,
+                closed_days,
+            )
+        })
     }
 }
 
 #[cfg(test)]
 mod unit_tests {
     use crate::clock::MockClock;
-    use crate::types::{BusinessTimes, BusinessWeek};
+    use crate::date_time_switch::{MockDateTimeSwitch, Transitions};
+    use crate::storage::MockStorage;
+    use crate::types::{BusinessTimes, BusinessWeek, ClosedDay, Freeze};
     use crate::use_cases::get_config::use_case::{UseCase, UseCaseImpl};
-    use chrono::{DateTime, NaiveTime, Utc};
+    use chrono::{DateTime, NaiveDate, NaiveTime, Utc};
     use openapi::models;
     use rstest::rstest;
     use similar_asserts::assert_eq;
 
-    pub const fn test_data() -> BusinessWeek {
+    pub fn test_data() -> BusinessWeek {
         BusinessWeek {
-            monday: Some(BusinessTimes {
+            monday: vec![BusinessTimes {
                 start: NaiveTime::from_hms_opt(9, 0, 0).unwrap(),
                 end: NaiveTime::from_hms_opt(18, 0, 0).unwrap(),
-            }),
-            tuesday: Some(BusinessTimes {
+            }],
+            tuesday: vec![BusinessTimes {
                 start: NaiveTime::from_hms_opt(8, 0, 0).unwrap(),
                 end: NaiveTime::from_hms_opt(18, 0, 0).unwrap(),
-            }),
-            wednesday: None,
-            thursday: None,
-            friday: Some(BusinessTimes {
+            }],
+            wednesday: vec![],
+            thursday: vec![],
+            friday: vec![BusinessTimes {
                 start: NaiveTime::from_hms_opt(10, 0, 0).unwrap(),
                 end: NaiveTime::from_hms_opt(16, 0, 0).unwrap(),
-            }),
-            saturday: None,
-            sunday: None,
+            }],
+            saturday: vec![],
+            sunday: vec![],
+            time_zone: chrono_tz::America::New_York,
         }
     }
 
-    #[rstest(business_week, expected_business_times,
-        case(test_data(), test_data().into()),
-        case(BusinessWeek::default(), BusinessWeek::default().into()),
+    fn given_date_time_switch(business_week: BusinessWeek) -> MockDateTimeSwitch {
+        given_date_time_switch_enabled(business_week, true)
+    }
+
+    fn given_date_time_switch_enabled(
+        business_week: BusinessWeek,
+        enabled: bool,
+    ) -> MockDateTimeSwitch {
+        let mut mock_date_time_switch = MockDateTimeSwitch::new();
+        mock_date_time_switch
+            .expect_business_week()
+            .return_const(business_week);
+        mock_date_time_switch
+            .expect_is_enabled()
+            .return_const(enabled);
+        mock_date_time_switch
+            .expect_next_global_transitions()
+            .return_const(Transitions {
+                next_open: Some(
+                    DateTime::parse_from_rfc3339("2023-04-14T14:00:00+00:00")
+                        .unwrap()
+                        .to_utc(),
+                ),
+                next_close: Some(
+                    DateTime::parse_from_rfc3339("2023-04-14T20:00:00+00:00")
+                        .unwrap()
+                        .to_utc(),
+                ),
+            });
+        mock_date_time_switch
+    }
+
+    #[rstest(business_week, expected_business_times, expected_time_zone,
+        case(test_data(), test_data().into(), "America/New_York"),
+        case(BusinessWeek::default(), BusinessWeek::default().into(), "Europe/Berlin"),
     )]
     #[tokio::test]
     async fn should_get_config(
         business_week: BusinessWeek,
         expected_business_times: models::BusinessWeek,
+        expected_time_zone: &str,
     ) {
         // given
         let mut mock_clock = MockClock::new();
@@ -83,13 +163,161 @@ mod unit_tests {
             .to_utc();
         mock_clock.expect_now().return_const(now);
 
+        let mut mock_storage = MockStorage::new();
+        mock_storage.expect_find_freeze().return_once(|| Ok(None));
+        mock_storage
+            .expect_find_freeze_windows()
+            .return_once(|| Ok(vec![]));
+        mock_storage
+            .expect_find_closed_days()
+            .return_once(|| Ok(vec![]));
+
         // when
-        let actual = UseCaseImpl {}.execute(&mock_clock, business_week).await;
+        let actual = UseCaseImpl {}
+            .execute(
+                &mock_storage,
+                &mock_clock,
+                &given_date_time_switch(business_week),
+            )
+            .await;
 
         // then
         assert!(actual.is_ok());
         let config_result = actual.unwrap();
         assert_eq!(config_result.system_time, now.to_rfc3339());
+        assert!(config_result.business_hours_enabled);
         assert_eq!(config_result.business_week, expected_business_times);
+        assert_eq!(config_result.time_zone, expected_time_zone);
+        assert_eq!(config_result.freeze, None);
+        assert_eq!(config_result.closed_days, vec![]);
+    }
+
+    #[rstest(enabled, expected_transitions,
+        case(true, Some(models::Transitions {
+            next_open: Some("2023-04-14T14:00:00+00:00".to_owned()),
+            next_close: Some("2023-04-14T20:00:00+00:00".to_owned()),
+        })),
+        case(false, None),
+    )]
+    #[tokio::test]
+    async fn should_get_config_with_transitions(
+        enabled: bool,
+        expected_transitions: Option<models::Transitions>,
+    ) {
+        // given
+        let mut mock_clock = MockClock::new();
+        let now: DateTime<Utc> = DateTime::parse_from_rfc3339("2023-04-12T22:10:57+02:00")
+            .expect("failed to parse date")
+            .to_utc();
+        mock_clock.expect_now().return_const(now);
+
+        let mut mock_storage = MockStorage::new();
+        mock_storage.expect_find_freeze().return_once(|| Ok(None));
+        mock_storage
+            .expect_find_freeze_windows()
+            .return_once(|| Ok(vec![]));
+        mock_storage
+            .expect_find_closed_days()
+            .return_once(|| Ok(vec![]));
+
+        // when
+        let actual = UseCaseImpl {}
+            .execute(
+                &mock_storage,
+                &mock_clock,
+                &given_date_time_switch_enabled(test_data(), enabled),
+            )
+            .await;
+
+        // then
+        assert_eq!(
+            actual
+                .expect("There is no error expected here!")
+                .transitions,
+            expected_transitions
+        );
+    }
+
+    #[tokio::test]
+    async fn should_get_config_with_upcoming_closed_days() {
+        // given
+        let mut mock_clock = MockClock::new();
+        let now: DateTime<Utc> = DateTime::parse_from_rfc3339("2023-12-24T23:30:00+00:00")
+            .expect("failed to parse date")
+            .to_utc();
+        mock_clock.expect_now().return_const(now);
+
+        let closed_day = |day| ClosedDay {
+            date: NaiveDate::from_ymd_opt(2023, 12, day).unwrap(),
+            reason: "some holiday".to_owned(),
+        };
+        let mut mock_storage = MockStorage::new();
+        mock_storage.expect_find_freeze().return_once(|| Ok(None));
+        mock_storage
+            .expect_find_freeze_windows()
+            .return_once(|| Ok(vec![]));
+        mock_storage
+            .expect_find_closed_days()
+            .return_once(move || Ok(vec![closed_day(24), closed_day(25), closed_day(26)]));
+
+        // when
+        let actual = UseCaseImpl {}
+            .execute(
+                &mock_storage,
+                &mock_clock,
+                &given_date_time_switch(BusinessWeek::default()),
+            )
+            .await;
+
+        // then
+        assert_eq!(
+            actual
+                .expect("There is no error expected here!")
+                .closed_days,
+            vec![closed_day(25).into(), closed_day(26).into()]
+        );
+    }
+
+    #[tokio::test]
+    async fn should_get_config_with_active_freeze() {
+        // given
+        let mut mock_clock = MockClock::new();
+        let now: DateTime<Utc> = DateTime::parse_from_rfc3339("2023-04-12T22:10:57+02:00")
+            .expect("failed to parse date")
+            .to_utc();
+        mock_clock.expect_now().return_const(now);
+
+        let freeze = Freeze {
+            reason: "release freeze".to_owned(),
+            actor: Some("Max".to_owned()),
+            created: now,
+            until: None,
+        };
+        let mut mock_storage = MockStorage::new();
+        mock_storage
+            .expect_find_closed_days()
+            .return_once(|| Ok(vec![]));
+        let stored_freeze = freeze.clone();
+        mock_storage
+            .expect_find_freeze()
+            .return_once(|| Ok(Some(stored_freeze)));
+        mock_storage
+            .expect_find_freeze_windows()
+            .return_once(|| Ok(vec![]));
+
+        // when
+        let actual = UseCaseImpl {}
+            .execute(
+                &mock_storage,
+                &mock_clock,
+                &given_date_time_switch(BusinessWeek::default()),
+            )
+            .await;
+
+        // then
+        assert_eq!(
+            actual.expect("There is no error expected here!").freeze,
+            Some(freeze.into())
+        );
     }
 }
@@ -1,7 +1,9 @@
+use crate::etag;
 use crate::types::app_state::AppState;
 use crate::use_cases::get_gate::use_case;
 use crate::use_cases::get_gate::use_case::Error;
 use axum::extract::{Path, State};
+use axum::http::header::ETAG;
 use axum::http::StatusCode;
 use axum::response::IntoResponse;
 use axum::Json;
@@ -37,7 +39,7 @@ pub async fn handler(
         )
         .await
     {
-        Ok(Some(gate)) => Json(gate).into_response(),
+        Ok(Some(gate)) => ([(ETAG, etag::from_version(gate.version))], Json(gate)).into_response(),
         Ok(None) => StatusCode::NO_CONTENT.into_response(),
         Err(error) => match error {
             Error::Internal(error) => {
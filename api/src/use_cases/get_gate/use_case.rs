@@ -1,7 +1,10 @@
+use std::slice;
+
 use async_trait::async_trait;
 use openapi::models;
 
 use crate::clock::Clock;
+use crate::closure::Closures;
 use crate::date_time_switch::DateTimeSwitch;
 use crate::storage;
 use crate::storage::Storage;
@@ -22,9 +25,9 @@ pub enum Error {
 impl From<storage::FindError> for Error {
     fn from(value: storage::FindError) -> Self {
         match value {
-            storage::FindError::ItemCouldNotBeDecoded(error) | storage::FindError::Other(error) => {
-                Self::Internal(error)
-            }
+            storage::FindError::ItemCouldNotBeDecoded(error)
+            | storage::FindError::InvalidCursor(error)
+            | storage::FindError::Other(error) => Self::Internal(error),
         }
     }
 }
@@ -60,16 +63,19 @@ impl UseCase for UseCaseImpl {
         clock: &(dyn Clock + Send + Sync),
         date_time_switch: &(dyn DateTimeSwitch + Send + Sync),
     ) -> Result<Option<models::Gate>, Error> {
-        let gate = storage
+        let Some(gate) = storage
             .find_one(GateKey {
                 group,
                 service,
                 environment,
             })
             .await?
-            .map(|gate| date_time_switch.close_if_time(clock.now(), gate))
-            .map(Into::into);
-        Ok(gate)
+        else {
+            return Ok(None);
+        };
+        let closures = Closures::at(clock.now(), storage, date_time_switch).await?;
+        let dependencies = closures.dependencies_of(slice::from_ref(&gate)).await?;
+        Ok(Some(closures.rep(gate, &dependencies).await))
     }
 }
 
@@ -104,34 +110,24 @@ mod unit_tests {
 
         let mut mock_date_time_switch = MockDateTimeSwitch::new();
         mock_date_time_switch
-            .expect_close_if_time()
+            .expect_is_closed()
             .with(
                 eq(now),
-                eq(Gate {
-                    key: GateKey {
-                        group: group.to_string(),
-                        service: service.to_string(),
-                        environment: environment.to_string(),
-                    },
-                    state: GateState::Open,
-                    comments: HashSet::default(),
-                    last_updated: DateTime::default(),
-
-                    display_order: Some(5),
-                }),
-            )
-            .return_once(move |_, _| Gate {
-                key: GateKey {
+                eq(GateKey {
                     group: group.to_string(),
                     service: service.to_string(),
                     environment: environment.to_string(),
-                },
-                state: GateState::Closed,
-                comments: HashSet::default(),
-                last_updated: DateTime::default(),
-                display_order: Some(5),
-            });
+                }),
+            )
+            .return_const(true);
         let mut mock_storage = MockStorage::new();
+        mock_storage.expect_find_freeze().return_once(|| Ok(None));
+        mock_storage
+            .expect_find_freeze_windows()
+            .return_once(|| Ok(vec![]));
+        mock_storage
+            .expect_find_gate_dependencies()
+            .return_once(|| Ok(vec![]));
         mock_storage
             .expect_find_one()
             .with(eq(GateKey {
@@ -150,6 +146,8 @@ mod unit_tests {
                     comments: HashSet::default(),
                     last_updated: DateTime::default(),
                     display_order: Some(5),
+                    version: 0,
+                    open_until: None,
                 }))
             });
         let left = UseCaseImpl {}
@@ -173,6 +171,10 @@ mod unit_tests {
             comments: vec![],
             last_updated: DateTime::<Utc>::default().to_rfc3339(),
             display_order: Some(f64::from(5)),
+            version: 0,
+            open_until: None,
+            open_remaining_seconds: None,
+            closure_reason: Some(models::ClosureReason::BusinessHours),
         });
         assert_eq!(left.expect("could not unwrap gate"), expected_gate);
     }
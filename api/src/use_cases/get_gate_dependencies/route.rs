@@ -0,0 +1,47 @@
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::Json;
+use serde::{Deserialize, Serialize};
+
+use crate::types::app_state::AppState;
+use crate::use_cases::get_gate_dependencies::use_case;
+use crate::use_cases::get_gate_dependencies::use_case::Error;
+
+#[derive(Serialize, Deserialize)]
+pub struct PathParams {
+    group: String,
+    service: String,
+    environment: String,
+}
+
+pub async fn handler(
+    Path(PathParams {
+        group,
+        service,
+        environment,
+    }): Path<PathParams>,
+    State(app_state): State<AppState>,
+) -> impl IntoResponse {
+    match app_state
+        .use_cases
+        .get_gate_dependencies
+        .execute(
+            use_case::Input {
+                group,
+                service,
+                environment,
+            },
+            app_state.storage.as_ref(),
+        )
+        .await
+    {
+        Ok(dependencies) => Json(dependencies).into_response(),
+        Err(error) => match error {
+            Error::GateNotFound => StatusCode::NO_CONTENT.into_response(),
+            Error::Internal(error) => {
+                (StatusCode::INTERNAL_SERVER_ERROR, Json(error)).into_response()
+            }
+        },
+    }
+}
@@ -0,0 +1,164 @@
+use async_trait::async_trait;
+use openapi::models;
+
+use crate::storage;
+use crate::storage::Storage;
+use crate::types::GateKey;
+
+#[derive(Debug)]
+pub struct Input {
+    pub group: String,
+    pub service: String,
+    pub environment: String,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Error {
+    GateNotFound,
+    Internal(String),
+}
+
+impl From<storage::FindError> for Error {
+    fn from(value: storage::FindError) -> Self {
+        match value {
+            storage::FindError::ItemCouldNotBeDecoded(error)
+            | storage::FindError::InvalidCursor(error)
+            | storage::FindError::Other(error) => Self::Internal(error),
+        }
+    }
+}
+
+#[async_trait]
+pub trait UseCase {
+    async fn execute(
+        &self,
+        input: Input,
+        storage: &(dyn Storage + Send + Sync),
+    ) -> Result<models::GateDependencies, Error>;
+}
+
+pub fn create() -> impl UseCase {
+    UseCaseImpl {}
+}
+
+#[derive(Clone)]
+struct UseCaseImpl {}
+
+#[async_trait]
+impl UseCase for UseCaseImpl {
+    async fn execute(
+        &self,
+        Input {
+            group,
+            service,
+            environment,
+        }: Input,
+        storage: &(dyn Storage + Send + Sync),
+    ) -> Result<models::GateDependencies, Error> {
+        let key = GateKey {
+            group,
+            service,
+            environment,
+        };
+        if storage.find_one(key.clone()).await?.is_none() {
+            return Err(Error::GateNotFound);
+        }
+
+        Ok(models::GateDependencies {
+            depends_on: storage
+                .find_gate_dependencies()
+                .await?
+                .into_iter()
+                .find(|dependencies| dependencies.key == key)
+                .map(|dependencies| dependencies.depends_on)
+                .unwrap_or_default()
+                .into_iter()
+                .map(Into::into)
+                .collect(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use std::collections::HashSet;
+
+    use chrono::DateTime;
+    use similar_asserts::assert_eq;
+
+    use crate::storage::MockStorage;
+    use crate::types::{Gate, GateDependencies, GateState};
+
+    use super::*;
+
+    fn some_key(environment: &str) -> GateKey {
+        GateKey {
+            group: "some group".to_owned(),
+            service: "some service".to_owned(),
+            environment: environment.to_owned(),
+        }
+    }
+
+    fn some_input() -> Input {
+        Input {
+            group: "some group".to_owned(),
+            service: "some service".to_owned(),
+            environment: "live".to_owned(),
+        }
+    }
+
+    #[tokio::test]
+    async fn should_get_dependencies_of_gate() {
+        // given
+        let mut mock_storage = MockStorage::new();
+        mock_storage.expect_find_one().return_once(|key| {
+            Ok(Some(Gate {
+                key,
+                state: GateState::Open,
+                comments: HashSet::new(),
+                last_updated: DateTime::default(),
+                display_order: None,
+                version: 0,
+                open_until: None,
+            }))
+        });
+        mock_storage
+            .expect_find_gate_dependencies()
+            .return_once(|| {
+                Ok(vec![
+                    GateDependencies {
+                        key: some_key("develop"),
+                        depends_on: vec![some_key("database")],
+                    },
+                    GateDependencies {
+                        key: some_key("live"),
+                        depends_on: vec![some_key("staging")],
+                    },
+                ])
+            });
+
+        // when
+        let dependencies = UseCaseImpl {}.execute(some_input(), &mock_storage).await;
+
+        // then
+        assert_eq!(
+            dependencies.expect("failed to get dependencies"),
+            models::GateDependencies {
+                depends_on: vec![some_key("staging").into()],
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn should_not_get_dependencies_of_unknown_gate() {
+        // given
+        let mut mock_storage = MockStorage::new();
+        mock_storage.expect_find_one().return_once(|_| Ok(None));
+
+        // when
+        let dependencies = UseCaseImpl {}.execute(some_input(), &mock_storage).await;
+
+        // then
+        assert_eq!(dependencies, Err(Error::GateNotFound));
+    }
+}
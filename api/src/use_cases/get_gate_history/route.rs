@@ -0,0 +1,55 @@
+use crate::types::app_state::AppState;
+use crate::use_cases::get_gate_history::use_case;
+use crate::use_cases::get_gate_history::use_case::Error;
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::Json;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize)]
+pub struct PathParams {
+    group: String,
+    service: String,
+    environment: String,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct QueryParams {
+    limit: Option<u32>,
+    cursor: Option<String>,
+}
+
+pub async fn handler(
+    Path(PathParams {
+        group,
+        service,
+        environment,
+    }): Path<PathParams>,
+    Query(QueryParams { limit, cursor }): Query<QueryParams>,
+    State(app_state): State<AppState>,
+) -> impl IntoResponse {
+    match app_state
+        .use_cases
+        .get_gate_history
+        .execute(
+            use_case::Input {
+                group,
+                service,
+                environment,
+                limit,
+                cursor,
+            },
+            app_state.storage.as_ref(),
+        )
+        .await
+    {
+        Ok(history) => Json(history).into_response(),
+        Err(error) => match error {
+            Error::InvalidInput(error) => (StatusCode::BAD_REQUEST, Json(error)).into_response(),
+            Error::Internal(error) => {
+                (StatusCode::INTERNAL_SERVER_ERROR, Json(error)).into_response()
+            }
+        },
+    }
+}
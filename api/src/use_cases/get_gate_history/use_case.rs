@@ -0,0 +1,224 @@
+use async_trait::async_trait;
+use openapi::models;
+
+use crate::storage;
+use crate::storage::Storage;
+use crate::types::GateKey;
+
+pub const DEFAULT_LIMIT: u32 = 20;
+pub const MAX_LIMIT: u32 = 100;
+
+#[derive(Debug)]
+pub struct Input {
+    pub group: String,
+    pub service: String,
+    pub environment: String,
+    pub limit: Option<u32>,
+    pub cursor: Option<String>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Error {
+    InvalidInput(String),
+    Internal(String),
+}
+
+impl From<storage::FindError> for Error {
+    fn from(value: storage::FindError) -> Self {
+        match value {
+            storage::FindError::InvalidCursor(error) => Self::InvalidInput(error),
+            storage::FindError::ItemCouldNotBeDecoded(error) | storage::FindError::Other(error) => {
+                Self::Internal(error)
+            }
+        }
+    }
+}
+
+#[async_trait]
+pub trait UseCase {
+    async fn execute(
+        &self,
+        input: Input,
+        storage: &(dyn Storage + Send + Sync),
+    ) -> Result<models::GateHistory, Error>;
+}
+
+pub fn create() -> impl UseCase {
+    UseCaseImpl {}
+}
+
+#[derive(Clone)]
+struct UseCaseImpl {}
+
+#[async_trait]
+impl UseCase for UseCaseImpl {
+    async fn execute(
+        &self,
+        Input {
+            group,
+            service,
+            environment,
+            limit,
+            cursor,
+        }: Input,
+        storage: &(dyn Storage + Send + Sync),
+    ) -> Result<models::GateHistory, Error> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT);
+        if !(1..=MAX_LIMIT).contains(&limit) {
+            return Err(Error::InvalidInput(format!(
+                "limit must be between 1 and {MAX_LIMIT}"
+            )));
+        }
+
+        Ok(storage
+            .find_history(
+                GateKey {
+                    group,
+                    service,
+                    environment,
+                },
+                limit,
+                cursor,
+            )
+            .await?
+            .into())
+    }
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use chrono::DateTime;
+    use mockall::predicate::eq;
+    use similar_asserts::assert_eq;
+
+    use crate::storage::MockStorage;
+    use crate::types::{GateEvent, HistoryEntry, HistoryPage};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn should_get_gate_history() {
+        // given
+        let mut mock_storage = MockStorage::new();
+        mock_storage
+            .expect_find_history()
+            .with(
+                eq(some_key()),
+                eq(DEFAULT_LIMIT),
+                eq(Some("some cursor".to_owned())),
+            )
+            .return_once(|key, _, _| {
+                Ok(HistoryPage {
+                    entries: vec![HistoryEntry {
+                        id: "some id".to_owned(),
+                        key,
+                        timestamp: DateTime::parse_from_rfc3339("2023-04-12T22:10:57+02:00")
+                            .expect("failed to parse date")
+                            .into(),
+                        event: GateEvent::StateChanged,
+                        old_value: Some("closed".to_owned()),
+                        new_value: Some("open".to_owned()),
+                        open_until: None,
+                        actor: Some("Max".to_owned()),
+                    }],
+                    next_cursor: Some("some other cursor".to_owned()),
+                })
+            });
+
+        // when
+        let history = UseCaseImpl {}
+            .execute(
+                Input {
+                    group: "some group".to_owned(),
+                    service: "some service".to_owned(),
+                    environment: "some environment".to_owned(),
+                    limit: None,
+                    cursor: Some("some cursor".to_owned()),
+                },
+                &mock_storage,
+            )
+            .await;
+
+        // then
+        assert_eq!(
+            history.expect("failed to get gate history"),
+            models::GateHistory {
+                entries: vec![models::HistoryEntry {
+                    id: "some id".to_owned(),
+                    timestamp: "2023-04-12T20:10:57+00:00".to_owned(),
+                    event: models::GateEvent::StateChanged,
+                    old_value: Some("closed".to_owned()),
+                    new_value: Some("open".to_owned()),
+                    open_until: None,
+                    actor: Some("Max".to_owned()),
+                }],
+                next_cursor: Some("some other cursor".to_owned()),
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn should_reject_invalid_limit() {
+        // given
+        let mock_storage = MockStorage::new();
+
+        // when
+        let history = UseCaseImpl {}
+            .execute(
+                Input {
+                    group: "some group".to_owned(),
+                    service: "some service".to_owned(),
+                    environment: "some environment".to_owned(),
+                    limit: Some(MAX_LIMIT + 1),
+                    cursor: None,
+                },
+                &mock_storage,
+            )
+            .await;
+
+        // then
+        assert_eq!(
+            history.expect_err("unexpected history"),
+            Error::InvalidInput("limit must be between 1 and 100".to_owned())
+        );
+    }
+
+    #[tokio::test]
+    async fn should_reject_invalid_cursor() {
+        // given
+        let mut mock_storage = MockStorage::new();
+        mock_storage.expect_find_history().return_once(|_, _, _| {
+            Err(storage::FindError::InvalidCursor(
+                "invalid history cursor abc".to_owned(),
+            ))
+        });
+
+        // when
+        let history = UseCaseImpl {}
+            .execute(
+                Input {
+                    group: "some group".to_owned(),
+                    service: "some service".to_owned(),
+                    environment: "some environment".to_owned(),
+                    limit: Some(10),
+                    cursor: Some("abc".to_owned()),
+                },
+                &mock_storage,
+            )
+            .await;
+
+        // then
+        assert_eq!(
+            history.expect_err("unexpected history"),
+            Error::InvalidInput("invalid history cursor abc".to_owned())
+        );
+    }
+
+    fn some_key() -> GateKey {
+        GateKey {
+            group: "some group".to_owned(),
+            service: "some service".to_owned(),
+            environment: "some environment".to_owned(),
+        }
+    }
+}
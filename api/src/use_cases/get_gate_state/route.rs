@@ -1,10 +1,11 @@
 use crate::types::app_state::AppState;
 use crate::use_cases::get_gate_state::use_case;
 use crate::use_cases::get_gate_state::use_case::Error;
-use axum::extract::{Path, State};
+use axum::extract::{Path, Query, State};
 use axum::http::StatusCode;
 use axum::response::IntoResponse;
 use axum::Json;
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
 #[derive(Serialize, Deserialize)]
@@ -14,12 +15,18 @@ pub struct PathParams {
     environment: String,
 }
 
+#[derive(Serialize, Deserialize)]
+pub struct QueryParams {
+    at: Option<DateTime<Utc>>,
+}
+
 pub async fn handler(
     Path(PathParams {
         group,
         service,
         environment,
     }): Path<PathParams>,
+    Query(QueryParams { at }): Query<QueryParams>,
     State(app_state): State<AppState>,
 ) -> impl IntoResponse {
     match app_state
@@ -30,6 +37,7 @@ pub async fn handler(
                 group,
                 service,
                 environment,
+                at,
             },
             app_state.storage.as_ref(),
             app_state.clock.as_ref(),
@@ -40,6 +48,7 @@ pub async fn handler(
         Ok(Some(gate)) => Json(gate).into_response(),
         Ok(None) => StatusCode::NO_CONTENT.into_response(),
         Err(error) => match error {
+            Error::InvalidInput(error) => (StatusCode::BAD_REQUEST, Json(error)).into_response(),
             Error::Internal(error) => {
                 (StatusCode::INTERNAL_SERVER_ERROR, Json(error)).into_response()
             }
@@ -1,30 +1,39 @@
+use std::slice;
+
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use openapi::models;
 
 use crate::clock::Clock;
-use crate::date_time_switch::DateTimeSwitch;
+use crate::closure::Closures;
+use crate::date_time_switch::{DateTimeSwitch, Transitions};
+use crate::freeze::Freezes;
 use crate::storage;
 use crate::storage::Storage;
-use crate::types::GateKey;
+use crate::types::{ClosureReason, Gate, GateEvent, GateKey, GateState};
+
+const HISTORY_PAGE_SIZE: u32 = 100;
 
 #[derive(Debug)]
 pub struct Input {
     pub group: String,
     pub service: String,
     pub environment: String,
+    pub at: Option<DateTime<Utc>>,
 }
 
 #[derive(Debug, PartialEq, Eq)]
 pub enum Error {
+    InvalidInput(String),
     Internal(String),
 }
 
 impl From<storage::FindError> for Error {
     fn from(value: storage::FindError) -> Self {
         match value {
-            storage::FindError::ItemCouldNotBeDecoded(error) | storage::FindError::Other(error) => {
-                Self::Internal(error)
-            }
+            storage::FindError::ItemCouldNotBeDecoded(error)
+            | storage::FindError::InvalidCursor(error)
+            | storage::FindError::Other(error) => Self::Internal(error),
         }
     }
 }
@@ -55,22 +64,175 @@ impl UseCase for UseCaseImpl {
             group,
             service,
             environment,
+            at,
         }: Input,
         storage: &(dyn Storage + Send + Sync),
         clock: &(dyn Clock + Send + Sync),
         date_time_switch: &(dyn DateTimeSwitch + Send + Sync),
     ) -> Result<Option<models::GateStateRep>, Error> {
-        let gate = storage
-            .find_one(GateKey {
-                group,
-                service,
-                environment,
-            })
-            .await?
-            .map(|gate| date_time_switch.close_if_time(clock.now(), gate))
-            .map(|gate| gate.state)
-            .map(Into::into);
-        Ok(gate)
+        let key = GateKey {
+            group,
+            service,
+            environment,
+        };
+
+        let Some(at) = at else {
+            let Some(gate) = storage.find_one(key).await? else {
+                return Ok(None);
+            };
+            let closures = Closures::at(clock.now(), storage, date_time_switch).await?;
+            let transitions = date_time_switch
+                .next_transitions(closures.now, &gate.key)
+                .await;
+            let dependencies = closures.dependencies_of(slice::from_ref(&gate)).await?;
+            let closure_reason = closures.reason(&gate, &dependencies).await;
+            let next_transition =
+                next_transition(&gate, closure_reason, &closures.freezes, transitions);
+            return Ok(Some(models::GateStateRep {
+                reason: closures.freezes.reason(&gate.key),
+                freeze: closures.freezes.freeze.clone().map(Into::into),
+                closure_reason: closure_reason.map(Into::into),
+                latest_comment: gate
+                    .comments
+                    .into_iter()
+                    .max_by_key(|comment| comment.created)
+                    .map(Into::into),
+                next_transition: next_transition.map(|instant| instant.to_rfc3339()),
+                transitions: transitions.map(Into::into),
+                ..closure_reason
+                    .map_or(GateState::Open, |_| GateState::Closed)
+                    .into()
+            }));
+        };
+
+        if at > clock.now() {
+            return Err(Error::InvalidInput(
+                "the requested instant must not be in the future".to_owned(),
+            ));
+        }
+
+        let Some((state, open_until)) = find_state_at(storage, key.clone(), at).await? else {
+            return Ok(None);
+        };
+        // business hours and dependencies are evaluated as they are configured now, they are not
+        // recorded
+        let closures = Closures::at(at, storage, date_time_switch).await?;
+        let mut closure_reason =
+            reason_at(&key, state, open_until, &closures, date_time_switch).await;
+        if closure_reason.is_none() {
+            for dependency in closures.depends_on(&key) {
+                let Some((state, open_until)) =
+                    find_state_at(storage, dependency.clone(), at).await?
+                else {
+                    continue;
+                };
+                if reason_at(dependency, state, open_until, &closures, date_time_switch)
+                    .await
+                    .is_some()
+                {
+                    closure_reason = Some(ClosureReason::Dependency);
+                    break;
+                }
+            }
+        }
+        Ok(Some(models::GateStateRep {
+            reason: closures.freezes.reason(&key),
+            freeze: closures.freezes.freeze.clone().map(Into::into),
+            closure_reason: closure_reason.map(Into::into),
+            ..closure_reason
+                .map_or(GateState::Open, |_| GateState::Closed)
+                .into()
+        }))
+    }
+}
+
+/// When the gate closed for the given reason opens again, or when the open gate is closed next.
+fn next_transition(
+    gate: &Gate,
+    closure_reason: Option<ClosureReason>,
+    freezes: &Freezes,
+    transitions: Option<Transitions>,
+) -> Option<DateTime<Utc>> {
+    match closure_reason {
+        Some(ClosureReason::Freeze) => freezes.frozen_until(&gate.key),
+        Some(ClosureReason::BusinessHours) => {
+            transitions.and_then(|transitions| transitions.next_open)
+        }
+        Some(_) => None,
+        None => transitions
+            .and_then(|transitions| transitions.next_close)
+            .into_iter()
+            .chain(freezes.next_start(&gate.key))
+            .chain(gate.open_until)
+            .min(),
+    }
+}
+
+/// Why the gate was closed at the instant of the closures, given the state it had then and until
+/// when it was opened.
+async fn reason_at(
+    key: &GateKey,
+    state: GateState,
+    open_until: Option<DateTime<Utc>>,
+    closures: &Closures<'_>,
+    date_time_switch: &(dyn DateTimeSwitch + Send + Sync),
+) -> Option<ClosureReason> {
+    if closures.freezes.reason(key).is_some() {
+        Some(ClosureReason::Freeze)
+    } else if date_time_switch.is_closed(closures.now, key).await {
+        Some(ClosureReason::BusinessHours)
+    } else if state == GateState::Open
+        && open_until.is_some_and(|open_until| open_until <= closures.now)
+    {
+        Some(ClosureReason::Expired)
+    } else {
+        (state == GateState::Closed).then_some(ClosureReason::Manual)
+    }
+}
+
+/// The state the gate had at the given instant and until when it was opened, if it existed then.
+async fn find_state_at(
+    storage: &(dyn Storage + Send + Sync),
+    key: GateKey,
+    at: DateTime<Utc>,
+) -> Result<Option<(GateState, Option<DateTime<Utc>>)>, Error> {
+    // a gate that has not been touched since the requested instant still has the same state
+    if let Some(gate) = storage.find_one(key.clone()).await? {
+        if gate.last_updated <= at {
+            return Ok(Some((gate.state, gate.open_until)));
+        }
+    }
+
+    let mut cursor = None;
+    loop {
+        let page = storage
+            .find_history(key.clone(), HISTORY_PAGE_SIZE, cursor)
+            .await?;
+
+        for entry in page
+            .entries
+            .into_iter()
+            .filter(|entry| entry.timestamp <= at)
+        {
+            match entry.event {
+                GateEvent::Created | GateEvent::Restored | GateEvent::StateChanged => {
+                    return entry
+                        .new_value
+                        .map_or_else(|| Ok(GateState::default()), GateState::try_from)
+                        .map(|state| Some((state, entry.open_until)))
+                        .map_err(Error::Internal);
+                }
+                GateEvent::Deleted => return Ok(None),
+                GateEvent::DisplayOrderChanged
+                | GateEvent::CommentAdded
+                | GateEvent::CommentDeleted => {}
+            }
+        }
+
+        match page.next_cursor {
+            Some(next_cursor) => cursor = Some(next_cursor),
+            None => return Ok(None),
+        }
     }
 }
 
@@ -78,18 +240,169 @@ impl UseCase for UseCaseImpl {
 mod unit_tests {
     use std::collections::HashSet;
 
-    use chrono::{DateTime, Utc};
-    use mockall::predicate::eq;
+    use chrono::{DateTime, TimeDelta, Utc};
+    use mockall::predicate::{always, eq};
     use openapi::models;
+    use rstest::rstest;
 
     use crate::clock::MockClock;
-    use crate::date_time_switch::MockDateTimeSwitch;
+    use crate::date_time_switch::{MockDateTimeSwitch, Transitions};
     use crate::storage;
     use crate::storage::MockStorage;
-    use crate::types::{Gate, GateKey, GateState};
+    use crate::types::{
+        Comment, Freeze, FreezeScope, FreezeWindow, Gate, GateDependencies, GateEvent, GateKey,
+        GateState, HistoryEntry, HistoryPage,
+    };
     use crate::use_cases::get_gate_state::use_case::{Error, Input, UseCase, UseCaseImpl};
     use similar_asserts::assert_eq;
 
+    #[tokio::test]
+    async fn should_get_closed_state_with_freeze_while_frozen() {
+        // given
+        let mut mock_clock = MockClock::new();
+        let now: DateTime<Utc> = DateTime::parse_from_rfc3339("2023-04-12T12:10:57+02:00")
+            .expect("failed to parse date")
+            .to_utc();
+        mock_clock.expect_now().return_const(now);
+
+        let mut mock_date_time_switch = MockDateTimeSwitch::new();
+        mock_date_time_switch
+            .expect_next_transitions()
+            .return_const(None);
+
+        let freeze = Freeze {
+            reason: "release freeze".to_owned(),
+            actor: Some("Max".to_owned()),
+            created: now,
+            until: None,
+        };
+        let stored_freeze = freeze.clone();
+        let mut mock_storage = MockStorage::new();
+        mock_storage.expect_find_one().return_once(move |key| {
+            Ok(Some(Gate {
+                key,
+                state: GateState::Open,
+                comments: HashSet::default(),
+                last_updated: now,
+                display_order: None,
+                version: 0,
+                open_until: None,
+            }))
+        });
+        mock_storage
+            .expect_find_freeze()
+            .return_once(|| Ok(Some(stored_freeze)));
+        mock_storage
+            .expect_find_freeze_windows()
+            .return_once(|| Ok(vec![]));
+        mock_storage
+            .expect_find_gate_dependencies()
+            .return_once(|| Ok(vec![]));
+
+        // when
+        let actual = UseCaseImpl {}
+            .execute(
+                Input {
+                    group: "some group".to_owned(),
+                    service: "some service".to_owned(),
+                    environment: "some environment".to_owned(),
+                    at: None,
+                },
+                &mock_storage,
+                &mock_clock,
+                &mock_date_time_switch,
+            )
+            .await;
+
+        // then
+        assert_eq!(
+            actual.expect("There is no error expected here!"),
+            Some(models::GateStateRep {
+                state: models::GateState::Closed,
+                freeze: Some(freeze.into()),
+                reason: Some("release freeze".to_owned()),
+                closure_reason: Some(models::ClosureReason::Freeze),
+                latest_comment: None,
+                next_transition: None,
+                transitions: None,
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn should_get_closed_state_with_reason_during_freeze_window() {
+        // given
+        let mut mock_clock = MockClock::new();
+        let now: DateTime<Utc> = DateTime::parse_from_rfc3339("2023-04-12T12:10:57+02:00")
+            .expect("failed to parse date")
+            .to_utc();
+        mock_clock.expect_now().return_const(now);
+
+        let mut mock_date_time_switch = MockDateTimeSwitch::new();
+        mock_date_time_switch
+            .expect_next_transitions()
+            .return_const(None);
+
+        let freeze_window = FreezeWindow {
+            id: "some id".to_owned(),
+            name: "Year-end freeze".to_owned(),
+            reason: "annual closing".to_owned(),
+            start: now,
+            end: now + TimeDelta::days(1),
+            scope: FreezeScope::Group("some group".to_owned()),
+            actor: None,
+        };
+        let mut mock_storage = MockStorage::new();
+        mock_storage.expect_find_one().return_once(move |key| {
+            Ok(Some(Gate {
+                key,
+                state: GateState::Open,
+                comments: HashSet::default(),
+                last_updated: now,
+                display_order: None,
+                version: 0,
+                open_until: None,
+            }))
+        });
+        mock_storage.expect_find_freeze().return_once(|| Ok(None));
+        mock_storage
+            .expect_find_freeze_windows()
+            .return_once(|| Ok(vec![freeze_window]));
+        mock_storage
+            .expect_find_gate_dependencies()
+            .return_once(|| Ok(vec![]));
+        let end = (now + TimeDelta::days(1)).to_rfc3339();
+
+        // when
+        let actual = UseCaseImpl {}
+            .execute(
+                Input {
+                    group: "some group".to_owned(),
+                    service: "some service".to_owned(),
+                    environment: "some environment".to_owned(),
+                    at: None,
+                },
+                &mock_storage,
+                &mock_clock,
+                &mock_date_time_switch,
+            )
+            .await;
+
+        // then
+        assert_eq!(
+            actual.expect("There is no error expected here!"),
+            Some(models::GateStateRep {
+                state: models::GateState::Closed,
+                freeze: None,
+                reason: Some("Year-end freeze".to_owned()),
+                closure_reason: Some(models::ClosureReason::Freeze),
+                latest_comment: None,
+                next_transition: Some(end),
+                transitions: None,
+            })
+        );
+    }
+
     #[tokio::test]
     async fn should_get_gate() {
         // given
@@ -104,36 +417,33 @@ mod unit_tests {
         );
         mock_clock.expect_now().return_const(now);
 
+        let transitions = Transitions {
+            next_open: Some(now + TimeDelta::hours(9)),
+            next_close: Some(now + TimeDelta::hours(18)),
+        };
         let mut mock_date_time_switch = MockDateTimeSwitch::new();
         mock_date_time_switch
-            .expect_close_if_time()
+            .expect_is_closed()
             .with(
                 eq(now),
-                eq(Gate {
-                    key: GateKey {
-                        group: group.to_string(),
-                        service: service.to_string(),
-                        environment: environment.to_string(),
-                    },
-                    state: GateState::Open,
-                    comments: HashSet::default(),
-                    last_updated: DateTime::default(),
-
-                    display_order: Some(5),
-                }),
-            )
-            .return_once(move |_, _| Gate {
-                key: GateKey {
+                eq(GateKey {
                     group: group.to_string(),
                     service: service.to_string(),
                     environment: environment.to_string(),
-                },
-                state: GateState::Closed,
-                comments: HashSet::default(),
-                last_updated: DateTime::default(),
-                display_order: Some(5),
-            });
+                }),
+            )
+            .return_const(true);
+        mock_date_time_switch
+            .expect_next_transitions()
+            .return_const(Some(transitions));
         let mut mock_storage = MockStorage::new();
+        mock_storage.expect_find_freeze().return_once(|| Ok(None));
+        mock_storage
+            .expect_find_freeze_windows()
+            .return_once(|| Ok(vec![]));
+        mock_storage
+            .expect_find_gate_dependencies()
+            .return_once(|| Ok(vec![]));
         mock_storage
             .expect_find_one()
             .with(eq(GateKey {
@@ -152,6 +462,8 @@ mod unit_tests {
                     comments: HashSet::default(),
                     last_updated: DateTime::default(),
                     display_order: Some(5),
+                    version: 0,
+                    open_until: None,
                 }))
             });
         let left = UseCaseImpl {}
@@ -160,6 +472,7 @@ mod unit_tests {
                     group: group.to_string(),
                     service: service.to_string(),
                     environment: environment.to_string(),
+                    at: None,
                 },
                 &mock_storage,
                 &mock_clock,
@@ -169,10 +482,291 @@ mod unit_tests {
         assert!(left.is_ok());
         let expected_gate = Some(models::GateStateRep {
             state: GateState::Closed.into(),
+            freeze: None,
+            reason: None,
+            closure_reason: Some(models::ClosureReason::BusinessHours),
+            latest_comment: None,
+            next_transition: Some((now + TimeDelta::hours(9)).to_rfc3339()),
+            transitions: Some(transitions.into()),
         });
         assert_eq!(left.expect("could not unwrap gate"), expected_gate);
     }
 
+    #[tokio::test]
+    async fn should_get_manually_closed_state_with_latest_comment() {
+        // given
+        let mut mock_clock = MockClock::new();
+        let now = some_date_time("2023-04-12T12:00:00+00:00");
+        mock_clock.expect_now().return_const(now);
+
+        let mut mock_date_time_switch = MockDateTimeSwitch::new();
+        mock_date_time_switch.expect_is_closed().return_const(false);
+        mock_date_time_switch
+            .expect_next_transitions()
+            .return_const(Some(Transitions {
+                next_open: Some(some_date_time("2023-04-13T08:00:00+00:00")),
+                next_close: Some(some_date_time("2023-04-12T16:00:00+00:00")),
+            }));
+
+        let latest_comment = Comment {
+            id: "some other id".to_owned(),
+            message: "broken build".to_owned(),
+            created: some_date_time("2023-04-12T11:00:00+00:00"),
+        };
+        let comments = HashSet::from([
+            Comment {
+                id: "some id".to_owned(),
+                message: "waiting for approval".to_owned(),
+                created: some_date_time("2023-04-12T10:00:00+00:00"),
+            },
+            latest_comment.clone(),
+        ]);
+        let mut mock_storage = MockStorage::new();
+        mock_storage.expect_find_one().return_once(move |key| {
+            Ok(Some(Gate {
+                key,
+                state: GateState::Closed,
+                comments,
+                last_updated: now,
+                display_order: None,
+                version: 2,
+                open_until: None,
+            }))
+        });
+        mock_storage.expect_find_freeze().return_once(|| Ok(None));
+        mock_storage
+            .expect_find_freeze_windows()
+            .return_once(|| Ok(vec![]));
+        mock_storage
+            .expect_find_gate_dependencies()
+            .return_once(|| Ok(vec![]));
+
+        // when
+        let actual = UseCaseImpl {}
+            .execute(
+                some_input(None),
+                &mock_storage,
+                &mock_clock,
+                &mock_date_time_switch,
+            )
+            .await;
+
+        // then
+        assert_eq!(
+            actual.expect("There is no error expected here!"),
+            Some(models::GateStateRep {
+                state: models::GateState::Closed,
+                freeze: None,
+                reason: None,
+                closure_reason: Some(models::ClosureReason::Manual),
+                latest_comment: Some(latest_comment.into()),
+                next_transition: None,
+                transitions: Some(models::Transitions {
+                    next_open: Some("2023-04-13T08:00:00+00:00".to_owned()),
+                    next_close: Some("2023-04-12T16:00:00+00:00".to_owned()),
+                }),
+            })
+        );
+    }
+
+    #[rstest]
+    #[case("2023-04-12T16:00:00+00:00", None, "2023-04-12T15:00:00+00:00")]
+    #[case("2023-04-12T14:00:00+00:00", None, "2023-04-12T14:00:00+00:00")]
+    #[case(
+        "2023-04-12T16:00:00+00:00",
+        Some("2023-04-12T13:00:00+00:00"),
+        "2023-04-12T13:00:00+00:00"
+    )]
+    #[tokio::test]
+    async fn should_get_next_transition_of_open_gate(
+        #[case] end_of_business_hours: &str,
+        #[case] open_until: Option<&str>,
+        #[case] expected: &str,
+    ) {
+        // given
+        let mut mock_clock = MockClock::new();
+        let now = some_date_time("2023-04-12T12:00:00+00:00");
+        mock_clock.expect_now().return_const(now);
+
+        let mut mock_date_time_switch = MockDateTimeSwitch::new();
+        mock_date_time_switch.expect_is_closed().return_const(false);
+        mock_date_time_switch
+            .expect_next_transitions()
+            .return_const(Some(Transitions {
+                next_open: None,
+                next_close: Some(some_date_time(end_of_business_hours)),
+            }));
+
+        let freeze_window = FreezeWindow {
+            id: "some id".to_owned(),
+            name: "Release freeze".to_owned(),
+            reason: "release of version 2".to_owned(),
+            start: some_date_time("2023-04-12T15:00:00+00:00"),
+            end: some_date_time("2023-04-13T15:00:00+00:00"),
+            scope: FreezeScope::All,
+            actor: None,
+        };
+        let open_until = open_until.map(some_date_time);
+        let mut mock_storage = MockStorage::new();
+        mock_storage.expect_find_one().return_once(move |key| {
+            Ok(Some(Gate {
+                key,
+                state: GateState::Open,
+                comments: HashSet::default(),
+                last_updated: now,
+                display_order: None,
+                version: 1,
+                open_until,
+            }))
+        });
+        mock_storage.expect_find_freeze().return_once(|| Ok(None));
+        mock_storage
+            .expect_find_freeze_windows()
+            .return_once(|| Ok(vec![freeze_window]));
+        mock_storage
+            .expect_find_gate_dependencies()
+            .return_once(|| Ok(vec![]));
+
+        // when
+        let actual = UseCaseImpl {}
+            .execute(
+                some_input(None),
+                &mock_storage,
+                &mock_clock,
+                &mock_date_time_switch,
+            )
+            .await;
+
+        // then
+        assert_eq!(
+            actual.expect("There is no error expected here!"),
+            Some(models::GateStateRep {
+                state: models::GateState::Open,
+                freeze: None,
+                reason: None,
+                closure_reason: None,
+                latest_comment: None,
+                next_transition: Some(some_date_time(expected).to_rfc3339()),
+                transitions: Some(models::Transitions {
+                    next_open: None,
+                    next_close: Some(end_of_business_hours.to_owned()),
+                }),
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn should_get_closed_state_of_gate_whose_open_state_expired() {
+        // given
+        let mut mock_clock = MockClock::new();
+        let now = some_date_time("2023-04-12T12:00:00+00:00");
+        mock_clock.expect_now().return_const(now);
+
+        let mut mock_date_time_switch = MockDateTimeSwitch::new();
+        mock_date_time_switch.expect_is_closed().return_const(false);
+        mock_date_time_switch
+            .expect_next_transitions()
+            .return_const(None);
+
+        let mut mock_storage = MockStorage::new();
+        mock_storage.expect_find_one().return_once(move |key| {
+            Ok(Some(Gate {
+                key,
+                state: GateState::Open,
+                comments: HashSet::default(),
+                last_updated: some_date_time("2023-04-12T10:00:00+00:00"),
+                display_order: None,
+                version: 1,
+                open_until: Some(some_date_time("2023-04-12T11:00:00+00:00")),
+            }))
+        });
+        mock_storage.expect_find_freeze().return_once(|| Ok(None));
+        mock_storage
+            .expect_find_freeze_windows()
+            .return_once(|| Ok(vec![]));
+        mock_storage
+            .expect_find_gate_dependencies()
+            .return_once(|| Ok(vec![]));
+
+        // when
+        let actual = UseCaseImpl {}
+            .execute(
+                some_input(None),
+                &mock_storage,
+                &mock_clock,
+                &mock_date_time_switch,
+            )
+            .await;
+
+        // then
+        assert_eq!(
+            actual.expect("There is no error expected here!"),
+            Some(models::GateStateRep {
+                closure_reason: Some(models::ClosureReason::Expired),
+                ..GateState::Closed.into()
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn should_get_closed_state_of_gate_whose_dependency_is_closed() {
+        // given
+        let mut mock_clock = MockClock::new();
+        let now = some_date_time("2023-04-12T12:00:00+00:00");
+        mock_clock.expect_now().return_const(now);
+
+        let mut mock_date_time_switch = MockDateTimeSwitch::new();
+        mock_date_time_switch.expect_is_closed().return_const(false);
+        mock_date_time_switch
+            .expect_next_transitions()
+            .return_const(None);
+
+        let dependency = GateKey {
+            environment: "some-other-environment".to_owned(),
+            ..some_key()
+        };
+        let mut mock_storage = MockStorage::new();
+        mock_storage
+            .expect_find_one()
+            .with(eq(some_key()))
+            .return_once(|key| Ok(Some(some_gate(key, GateState::Open))));
+        mock_storage
+            .expect_find_one()
+            .with(eq(dependency.clone()))
+            .return_once(|key| Ok(Some(some_gate(key, GateState::Closed))));
+        mock_storage.expect_find_freeze().return_once(|| Ok(None));
+        mock_storage
+            .expect_find_freeze_windows()
+            .return_once(|| Ok(vec![]));
+        mock_storage
+            .expect_find_gate_dependencies()
+            .return_once(|| {
+                Ok(vec![GateDependencies {
+                    key: some_key(),
+                    depends_on: vec![dependency],
+                }])
+            });
+
+        // when
+        let actual = UseCaseImpl {}
+            .execute(
+                some_input(None),
+                &mock_storage,
+                &mock_clock,
+                &mock_date_time_switch,
+            )
+            .await;
+
+        // then
+        assert_eq!(
+            actual.expect("There is no error expected here!"),
+            Some(models::GateStateRep {
+                closure_reason: Some(models::ClosureReason::Dependency),
+                ..GateState::Closed.into()
+            })
+        );
+    }
+
     #[tokio::test]
     async fn should_return_error_if_storage_fails_to_decode_item() {
         // given
@@ -204,6 +798,7 @@ mod unit_tests {
                     group: group.to_string(),
                     service: service.to_string(),
                     environment: environment.to_string(),
+                    at: None,
                 },
                 &mock_storage,
                 &mock_clock,
@@ -246,6 +841,7 @@ mod unit_tests {
                     group: group.to_string(),
                     service: service.to_string(),
                     environment: environment.to_string(),
+                    at: None,
                 },
                 &mock_storage,
                 &mock_clock,
@@ -260,4 +856,494 @@ mod unit_tests {
             Error::Internal("some error".to_owned())
         );
     }
+
+    #[tokio::test]
+    async fn should_get_gate_state_at_instant_from_history() {
+        // given
+        let mut mock_clock = MockClock::new();
+        mock_clock
+            .expect_now()
+            .return_const(some_date_time("2023-04-14T12:00:00+00:00"));
+
+        let mut mock_date_time_switch = MockDateTimeSwitch::new();
+        mock_date_time_switch
+            .expect_is_closed()
+            .with(eq(some_date_time("2023-04-12T14:03:00+00:00")), always())
+            .return_const(false);
+
+        let mut mock_storage = MockStorage::new();
+        mock_storage.expect_find_one().return_once(|key| {
+            Ok(Some(Gate {
+                key,
+                state: GateState::Closed,
+                comments: HashSet::default(),
+                last_updated: some_date_time("2023-04-13T09:00:00+00:00"),
+                display_order: None,
+                version: 3,
+                open_until: None,
+            }))
+        });
+        mock_storage.expect_find_freeze().return_once(|| Ok(None));
+        mock_storage
+            .expect_find_freeze_windows()
+            .return_once(|| Ok(vec![]));
+        mock_storage
+            .expect_find_gate_dependencies()
+            .return_once(|| Ok(vec![]));
+        mock_storage
+            .expect_find_history()
+            .with(eq(some_key()), eq(100), eq(None))
+            .return_once(|key, _, _| {
+                Ok(HistoryPage {
+                    entries: vec![
+                        some_history_entry(
+                            &key,
+                            GateEvent::StateChanged,
+                            "2023-04-13T09:00:00+00:00",
+                            "closed",
+                        ),
+                        some_history_entry(
+                            &key,
+                            GateEvent::CommentAdded,
+                            "2023-04-12T15:00:00+00:00",
+                            "some comment",
+                        ),
+                        some_history_entry(
+                            &key,
+                            GateEvent::StateChanged,
+                            "2023-04-12T14:00:00+00:00",
+                            "open",
+                        ),
+                    ],
+                    next_cursor: Some("some cursor".to_owned()),
+                })
+            });
+
+        // when
+        let state = UseCaseImpl {}
+            .execute(
+                some_input(Some(some_date_time("2023-04-12T14:03:00+00:00"))),
+                &mock_storage,
+                &mock_clock,
+                &mock_date_time_switch,
+            )
+            .await;
+
+        // then
+        assert_eq!(
+            state.expect("failed to get gate state"),
+            Some(models::GateStateRep {
+                state: models::GateState::Open,
+                freeze: None,
+                reason: None,
+                closure_reason: None,
+                latest_comment: None,
+                next_transition: None,
+                transitions: None,
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn should_close_gate_state_at_instant_after_its_open_state_expired() {
+        // given
+        let mut mock_clock = MockClock::new();
+        mock_clock
+            .expect_now()
+            .return_const(some_date_time("2023-04-14T12:00:00+00:00"));
+
+        let mut mock_date_time_switch = MockDateTimeSwitch::new();
+        mock_date_time_switch.expect_is_closed().return_const(false);
+
+        let mut mock_storage = MockStorage::new();
+        mock_storage.expect_find_one().return_once(|key| {
+            Ok(Some(Gate {
+                key,
+                state: GateState::Closed,
+                comments: HashSet::default(),
+                last_updated: some_date_time("2023-04-13T09:00:00+00:00"),
+                display_order: None,
+                version: 3,
+                open_until: None,
+            }))
+        });
+        mock_storage.expect_find_freeze().return_once(|| Ok(None));
+        mock_storage
+            .expect_find_freeze_windows()
+            .return_once(|| Ok(vec![]));
+        mock_storage
+            .expect_find_gate_dependencies()
+            .return_once(|| Ok(vec![]));
+        mock_storage.expect_find_history().return_once(|key, _, _| {
+            Ok(HistoryPage {
+                entries: vec![
+                    some_history_entry(
+                        &key,
+                        GateEvent::StateChanged,
+                        "2023-04-13T09:00:00+00:00",
+                        "closed",
+                    ),
+                    HistoryEntry {
+                        open_until: Some(some_date_time("2023-04-12T14:02:00+00:00")),
+                        ..some_history_entry(
+                            &key,
+                            GateEvent::StateChanged,
+                            "2023-04-12T14:00:00+00:00",
+                            "open",
+                        )
+                    },
+                ],
+                next_cursor: None,
+            })
+        });
+
+        // when
+        let state = UseCaseImpl {}
+            .execute(
+                some_input(Some(some_date_time("2023-04-12T14:03:00+00:00"))),
+                &mock_storage,
+                &mock_clock,
+                &mock_date_time_switch,
+            )
+            .await;
+
+        // then
+        assert_eq!(
+            state.expect("failed to get gate state"),
+            Some(models::GateStateRep {
+                state: models::GateState::Closed,
+                freeze: None,
+                reason: None,
+                closure_reason: Some(models::ClosureReason::Expired),
+                latest_comment: None,
+                next_transition: None,
+                transitions: None,
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn should_close_gate_state_at_instant_outside_of_business_times() {
+        // given
+        let mut mock_clock = MockClock::new();
+        mock_clock
+            .expect_now()
+            .return_const(some_date_time("2023-04-14T12:00:00+00:00"));
+
+        let mut mock_date_time_switch = MockDateTimeSwitch::new();
+        mock_date_time_switch
+            .expect_is_closed()
+            .with(eq(some_date_time("2023-04-12T23:00:00+00:00")), always())
+            .return_const(true);
+
+        let mut mock_storage = MockStorage::new();
+        mock_storage.expect_find_one().return_once(|key| {
+            Ok(Some(Gate {
+                key,
+                state: GateState::Open,
+                comments: HashSet::default(),
+                last_updated: some_date_time("2023-04-12T14:00:00+00:00"),
+                display_order: None,
+                version: 1,
+                open_until: None,
+            }))
+        });
+        mock_storage.expect_find_freeze().return_once(|| Ok(None));
+        mock_storage
+            .expect_find_freeze_windows()
+            .return_once(|| Ok(vec![]));
+        mock_storage
+            .expect_find_gate_dependencies()
+            .return_once(|| Ok(vec![]));
+
+        // when
+        let state = UseCaseImpl {}
+            .execute(
+                some_input(Some(some_date_time("2023-04-12T23:00:00+00:00"))),
+                &mock_storage,
+                &mock_clock,
+                &mock_date_time_switch,
+            )
+            .await;
+
+        // then
+        assert_eq!(
+            state.expect("failed to get gate state"),
+            Some(models::GateStateRep {
+                state: models::GateState::Closed,
+                freeze: None,
+                reason: None,
+                closure_reason: Some(models::ClosureReason::BusinessHours),
+                latest_comment: None,
+                next_transition: None,
+                transitions: None,
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn should_close_gate_state_at_instant_within_freeze_window() {
+        // given
+        let mut mock_clock = MockClock::new();
+        mock_clock
+            .expect_now()
+            .return_const(some_date_time("2023-04-14T12:00:00+00:00"));
+
+        let mock_date_time_switch = MockDateTimeSwitch::new();
+
+        let mut mock_storage = MockStorage::new();
+        mock_storage.expect_find_one().return_once(|key| {
+            Ok(Some(Gate {
+                key,
+                state: GateState::Open,
+                comments: HashSet::default(),
+                last_updated: some_date_time("2023-04-12T14:00:00+00:00"),
+                display_order: None,
+                version: 1,
+                open_until: None,
+            }))
+        });
+        // frozen only after the requested instant
+        mock_storage.expect_find_freeze().return_once(|| {
+            Ok(Some(Freeze {
+                reason: "release freeze".to_owned(),
+                actor: None,
+                created: some_date_time("2023-04-13T12:00:00+00:00"),
+                until: None,
+            }))
+        });
+        mock_storage.expect_find_freeze_windows().return_once(|| {
+            Ok(vec![
+                FreezeWindow {
+                    id: "some id".to_owned(),
+                    name: "Easter freeze".to_owned(),
+                    reason: "holidays".to_owned(),
+                    start: some_date_time("2023-04-12T00:00:00+00:00"),
+                    end: some_date_time("2023-04-13T00:00:00+00:00"),
+                    scope: FreezeScope::All,
+                    actor: None,
+                },
+                FreezeWindow {
+                    id: "some other id".to_owned(),
+                    name: "Release freeze".to_owned(),
+                    reason: "release".to_owned(),
+                    start: some_date_time("2023-04-13T00:00:00+00:00"),
+                    end: some_date_time("2023-04-14T00:00:00+00:00"),
+                    scope: FreezeScope::All,
+                    actor: None,
+                },
+            ])
+        });
+        mock_storage
+            .expect_find_gate_dependencies()
+            .return_once(|| Ok(vec![]));
+
+        // when
+        let state = UseCaseImpl {}
+            .execute(
+                some_input(Some(some_date_time("2023-04-12T23:00:00+00:00"))),
+                &mock_storage,
+                &mock_clock,
+                &mock_date_time_switch,
+            )
+            .await;
+
+        // then
+        assert_eq!(
+            state.expect("failed to get gate state"),
+            Some(models::GateStateRep {
+                state: models::GateState::Closed,
+                freeze: None,
+                reason: Some("Easter freeze".to_owned()),
+                closure_reason: Some(models::ClosureReason::Freeze),
+                latest_comment: None,
+                next_transition: None,
+                transitions: None,
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn should_close_gate_state_at_instant_if_dependency_was_closed() {
+        // given
+        let mut mock_clock = MockClock::new();
+        mock_clock
+            .expect_now()
+            .return_const(some_date_time("2023-04-14T12:00:00+00:00"));
+
+        let mut mock_date_time_switch = MockDateTimeSwitch::new();
+        mock_date_time_switch.expect_is_closed().return_const(false);
+
+        let dependency = GateKey {
+            environment: "some-other-environment".to_owned(),
+            ..some_key()
+        };
+        let mut mock_storage = MockStorage::new();
+        mock_storage
+            .expect_find_one()
+            .with(eq(some_key()))
+            .return_once(|key| Ok(Some(some_gate(key, GateState::Open))));
+        mock_storage
+            .expect_find_one()
+            .with(eq(dependency.clone()))
+            .return_once(|key| Ok(Some(some_gate(key, GateState::Closed))));
+        mock_storage.expect_find_freeze().return_once(|| Ok(None));
+        mock_storage
+            .expect_find_freeze_windows()
+            .return_once(|| Ok(vec![]));
+        mock_storage
+            .expect_find_gate_dependencies()
+            .return_once(|| {
+                Ok(vec![GateDependencies {
+                    key: some_key(),
+                    depends_on: vec![dependency],
+                }])
+            });
+
+        // when
+        let state = UseCaseImpl {}
+            .execute(
+                some_input(Some(some_date_time("2023-04-13T12:00:00+00:00"))),
+                &mock_storage,
+                &mock_clock,
+                &mock_date_time_switch,
+            )
+            .await;
+
+        // then
+        assert_eq!(
+            state.expect("failed to get gate state"),
+            Some(models::GateStateRep {
+                closure_reason: Some(models::ClosureReason::Dependency),
+                ..GateState::Closed.into()
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn should_not_get_gate_state_at_instant_before_creation() {
+        // given
+        let mut mock_clock = MockClock::new();
+        mock_clock
+            .expect_now()
+            .return_const(some_date_time("2023-04-14T12:00:00+00:00"));
+
+        let mock_date_time_switch = MockDateTimeSwitch::new();
+
+        let mut mock_storage = MockStorage::new();
+        mock_storage.expect_find_one().return_once(|_| Ok(None));
+        mock_storage.expect_find_history().return_once(|key, _, _| {
+            Ok(HistoryPage {
+                entries: vec![
+                    some_history_entry(
+                        &key,
+                        GateEvent::Deleted,
+                        "2023-04-13T10:00:00+00:00",
+                        "closed",
+                    ),
+                    some_history_entry(
+                        &key,
+                        GateEvent::Created,
+                        "2023-04-13T09:00:00+00:00",
+                        "closed",
+                    ),
+                ],
+                next_cursor: None,
+            })
+        });
+
+        // when
+        let state = UseCaseImpl {}
+            .execute(
+                some_input(Some(some_date_time("2023-04-12T14:03:00+00:00"))),
+                &mock_storage,
+                &mock_clock,
+                &mock_date_time_switch,
+            )
+            .await;
+
+        // then
+        assert_eq!(state.expect("failed to get gate state"), None);
+    }
+
+    #[tokio::test]
+    async fn should_reject_instant_in_the_future() {
+        // given
+        let mut mock_clock = MockClock::new();
+        mock_clock
+            .expect_now()
+            .return_const(some_date_time("2023-04-14T12:00:00+00:00"));
+
+        let mock_date_time_switch = MockDateTimeSwitch::new();
+        let mock_storage = MockStorage::new();
+
+        // when
+        let state = UseCaseImpl {}
+            .execute(
+                some_input(Some(some_date_time("2023-04-15T12:00:00+00:00"))),
+                &mock_storage,
+                &mock_clock,
+                &mock_date_time_switch,
+            )
+            .await;
+
+        // then
+        assert_eq!(
+            state.expect_err("unexpected gate state"),
+            Error::InvalidInput("the requested instant must not be in the future".to_owned())
+        );
+    }
+
+    fn some_input(at: Option<DateTime<Utc>>) -> Input {
+        Input {
+            group: "some-group".to_owned(),
+            service: "some-service".to_owned(),
+            environment: "some-environment".to_owned(),
+            at,
+        }
+    }
+
+    fn some_key() -> GateKey {
+        GateKey {
+            group: "some-group".to_owned(),
+            service: "some-service".to_owned(),
+            environment: "some-environment".to_owned(),
+        }
+    }
+
+    fn some_gate(key: GateKey, state: GateState) -> Gate {
+        Gate {
+            key,
+            state,
+            comments: HashSet::default(),
+            last_updated: some_date_time("2023-04-12T10:00:00+00:00"),
+            display_order: None,
+            version: 1,
+            open_until: None,
+        }
+    }
+
+    fn some_history_entry(
+        key: &GateKey,
+        event: GateEvent,
+        timestamp: &str,
+        new_value: &str,
+    ) -> HistoryEntry {
+        HistoryEntry {
+            id: timestamp.to_owned(),
+            key: key.clone(),
+            timestamp: some_date_time(timestamp),
+            event,
+            old_value: None,
+            new_value: Some(new_value.to_owned()),
+            open_until: None,
+            actor: None,
+        }
+    }
+
+    fn some_date_time(value: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(value)
+            .expect("failed to parse date")
+            .into()
+    }
 }
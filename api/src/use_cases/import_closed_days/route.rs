@@ -0,0 +1,29 @@
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::Json;
+
+use crate::types::app_state::AppState;
+use crate::use_cases::import_closed_days::use_case;
+use crate::use_cases::import_closed_days::use_case::Error;
+
+pub async fn handler(State(app_state): State<AppState>, calendar: String) -> impl IntoResponse {
+    match app_state
+        .use_cases
+        .import_closed_days
+        .execute(
+            use_case::Input { calendar },
+            app_state.storage.as_ref(),
+            app_state.date_time_switch.as_ref(),
+        )
+        .await
+    {
+        Ok(closed_days) => Json(closed_days).into_response(),
+        Err(error) => match error {
+            Error::InvalidInput(error) => (StatusCode::BAD_REQUEST, Json(error)).into_response(),
+            Error::Internal(error) => {
+                (StatusCode::INTERNAL_SERVER_ERROR, Json(error)).into_response()
+            }
+        },
+    }
+}
@@ -0,0 +1,366 @@
+use std::collections::BTreeMap;
+
+use async_trait::async_trait;
+use chrono::{NaiveDate, TimeDelta};
+use openapi::models;
+
+use crate::date_time_switch::DateTimeSwitch;
+use crate::storage;
+use crate::storage::Storage;
+use crate::types::ClosedDay;
+
+// guards against events that would add closed days for years
+const MAX_DAYS_PER_EVENT: i64 = 366;
+// all closed days of a calendar are saved in one transaction
+const MAX_CLOSED_DAYS: usize = 100;
+
+#[derive(Debug)]
+pub struct Input {
+    pub calendar: String,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Error {
+    InvalidInput(String),
+    Internal(String),
+}
+
+impl From<storage::InsertError> for Error {
+    fn from(value: storage::InsertError) -> Self {
+        match value {
+            storage::InsertError::ItemAlreadyExists(error) | storage::InsertError::Other(error) => {
+                Self::Internal(error)
+            }
+        }
+    }
+}
+
+#[async_trait]
+pub trait UseCase {
+    async fn execute(
+        &self,
+        input: Input,
+        storage: &(dyn Storage + Send + Sync),
+        date_time_switch: &(dyn DateTimeSwitch + Send + Sync),
+    ) -> Result<Vec<models::ClosedDay>, Error>;
+}
+
+pub fn create() -> impl UseCase {
+    UseCaseImpl {}
+}
+
+#[derive(Clone)]
+struct UseCaseImpl {}
+
+#[async_trait]
+impl UseCase for UseCaseImpl {
+    async fn execute(
+        &self,
+        Input { calendar }: Input,
+        storage: &(dyn Storage + Send + Sync),
+        date_time_switch: &(dyn DateTimeSwitch + Send + Sync),
+    ) -> Result<Vec<models::ClosedDay>, Error> {
+        let closed_days = parse_calendar(&calendar).map_err(Error::InvalidInput)?;
+        if closed_days.is_empty() {
+            return Err(Error::InvalidInput(
+                "calendar does not contain any events".to_owned(),
+            ));
+        }
+
+        if closed_days.len() > MAX_CLOSED_DAYS {
+            return Err(Error::InvalidInput(format!(
+                "calendar must not contain more than {MAX_CLOSED_DAYS} closed days"
+            )));
+        }
+
+        storage.save_closed_days(&closed_days).await?;
+        date_time_switch.clear_cache();
+
+        Ok(closed_days.into_iter().map(Into::into).collect())
+    }
+}
+
+#[derive(Default)]
+struct Event {
+    start: Option<(NaiveDate, bool)>,
+    end: Option<(NaiveDate, bool)>,
+    summary: Option<String>,
+}
+
+/// Returns a closed day for each day covered by an event of the iCalendar, ordered by date.
+fn parse_calendar(calendar: &str) -> Result<Vec<ClosedDay>, String> {
+    let mut closed_days = BTreeMap::new();
+    let mut event: Option<Event> = None;
+
+    for line in unfold(calendar) {
+        let Some((name, value)) = line.split_once(':') else {
+            continue;
+        };
+        let (name, parameters) = name.split_once(';').unwrap_or((name, ""));
+        match name.to_ascii_uppercase().as_str() {
+            "BEGIN" if value.eq_ignore_ascii_case("VEVENT") => event = Some(Event::default()),
+            "END" if value.eq_ignore_ascii_case("VEVENT") => {
+                if let Some(finished) = event.take() {
+                    for closed_day in closed_days_of(finished)? {
+                        closed_days.insert(closed_day.date, closed_day);
+                    }
+                }
+            }
+            property => {
+                let Some(event) = event.as_mut() else {
+                    continue;
+                };
+                match property {
+                    "DTSTART" => event.start = Some(parse_date(parameters, value)?),
+                    "DTEND" => event.end = Some(parse_date(parameters, value)?),
+                    "SUMMARY" => event.summary = Some(unescape(value)),
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    Ok(closed_days.into_values().collect())
+}
+
+fn closed_days_of(event: Event) -> Result<Vec<ClosedDay>, String> {
+    let (start, _) = event
+        .start
+        .ok_or_else(|| "event without start".to_owned())?;
+    let reason = event
+        .summary
+        .map(|summary| summary.trim().to_owned())
+        .filter(|summary| !summary.is_empty())
+        .ok_or_else(|| format!("event on {start} without summary"))?;
+    // the end of all-day events and of events ending at midnight is exclusive
+    let end = match event.end {
+        Some((end, true)) if end > start => end - TimeDelta::days(1),
+        Some((end, _)) => end.max(start),
+        None => start,
+    };
+    if (end - start).num_days() >= MAX_DAYS_PER_EVENT {
+        return Err(format!(
+            "event on {start} must not last longer than {MAX_DAYS_PER_EVENT} days"
+        ));
+    }
+
+    Ok(start
+        .iter_days()
+        .take_while(|date| *date <= end)
+        .map(|date| ClosedDay {
+            date,
+            reason: reason.clone(),
+        })
+        .collect())
+}
+
+/// Returns the date and whether the value starts at midnight, i.e. ends the day before.
+fn parse_date(parameters: &str, value: &str) -> Result<(NaiveDate, bool), String> {
+    let value = value.trim();
+    let date = value
+        .get(..8)
+        .and_then(|date| NaiveDate::parse_from_str(date, "%Y%m%d").ok())
+        .ok_or_else(|| format!("{value} is not a valid date"))?;
+    let is_date = parameters.to_ascii_uppercase().contains("VALUE=DATE") && value.len() == 8;
+    let at_midnight = value.len() == 8 || value.get(8..15) == Some("T000000");
+    Ok((date, is_date || at_midnight))
+}
+
+// long lines are continued on lines starting with a space or a tab
+fn unfold(calendar: &str) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    for line in calendar.lines() {
+        match (line.strip_prefix([' ', '\t']), lines.last_mut()) {
+            (Some(continuation), Some(last)) => last.push_str(continuation),
+            _ => lines.push(line.to_owned()),
+        }
+    }
+    lines
+}
+
+fn unescape(value: &str) -> String {
+    value
+        .replace("\\n", " ")
+        .replace("\\N", " ")
+        .replace("\\,", ",")
+        .replace("\\;", ";")
+        .replace("\\\\", "\\")
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use mockall::predicate::eq;
+    use similar_asserts::assert_eq;
+
+    use crate::date_time_switch::MockDateTimeSwitch;
+    use crate::storage::MockStorage;
+
+    use super::*;
+
+    fn closed_day(month: u32, day: u32, reason: &str) -> ClosedDay {
+        ClosedDay {
+            date: NaiveDate::from_ymd_opt(2025, month, day).unwrap(),
+            reason: reason.to_owned(),
+        }
+    }
+
+    #[test]
+    fn should_parse_calendar() {
+        // given
+        let calendar = "BEGIN:VCALENDAR\r\n\
+            VERSION:2.0\r\n\
+            BEGIN:VEVENT\r\n\
+            DTSTART;VALUE=DATE:20251225\r\n\
+            DTEND;VALUE=DATE:20251227\r\n\
+            SUMMARY:Christmas\r\n\
+            END:VEVENT\r\n\
+            BEGIN:VEVENT\r\n\
+            DTSTART:20251003T000000\r\n\
+            SUMMARY:Day of German\r\n  Unity\\, national holiday\r\n\
+            END:VEVENT\r\n\
+            BEGIN:VEVENT\r\n\
+            DTSTART:20251231T080000Z\r\n\
+            DTEND:20260101T000000Z\r\n\
+            SUMMARY:New Year's Eve\r\n\
+            END:VEVENT\r\n\
+            END:VCALENDAR\r\n";
+
+        // when
+        let actual = parse_calendar(calendar);
+
+        // then
+        assert_eq!(
+            actual,
+            Ok(vec![
+                closed_day(10, 3, "Day of German Unity, national holiday"),
+                closed_day(12, 25, "Christmas"),
+                closed_day(12, 26, "Christmas"),
+                closed_day(12, 31, "New Year's Eve"),
+            ])
+        );
+    }
+
+    #[test]
+    fn should_fail_to_parse_event_with_invalid_date() {
+        // given
+        let calendar = "BEGIN:VEVENT\nDTSTART:2025-12-25\nSUMMARY:Christmas\nEND:VEVENT\n";
+
+        // when
+        let actual = parse_calendar(calendar);
+
+        // then
+        assert!(actual.is_err());
+    }
+
+    #[tokio::test]
+    async fn should_import_closed_days() {
+        // given
+        let mut mock_storage = MockStorage::new();
+        mock_storage
+            .expect_save_closed_days()
+            .with(eq(vec![closed_day(12, 24, "Christmas Eve")]))
+            .times(1)
+            .return_once(|_| Ok(()));
+
+        let mut mock_date_time_switch = MockDateTimeSwitch::new();
+        mock_date_time_switch
+            .expect_clear_cache()
+            .times(1)
+            .return_const(());
+
+        // when
+        let result = UseCaseImpl {}
+            .execute(
+                Input {
+                    calendar: "BEGIN:VEVENT\nDTSTART;VALUE=DATE:20251224\nSUMMARY:Christmas Eve\nEND:VEVENT\n"
+                        .to_owned(),
+                },
+                &mock_storage,
+                &mock_date_time_switch,
+            )
+            .await;
+
+        // then
+        assert_eq!(result, Ok(vec![closed_day(12, 24, "Christmas Eve").into()]));
+    }
+
+    #[tokio::test]
+    async fn should_import_no_closed_day_if_any_could_not_be_saved() {
+        // given
+        let mut mock_storage = MockStorage::new();
+        mock_storage
+            .expect_save_closed_days()
+            .return_once(|_| Err(storage::InsertError::Other("some error".to_owned())));
+
+        let mut mock_date_time_switch = MockDateTimeSwitch::new();
+        mock_date_time_switch.expect_clear_cache().never();
+
+        // when
+        let result = UseCaseImpl {}
+            .execute(
+                Input {
+                    calendar: "BEGIN:VEVENT\nDTSTART;VALUE=DATE:20251224\nDTEND;VALUE=DATE:20251227\nSUMMARY:Christmas\nEND:VEVENT\n"
+                        .to_owned(),
+                },
+                &mock_storage,
+                &mock_date_time_switch,
+            )
+            .await;
+
+        // then
+        assert_eq!(result, Err(Error::Internal("some error".to_owned())));
+    }
+
+    #[tokio::test]
+    async fn should_fail_to_import_calendar_with_too_many_closed_days() {
+        // given
+        let mock_storage = MockStorage::new();
+        let mock_date_time_switch = MockDateTimeSwitch::new();
+
+        // when
+        let result = UseCaseImpl {}
+            .execute(
+                Input {
+                    calendar: "BEGIN:VEVENT\nDTSTART;VALUE=DATE:20250101\nDTEND;VALUE=DATE:20250502\nSUMMARY:Shutdown\nEND:VEVENT\n"
+                        .to_owned(),
+                },
+                &mock_storage,
+                &mock_date_time_switch,
+            )
+            .await;
+
+        // then
+        assert_eq!(
+            result,
+            Err(Error::InvalidInput(
+                "calendar must not contain more than 100 closed days".to_owned()
+            ))
+        );
+    }
+
+    #[tokio::test]
+    async fn should_fail_to_import_calendar_without_events() {
+        // given
+        let mock_storage = MockStorage::new();
+        let mock_date_time_switch = MockDateTimeSwitch::new();
+
+        // when
+        let result = UseCaseImpl {}
+            .execute(
+                Input {
+                    calendar: "BEGIN:VCALENDAR\nEND:VCALENDAR\n".to_owned(),
+                },
+                &mock_storage,
+                &mock_date_time_switch,
+            )
+            .await;
+
+        // then
+        assert_eq!(
+            result,
+            Err(Error::InvalidInput(
+                "calendar does not contain any events".to_owned()
+            ))
+        );
+    }
+}
@@ -0,0 +1,21 @@
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::Json;
+
+use crate::types::app_state::AppState;
+use crate::use_cases::list_business_week_profiles::use_case::Error;
+
+pub async fn handler(State(app_state): State<AppState>) -> impl IntoResponse {
+    match app_state
+        .use_cases
+        .list_business_week_profiles
+        .execute(app_state.storage.as_ref())
+        .await
+    {
+        Ok(business_week_profiles) => Json(business_week_profiles).into_response(),
+        Err(Error::Internal(error)) => {
+            (StatusCode::INTERNAL_SERVER_ERROR, Json(error)).into_response()
+        }
+    }
+}
@@ -0,0 +1,85 @@
+use async_trait::async_trait;
+use openapi::models;
+
+use crate::storage;
+use crate::storage::Storage;
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Error {
+    Internal(String),
+}
+
+impl From<storage::FindError> for Error {
+    fn from(value: storage::FindError) -> Self {
+        match value {
+            storage::FindError::ItemCouldNotBeDecoded(error)
+            | storage::FindError::InvalidCursor(error)
+            | storage::FindError::Other(error) => Self::Internal(error),
+        }
+    }
+}
+
+#[async_trait]
+pub trait UseCase {
+    async fn execute(
+        &self,
+        storage: &(dyn Storage + Send + Sync),
+    ) -> Result<Vec<models::BusinessWeekProfile>, Error>;
+}
+
+pub fn create() -> impl UseCase {
+    UseCaseImpl {}
+}
+
+#[derive(Clone)]
+struct UseCaseImpl {}
+
+#[async_trait]
+impl UseCase for UseCaseImpl {
+    async fn execute(
+        &self,
+        storage: &(dyn Storage + Send + Sync),
+    ) -> Result<Vec<models::BusinessWeekProfile>, Error> {
+        Ok(storage
+            .find_business_week_profiles()
+            .await?
+            .into_iter()
+            .map(Into::into)
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use similar_asserts::assert_eq;
+
+    use crate::storage::MockStorage;
+    use crate::types::{BusinessWeek, BusinessWeekProfile, BusinessWeekScope};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn should_list_business_week_profiles() {
+        // given
+        let business_week_profile = BusinessWeekProfile {
+            scope: BusinessWeekScope::new(
+                "some group".to_owned(),
+                Some("some service".to_owned()),
+                None,
+            )
+            .unwrap(),
+            business_week: BusinessWeek::default(),
+        };
+        let mut mock_storage = MockStorage::new();
+        let stored_business_week_profile = business_week_profile.clone();
+        mock_storage
+            .expect_find_business_week_profiles()
+            .return_once(|| Ok(vec![stored_business_week_profile]));
+
+        // when
+        let result = UseCaseImpl {}.execute(&mock_storage).await;
+
+        // then
+        assert_eq!(result, Ok(vec![business_week_profile.into()]));
+    }
+}
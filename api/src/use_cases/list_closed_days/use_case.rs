@@ -0,0 +1,81 @@
+use async_trait::async_trait;
+use openapi::models;
+
+use crate::storage;
+use crate::storage::Storage;
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Error {
+    Internal(String),
+}
+
+impl From<storage::FindError> for Error {
+    fn from(value: storage::FindError) -> Self {
+        match value {
+            storage::FindError::ItemCouldNotBeDecoded(error)
+            | storage::FindError::InvalidCursor(error)
+            | storage::FindError::Other(error) => Self::Internal(error),
+        }
+    }
+}
+
+#[async_trait]
+pub trait UseCase {
+    async fn execute(
+        &self,
+        storage: &(dyn Storage + Send + Sync),
+    ) -> Result<Vec<models::ClosedDay>, Error>;
+}
+
+pub fn create() -> impl UseCase {
+    UseCaseImpl {}
+}
+
+#[derive(Clone)]
+struct UseCaseImpl {}
+
+#[async_trait]
+impl UseCase for UseCaseImpl {
+    async fn execute(
+        &self,
+        storage: &(dyn Storage + Send + Sync),
+    ) -> Result<Vec<models::ClosedDay>, Error> {
+        Ok(storage
+            .find_closed_days()
+            .await?
+            .into_iter()
+            .map(Into::into)
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use chrono::NaiveDate;
+    use similar_asserts::assert_eq;
+
+    use crate::storage::MockStorage;
+    use crate::types::ClosedDay;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn should_list_closed_days() {
+        // given
+        let closed_day = ClosedDay {
+            date: NaiveDate::from_ymd_opt(2025, 12, 24).unwrap(),
+            reason: "Christmas Eve".to_owned(),
+        };
+        let mut mock_storage = MockStorage::new();
+        let stored_closed_day = closed_day.clone();
+        mock_storage
+            .expect_find_closed_days()
+            .return_once(|| Ok(vec![stored_closed_day]));
+
+        // when
+        let result = UseCaseImpl {}.execute(&mock_storage).await;
+
+        // then
+        assert_eq!(result, Ok(vec![closed_day.into()]));
+    }
+}
@@ -0,0 +1,90 @@
+use async_trait::async_trait;
+use openapi::models;
+
+use crate::storage;
+use crate::storage::Storage;
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Error {
+    Internal(String),
+}
+
+impl From<storage::FindError> for Error {
+    fn from(value: storage::FindError) -> Self {
+        match value {
+            storage::FindError::ItemCouldNotBeDecoded(error)
+            | storage::FindError::InvalidCursor(error)
+            | storage::FindError::Other(error) => Self::Internal(error),
+        }
+    }
+}
+
+#[async_trait]
+pub trait UseCase {
+    async fn execute(
+        &self,
+        storage: &(dyn Storage + Send + Sync),
+    ) -> Result<Vec<models::FreezeWindow>, Error>;
+}
+
+pub fn create() -> impl UseCase {
+    UseCaseImpl {}
+}
+
+#[derive(Clone)]
+struct UseCaseImpl {}
+
+#[async_trait]
+impl UseCase for UseCaseImpl {
+    async fn execute(
+        &self,
+        storage: &(dyn Storage + Send + Sync),
+    ) -> Result<Vec<models::FreezeWindow>, Error> {
+        Ok(storage
+            .find_freeze_windows()
+            .await?
+            .into_iter()
+            .map(Into::into)
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use chrono::DateTime;
+    use similar_asserts::assert_eq;
+
+    use crate::storage::MockStorage;
+    use crate::types::{FreezeScope, FreezeWindow};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn should_list_freeze_windows() {
+        // given
+        let freeze_window = FreezeWindow {
+            id: "some id".to_owned(),
+            name: "Year-end freeze".to_owned(),
+            reason: "annual closing".to_owned(),
+            start: DateTime::parse_from_rfc3339("2025-12-19T17:00:00Z")
+                .expect("failed to parse date")
+                .to_utc(),
+            end: DateTime::parse_from_rfc3339("2026-01-05T06:00:00Z")
+                .expect("failed to parse date")
+                .to_utc(),
+            scope: FreezeScope::Group("some group".to_owned()),
+            actor: None,
+        };
+        let mut mock_storage = MockStorage::new();
+        let stored_freeze_window = freeze_window.clone();
+        mock_storage
+            .expect_find_freeze_windows()
+            .return_once(|| Ok(vec![stored_freeze_window]));
+
+        // when
+        let result = UseCaseImpl {}.execute(&mock_storage).await;
+
+        // then
+        assert_eq!(result, Ok(vec![freeze_window.into()]));
+    }
+}
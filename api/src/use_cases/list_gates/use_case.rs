@@ -4,9 +4,9 @@ use itertools::Itertools;
 use openapi::models;
 
 use crate::clock::Clock;
+use crate::closure::Closures;
 use crate::date_time_switch::DateTimeSwitch;
 use crate::storage::Storage;
-use crate::types::Gate;
 
 #[derive(Debug, PartialEq, Eq)]
 pub enum Error {
@@ -16,9 +16,9 @@ pub enum Error {
 impl From<storage::FindError> for Error {
     fn from(value: storage::FindError) -> Self {
         match value {
-            storage::FindError::ItemCouldNotBeDecoded(error) | storage::FindError::Other(error) => {
-                Self::Internal(error)
-            }
+            storage::FindError::ItemCouldNotBeDecoded(error)
+            | storage::FindError::InvalidCursor(error)
+            | storage::FindError::Other(error) => Self::Internal(error),
         }
     }
 }
@@ -48,37 +48,37 @@ impl UseCase for UseCaseImpl {
         clock: &(dyn Clock + Send + Sync),
         date_time_switch: &(dyn DateTimeSwitch + Send + Sync),
     ) -> Result<Vec<models::Group>, Error> {
-        Ok(ordered_by_group(
-            storage
-                .find_all()
-                .await?
-                .into_iter()
-                .map(|gate| date_time_switch.close_if_time(clock.now(), gate))
-                .collect(),
-        ))
+        let gates = storage.find_all().await?;
+        let closures = Closures::at(clock.now(), storage, date_time_switch).await?;
+        let dependencies = closures.dependencies_of(&gates).await?;
+        let mut reps = Vec::with_capacity(gates.len());
+        for gate in gates {
+            reps.push(closures.rep(gate, &dependencies).await);
+        }
+        Ok(ordered_by_group(reps))
     }
 }
 
-fn ordered_by_group(gates: Vec<Gate>) -> Vec<models::Group> {
+pub fn ordered_by_group(gates: Vec<models::Gate>) -> Vec<models::Group> {
     let mut groups: Vec<models::Group> = Vec::new();
     let group_to_items = gates
         .into_iter()
-        .sorted_by_key(|item| item.key.group.clone())
-        .chunk_by(|item| item.key.group.clone());
+        .sorted_by_key(|item| item.group.clone())
+        .chunk_by(|item| item.group.clone());
 
     for (group, items) in &group_to_items {
         let service_to_items = items
             .into_iter()
-            .sorted_by_key(|item| item.key.service.clone())
-            .chunk_by(|item| item.key.service.clone());
+            .sorted_by_key(|item| item.service.clone())
+            .chunk_by(|item| item.service.clone());
 
         let mut services: Vec<models::Service> = Vec::new();
         for (service, items) in &service_to_items {
             let mut environments: Vec<models::Environment> = Vec::new();
             for item in items {
                 environments.push(models::Environment {
-                    name: item.key.environment.clone(),
-                    gate: item.into(),
+                    name: item.environment.clone(),
+                    gate: item,
                 });
             }
             environments.sort_by(|a, b| {
@@ -112,7 +112,7 @@ mod unit_tests {
     use crate::clock::MockClock;
     use crate::date_time_switch::MockDateTimeSwitch;
     use crate::storage::MockStorage;
-    use crate::types::{Comment, GateKey, GateState};
+    use crate::types::{Comment, Gate, GateKey, GateState};
 
     use super::*;
 
@@ -126,48 +126,37 @@ mod unit_tests {
 
         let mut mock_date_time_switch = MockDateTimeSwitch::new();
         mock_date_time_switch
-            .expect_close_if_time()
+            .expect_is_closed()
             .with(
                 eq::<chrono::DateTime<chrono::Utc>>(now.into()),
-                eq(some_gate(
-                    "some group",
-                    "1 some service",
-                    "some environment",
-                )),
+                eq(some_gate("some group", "1 some service", "some environment").key),
             )
-            .return_once(|_, gate| Gate {
-                key: gate.key,
-                state: GateState::Closed,
-                comments: gate.comments,
-                last_updated: gate.last_updated,
-                display_order: gate.display_order,
-            });
+            .return_const(true);
 
         mock_date_time_switch
-            .expect_close_if_time()
+            .expect_is_closed()
             .with(
                 eq::<chrono::DateTime<chrono::Utc>>(now.into()),
-                eq(some_gate(
-                    "some group",
-                    "1 some service",
-                    "some other environment",
-                )),
+                eq(some_gate("some group", "1 some service", "some other environment").key),
             )
-            .return_once(|_, gate| gate);
+            .return_const(false);
 
         mock_date_time_switch
-            .expect_close_if_time()
+            .expect_is_closed()
             .with(
                 eq::<chrono::DateTime<chrono::Utc>>(now.into()),
-                eq(some_gate(
-                    "some group",
-                    "2 some other service",
-                    "some environment",
-                )),
+                eq(some_gate("some group", "2 some other service", "some environment").key),
             )
-            .return_once(|_, gate| gate);
+            .return_const(false);
 
         let mut mock_storage = MockStorage::new();
+        mock_storage.expect_find_freeze().return_once(|| Ok(None));
+        mock_storage
+            .expect_find_freeze_windows()
+            .return_once(|| Ok(vec![]));
+        mock_storage
+            .expect_find_gate_dependencies()
+            .return_once(|| Ok(vec![]));
 
         let gate1 = some_gate("some group", "1 some service", "some environment");
         let gate2 = some_gate("some group", "1 some service", "some other environment");
@@ -198,14 +187,19 @@ mod unit_tests {
                         environments: vec![
                             models::Environment {
                                 name: "some environment".to_owned(),
-                                gate: Gate {
-                                    key: gate1.key,
-                                    state: GateState::Closed,
-                                    comments: gate1.comments,
-                                    last_updated: gate1.last_updated,
-                                    display_order: gate1.display_order,
+                                gate: models::Gate {
+                                    closure_reason: Some(models::ClosureReason::BusinessHours),
+                                    ..Gate {
+                                        key: gate1.key,
+                                        state: GateState::Closed,
+                                        comments: gate1.comments,
+                                        last_updated: gate1.last_updated,
+                                        display_order: gate1.display_order,
+                                        version: 0,
+                                        open_until: None,
+                                    }
+                                    .into()
                                 }
-                                .into()
                             },
                             models::Environment {
                                 name: "some other environment".to_owned(),
@@ -235,26 +229,34 @@ mod unit_tests {
 
         let mut mock_date_time_switch = MockDateTimeSwitch::new();
         mock_date_time_switch
-            .expect_close_if_time()
+            .expect_is_closed()
             .with(
                 eq::<DateTime<chrono::Utc>>(now.into()),
-                eq(some_gate("some group", "some service", "some environment")),
+                eq(some_gate("some group", "some service", "some environment").key),
             )
-            .return_once(|_, gate| gate);
+            .return_const(false);
 
         mock_date_time_switch
-            .expect_close_if_time()
+            .expect_is_closed()
             .with(
                 eq::<DateTime<chrono::Utc>>(now.into()),
                 eq(some_gate(
                     "some other group",
                     "some other service",
                     "some other environment",
-                )),
+                )
+                .key),
             )
-            .return_once(|_, gate| gate);
+            .return_const(false);
 
         let mut mock_storage = MockStorage::new();
+        mock_storage.expect_find_freeze().return_once(|| Ok(None));
+        mock_storage
+            .expect_find_freeze_windows()
+            .return_once(|| Ok(vec![]));
+        mock_storage
+            .expect_find_gate_dependencies()
+            .return_once(|| Ok(vec![]));
         let gate1 = some_gate("some group", "some service", "some environment");
         let gate2 = some_gate(
             "some other group",
@@ -317,20 +319,21 @@ mod unit_tests {
 
         let mut mock_date_time_switch = MockDateTimeSwitch::new();
         mock_date_time_switch
-            .expect_close_if_time()
+            .expect_is_closed()
             .with(
                 eq::<DateTime<chrono::Utc>>(now.into()),
-                eq(some_gate("some group", "some service", "some environment")),
+                eq(some_gate("some group", "some service", "some environment").key),
             )
-            .return_once(|_, gate| Gate {
-                key: gate.key,
-                state: GateState::Closed,
-                comments: gate.comments,
-                last_updated: gate.last_updated,
-                display_order: gate.display_order,
-            });
+            .return_const(true);
 
         let mut mock_storage = MockStorage::new();
+        mock_storage.expect_find_freeze().return_once(|| Ok(None));
+        mock_storage
+            .expect_find_freeze_windows()
+            .return_once(|| Ok(vec![]));
+        mock_storage
+            .expect_find_gate_dependencies()
+            .return_once(|| Ok(vec![]));
         let gate1 = some_gate("some group", "some service", "some environment");
 
         mock_storage
@@ -362,7 +365,11 @@ mod unit_tests {
                             state: models::GateState::Closed,
                             comments: gate_representation.comments,
                             last_updated: gate_representation.last_updated,
-                            display_order: gate_representation.display_order
+                            display_order: gate_representation.display_order,
+                            version: gate_representation.version,
+                            open_until: None,
+                            open_remaining_seconds: None,
+                            closure_reason: Some(models::ClosureReason::BusinessHours),
                         }
                     },],
                 },],
@@ -448,6 +455,8 @@ mod unit_tests {
                 .expect("failed creating date")
                 .into(),
             display_order: Option::default(),
+            version: 0,
+            open_until: None,
         }
     }
 }
@@ -0,0 +1,236 @@
+use crate::storage;
+use async_trait::async_trait;
+use openapi::models;
+
+use crate::clock::Clock;
+use crate::closure::Closures;
+use crate::date_time_switch::DateTimeSwitch;
+use crate::storage::Storage;
+use crate::use_cases::list_gates::use_case::ordered_by_group;
+
+#[derive(Debug)]
+pub struct Input {
+    pub group: String,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Error {
+    Internal(String),
+}
+
+impl From<storage::FindError> for Error {
+    fn from(value: storage::FindError) -> Self {
+        match value {
+            storage::FindError::ItemCouldNotBeDecoded(error)
+            | storage::FindError::InvalidCursor(error)
+            | storage::FindError::Other(error) => Self::Internal(error),
+        }
+    }
+}
+
+#[async_trait]
+pub trait UseCase {
+    async fn execute(
+        &self,
+        input: Input,
+        storage: &(dyn Storage + Send + Sync),
+        clock: &(dyn Clock + Send + Sync),
+        date_time_switch: &(dyn DateTimeSwitch + Send + Sync),
+    ) -> Result<Option<models::Group>, Error>;
+}
+
+pub fn create() -> impl UseCase {
+    UseCaseImpl {}
+}
+
+#[derive(Clone)]
+struct UseCaseImpl;
+
+#[async_trait]
+impl UseCase for UseCaseImpl {
+    async fn execute(
+        &self,
+        Input { group }: Input,
+        storage: &(dyn Storage + Send + Sync),
+        clock: &(dyn Clock + Send + Sync),
+        date_time_switch: &(dyn DateTimeSwitch + Send + Sync),
+    ) -> Result<Option<models::Group>, Error> {
+        let gates = storage.find_by_group(group).await?;
+        let closures = Closures::at(clock.now(), storage, date_time_switch).await?;
+        let dependencies = closures.dependencies_of(&gates).await?;
+        let mut reps = Vec::with_capacity(gates.len());
+        for gate in gates {
+            reps.push(closures.rep(gate, &dependencies).await);
+        }
+        Ok(ordered_by_group(reps).into_iter().next())
+    }
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use std::collections::HashSet;
+
+    use chrono::DateTime;
+    use mockall::predicate::eq;
+    use similar_asserts::assert_eq;
+
+    use crate::clock::MockClock;
+    use crate::date_time_switch::MockDateTimeSwitch;
+    use crate::storage::MockStorage;
+    use crate::types::{Gate, GateKey, GateState};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn should_list_gates_of_group() {
+        // given
+        let mut mock_clock = MockClock::new();
+        let now = DateTime::parse_from_rfc3339("2023-04-12T22:10:57+02:00")
+            .expect("failed to parse date");
+        mock_clock.expect_now().return_const(now);
+
+        let mut mock_date_time_switch = MockDateTimeSwitch::new();
+        mock_date_time_switch.expect_is_closed().return_const(false);
+
+        let mut mock_storage = MockStorage::new();
+        mock_storage.expect_find_freeze().return_once(|| Ok(None));
+        mock_storage
+            .expect_find_freeze_windows()
+            .return_once(|| Ok(vec![]));
+        mock_storage
+            .expect_find_gate_dependencies()
+            .return_once(|| Ok(vec![]));
+        mock_storage
+            .expect_find_by_group()
+            .with(eq("some group".to_owned()))
+            .return_once(|_| {
+                Ok(vec![
+                    some_gate("some group", "some service", "some environment"),
+                    some_gate("some group", "some other service", "some environment"),
+                ])
+            });
+
+        // when
+        let group = UseCaseImpl {}
+            .execute(
+                Input {
+                    group: "some group".to_owned(),
+                },
+                &mock_storage,
+                &mock_clock,
+                &mock_date_time_switch,
+            )
+            .await;
+
+        // then
+        assert_eq!(
+            group.expect("failed to list gates of group"),
+            Some(models::Group {
+                name: "some group".to_owned(),
+                services: vec![
+                    models::Service {
+                        name: "some other service".to_owned(),
+                        environments: vec![models::Environment {
+                            name: "some environment".to_owned(),
+                            gate: some_gate("some group", "some other service", "some environment")
+                                .into(),
+                        }],
+                    },
+                    models::Service {
+                        name: "some service".to_owned(),
+                        environments: vec![models::Environment {
+                            name: "some environment".to_owned(),
+                            gate: some_gate("some group", "some service", "some environment")
+                                .into(),
+                        }],
+                    },
+                ],
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn should_not_list_gates_of_unknown_group() {
+        // given
+        let mut mock_clock = MockClock::new();
+        mock_clock.expect_now().return_const(
+            DateTime::parse_from_rfc3339("2023-04-12T22:10:57+02:00")
+                .expect("failed to parse date")
+                .to_utc(),
+        );
+        let mock_date_time_switch = MockDateTimeSwitch::new();
+        let mut mock_storage = MockStorage::new();
+        mock_storage.expect_find_freeze().return_once(|| Ok(None));
+        mock_storage
+            .expect_find_freeze_windows()
+            .return_once(|| Ok(vec![]));
+        mock_storage
+            .expect_find_gate_dependencies()
+            .return_once(|| Ok(vec![]));
+        mock_storage
+            .expect_find_by_group()
+            .return_once(|_| Ok(vec![]));
+
+        // when
+        let group = UseCaseImpl {}
+            .execute(
+                Input {
+                    group: "some unknown group".to_owned(),
+                },
+                &mock_storage,
+                &mock_clock,
+                &mock_date_time_switch,
+            )
+            .await;
+
+        // then
+        assert_eq!(group.expect("failed to list gates of group"), None);
+    }
+
+    #[tokio::test]
+    async fn should_return_error_if_storage_fails() {
+        // given
+        let mock_clock = MockClock::new();
+        let mock_date_time_switch = MockDateTimeSwitch::new();
+        let mut mock_storage = MockStorage::new();
+        mock_storage
+            .expect_find_by_group()
+            .return_once(|_| Err(storage::FindError::Other("some error".to_owned())));
+
+        // when
+        let group = UseCaseImpl {}
+            .execute(
+                Input {
+                    group: "some group".to_owned(),
+                },
+                &mock_storage,
+                &mock_clock,
+                &mock_date_time_switch,
+            )
+            .await;
+
+        // then
+        assert_eq!(
+            group.expect_err("unexpected group"),
+            Error::Internal("some error".to_owned())
+        );
+    }
+
+    fn some_gate(group: &str, service: &str, environment: &str) -> Gate {
+        Gate {
+            key: GateKey {
+                group: group.to_owned(),
+                service: service.to_owned(),
+                environment: environment.to_owned(),
+            },
+            state: GateState::Open,
+            comments: HashSet::default(),
+            last_updated: DateTime::parse_from_rfc3339("2023-04-12T22:10:57+02:00")
+                .expect("failed creating date")
+                .into(),
+            display_order: Option::default(),
+            version: 0,
+            open_until: None,
+        }
+    }
+}
@@ -0,0 +1,39 @@
+use crate::types::app_state::AppState;
+use crate::use_cases::list_gates_by_service::use_case;
+use crate::use_cases::list_gates_by_service::use_case::Error;
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::Json;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize)]
+pub struct PathParams {
+    group: String,
+    service: String,
+}
+
+pub async fn handler(
+    Path(PathParams { group, service }): Path<PathParams>,
+    State(app_state): State<AppState>,
+) -> impl IntoResponse {
+    match app_state
+        .use_cases
+        .list_gates_by_service
+        .execute(
+            use_case::Input { group, service },
+            app_state.storage.as_ref(),
+            app_state.clock.as_ref(),
+            app_state.date_time_switch.as_ref(),
+        )
+        .await
+    {
+        Ok(Some(service)) => Json(service).into_response(),
+        Ok(None) => StatusCode::NO_CONTENT.into_response(),
+        Err(error) => match error {
+            Error::Internal(error) => {
+                (StatusCode::INTERNAL_SERVER_ERROR, Json(error)).into_response()
+            }
+        },
+    }
+}
@@ -0,0 +1,259 @@
+use crate::storage;
+use async_trait::async_trait;
+use openapi::models;
+
+use crate::clock::Clock;
+use crate::closure::Closures;
+use crate::date_time_switch::DateTimeSwitch;
+use crate::storage::Storage;
+use crate::use_cases::list_gates::use_case::ordered_by_group;
+
+#[derive(Debug)]
+pub struct Input {
+    pub group: String,
+    pub service: String,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Error {
+    Internal(String),
+}
+
+impl From<storage::FindError> for Error {
+    fn from(value: storage::FindError) -> Self {
+        match value {
+            storage::FindError::ItemCouldNotBeDecoded(error)
+            | storage::FindError::InvalidCursor(error)
+            | storage::FindError::Other(error) => Self::Internal(error),
+        }
+    }
+}
+
+#[async_trait]
+pub trait UseCase {
+    async fn execute(
+        &self,
+        input: Input,
+        storage: &(dyn Storage + Send + Sync),
+        clock: &(dyn Clock + Send + Sync),
+        date_time_switch: &(dyn DateTimeSwitch + Send + Sync),
+    ) -> Result<Option<models::Service>, Error>;
+}
+
+pub fn create() -> impl UseCase {
+    UseCaseImpl {}
+}
+
+#[derive(Clone)]
+struct UseCaseImpl;
+
+#[async_trait]
+impl UseCase for UseCaseImpl {
+    async fn execute(
+        &self,
+        Input { group, service }: Input,
+        storage: &(dyn Storage + Send + Sync),
+        clock: &(dyn Clock + Send + Sync),
+        date_time_switch: &(dyn DateTimeSwitch + Send + Sync),
+    ) -> Result<Option<models::Service>, Error> {
+        let gates = storage.find_by_group_and_service(group, service).await?;
+        let closures = Closures::at(clock.now(), storage, date_time_switch).await?;
+        let dependencies = closures.dependencies_of(&gates).await?;
+        let mut reps = Vec::with_capacity(gates.len());
+        for gate in gates {
+            reps.push(closures.rep(gate, &dependencies).await);
+        }
+        Ok(ordered_by_group(reps)
+            .into_iter()
+            .next()
+            .and_then(|group| group.services.into_iter().next()))
+    }
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use std::collections::HashSet;
+
+    use chrono::DateTime;
+    use mockall::predicate::eq;
+    use similar_asserts::assert_eq;
+
+    use crate::clock::MockClock;
+    use crate::date_time_switch::MockDateTimeSwitch;
+    use crate::storage::MockStorage;
+    use crate::types::{Gate, GateKey, GateState};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn should_list_gates_of_service() {
+        // given
+        let mut mock_clock = MockClock::new();
+        let now = DateTime::parse_from_rfc3339("2023-04-12T22:10:57+02:00")
+            .expect("failed to parse date");
+        mock_clock.expect_now().return_const(now);
+
+        let mut mock_date_time_switch = MockDateTimeSwitch::new();
+        mock_date_time_switch
+            .expect_is_closed()
+            .with(
+                eq::<DateTime<chrono::Utc>>(now.into()),
+                eq(some_gate("some group", "some service", "live").key),
+            )
+            .return_const(true);
+        mock_date_time_switch
+            .expect_is_closed()
+            .with(
+                eq::<DateTime<chrono::Utc>>(now.into()),
+                eq(some_gate("some group", "some service", "develop").key),
+            )
+            .return_const(false);
+
+        let mut mock_storage = MockStorage::new();
+        mock_storage.expect_find_freeze().return_once(|| Ok(None));
+        mock_storage
+            .expect_find_freeze_windows()
+            .return_once(|| Ok(vec![]));
+        mock_storage
+            .expect_find_gate_dependencies()
+            .return_once(|| Ok(vec![]));
+        mock_storage
+            .expect_find_by_group_and_service()
+            .with(eq("some group".to_owned()), eq("some service".to_owned()))
+            .return_once(|_, _| {
+                Ok(vec![
+                    some_gate("some group", "some service", "develop"),
+                    some_gate("some group", "some service", "live"),
+                ])
+            });
+
+        // when
+        let service = UseCaseImpl {}
+            .execute(
+                Input {
+                    group: "some group".to_owned(),
+                    service: "some service".to_owned(),
+                },
+                &mock_storage,
+                &mock_clock,
+                &mock_date_time_switch,
+            )
+            .await;
+
+        // then
+        assert_eq!(
+            service.expect("failed to list gates of service"),
+            Some(models::Service {
+                name: "some service".to_owned(),
+                environments: vec![
+                    models::Environment {
+                        name: "develop".to_owned(),
+                        gate: some_gate("some group", "some service", "develop").into(),
+                    },
+                    models::Environment {
+                        name: "live".to_owned(),
+                        gate: models::Gate {
+                            closure_reason: Some(models::ClosureReason::BusinessHours),
+                            ..Gate {
+                                state: GateState::Closed,
+                                ..some_gate("some group", "some service", "live")
+                            }
+                            .into()
+                        },
+                    },
+                ],
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn should_not_list_gates_of_unknown_service() {
+        // given
+        let mut mock_clock = MockClock::new();
+        mock_clock.expect_now().return_const(
+            DateTime::parse_from_rfc3339("2023-04-12T22:10:57+02:00")
+                .expect("failed to parse date")
+                .to_utc(),
+        );
+        let mock_date_time_switch = MockDateTimeSwitch::new();
+        let mut mock_storage = MockStorage::new();
+        mock_storage.expect_find_freeze().return_once(|| Ok(None));
+        mock_storage
+            .expect_find_freeze_windows()
+            .return_once(|| Ok(vec![]));
+        mock_storage
+            .expect_find_gate_dependencies()
+            .return_once(|| Ok(vec![]));
+        mock_storage
+            .expect_find_by_group_and_service()
+            .return_once(|_, _| Ok(vec![]));
+
+        // when
+        let service = UseCaseImpl {}
+            .execute(
+                Input {
+                    group: "some group".to_owned(),
+                    service: "some unknown service".to_owned(),
+                },
+                &mock_storage,
+                &mock_clock,
+                &mock_date_time_switch,
+            )
+            .await;
+
+        // then
+        assert_eq!(service.expect("failed to list gates of service"), None);
+    }
+
+    #[tokio::test]
+    async fn should_return_error_if_storage_fails() {
+        // given
+        let mock_clock = MockClock::new();
+        let mock_date_time_switch = MockDateTimeSwitch::new();
+        let mut mock_storage = MockStorage::new();
+        mock_storage
+            .expect_find_by_group_and_service()
+            .return_once(|_, _| {
+                Err(storage::FindError::ItemCouldNotBeDecoded(
+                    "some error".to_owned(),
+                ))
+            });
+
+        // when
+        let service = UseCaseImpl {}
+            .execute(
+                Input {
+                    group: "some group".to_owned(),
+                    service: "some service".to_owned(),
+                },
+                &mock_storage,
+                &mock_clock,
+                &mock_date_time_switch,
+            )
+            .await;
+
+        // then
+        assert_eq!(
+            service.expect_err("unexpected service"),
+            Error::Internal("some error".to_owned())
+        );
+    }
+
+    fn some_gate(group: &str, service: &str, environment: &str) -> Gate {
+        Gate {
+            key: GateKey {
+                group: group.to_owned(),
+                service: service.to_owned(),
+                environment: environment.to_owned(),
+            },
+            state: GateState::Open,
+            comments: HashSet::default(),
+            last_updated: DateTime::parse_from_rfc3339("2023-04-12T22:10:57+02:00")
+                .expect("failed creating date")
+                .into(),
+            display_order: Option::default(),
+            version: 0,
+            open_until: None,
+        }
+    }
+}
@@ -0,0 +1,156 @@
+use async_trait::async_trait;
+use openapi::models;
+
+use crate::storage;
+use crate::storage::Storage;
+use crate::types::GateKey;
+
+#[derive(Debug)]
+pub struct Input {
+    pub group: String,
+    pub service: String,
+    pub environment: String,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Error {
+    GateNotFound,
+    Internal(String),
+}
+
+impl From<storage::FindError> for Error {
+    fn from(value: storage::FindError) -> Self {
+        match value {
+            storage::FindError::ItemCouldNotBeDecoded(error)
+            | storage::FindError::InvalidCursor(error)
+            | storage::FindError::Other(error) => Self::Internal(error),
+        }
+    }
+}
+
+#[async_trait]
+pub trait UseCase {
+    async fn execute(
+        &self,
+        input: Input,
+        storage: &(dyn Storage + Send + Sync),
+    ) -> Result<Vec<models::ScheduledStateChange>, Error>;
+}
+
+pub fn create() -> impl UseCase {
+    UseCaseImpl {}
+}
+
+#[derive(Clone)]
+struct UseCaseImpl {}
+
+#[async_trait]
+impl UseCase for UseCaseImpl {
+    async fn execute(
+        &self,
+        Input {
+            group,
+            service,
+            environment,
+        }: Input,
+        storage: &(dyn Storage + Send + Sync),
+    ) -> Result<Vec<models::ScheduledStateChange>, Error> {
+        let key = GateKey {
+            group,
+            service,
+            environment,
+        };
+        if storage.find_one(key.clone()).await?.is_none() {
+            return Err(Error::GateNotFound);
+        }
+
+        Ok(storage
+            .find_scheduled_state_changes()
+            .await?
+            .into_iter()
+            .filter(|change| change.key == key)
+            .map(Into::into)
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use std::collections::HashSet;
+
+    use chrono::DateTime;
+    use similar_asserts::assert_eq;
+
+    use crate::storage::MockStorage;
+    use crate::types::{Gate, GateState, ScheduledStateChange};
+
+    use super::*;
+
+    fn some_change(id: &str, environment: &str) -> ScheduledStateChange {
+        ScheduledStateChange {
+            id: id.to_owned(),
+            key: GateKey {
+                group: "some group".to_owned(),
+                service: "some service".to_owned(),
+                environment: environment.to_owned(),
+            },
+            state: GateState::Open,
+            at: DateTime::parse_from_rfc3339("2023-06-06T04:00:00Z")
+                .expect("failed to parse date")
+                .to_utc(),
+            actor: None,
+        }
+    }
+
+    fn some_input() -> Input {
+        Input {
+            group: "some group".to_owned(),
+            service: "some service".to_owned(),
+            environment: "live".to_owned(),
+        }
+    }
+
+    #[tokio::test]
+    async fn should_list_scheduled_state_changes_of_gate() {
+        // given
+        let mut mock_storage = MockStorage::new();
+        mock_storage.expect_find_one().return_once(|key| {
+            Ok(Some(Gate {
+                key,
+                state: GateState::Closed,
+                comments: HashSet::default(),
+                last_updated: DateTime::default(),
+                display_order: None,
+                version: 0,
+                open_until: None,
+            }))
+        });
+        mock_storage
+            .expect_find_scheduled_state_changes()
+            .return_once(|| {
+                Ok(vec![
+                    some_change("some id", "live"),
+                    some_change("some other id", "develop"),
+                ])
+            });
+
+        // when
+        let result = UseCaseImpl {}.execute(some_input(), &mock_storage).await;
+
+        // then
+        assert_eq!(result, Ok(vec![some_change("some id", "live").into()]));
+    }
+
+    #[tokio::test]
+    async fn should_not_list_scheduled_state_changes_of_missing_gate() {
+        // given
+        let mut mock_storage = MockStorage::new();
+        mock_storage.expect_find_one().return_once(|_| Ok(None));
+
+        // when
+        let result = UseCaseImpl {}.execute(some_input(), &mock_storage).await;
+
+        // then
+        assert_eq!(result, Err(Error::GateNotFound));
+    }
+}
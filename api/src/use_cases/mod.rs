@@ -1,11 +1,39 @@
 pub mod add_comment;
 pub mod api_info;
+pub mod apply_scheduled_state_changes;
+pub mod cancel_scheduled_state_change;
+pub mod clear_freeze;
+pub mod close_expired_gates;
+pub mod close_gates_after_business_hours;
+pub mod create_freeze_window;
 pub mod create_gate;
+pub mod create_gates;
+pub mod delete_business_week_profile;
+pub mod delete_closed_day;
 pub mod delete_comment;
+pub mod delete_freeze_window;
 pub mod delete_gate;
 pub mod get_config;
 pub mod get_gate;
+pub mod get_gate_dependencies;
+pub mod get_gate_history;
 pub mod get_gate_state;
+pub mod import_closed_days;
+pub mod list_business_week_profiles;
+pub mod list_closed_days;
+pub mod list_freeze_windows;
 pub mod list_gates;
+pub mod list_gates_by_group;
+pub mod list_gates_by_service;
+pub mod list_scheduled_state_changes;
+pub mod purge_trash;
+pub mod restore_gate;
+pub mod schedule_state_change;
+pub mod set_business_week;
+pub mod set_business_week_profile;
+pub mod set_closed_day;
+pub mod set_freeze;
+pub mod set_gate_dependencies;
 pub mod update_display_order;
 pub mod update_gate_state;
+pub mod update_gate_states;
@@ -0,0 +1,107 @@
+use async_trait::async_trait;
+use chrono::TimeDelta;
+
+use crate::clock::Clock;
+use crate::storage;
+use crate::storage::Storage;
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Error {
+    Internal(String),
+}
+
+impl From<storage::DeleteError> for Error {
+    fn from(value: storage::DeleteError) -> Self {
+        match value {
+            storage::DeleteError::ItemToDeleteNotFound(error)
+            | storage::DeleteError::Other(error) => Self::Internal(error),
+        }
+    }
+}
+
+#[async_trait]
+pub trait UseCase {
+    /// Permanently deletes all gates past their retention period in the trash and returns how many
+    /// were deleted.
+    async fn execute(
+        &self,
+        storage: &(dyn Storage + Send + Sync),
+        clock: &(dyn Clock + Send + Sync),
+        trash_retention: TimeDelta,
+    ) -> Result<u64, Error>;
+}
+
+pub fn create() -> impl UseCase {
+    UseCaseImpl {}
+}
+
+#[derive(Clone)]
+struct UseCaseImpl {}
+
+#[async_trait]
+impl UseCase for UseCaseImpl {
+    async fn execute(
+        &self,
+        storage: &(dyn Storage + Send + Sync),
+        clock: &(dyn Clock + Send + Sync),
+        trash_retention: TimeDelta,
+    ) -> Result<u64, Error> {
+        Ok(storage.purge_trash(clock.now() - trash_retention).await?)
+    }
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use chrono::{DateTime, Utc};
+    use mockall::predicate::eq;
+    use similar_asserts::assert_eq;
+
+    use crate::clock::MockClock;
+    use crate::storage::MockStorage;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn should_purge_gates_past_retention_period() {
+        // given
+        let now: DateTime<Utc> = DateTime::parse_from_rfc3339("2023-04-12T22:10:57+02:00")
+            .expect("failed to parse date")
+            .into();
+        let mut mock_clock = MockClock::new();
+        mock_clock.expect_now().return_const(now);
+        let mut mock_storage = MockStorage::new();
+        mock_storage
+            .expect_purge_trash()
+            .with(eq(now - TimeDelta::days(30)))
+            .return_once(|_| Ok(2));
+
+        // when
+        let result = UseCaseImpl {}
+            .execute(&mock_storage, &mock_clock, TimeDelta::days(30))
+            .await;
+
+        // then
+        assert_eq!(result, Ok(2));
+    }
+
+    #[tokio::test]
+    async fn should_return_storage_error() {
+        // given
+        let mut mock_clock = MockClock::new();
+        mock_clock
+            .expect_now()
+            .return_const(DateTime::<Utc>::default());
+        let mut mock_storage = MockStorage::new();
+        mock_storage
+            .expect_purge_trash()
+            .return_once(|_| Err(storage::DeleteError::Other("some error".to_owned())));
+
+        // when
+        let result = UseCaseImpl {}
+            .execute(&mock_storage, &mock_clock, TimeDelta::days(30))
+            .await;
+
+        // then
+        assert_eq!(result, Err(Error::Internal("some error".to_owned())));
+    }
+}
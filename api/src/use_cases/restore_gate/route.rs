@@ -0,0 +1,54 @@
+use crate::actor;
+use crate::etag;
+use crate::types::app_state::AppState;
+use crate::use_cases::restore_gate::use_case;
+use crate::use_cases::restore_gate::use_case::Error;
+use axum::extract::{Path, State};
+use axum::http::header::ETAG;
+use axum::http::{HeaderMap, StatusCode};
+use axum::response::IntoResponse;
+use axum::Json;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize)]
+pub struct PathParams {
+    group: String,
+    service: String,
+    environment: String,
+}
+
+pub async fn handler(
+    Path(PathParams {
+        group,
+        service,
+        environment,
+    }): Path<PathParams>,
+    State(app_state): State<AppState>,
+    headers: HeaderMap,
+) -> impl IntoResponse {
+    match app_state
+        .use_cases
+        .restore_gate
+        .execute(
+            use_case::Input {
+                group,
+                service,
+                environment,
+                actor: actor::from_headers(&headers),
+            },
+            app_state.storage.as_ref(),
+            app_state.clock.as_ref(),
+            app_state.id_provider.as_ref(),
+            app_state.trash_retention,
+        )
+        .await
+    {
+        Ok(gate) => ([(ETAG, etag::from_version(gate.version))], Json(gate)).into_response(),
+        Err(error) => match error {
+            Error::GateNotFound => StatusCode::NO_CONTENT.into_response(),
+            Error::Internal(error) => {
+                (StatusCode::INTERNAL_SERVER_ERROR, Json(error)).into_response()
+            }
+        },
+    }
+}
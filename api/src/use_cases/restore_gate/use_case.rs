@@ -0,0 +1,249 @@
+use crate::clock::Clock;
+use crate::history;
+use crate::id_provider::IdProvider;
+use crate::storage::{DeleteError, Storage, UpdateError};
+use crate::types::{GateEvent, GateKey, HistoryEntry};
+use async_trait::async_trait;
+use chrono::TimeDelta;
+use openapi::models;
+
+#[derive(Debug)]
+pub struct Input {
+    pub group: String,
+    pub service: String,
+    pub environment: String,
+    pub actor: Option<String>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Error {
+    GateNotFound,
+    Internal(String),
+}
+
+impl From<UpdateError> for Error {
+    fn from(value: UpdateError) -> Self {
+        match value {
+            UpdateError::ItemToUpdateNotFound(_) => Self::GateNotFound,
+            UpdateError::VersionConflict(error) | UpdateError::Other(error) => {
+                Self::Internal(error)
+            }
+        }
+    }
+}
+
+impl From<DeleteError> for Error {
+    fn from(value: DeleteError) -> Self {
+        match value {
+            DeleteError::ItemToDeleteNotFound(error) | DeleteError::Other(error) => {
+                Self::Internal(error)
+            }
+        }
+    }
+}
+
+#[async_trait]
+pub trait UseCase {
+    async fn execute(
+        &self,
+        input: Input,
+        storage: &(dyn Storage + Send + Sync),
+        clock: &(dyn Clock + Send + Sync),
+        id_provider: &(dyn IdProvider + Send + Sync),
+        trash_retention: TimeDelta,
+    ) -> Result<models::Gate, Error>;
+}
+
+pub fn create() -> impl UseCase {
+    UseCaseImpl {}
+}
+
+#[derive(Clone)]
+struct UseCaseImpl;
+
+#[async_trait]
+impl UseCase for UseCaseImpl {
+    async fn execute(
+        &self,
+        Input {
+            group,
+            service,
+            environment,
+            actor,
+        }: Input,
+        storage: &(dyn Storage + Send + Sync),
+        clock: &(dyn Clock + Send + Sync),
+        id_provider: &(dyn IdProvider + Send + Sync),
+        trash_retention: TimeDelta,
+    ) -> Result<models::Gate, Error> {
+        let key = GateKey {
+            group,
+            service,
+            environment,
+        };
+        let now = clock.now();
+        // gates past their retention period must not be restored anymore, even before they are purged
+        let gate = storage.restore(key.clone(), now - trash_retention).await?;
+
+        history::record(
+            storage,
+            &HistoryEntry {
+                id: id_provider.get(),
+                key,
+                timestamp: now,
+                event: GateEvent::Restored,
+                old_value: None,
+                new_value: Some(gate.state.clone().try_into().map_err(Error::Internal)?),
+                open_until: gate.open_until,
+                actor,
+            },
+        )
+        .await;
+
+        Ok(gate.into())
+    }
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use std::collections::HashSet;
+
+    use chrono::{DateTime, Utc};
+    use mockall::predicate::eq;
+    use similar_asserts::assert_eq;
+
+    use crate::clock::MockClock;
+    use crate::id_provider::MockIdProvider;
+    use crate::storage::MockStorage;
+    use crate::types::{Gate, GateState};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn should_restore_gate() {
+        // given
+        let now = some_date_time("2023-04-12T22:10:57+02:00");
+        let mut mock_clock = MockClock::new();
+        mock_clock.expect_now().return_const(now);
+
+        let mut mock_storage = MockStorage::new();
+        mock_storage
+            .expect_restore()
+            .with(eq(some_key()), eq(now - TimeDelta::days(30)))
+            .return_once(|key, _| {
+                Ok(Gate {
+                    key,
+                    state: GateState::Open,
+                    comments: HashSet::default(),
+                    last_updated: some_date_time("2023-04-10T10:00:00+02:00"),
+                    display_order: Some(2),
+                    version: 4,
+                    open_until: None,
+                })
+            });
+        mock_storage
+            .expect_insert_history_entry()
+            .with(eq(HistoryEntry {
+                id: "some id".to_owned(),
+                key: some_key(),
+                timestamp: now,
+                event: GateEvent::Restored,
+                old_value: None,
+                new_value: Some("open".to_owned()),
+                open_until: None,
+                actor: Some("Max".to_owned()),
+            }))
+            .return_once(|_| Ok(()));
+
+        let mut mock_id_provider = MockIdProvider::new();
+        mock_id_provider
+            .expect_get()
+            .return_const("some id".to_owned());
+
+        // when
+        let gate = UseCaseImpl {}
+            .execute(
+                some_input(),
+                &mock_storage,
+                &mock_clock,
+                &mock_id_provider,
+                TimeDelta::days(30),
+            )
+            .await;
+
+        // then
+        assert_eq!(
+            gate.expect("failed to restore gate"),
+            models::Gate {
+                group: "some group".to_owned(),
+                service: "some service".to_owned(),
+                environment: "some environment".to_owned(),
+                state: models::GateState::Open,
+                comments: vec![],
+                last_updated: "2023-04-10T08:00:00+00:00".to_owned(),
+                display_order: Some(2.),
+                version: 4,
+                open_until: None,
+                open_remaining_seconds: None,
+                closure_reason: None,
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn should_return_gate_not_found_error_if_gate_is_not_deleted() {
+        // given
+        let mut mock_clock = MockClock::new();
+        mock_clock
+            .expect_now()
+            .return_const(some_date_time("2023-04-12T22:10:57+02:00"));
+
+        let mut mock_storage = MockStorage::new();
+        mock_storage.expect_restore().return_once(|_, _| {
+            Err(UpdateError::ItemToUpdateNotFound(
+                "deleted gate does not exist".to_owned(),
+            ))
+        });
+        mock_storage.expect_insert_history_entry().never();
+
+        // when
+        let gate = UseCaseImpl {}
+            .execute(
+                some_input(),
+                &mock_storage,
+                &mock_clock,
+                &MockIdProvider::new(),
+                TimeDelta::days(30),
+            )
+            .await;
+
+        // then
+        assert_eq!(
+            gate.expect_err("unexpected restored gate"),
+            Error::GateNotFound
+        );
+    }
+
+    fn some_input() -> Input {
+        Input {
+            group: "some group".to_owned(),
+            service: "some service".to_owned(),
+            environment: "some environment".to_owned(),
+            actor: Some("Max".to_owned()),
+        }
+    }
+
+    fn some_key() -> GateKey {
+        GateKey {
+            group: "some group".to_owned(),
+            service: "some service".to_owned(),
+            environment: "some environment".to_owned(),
+        }
+    }
+
+    fn some_date_time(value: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(value)
+            .expect("failed to parse date")
+            .into()
+    }
+}
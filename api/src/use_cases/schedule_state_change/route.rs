@@ -0,0 +1,64 @@
+use axum::extract::{Path, State};
+use axum::http::{HeaderMap, StatusCode};
+use axum::response::IntoResponse;
+use axum::Json;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::actor;
+use crate::types::app_state::AppState;
+use crate::types::GateState;
+use crate::use_cases::schedule_state_change::use_case;
+use crate::use_cases::schedule_state_change::use_case::Error;
+
+#[derive(Serialize, Deserialize)]
+pub struct PathParams {
+    group: String,
+    service: String,
+    environment: String,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Payload {
+    pub state: GateState,
+    pub at: DateTime<Utc>,
+}
+
+pub async fn handler(
+    Path(PathParams {
+        group,
+        service,
+        environment,
+    }): Path<PathParams>,
+    State(app_state): State<AppState>,
+    headers: HeaderMap,
+    Json(Payload { state, at }): Json<Payload>,
+) -> impl IntoResponse {
+    match app_state
+        .use_cases
+        .schedule_state_change
+        .execute(
+            use_case::Input {
+                group,
+                service,
+                environment,
+                state,
+                at,
+                actor: actor::from_headers(&headers),
+            },
+            app_state.storage.as_ref(),
+            app_state.clock.as_ref(),
+            app_state.id_provider.as_ref(),
+        )
+        .await
+    {
+        Ok(change) => Json(change).into_response(),
+        Err(error) => match error {
+            Error::GateNotFound => StatusCode::NO_CONTENT.into_response(),
+            Error::InvalidInput(error) => (StatusCode::BAD_REQUEST, Json(error)).into_response(),
+            Error::Internal(error) => {
+                (StatusCode::INTERNAL_SERVER_ERROR, Json(error)).into_response()
+            }
+        },
+    }
+}
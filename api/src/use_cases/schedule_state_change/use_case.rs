@@ -0,0 +1,244 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use openapi::models;
+
+use crate::clock::Clock;
+use crate::id_provider::IdProvider;
+use crate::storage;
+use crate::storage::Storage;
+use crate::types::{GateKey, GateState, ScheduledStateChange};
+
+#[derive(Debug)]
+pub struct Input {
+    pub group: String,
+    pub service: String,
+    pub environment: String,
+    pub state: GateState,
+    pub at: DateTime<Utc>,
+    pub actor: Option<String>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Error {
+    GateNotFound,
+    InvalidInput(String),
+    Internal(String),
+}
+
+impl From<storage::FindError> for Error {
+    fn from(value: storage::FindError) -> Self {
+        match value {
+            storage::FindError::ItemCouldNotBeDecoded(error)
+            | storage::FindError::InvalidCursor(error)
+            | storage::FindError::Other(error) => Self::Internal(error),
+        }
+    }
+}
+
+impl From<storage::InsertError> for Error {
+    fn from(value: storage::InsertError) -> Self {
+        match value {
+            storage::InsertError::ItemAlreadyExists(error) | storage::InsertError::Other(error) => {
+                Self::Internal(error)
+            }
+        }
+    }
+}
+
+#[async_trait]
+pub trait UseCase {
+    async fn execute(
+        &self,
+        input: Input,
+        storage: &(dyn Storage + Send + Sync),
+        clock: &(dyn Clock + Send + Sync),
+        id_provider: &(dyn IdProvider + Send + Sync),
+    ) -> Result<models::ScheduledStateChange, Error>;
+}
+
+pub fn create() -> impl UseCase {
+    UseCaseImpl {}
+}
+
+#[derive(Clone)]
+struct UseCaseImpl {}
+
+#[async_trait]
+impl UseCase for UseCaseImpl {
+    async fn execute(
+        &self,
+        Input {
+            group,
+            service,
+            environment,
+            state,
+            at,
+            actor,
+        }: Input,
+        storage: &(dyn Storage + Send + Sync),
+        clock: &(dyn Clock + Send + Sync),
+        id_provider: &(dyn IdProvider + Send + Sync),
+    ) -> Result<models::ScheduledStateChange, Error> {
+        if at <= clock.now() {
+            return Err(Error::InvalidInput(
+                "the state change must be scheduled in the future".to_owned(),
+            ));
+        }
+        let key = GateKey {
+            group,
+            service,
+            environment,
+        };
+        if storage.find_one(key.clone()).await?.is_none() {
+            return Err(Error::GateNotFound);
+        }
+
+        let change = ScheduledStateChange {
+            id: id_provider.get(),
+            key,
+            state,
+            at,
+            actor,
+        };
+        storage.insert_scheduled_state_change(&change).await?;
+
+        Ok(change.into())
+    }
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use std::collections::HashSet;
+
+    use mockall::predicate::eq;
+    use similar_asserts::assert_eq;
+
+    use crate::clock::MockClock;
+    use crate::id_provider::MockIdProvider;
+    use crate::storage::MockStorage;
+    use crate::types::Gate;
+
+    use super::*;
+
+    fn instant(value: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(value)
+            .expect("failed to parse date")
+            .to_utc()
+    }
+
+    fn some_key() -> GateKey {
+        GateKey {
+            group: "some group".to_owned(),
+            service: "some service".to_owned(),
+            environment: "some environment".to_owned(),
+        }
+    }
+
+    fn given_input(at: &str) -> Input {
+        Input {
+            group: "some group".to_owned(),
+            service: "some service".to_owned(),
+            environment: "some environment".to_owned(),
+            state: GateState::Open,
+            at: instant(at),
+            actor: Some("Max".to_owned()),
+        }
+    }
+
+    fn given_clock() -> MockClock {
+        let mut mock_clock = MockClock::new();
+        mock_clock
+            .expect_now()
+            .return_const(instant("2023-06-05T13:00:00Z"));
+        mock_clock
+    }
+
+    #[tokio::test]
+    async fn should_schedule_state_change() {
+        // given
+        let mut mock_id_provider = MockIdProvider::new();
+        mock_id_provider.expect_get().return_const("some id");
+
+        let change = ScheduledStateChange {
+            id: "some id".to_owned(),
+            key: some_key(),
+            state: GateState::Open,
+            at: instant("2023-06-06T04:00:00Z"),
+            actor: Some("Max".to_owned()),
+        };
+        let mut mock_storage = MockStorage::new();
+        mock_storage
+            .expect_find_one()
+            .with(eq(some_key()))
+            .return_once(|key| {
+                Ok(Some(Gate {
+                    key,
+                    state: GateState::Closed,
+                    comments: HashSet::default(),
+                    last_updated: DateTime::default(),
+                    display_order: None,
+                    version: 0,
+                    open_until: None,
+                }))
+            });
+        mock_storage
+            .expect_insert_scheduled_state_change()
+            .with(eq(change.clone()))
+            .return_once(|_| Ok(()));
+
+        // when
+        let result = UseCaseImpl {}
+            .execute(
+                given_input("2023-06-06T04:00:00Z"),
+                &mock_storage,
+                &given_clock(),
+                &mock_id_provider,
+            )
+            .await;
+
+        // then
+        assert_eq!(result, Ok(change.into()));
+    }
+
+    #[tokio::test]
+    async fn should_not_schedule_state_change_of_missing_gate() {
+        // given
+        let mut mock_id_provider = MockIdProvider::new();
+        mock_id_provider.expect_get().return_const("some id");
+        let mut mock_storage = MockStorage::new();
+        mock_storage.expect_find_one().return_once(|_| Ok(None));
+
+        // when
+        let result = UseCaseImpl {}
+            .execute(
+                given_input("2023-06-06T04:00:00Z"),
+                &mock_storage,
+                &given_clock(),
+                &mock_id_provider,
+            )
+            .await;
+
+        // then
+        assert_eq!(result, Err(Error::GateNotFound));
+    }
+
+    #[tokio::test]
+    async fn should_not_schedule_state_change_in_the_past() {
+        // given
+        let mock_id_provider = MockIdProvider::new();
+        let mock_storage = MockStorage::new();
+
+        // when
+        let result = UseCaseImpl {}
+            .execute(
+                given_input("2023-06-05T13:00:00Z"),
+                &mock_storage,
+                &given_clock(),
+                &mock_id_provider,
+            )
+            .await;
+
+        // then
+        assert!(matches!(result, Err(Error::InvalidInput(_))));
+    }
+}
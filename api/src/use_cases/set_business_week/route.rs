@@ -0,0 +1,46 @@
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::Json;
+use openapi::models;
+use serde::{Deserialize, Serialize};
+
+use crate::types::app_state::AppState;
+use crate::use_cases::set_business_week::use_case;
+use crate::use_cases::set_business_week::use_case::Error;
+
+#[derive(Serialize, Deserialize)]
+pub struct Payload {
+    pub business_week: models::BusinessWeek,
+    pub time_zone: String,
+}
+
+pub async fn handler(
+    State(app_state): State<AppState>,
+    Json(Payload {
+        business_week,
+        time_zone,
+    }): Json<Payload>,
+) -> impl IntoResponse {
+    match app_state
+        .use_cases
+        .set_business_week
+        .execute(
+            use_case::Input {
+                business_week,
+                time_zone,
+            },
+            app_state.storage.as_ref(),
+            app_state.date_time_switch.as_ref(),
+        )
+        .await
+    {
+        Ok(business_week) => Json(business_week).into_response(),
+        Err(error) => match error {
+            Error::InvalidInput(error) => (StatusCode::BAD_REQUEST, Json(error)).into_response(),
+            Error::Internal(error) => {
+                (StatusCode::INTERNAL_SERVER_ERROR, Json(error)).into_response()
+            }
+        },
+    }
+}
@@ -0,0 +1,196 @@
+use async_trait::async_trait;
+use openapi::models;
+
+use crate::date_time_switch::DateTimeSwitch;
+use crate::storage;
+use crate::storage::Storage;
+use crate::types::{BusinessWeek, BusinessWeekProfile, BusinessWeekScope};
+
+#[derive(Debug)]
+pub struct Input {
+    pub group: String,
+    pub service: Option<String>,
+    pub environment: Option<String>,
+    pub business_week: models::BusinessWeek,
+    pub time_zone: String,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Error {
+    InvalidInput(String),
+    Internal(String),
+}
+
+impl From<storage::InsertError> for Error {
+    fn from(value: storage::InsertError) -> Self {
+        match value {
+            storage::InsertError::ItemAlreadyExists(error) | storage::InsertError::Other(error) => {
+                Self::Internal(error)
+            }
+        }
+    }
+}
+
+#[async_trait]
+pub trait UseCase {
+    async fn execute(
+        &self,
+        input: Input,
+        storage: &(dyn Storage + Send + Sync),
+        date_time_switch: &(dyn DateTimeSwitch + Send + Sync),
+    ) -> Result<models::BusinessWeekProfile, Error>;
+}
+
+pub fn create() -> impl UseCase {
+    UseCaseImpl {}
+}
+
+#[derive(Clone)]
+struct UseCaseImpl {}
+
+#[async_trait]
+impl UseCase for UseCaseImpl {
+    async fn execute(
+        &self,
+        Input {
+            group,
+            service,
+            environment,
+            business_week,
+            time_zone,
+        }: Input,
+        storage: &(dyn Storage + Send + Sync),
+        date_time_switch: &(dyn DateTimeSwitch + Send + Sync),
+    ) -> Result<models::BusinessWeekProfile, Error> {
+        let business_week_profile = BusinessWeekProfile {
+            scope: BusinessWeekScope::new(group, service, environment)
+                .map_err(Error::InvalidInput)?,
+            business_week: BusinessWeek::parse(business_week, &time_zone)
+                .map_err(Error::InvalidInput)?,
+        };
+
+        storage
+            .save_business_week_profile(&business_week_profile)
+            .await?;
+        date_time_switch.clear_cache();
+
+        Ok(business_week_profile.into())
+    }
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use chrono::NaiveTime;
+    use mockall::predicate::eq;
+    use rstest::rstest;
+    use similar_asserts::assert_eq;
+
+    use crate::date_time_switch::MockDateTimeSwitch;
+    use crate::storage::MockStorage;
+    use crate::types::BusinessTimes;
+
+    use super::*;
+
+    fn business_week() -> models::BusinessWeek {
+        models::BusinessWeek {
+            monday: Some(vec![models::BusinessTimes::new(
+                "07:00:00".to_owned(),
+                "18:30:00".to_owned(),
+            )]),
+            ..models::BusinessWeek::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn should_set_business_week_profile() {
+        // given
+        let business_week_profile = BusinessWeekProfile {
+            scope: BusinessWeekScope {
+                group: "some group".to_owned(),
+                service: Some("some service".to_owned()),
+                environment: None,
+            },
+            business_week: BusinessWeek {
+                monday: vec![BusinessTimes {
+                    start: NaiveTime::from_hms_opt(7, 0, 0).unwrap(),
+                    end: NaiveTime::from_hms_opt(18, 30, 0).unwrap(),
+                }],
+                tuesday: vec![],
+                wednesday: vec![],
+                thursday: vec![],
+                friday: vec![],
+                saturday: vec![],
+                sunday: vec![],
+                time_zone: chrono_tz::Europe::Vienna,
+            },
+        };
+
+        let mut mock_storage = MockStorage::new();
+        mock_storage
+            .expect_save_business_week_profile()
+            .with(eq(business_week_profile.clone()))
+            .return_once(|_| Ok(()));
+
+        let mut mock_date_time_switch = MockDateTimeSwitch::new();
+        mock_date_time_switch
+            .expect_clear_cache()
+            .times(1)
+            .return_const(());
+
+        // when
+        let result = UseCaseImpl {}
+            .execute(
+                Input {
+                    group: "some group".to_owned(),
+                    service: Some("some service".to_owned()),
+                    environment: None,
+                    business_week: business_week(),
+                    time_zone: "Europe/Vienna".to_owned(),
+                },
+                &mock_storage,
+                &mock_date_time_switch,
+            )
+            .await;
+
+        // then
+        assert_eq!(
+            result.expect("There is no error expected here!"),
+            business_week_profile.into()
+        );
+    }
+
+    #[rstest]
+    #[case("", None, None, "Europe/Berlin")]
+    #[case("some group", Some(""), None, "Europe/Berlin")]
+    #[case("some group", None, Some("live"), "Europe/Berlin")]
+    #[case("some group", None, None, "Europe/Atlantis")]
+    #[tokio::test]
+    async fn should_fail_for_invalid_business_week_profile(
+        #[case] group: &str,
+        #[case] service: Option<&str>,
+        #[case] environment: Option<&str>,
+        #[case] time_zone: &str,
+    ) {
+        // given
+        let mock_storage = MockStorage::new();
+        let mock_date_time_switch = MockDateTimeSwitch::new();
+
+        // when
+        let result = UseCaseImpl {}
+            .execute(
+                Input {
+                    group: group.to_owned(),
+                    service: service.map(str::to_owned),
+                    environment: environment.map(str::to_owned),
+                    business_week: business_week(),
+                    time_zone: time_zone.to_owned(),
+                },
+                &mock_storage,
+                &mock_date_time_switch,
+            )
+            .await;
+
+        // then
+        assert!(matches!(result, Err(Error::InvalidInput(_))));
+    }
+}
@@ -0,0 +1,45 @@
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::Json;
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+use crate::types::app_state::AppState;
+use crate::use_cases::set_closed_day::use_case;
+use crate::use_cases::set_closed_day::use_case::Error;
+
+#[derive(Serialize, Deserialize)]
+pub struct PathParams {
+    date: NaiveDate,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Payload {
+    pub reason: String,
+}
+
+pub async fn handler(
+    Path(PathParams { date }): Path<PathParams>,
+    State(app_state): State<AppState>,
+    Json(Payload { reason }): Json<Payload>,
+) -> impl IntoResponse {
+    match app_state
+        .use_cases
+        .set_closed_day
+        .execute(
+            use_case::Input { date, reason },
+            app_state.storage.as_ref(),
+            app_state.date_time_switch.as_ref(),
+        )
+        .await
+    {
+        Ok(closed_day) => Json(closed_day).into_response(),
+        Err(error) => match error {
+            Error::InvalidInput(error) => (StatusCode::BAD_REQUEST, Json(error)).into_response(),
+            Error::Internal(error) => {
+                (StatusCode::INTERNAL_SERVER_ERROR, Json(error)).into_response()
+            }
+        },
+    }
+}
@@ -0,0 +1,144 @@
+use async_trait::async_trait;
+use chrono::NaiveDate;
+use openapi::models;
+
+use crate::date_time_switch::DateTimeSwitch;
+use crate::storage;
+use crate::storage::Storage;
+use crate::types::ClosedDay;
+
+#[derive(Debug)]
+pub struct Input {
+    pub date: NaiveDate,
+    pub reason: String,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Error {
+    InvalidInput(String),
+    Internal(String),
+}
+
+impl From<storage::InsertError> for Error {
+    fn from(value: storage::InsertError) -> Self {
+        match value {
+            storage::InsertError::ItemAlreadyExists(error) | storage::InsertError::Other(error) => {
+                Self::Internal(error)
+            }
+        }
+    }
+}
+
+#[async_trait]
+pub trait UseCase {
+    async fn execute(
+        &self,
+        input: Input,
+        storage: &(dyn Storage + Send + Sync),
+        date_time_switch: &(dyn DateTimeSwitch + Send + Sync),
+    ) -> Result<models::ClosedDay, Error>;
+}
+
+pub fn create() -> impl UseCase {
+    UseCaseImpl {}
+}
+
+#[derive(Clone)]
+struct UseCaseImpl {}
+
+#[async_trait]
+impl UseCase for UseCaseImpl {
+    async fn execute(
+        &self,
+        Input { date, reason }: Input,
+        storage: &(dyn Storage + Send + Sync),
+        date_time_switch: &(dyn DateTimeSwitch + Send + Sync),
+    ) -> Result<models::ClosedDay, Error> {
+        if reason.trim().is_empty() {
+            return Err(Error::InvalidInput("reason must not be empty".to_owned()));
+        }
+
+        let closed_day = ClosedDay {
+            date,
+            reason: reason.trim().to_owned(),
+        };
+        storage.save_closed_day(&closed_day).await?;
+        date_time_switch.clear_cache();
+
+        Ok(closed_day.into())
+    }
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use mockall::predicate::eq;
+    use similar_asserts::assert_eq;
+
+    use crate::date_time_switch::MockDateTimeSwitch;
+    use crate::storage::MockStorage;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn should_set_closed_day() {
+        // given
+        let closed_day = ClosedDay {
+            date: NaiveDate::from_ymd_opt(2025, 12, 24).unwrap(),
+            reason: "Christmas Eve".to_owned(),
+        };
+        let mut mock_storage = MockStorage::new();
+        mock_storage
+            .expect_save_closed_day()
+            .with(eq(closed_day.clone()))
+            .return_once(|_| Ok(()));
+
+        let mut mock_date_time_switch = MockDateTimeSwitch::new();
+        mock_date_time_switch
+            .expect_clear_cache()
+            .times(1)
+            .return_const(());
+
+        // when
+        let result = UseCaseImpl {}
+            .execute(
+                Input {
+                    date: closed_day.date,
+                    reason: " Christmas Eve ".to_owned(),
+                },
+                &mock_storage,
+                &mock_date_time_switch,
+            )
+            .await;
+
+        // then
+        assert_eq!(
+            result.expect("There is no error expected here!"),
+            closed_day.into()
+        );
+    }
+
+    #[tokio::test]
+    async fn should_fail_when_reason_is_empty() {
+        // given
+        let mock_storage = MockStorage::new();
+        let mock_date_time_switch = MockDateTimeSwitch::new();
+
+        // when
+        let result = UseCaseImpl {}
+            .execute(
+                Input {
+                    date: NaiveDate::from_ymd_opt(2025, 12, 24).unwrap(),
+                    reason: " ".to_owned(),
+                },
+                &mock_storage,
+                &mock_date_time_switch,
+            )
+            .await;
+
+        // then
+        assert_eq!(
+            result,
+            Err(Error::InvalidInput("reason must not be empty".to_owned()))
+        );
+    }
+}
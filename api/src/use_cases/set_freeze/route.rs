@@ -0,0 +1,46 @@
+use axum::extract::State;
+use axum::http::{HeaderMap, StatusCode};
+use axum::response::IntoResponse;
+use axum::Json;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::actor;
+use crate::types::app_state::AppState;
+use crate::use_cases::set_freeze::use_case;
+use crate::use_cases::set_freeze::use_case::Error;
+
+#[derive(Serialize, Deserialize)]
+pub struct Payload {
+    pub reason: String,
+    pub until: Option<DateTime<Utc>>,
+}
+
+pub async fn handler(
+    State(app_state): State<AppState>,
+    headers: HeaderMap,
+    Json(Payload { reason, until }): Json<Payload>,
+) -> impl IntoResponse {
+    match app_state
+        .use_cases
+        .set_freeze
+        .execute(
+            use_case::Input {
+                reason,
+                until,
+                actor: actor::from_headers(&headers),
+            },
+            app_state.storage.as_ref(),
+            app_state.clock.as_ref(),
+        )
+        .await
+    {
+        Ok(freeze) => Json(freeze).into_response(),
+        Err(error) => match error {
+            Error::InvalidInput(error) => (StatusCode::BAD_REQUEST, Json(error)).into_response(),
+            Error::Internal(error) => {
+                (StatusCode::INTERNAL_SERVER_ERROR, Json(error)).into_response()
+            }
+        },
+    }
+}
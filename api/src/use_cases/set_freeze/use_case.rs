@@ -0,0 +1,200 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use openapi::models;
+
+use crate::clock::Clock;
+use crate::storage;
+use crate::storage::Storage;
+use crate::types::Freeze;
+
+#[derive(Debug)]
+pub struct Input {
+    pub reason: String,
+    pub until: Option<DateTime<Utc>>,
+    pub actor: Option<String>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Error {
+    InvalidInput(String),
+    Internal(String),
+}
+
+impl From<storage::InsertError> for Error {
+    fn from(value: storage::InsertError) -> Self {
+        match value {
+            storage::InsertError::ItemAlreadyExists(error) | storage::InsertError::Other(error) => {
+                Self::Internal(error)
+            }
+        }
+    }
+}
+
+#[async_trait]
+pub trait UseCase {
+    async fn execute(
+        &self,
+        input: Input,
+        storage: &(dyn Storage + Send + Sync),
+        clock: &(dyn Clock + Send + Sync),
+    ) -> Result<models::Freeze, Error>;
+}
+
+pub fn create() -> impl UseCase {
+    UseCaseImpl {}
+}
+
+#[derive(Clone)]
+struct UseCaseImpl {}
+
+#[async_trait]
+impl UseCase for UseCaseImpl {
+    async fn execute(
+        &self,
+        Input {
+            reason,
+            until,
+            actor,
+        }: Input,
+        storage: &(dyn Storage + Send + Sync),
+        clock: &(dyn Clock + Send + Sync),
+    ) -> Result<models::Freeze, Error> {
+        if reason.trim().is_empty() {
+            return Err(Error::InvalidInput("reason must not be empty".to_owned()));
+        }
+
+        let now = clock.now();
+        if until.is_some_and(|until| until <= now) {
+            return Err(Error::InvalidInput(
+                "the end of the freeze must be in the future".to_owned(),
+            ));
+        }
+
+        let freeze = Freeze {
+            reason: reason.trim().to_owned(),
+            actor,
+            created: now,
+            until,
+        };
+        storage.save_freeze(&freeze).await?;
+
+        Ok(freeze.into())
+    }
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use chrono::DateTime;
+    use mockall::predicate::eq;
+    use similar_asserts::assert_eq;
+
+    use crate::clock::MockClock;
+    use crate::storage::MockStorage;
+    use crate::types::Freeze;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn should_set_freeze() {
+        // given
+        let mut mock_storage = MockStorage::new();
+        let mut mock_clock = MockClock::new();
+
+        let now = DateTime::parse_from_rfc3339("2023-04-12T22:10:57+02:00")
+            .expect("failed to parse date")
+            .to_utc();
+        mock_clock.expect_now().return_const(now);
+
+        let until = DateTime::parse_from_rfc3339("2023-04-20T06:00:00+02:00")
+            .expect("failed to parse date")
+            .to_utc();
+        let freeze = Freeze {
+            reason: "release freeze".to_owned(),
+            actor: Some("Max".to_owned()),
+            created: now,
+            until: Some(until),
+        };
+        mock_storage
+            .expect_save_freeze()
+            .with(eq(freeze.clone()))
+            .return_once(|_| Ok(()));
+
+        // when
+        let result = UseCaseImpl {}
+            .execute(
+                Input {
+                    reason: " release freeze ".to_owned(),
+                    until: Some(until),
+                    actor: Some("Max".to_owned()),
+                },
+                &mock_storage,
+                &mock_clock,
+            )
+            .await;
+
+        // then
+        assert_eq!(
+            result.expect("There is no error expected here!"),
+            freeze.into()
+        );
+    }
+
+    #[tokio::test]
+    async fn should_fail_when_reason_is_empty() {
+        // given
+        let mock_storage = MockStorage::new();
+        let mock_clock = MockClock::new();
+
+        // when
+        let result = UseCaseImpl {}
+            .execute(
+                Input {
+                    reason: " ".to_owned(),
+                    until: None,
+                    actor: None,
+                },
+                &mock_storage,
+                &mock_clock,
+            )
+            .await;
+
+        // then
+        assert_eq!(
+            result,
+            Err(Error::InvalidInput("reason must not be empty".to_owned()))
+        );
+    }
+
+    #[tokio::test]
+    async fn should_fail_when_end_is_not_in_the_future() {
+        // given
+        let mock_storage = MockStorage::new();
+        let mut mock_clock = MockClock::new();
+
+        let now = DateTime::parse_from_rfc3339("2023-04-12T22:10:57+02:00")
+            .expect("failed to parse date")
+            .to_utc();
+        mock_clock.expect_now().return_const(now);
+
+        // when
+        let result = UseCaseImpl {}
+            .execute(
+                Input {
+                    reason: "release freeze".to_owned(),
+                    until: Some(now),
+                    actor: None,
+                },
+                &mock_storage,
+                &mock_clock,
+            )
+            .await;
+
+        // then
+        assert_eq!(
+            result,
+            Err(Error::InvalidInput(
+                "the end of the freeze must be in the future".to_owned()
+            ))
+        );
+    }
+}
@@ -0,0 +1,241 @@
+use async_trait::async_trait;
+use itertools::Itertools;
+use openapi::models;
+
+use crate::storage;
+use crate::storage::Storage;
+use crate::types::{GateDependencies, GateKey};
+
+#[derive(Debug)]
+pub struct Input {
+    pub group: String,
+    pub service: String,
+    pub environment: String,
+    pub depends_on: Vec<models::GateKey>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Error {
+    GateNotFound,
+    InvalidInput(String),
+    Internal(String),
+}
+
+impl From<storage::FindError> for Error {
+    fn from(value: storage::FindError) -> Self {
+        match value {
+            storage::FindError::ItemCouldNotBeDecoded(error)
+            | storage::FindError::InvalidCursor(error)
+            | storage::FindError::Other(error) => Self::Internal(error),
+        }
+    }
+}
+
+impl From<storage::InsertError> for Error {
+    fn from(value: storage::InsertError) -> Self {
+        match value {
+            storage::InsertError::ItemAlreadyExists(error) | storage::InsertError::Other(error) => {
+                Self::Internal(error)
+            }
+        }
+    }
+}
+
+#[async_trait]
+pub trait UseCase {
+    async fn execute(
+        &self,
+        input: Input,
+        storage: &(dyn Storage + Send + Sync),
+    ) -> Result<models::GateDependencies, Error>;
+}
+
+pub fn create() -> impl UseCase {
+    UseCaseImpl {}
+}
+
+#[derive(Clone)]
+struct UseCaseImpl {}
+
+#[async_trait]
+impl UseCase for UseCaseImpl {
+    async fn execute(
+        &self,
+        Input {
+            group,
+            service,
+            environment,
+            depends_on,
+        }: Input,
+        storage: &(dyn Storage + Send + Sync),
+    ) -> Result<models::GateDependencies, Error> {
+        let key = GateKey {
+            group,
+            service,
+            environment,
+        };
+        if storage.find_one(key.clone()).await?.is_none() {
+            return Err(Error::GateNotFound);
+        }
+
+        let depends_on: Vec<GateKey> = depends_on.into_iter().map(Into::into).unique().collect();
+        for dependency in &depends_on {
+            if *dependency == key {
+                return Err(Error::InvalidInput(
+                    "a gate must not depend on itself".to_owned(),
+                ));
+            }
+            if storage.find_one(dependency.clone()).await?.is_none() {
+                return Err(Error::InvalidInput(format!(
+                    "gate {dependency} does not exist"
+                )));
+            }
+        }
+
+        let dependencies = GateDependencies { key, depends_on };
+        storage.save_gate_dependencies(&dependencies).await?;
+
+        Ok(dependencies.into())
+    }
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use std::collections::HashSet;
+
+    use chrono::DateTime;
+    use mockall::predicate::eq;
+    use similar_asserts::assert_eq;
+
+    use crate::storage::MockStorage;
+    use crate::types::{Gate, GateState};
+
+    use super::*;
+
+    fn some_key(environment: &str) -> GateKey {
+        GateKey {
+            group: "some group".to_owned(),
+            service: "some service".to_owned(),
+            environment: environment.to_owned(),
+        }
+    }
+
+    fn some_gate(key: GateKey) -> Gate {
+        Gate {
+            key,
+            state: GateState::Open,
+            comments: HashSet::new(),
+            last_updated: DateTime::default(),
+            display_order: None,
+            version: 0,
+            open_until: None,
+        }
+    }
+
+    fn some_input(depends_on: &[&str]) -> Input {
+        Input {
+            group: "some group".to_owned(),
+            service: "some service".to_owned(),
+            environment: "live".to_owned(),
+            depends_on: depends_on
+                .iter()
+                .map(|environment| some_key(environment).into())
+                .collect(),
+        }
+    }
+
+    #[tokio::test]
+    async fn should_set_dependencies_of_gate() {
+        // given
+        let mut mock_storage = MockStorage::new();
+        mock_storage
+            .expect_find_one()
+            .returning(|key| Ok(Some(some_gate(key))));
+        mock_storage
+            .expect_save_gate_dependencies()
+            .with(eq(GateDependencies {
+                key: some_key("live"),
+                depends_on: vec![some_key("staging"), some_key("database")],
+            }))
+            .times(1)
+            .return_once(|_| Ok(()));
+
+        // when
+        let dependencies = UseCaseImpl {}
+            .execute(
+                some_input(&["staging", "database", "staging"]),
+                &mock_storage,
+            )
+            .await;
+
+        // then
+        assert_eq!(
+            dependencies.expect("failed to set dependencies"),
+            models::GateDependencies {
+                depends_on: vec![some_key("staging").into(), some_key("database").into()],
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn should_not_set_dependency_on_gate_itself() {
+        // given
+        let mut mock_storage = MockStorage::new();
+        mock_storage
+            .expect_find_one()
+            .returning(|key| Ok(Some(some_gate(key))));
+        mock_storage.expect_save_gate_dependencies().never();
+
+        // when
+        let dependencies = UseCaseImpl {}
+            .execute(some_input(&["live"]), &mock_storage)
+            .await;
+
+        // then
+        assert!(matches!(dependencies, Err(Error::InvalidInput(_))));
+    }
+
+    #[tokio::test]
+    async fn should_not_set_dependency_on_unknown_gate() {
+        // given
+        let mut mock_storage = MockStorage::new();
+        mock_storage
+            .expect_find_one()
+            .with(eq(some_key("live")))
+            .return_once(|key| Ok(Some(some_gate(key))));
+        mock_storage
+            .expect_find_one()
+            .with(eq(some_key("unknown")))
+            .return_once(|_| Ok(None));
+        mock_storage.expect_save_gate_dependencies().never();
+
+        // when
+        let dependencies = UseCaseImpl {}
+            .execute(some_input(&["unknown"]), &mock_storage)
+            .await;
+
+        // then
+        assert_eq!(
+            dependencies,
+            Err(Error::InvalidInput(
+                "gate some group/some service/unknown does not exist".to_owned()
+            ))
+        );
+    }
+
+    #[tokio::test]
+    async fn should_not_set_dependencies_of_unknown_gate() {
+        // given
+        let mut mock_storage = MockStorage::new();
+        mock_storage.expect_find_one().return_once(|_| Ok(None));
+        mock_storage.expect_save_gate_dependencies().never();
+
+        // when
+        let dependencies = UseCaseImpl {}
+            .execute(some_input(&["staging"]), &mock_storage)
+            .await;
+
+        // then
+        assert_eq!(dependencies, Err(Error::GateNotFound));
+    }
+}
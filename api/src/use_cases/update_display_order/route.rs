@@ -1,7 +1,10 @@
+use crate::actor;
+use crate::etag;
 use crate::types::app_state::AppState;
 use crate::use_cases::update_display_order::use_case::{Error, Input};
 use axum::extract::{Path, State};
-use axum::http::StatusCode;
+use axum::http::header::ETAG;
+use axum::http::{HeaderMap, StatusCode};
 use axum::response::IntoResponse;
 use axum::Json;
 use serde::{Deserialize, Serialize};
@@ -17,6 +20,7 @@ pub struct PathParams {
 #[derive(Serialize, Deserialize)]
 pub struct Payload {
     pub display_order: u32,
+    pub expected_version: Option<u32>,
 }
 
 pub async fn handler(
@@ -26,8 +30,16 @@ pub async fn handler(
         environment,
     }): Path<PathParams>,
     State(app_state): State<AppState>,
-    Json(Payload { display_order }): Json<Payload>,
+    headers: HeaderMap,
+    Json(Payload {
+        display_order,
+        expected_version,
+    }): Json<Payload>,
 ) -> impl IntoResponse {
+    let expected_version = match etag::expected_version(&headers, expected_version) {
+        Ok(expected_version) => expected_version,
+        Err(error) => return (StatusCode::BAD_REQUEST, Json(error)).into_response(),
+    };
     match app_state
         .use_cases
         .update_display_order
@@ -37,15 +49,21 @@ pub async fn handler(
                 service,
                 environment,
                 display_order,
+                expected_version,
+                actor: actor::from_headers(&headers),
             },
             app_state.storage.as_ref(),
             app_state.clock.as_ref(),
+            app_state.id_provider.as_ref(),
         )
         .await
     {
-        Ok(gate) => Json(gate).into_response(),
+        Ok(gate) => ([(ETAG, etag::from_version(gate.version))], Json(gate)).into_response(),
         Err(error) => match error {
             GateNotFound => StatusCode::NO_CONTENT.into_response(),
+            Error::VersionConflict(error) => {
+                (StatusCode::PRECONDITION_FAILED, Json(error)).into_response()
+            }
             Error::Internal(error) => {
                 (StatusCode::INTERNAL_SERVER_ERROR, Json(error)).into_response()
             }
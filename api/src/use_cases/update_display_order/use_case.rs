@@ -1,6 +1,8 @@
 use crate::clock::Clock;
-use crate::storage::{Storage, UpdateError};
-use crate::types::GateKey;
+use crate::history;
+use crate::id_provider::IdProvider;
+use crate::storage::{FindError, Storage, UpdateError};
+use crate::types::{GateEvent, GateKey, HistoryEntry};
 use async_trait::async_trait;
 use openapi::models;
 
@@ -10,11 +12,14 @@ pub struct Input {
     pub service: String,
     pub environment: String,
     pub display_order: u32,
+    pub expected_version: Option<u32>,
+    pub actor: Option<String>,
 }
 
 #[derive(Debug, PartialEq, Eq)]
 pub enum Error {
     GateNotFound,
+    VersionConflict(String),
     Internal(String),
 }
 
@@ -22,11 +27,22 @@ impl From<UpdateError> for Error {
     fn from(value: UpdateError) -> Self {
         match value {
             UpdateError::ItemToUpdateNotFound(_) => Self::GateNotFound,
+            UpdateError::VersionConflict(error) => Self::VersionConflict(error),
             UpdateError::Other(error) => Self::Internal(error),
         }
     }
 }
 
+impl From<FindError> for Error {
+    fn from(value: FindError) -> Self {
+        match value {
+            FindError::ItemCouldNotBeDecoded(error)
+            | FindError::InvalidCursor(error)
+            | FindError::Other(error) => Self::Internal(error),
+        }
+    }
+}
+
 #[async_trait]
 pub trait UseCase {
     async fn execute(
@@ -34,6 +50,7 @@ pub trait UseCase {
         input: Input,
         storage: &(dyn Storage + Send + Sync),
         clock: &(dyn Clock + Send + Sync),
+        id_provider: &(dyn IdProvider + Send + Sync),
     ) -> Result<models::Gate, Error>;
 }
 
@@ -53,22 +70,45 @@ impl UseCase for UseCaseImpl {
             service,
             environment,
             display_order,
+            expected_version,
+            actor,
         }: Input,
         storage: &(dyn Storage + Send + Sync),
         clock: &(dyn Clock + Send + Sync),
+        id_provider: &(dyn IdProvider + Send + Sync),
     ) -> Result<models::Gate, Error> {
-        Ok(storage
-            .update_display_order_and_last_updated(
-                GateKey {
-                    group,
-                    service,
-                    environment,
-                },
-                display_order,
-                clock.now(),
-            )
+        let now = clock.now();
+        let key = GateKey {
+            group,
+            service,
+            environment,
+        };
+        let previous_display_order = storage
+            .find_one(key.clone())
             .await?
-            .into())
+            .ok_or(Error::GateNotFound)?
+            .display_order;
+
+        let gate = storage
+            .update_display_order_and_last_updated(key, display_order, now, expected_version)
+            .await?;
+
+        history::record(
+            storage,
+            &HistoryEntry {
+                id: id_provider.get(),
+                key: gate.key.clone(),
+                timestamp: now,
+                event: GateEvent::DisplayOrderChanged,
+                old_value: previous_display_order.map(|display_order| display_order.to_string()),
+                new_value: Some(display_order.to_string()),
+                open_until: None,
+                actor,
+            },
+        )
+        .await;
+
+        Ok(gate.into())
     }
 }
 
@@ -77,10 +117,12 @@ mod unit_tests {
     use std::collections::HashSet;
 
     use chrono::DateTime;
+    use mockall::predicate::eq;
     use openapi::models;
     use similar_asserts::assert_eq;
 
     use crate::clock::MockClock;
+    use crate::id_provider::MockIdProvider;
     use crate::storage::MockStorage;
     use crate::types::{Gate, GateState};
 
@@ -108,20 +150,47 @@ mod unit_tests {
             last_updated: DateTime::parse_from_rfc3339("2023-04-12T22:10:57+02:00")
                 .expect("failed creating date")
                 .into(),
-            display_order: Option::default(),
+            display_order: Some(3),
+            version: 0,
+            open_until: None,
         };
 
+        let stored_gate = gate.clone();
+        mock_storage
+            .expect_find_one()
+            .with(eq(gate.key.clone()))
+            .return_once(|_| Ok(Some(stored_gate)));
         mock_storage
             .expect_update_display_order_and_last_updated()
-            .return_once(move |key, display_order, last_updated| {
+            .return_once(move |key, display_order, last_updated, _| {
                 Ok(Gate {
                     key,
                     state: GateState::default(),
                     comments: HashSet::default(),
                     last_updated,
                     display_order: Some(display_order),
+                    version: 0,
+                    open_until: None,
                 })
             });
+        mock_storage
+            .expect_insert_history_entry()
+            .with(eq(HistoryEntry {
+                id: "some id".to_owned(),
+                key: gate.key.clone(),
+                timestamp: now,
+                event: GateEvent::DisplayOrderChanged,
+                old_value: Some("3".to_owned()),
+                new_value: Some("1".to_owned()),
+                open_until: None,
+                actor: None,
+            }))
+            .return_once(|_| Ok(()));
+
+        let mut mock_id_provider = MockIdProvider::new();
+        mock_id_provider
+            .expect_get()
+            .return_const("some id".to_owned());
 
         // when
         let gate_with_state = UseCaseImpl {}
@@ -131,9 +200,12 @@ mod unit_tests {
                     service: "some service".to_owned(),
                     environment: "some environment".to_owned(),
                     display_order: 1,
+                    expected_version: None,
+                    actor: None,
                 },
                 &mock_storage,
                 &mock_clock,
+                &mock_id_provider,
             )
             .await;
 
@@ -149,6 +221,10 @@ mod unit_tests {
                 comments: vec![],
                 last_updated: now.to_rfc3339(),
                 display_order: Some(1f64),
+                version: 0,
+                open_until: None,
+                open_remaining_seconds: None,
+                closure_reason: Some(models::ClosureReason::Manual),
             }
         );
     }
@@ -1,9 +1,13 @@
 use axum::extract::{Path, State};
-use axum::http::StatusCode;
+use axum::http::header::ETAG;
+use axum::http::{HeaderMap, StatusCode};
 use axum::response::IntoResponse;
 use axum::Json;
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
+use crate::actor;
+use crate::etag;
 use crate::types::app_state::AppState;
 use crate::types::GateState;
 use crate::use_cases::update_gate_state::use_case;
@@ -16,9 +20,12 @@ pub struct PathParams {
     environment: String,
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Default)]
 pub struct Payload {
     pub state: GateState,
+    pub expected_version: Option<u32>,
+    pub open_until: Option<DateTime<Utc>>,
+    pub ttl_seconds: Option<u32>,
 }
 
 pub async fn handler(
@@ -28,8 +35,18 @@ pub async fn handler(
         environment,
     }): Path<PathParams>,
     State(app_state): State<AppState>,
-    Json(Payload { state }): Json<Payload>,
+    headers: HeaderMap,
+    Json(Payload {
+        state,
+        expected_version,
+        open_until,
+        ttl_seconds,
+    }): Json<Payload>,
 ) -> impl IntoResponse {
+    let expected_version = match etag::expected_version(&headers, expected_version) {
+        Ok(expected_version) => expected_version,
+        Err(error) => return (StatusCode::BAD_REQUEST, Json(error)).into_response(),
+    };
     match app_state
         .use_cases
         .update_gate_state
@@ -39,17 +56,26 @@ pub async fn handler(
                 service,
                 environment,
                 state,
+                expected_version,
+                actor: actor::from_headers(&headers),
+                open_until,
+                ttl_seconds,
             },
             app_state.storage.as_ref(),
             app_state.clock.as_ref(),
             app_state.date_time_switch.as_ref(),
+            app_state.id_provider.as_ref(),
         )
         .await
     {
-        Ok(gate) => Json(gate).into_response(),
+        Ok(gate) => ([(ETAG, etag::from_version(gate.version))], Json(gate)).into_response(),
         Err(error) => match error {
             Error::GateClosed(error) => (StatusCode::CONFLICT, Json(error)).into_response(),
             Error::GateNotFound => StatusCode::NO_CONTENT.into_response(),
+            Error::InvalidInput(error) => (StatusCode::BAD_REQUEST, Json(error)).into_response(),
+            Error::VersionConflict(error) => {
+                (StatusCode::PRECONDITION_FAILED, Json(error)).into_response()
+            }
             Error::Internal(error) => {
                 (StatusCode::INTERNAL_SERVER_ERROR, Json(error)).into_response()
             }
@@ -1,34 +1,57 @@
 use crate::clock::Clock;
 use crate::date_time_switch::DateTimeSwitch;
-use crate::storage::{Storage, UpdateError};
-use crate::types::{GateKey, GateState};
+use crate::history;
+use crate::id_provider::IdProvider;
+use crate::storage::{FindError, Storage, UpdateError};
+use crate::types::{Gate, GateEvent, GateKey, GateState, HistoryEntry};
 use async_trait::async_trait;
+use chrono::{DateTime, TimeDelta, Utc};
 use openapi::models;
 
+// a change not based on a given version is retried if the gate was changed in the meantime
+const MAX_ATTEMPTS: u32 = 3;
+
 #[derive(Debug)]
 pub struct Input {
     pub group: String,
     pub service: String,
     pub environment: String,
     pub state: GateState,
+    pub expected_version: Option<u32>,
+    pub actor: Option<String>,
+    pub open_until: Option<DateTime<Utc>>,
+    pub ttl_seconds: Option<u32>,
 }
 
 #[derive(Debug, PartialEq, Eq)]
 pub enum Error {
     GateNotFound,
+    InvalidInput(String),
     Internal(String),
     GateClosed(String),
+    VersionConflict(String),
 }
 
 impl From<UpdateError> for Error {
     fn from(value: UpdateError) -> Self {
         match value {
             UpdateError::ItemToUpdateNotFound(_) => Self::GateNotFound,
+            UpdateError::VersionConflict(error) => Self::VersionConflict(error),
             UpdateError::Other(error) => Self::Internal(error),
         }
     }
 }
 
+impl From<FindError> for Error {
+    fn from(value: FindError) -> Self {
+        match value {
+            FindError::ItemCouldNotBeDecoded(error)
+            | FindError::InvalidCursor(error)
+            | FindError::Other(error) => Self::Internal(error),
+        }
+    }
+}
+
 #[async_trait]
 pub trait UseCase {
     async fn execute(
@@ -37,6 +60,7 @@ pub trait UseCase {
         storage: &(dyn Storage + Send + Sync),
         clock: &(dyn Clock + Send + Sync),
         date_time_switch: &(dyn DateTimeSwitch + Send + Sync),
+        id_provider: &(dyn IdProvider + Send + Sync),
     ) -> Result<models::Gate, Error>;
 }
 
@@ -56,43 +80,131 @@ impl UseCase for UseCaseImpl {
             service,
             environment,
             state,
+            expected_version,
+            actor,
+            open_until,
+            ttl_seconds,
         }: Input,
         storage: &(dyn Storage + Send + Sync),
         clock: &(dyn Clock + Send + Sync),
         date_time_switch: &(dyn DateTimeSwitch + Send + Sync),
+        id_provider: &(dyn IdProvider + Send + Sync),
     ) -> Result<models::Gate, Error> {
-        if date_time_switch.is_closed(clock.now()) {
+        let now = clock.now();
+        let open_until = resolve_open_until(&state, open_until, ttl_seconds, now)?;
+        let key = GateKey {
+            group,
+            service,
+            environment,
+        };
+        if date_time_switch.is_closed(now, &key).await {
             return Err(Error::GateClosed(
                 "Already after business hours - rejecting attempt to change state".to_owned(),
             ));
         }
-        Ok(storage
+        let (previous_state, gate) =
+            update_state(key, state, open_until, now, expected_version, storage).await?;
+
+        history::record(
+            storage,
+            &HistoryEntry {
+                id: id_provider.get(),
+                key: gate.key.clone(),
+                timestamp: now,
+                event: GateEvent::StateChanged,
+                old_value: Some(previous_state.try_into().map_err(Error::Internal)?),
+                new_value: Some(gate.state.clone().try_into().map_err(Error::Internal)?),
+                open_until: gate.open_until,
+                actor,
+            },
+        )
+        .await;
+
+        Ok(gate.into_rep(now))
+    }
+}
+
+/// Returns the replaced state along with the updated gate. The update is based on the version the
+/// replaced state was read with, so the state cannot have changed in between.
+async fn update_state(
+    key: GateKey,
+    state: GateState,
+    open_until: Option<DateTime<Utc>>,
+    now: DateTime<Utc>,
+    expected_version: Option<u32>,
+    storage: &(dyn Storage + Send + Sync),
+) -> Result<(GateState, Gate), Error> {
+    let mut attempts = 1;
+    loop {
+        let previous = storage
+            .find_one(key.clone())
+            .await?
+            .ok_or(Error::GateNotFound)?;
+        match storage
             .update_state_and_last_updated(
-                GateKey {
-                    group,
-                    service,
-                    environment,
-                },
-                state,
-                clock.now(),
+                key.clone(),
+                state.clone(),
+                open_until,
+                now,
+                Some(expected_version.unwrap_or(previous.version)),
             )
-            .await?
-            .into())
+            .await
+        {
+            Ok(gate) => return Ok((previous.state, gate)),
+            Err(UpdateError::VersionConflict(_))
+                if expected_version.is_none() && attempts < MAX_ATTEMPTS =>
+            {
+                attempts += 1;
+            }
+            Err(error) => return Err(error.into()),
+        }
     }
 }
 
+/// When the gate is closed again, given either as instant or as time to live.
+fn resolve_open_until(
+    state: &GateState,
+    open_until: Option<DateTime<Utc>>,
+    ttl_seconds: Option<u32>,
+    now: DateTime<Utc>,
+) -> Result<Option<DateTime<Utc>>, Error> {
+    let open_until = match (open_until, ttl_seconds) {
+        (Some(_), Some(_)) => {
+            return Err(Error::InvalidInput(
+                "only one of open_until and ttl_seconds can be given".to_owned(),
+            ))
+        }
+        (Some(open_until), None) => open_until,
+        (None, Some(ttl_seconds)) => now + TimeDelta::seconds(i64::from(ttl_seconds)),
+        (None, None) => return Ok(None),
+    };
+    if *state != GateState::Open {
+        return Err(Error::InvalidInput(
+            "open_until and ttl_seconds are only allowed when opening the gate".to_owned(),
+        ));
+    }
+    if open_until <= now {
+        return Err(Error::InvalidInput(
+            "the gate can only be opened until an instant in the future".to_owned(),
+        ));
+    }
+    Ok(Some(open_until))
+}
+
 #[cfg(test)]
 mod unit_tests {
     use std::collections::HashSet;
 
     use chrono::DateTime;
+    use mockall::predicate::eq;
     use similar_asserts::assert_eq;
 
     use crate::clock::MockClock;
     use crate::date_time_switch::MockDateTimeSwitch;
+    use crate::id_provider::MockIdProvider;
     use crate::storage::MockStorage;
     use crate::types::GateState::Open;
-    use crate::types::{Gate, GateKey, GateState};
+    use crate::types::{Gate, GateEvent, GateKey, GateState, HistoryEntry};
 
     use super::*;
 
@@ -112,17 +224,46 @@ mod unit_tests {
 
         let gate = some_gate("some group", "some service", "some environment");
 
+        mock_storage
+            .expect_find_one()
+            .with(eq(gate.key.clone()))
+            .return_once(|key| {
+                Ok(Some(Gate {
+                    state: GateState::Closed,
+                    ..some_gate(&key.group, &key.service, &key.environment)
+                }))
+            });
         mock_storage
             .expect_update_state_and_last_updated()
-            .return_once(move |key, state, last_updated| {
+            .return_once(move |key, state, open_until, last_updated, _| {
                 Ok(Gate {
                     key,
                     state,
                     comments: HashSet::default(),
                     last_updated,
                     display_order: Option::default(),
+                    version: 0,
+                    open_until,
                 })
             });
+        mock_storage
+            .expect_insert_history_entry()
+            .with(eq(HistoryEntry {
+                id: "some id".to_owned(),
+                key: gate.key.clone(),
+                timestamp: now,
+                event: GateEvent::StateChanged,
+                old_value: Some("closed".to_owned()),
+                new_value: Some("open".to_owned()),
+                open_until: None,
+                actor: Some("Max".to_owned()),
+            }))
+            .return_once(|_| Ok(()));
+
+        let mut mock_id_provider = MockIdProvider::new();
+        mock_id_provider
+            .expect_get()
+            .return_const("some id".to_owned());
 
         // when
         let gate_with_state = UseCaseImpl {}
@@ -132,10 +273,15 @@ mod unit_tests {
                     service: "some service".to_owned(),
                     environment: "some environment".to_owned(),
                     state: Open,
+                    expected_version: None,
+                    actor: Some("Max".to_owned()),
+                    open_until: None,
+                    ttl_seconds: None,
                 },
                 &mock_storage,
                 &mock_clock,
                 &mock_date_time_switch,
+                &mock_id_provider,
             )
             .await;
 
@@ -151,6 +297,10 @@ mod unit_tests {
                 comments: vec![],
                 last_updated: now.to_rfc3339(),
                 display_order: Option::default(),
+                version: 0,
+                open_until: None,
+                open_remaining_seconds: None,
+                closure_reason: None,
             }
         );
     }
@@ -174,10 +324,15 @@ mod unit_tests {
                     service: "some service".to_owned(),
                     environment: "some environment".to_owned(),
                     state: GateState::default(),
+                    expected_version: None,
+                    actor: None,
+                    open_until: None,
+                    ttl_seconds: None,
                 },
                 &mock_storage,
                 &mock_clock,
                 &mock_date_time_switch,
+                &MockIdProvider::new(),
             )
             .await;
 
@@ -191,6 +346,190 @@ mod unit_tests {
         );
     }
 
+    #[tokio::test]
+    async fn should_reject_state_change_if_gate_was_changed_in_the_meantime() {
+        // given
+        let mut mock_storage = MockStorage::new();
+        let mut mock_clock = MockClock::new();
+        let mut mock_date_time_switch = MockDateTimeSwitch::new();
+
+        mock_date_time_switch.expect_is_closed().return_const(false);
+        mock_clock.expect_now().return_const(
+            DateTime::parse_from_rfc3339("2023-04-12T22:10:57+02:00")
+                .expect("failed to parse date")
+                .to_utc(),
+        );
+
+        mock_storage
+            .expect_find_one()
+            .return_once(|key| Ok(Some(some_gate(&key.group, &key.service, &key.environment))));
+        mock_storage
+            .expect_update_state_and_last_updated()
+            .withf(|_, _, _, _, expected_version| *expected_version == Some(3))
+            .return_once(|_, _, _, _, _| {
+                Err(UpdateError::VersionConflict(
+                    "some version conflict".to_owned(),
+                ))
+            });
+
+        // when
+        let gate_with_state = UseCaseImpl {}
+            .execute(
+                Input {
+                    group: "some group".to_owned(),
+                    service: "some service".to_owned(),
+                    environment: "some environment".to_owned(),
+                    state: Open,
+                    expected_version: Some(3),
+                    actor: None,
+                    open_until: None,
+                    ttl_seconds: None,
+                },
+                &mock_storage,
+                &mock_clock,
+                &mock_date_time_switch,
+                &MockIdProvider::new(),
+            )
+            .await;
+
+        // then
+        assert_eq!(
+            gate_with_state.expect_err("expected version conflict"),
+            Error::VersionConflict("some version conflict".to_owned())
+        );
+    }
+
+    #[tokio::test]
+    async fn should_record_state_replaced_by_change_if_gate_was_changed_in_the_meantime() {
+        // given
+        let mut mock_storage = MockStorage::new();
+        let mut mock_clock = MockClock::new();
+        let mut mock_date_time_switch = MockDateTimeSwitch::new();
+
+        mock_date_time_switch.expect_is_closed().return_const(false);
+        let now = DateTime::parse_from_rfc3339("2023-04-12T22:10:57+02:00")
+            .expect("failed to parse date")
+            .to_utc();
+        mock_clock.expect_now().return_const(now);
+
+        mock_storage
+            .expect_find_one()
+            .times(1)
+            .return_once(|key| Ok(Some(some_gate(&key.group, &key.service, &key.environment))));
+        mock_storage.expect_find_one().times(1).return_once(|key| {
+            Ok(Some(Gate {
+                state: GateState::Closed,
+                version: 1,
+                ..some_gate(&key.group, &key.service, &key.environment)
+            }))
+        });
+        mock_storage
+            .expect_update_state_and_last_updated()
+            .withf(|_, _, _, _, expected_version| *expected_version == Some(0))
+            .times(1)
+            .return_once(|_, _, _, _, _| {
+                Err(UpdateError::VersionConflict(
+                    "some version conflict".to_owned(),
+                ))
+            });
+        mock_storage
+            .expect_update_state_and_last_updated()
+            .withf(|_, _, _, _, expected_version| *expected_version == Some(1))
+            .times(1)
+            .return_once(|key, state, open_until, last_updated, _| {
+                Ok(Gate {
+                    state,
+                    open_until,
+                    last_updated,
+                    version: 2,
+                    ..some_gate(&key.group, &key.service, &key.environment)
+                })
+            });
+        mock_storage
+            .expect_insert_history_entry()
+            .withf(|entry| {
+                entry.old_value == Some("closed".to_owned())
+                    && entry.new_value == Some("open".to_owned())
+            })
+            .times(1)
+            .return_once(|_| Ok(()));
+
+        let mut mock_id_provider = MockIdProvider::new();
+        mock_id_provider
+            .expect_get()
+            .return_const("some id".to_owned());
+
+        // when
+        let gate_with_state = UseCaseImpl {}
+            .execute(
+                Input {
+                    group: "some group".to_owned(),
+                    service: "some service".to_owned(),
+                    environment: "some environment".to_owned(),
+                    state: Open,
+                    expected_version: None,
+                    actor: None,
+                    open_until: None,
+                    ttl_seconds: None,
+                },
+                &mock_storage,
+                &mock_clock,
+                &mock_date_time_switch,
+                &mock_id_provider,
+            )
+            .await;
+
+        // then
+        assert_eq!(
+            gate_with_state
+                .expect("There is no error expected here!")
+                .version,
+            2
+        );
+    }
+
+    #[tokio::test]
+    async fn should_not_change_state_of_unknown_gate() {
+        // given
+        let mut mock_storage = MockStorage::new();
+        let mut mock_clock = MockClock::new();
+        let mut mock_date_time_switch = MockDateTimeSwitch::new();
+
+        mock_date_time_switch.expect_is_closed().return_const(false);
+        mock_clock.expect_now().return_const(
+            DateTime::parse_from_rfc3339("2023-04-12T22:10:57+02:00")
+                .expect("failed to parse date")
+                .to_utc(),
+        );
+        mock_storage.expect_find_one().return_once(|_| Ok(None));
+
+        // when
+        let gate_with_state = UseCaseImpl {}
+            .execute(
+                Input {
+                    group: "some group".to_owned(),
+                    service: "some service".to_owned(),
+                    environment: "some environment".to_owned(),
+                    state: Open,
+                    expected_version: None,
+                    actor: None,
+                    open_until: None,
+                    ttl_seconds: None,
+                },
+                &mock_storage,
+                &mock_clock,
+                &mock_date_time_switch,
+                &MockIdProvider::new(),
+            )
+            .await;
+
+        // then
+        assert_eq!(
+            gate_with_state.expect_err("unexpected gate"),
+            Error::GateNotFound
+        );
+    }
+
     fn some_gate(group: &str, service: &str, environment: &str) -> Gate {
         Gate {
             key: GateKey {
@@ -204,6 +543,8 @@ mod unit_tests {
                 .expect("failed creating date")
                 .into(),
             display_order: Option::default(),
+            version: 0,
+            open_until: None,
         }
     }
 }
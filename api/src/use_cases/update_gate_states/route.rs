@@ -0,0 +1,86 @@
+use axum::extract::State;
+use axum::http::{HeaderMap, StatusCode};
+use axum::response::IntoResponse;
+use axum::Json;
+use serde::{Deserialize, Serialize};
+
+use crate::actor;
+use crate::types::app_state::AppState;
+use crate::types::{GateKey, GateState};
+use crate::use_cases::update_gate_states::use_case;
+use crate::use_cases::update_gate_states::use_case::Error;
+
+#[derive(Serialize, Deserialize)]
+pub struct GateKeyPayload {
+    pub group: String,
+    pub service: String,
+    pub environment: String,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct FilterPayload {
+    pub group: String,
+    pub environment: Option<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Payload {
+    pub gates: Option<Vec<GateKeyPayload>>,
+    pub filter: Option<FilterPayload>,
+    pub state: GateState,
+    pub comment: Option<String>,
+}
+
+pub async fn handler(
+    State(app_state): State<AppState>,
+    headers: HeaderMap,
+    Json(Payload {
+        gates,
+        filter,
+        state,
+        comment,
+    }): Json<Payload>,
+) -> impl IntoResponse {
+    match app_state
+        .use_cases
+        .update_gate_states
+        .execute(
+            use_case::Input {
+                gates: gates.map(|gates| {
+                    gates
+                        .into_iter()
+                        .map(|gate| GateKey {
+                            group: gate.group,
+                            service: gate.service,
+                            environment: gate.environment,
+                        })
+                        .collect()
+                }),
+                filter: filter.map(|filter| use_case::Filter {
+                    group: filter.group,
+                    environment: filter.environment,
+                }),
+                state,
+                comment,
+                actor: actor::from_headers(&headers),
+            },
+            app_state.storage.as_ref(),
+            app_state.clock.as_ref(),
+            app_state.date_time_switch.as_ref(),
+            app_state.id_provider.as_ref(),
+        )
+        .await
+    {
+        Ok(gates) => Json(gates).into_response(),
+        Err(error) => match error {
+            Error::InvalidInput(error) => (StatusCode::BAD_REQUEST, Json(error)).into_response(),
+            Error::GateClosed(error) | Error::VersionConflict(error) => {
+                (StatusCode::CONFLICT, Json(error)).into_response()
+            }
+            Error::GateNotFound => StatusCode::NO_CONTENT.into_response(),
+            Error::Internal(error) => {
+                (StatusCode::INTERNAL_SERVER_ERROR, Json(error)).into_response()
+            }
+        },
+    }
+}
@@ -0,0 +1,677 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use openapi::models;
+use std::collections::{HashMap, HashSet};
+
+use crate::clock::Clock;
+use crate::date_time_switch::DateTimeSwitch;
+use crate::history;
+use crate::id_provider::IdProvider;
+use crate::storage::{FindError, Storage, UpdateError};
+use crate::types::{Comment, Gate, GateEvent, GateKey, GateState, HistoryEntry};
+
+pub const MAX_GATES: usize = 100;
+// the gates are read again if one of them was changed between reading and updating them
+const MAX_ATTEMPTS: u32 = 3;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Filter {
+    pub group: String,
+    pub environment: Option<String>,
+}
+
+#[derive(Debug)]
+pub struct Input {
+    pub gates: Option<Vec<GateKey>>,
+    pub filter: Option<Filter>,
+    pub state: GateState,
+    pub comment: Option<String>,
+    pub actor: Option<String>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Error {
+    InvalidInput(String),
+    GateNotFound,
+    GateClosed(String),
+    VersionConflict(String),
+    Internal(String),
+}
+
+impl From<UpdateError> for Error {
+    fn from(value: UpdateError) -> Self {
+        match value {
+            UpdateError::ItemToUpdateNotFound(_) => Self::GateNotFound,
+            UpdateError::VersionConflict(error) => Self::VersionConflict(error),
+            UpdateError::Other(error) => Self::Internal(error),
+        }
+    }
+}
+
+impl From<FindError> for Error {
+    fn from(value: FindError) -> Self {
+        match value {
+            FindError::ItemCouldNotBeDecoded(error)
+            | FindError::InvalidCursor(error)
+            | FindError::Other(error) => Self::Internal(error),
+        }
+    }
+}
+
+#[async_trait]
+pub trait UseCase {
+    async fn execute(
+        &self,
+        input: Input,
+        storage: &(dyn Storage + Send + Sync),
+        clock: &(dyn Clock + Send + Sync),
+        date_time_switch: &(dyn DateTimeSwitch + Send + Sync),
+        id_provider: &(dyn IdProvider + Send + Sync),
+    ) -> Result<Vec<models::Gate>, Error>;
+}
+
+pub fn create() -> impl UseCase {
+    UseCaseImpl {}
+}
+
+#[derive(Clone)]
+struct UseCaseImpl {}
+
+#[async_trait]
+impl UseCase for UseCaseImpl {
+    async fn execute(
+        &self,
+        Input {
+            gates,
+            filter,
+            state,
+            comment,
+            actor,
+        }: Input,
+        storage: &(dyn Storage + Send + Sync),
+        clock: &(dyn Clock + Send + Sync),
+        date_time_switch: &(dyn DateTimeSwitch + Send + Sync),
+        id_provider: &(dyn IdProvider + Send + Sync),
+    ) -> Result<Vec<models::Gate>, Error> {
+        let comment = match comment {
+            Some(comment) if comment.trim().is_empty() => {
+                return Err(Error::InvalidInput("comment must not be empty".to_owned()));
+            }
+            comment => comment.map(|comment| comment.trim().to_owned()),
+        };
+
+        let now = clock.now();
+        let mut attempts = 1;
+        let comment = comment.map(|message| Comment {
+            id: id_provider.get(),
+            message,
+            created: now,
+        });
+        let (previous_states, gates) = loop {
+            let previous_gates = find_open_gates(
+                gates.clone(),
+                filter.clone(),
+                now,
+                storage,
+                date_time_switch,
+            )
+            .await?;
+            if previous_gates.is_empty() {
+                return Ok(Vec::new());
+            }
+            // the versions make the update fail if a gate was changed since reading its state
+            let keys: Vec<(GateKey, u32)> = previous_gates
+                .iter()
+                .map(|gate| (gate.key.clone(), gate.version))
+                .collect();
+            match storage
+                .update_states_and_last_updated(&keys, state.clone(), comment.clone(), now)
+                .await
+            {
+                Ok(updated) => {
+                    let previous_states: HashMap<GateKey, GateState> = previous_gates
+                        .into_iter()
+                        .map(|gate| (gate.key, gate.state))
+                        .collect();
+                    break (previous_states, updated);
+                }
+                Err(UpdateError::VersionConflict(_)) if attempts < MAX_ATTEMPTS => attempts += 1,
+                Err(error) => return Err(error.into()),
+            }
+        };
+
+        for gate in &gates {
+            history::record(
+                storage,
+                &HistoryEntry {
+                    id: id_provider.get(),
+                    key: gate.key.clone(),
+                    timestamp: now,
+                    event: GateEvent::StateChanged,
+                    old_value: previous_states
+                        .get(&gate.key)
+                        .cloned()
+                        .map(String::try_from)
+                        .transpose()
+                        .map_err(Error::Internal)?,
+                    new_value: Some(gate.state.clone().try_into().map_err(Error::Internal)?),
+                    open_until: None,
+                    actor: actor.clone(),
+                },
+            )
+            .await;
+
+            if let Some(comment) = &comment {
+                history::record(
+                    storage,
+                    &HistoryEntry {
+                        id: id_provider.get(),
+                        key: gate.key.clone(),
+                        timestamp: now,
+                        event: GateEvent::CommentAdded,
+                        old_value: None,
+                        // the stored message, it is replaced in demo mode
+                        new_value: gate
+                            .comments
+                            .iter()
+                            .find(|existing| existing.id == comment.id)
+                            .map(|existing| existing.message.clone()),
+                        open_until: None,
+                        actor: actor.clone(),
+                    },
+                )
+                .await;
+            }
+        }
+
+        Ok(gates.into_iter().map(Into::into).collect())
+    }
+}
+
+/// Finds the selected gates, all of which must be within their business hours.
+async fn find_open_gates(
+    gates: Option<Vec<GateKey>>,
+    filter: Option<Filter>,
+    now: DateTime<Utc>,
+    storage: &(dyn Storage + Send + Sync),
+    date_time_switch: &(dyn DateTimeSwitch + Send + Sync),
+) -> Result<Vec<Gate>, Error> {
+    let gates = match (gates, filter) {
+        (Some(keys), None) => find_by_keys(keys, storage).await?,
+        (None, Some(filter)) => find_by_filter(filter, storage).await?,
+        _ => {
+            return Err(Error::InvalidInput(
+                "either gates or filter must be given".to_owned(),
+            ))
+        }
+    };
+    if gates.len() > MAX_GATES {
+        return Err(Error::InvalidInput(format!(
+            "at most {MAX_GATES} gates can be changed at once"
+        )));
+    }
+    // business hours may differ between the gates, so all of them must be open
+    for gate in &gates {
+        if date_time_switch.is_closed(now, &gate.key).await {
+            return Err(Error::GateClosed(format!(
+                "Already after business hours of {} - rejecting attempt to change state",
+                gate.key
+            )));
+        }
+    }
+    Ok(gates)
+}
+
+async fn find_by_keys(
+    keys: Vec<GateKey>,
+    storage: &(dyn Storage + Send + Sync),
+) -> Result<Vec<Gate>, Error> {
+    if keys.is_empty() {
+        return Err(Error::InvalidInput("gates must not be empty".to_owned()));
+    }
+    if keys.len() > MAX_GATES {
+        return Err(Error::InvalidInput(format!(
+            "at most {MAX_GATES} gates can be changed at once"
+        )));
+    }
+    let mut seen = HashSet::new();
+    if let Some(key) = keys.iter().find(|key| !seen.insert(*key)) {
+        return Err(Error::InvalidInput(format!(
+            "gate {key} is contained more than once"
+        )));
+    }
+
+    let mut gates = Vec::with_capacity(keys.len());
+    for key in keys {
+        gates.push(storage.find_one(key).await?.ok_or(Error::GateNotFound)?);
+    }
+    Ok(gates)
+}
+
+async fn find_by_filter(
+    Filter { group, environment }: Filter,
+    storage: &(dyn Storage + Send + Sync),
+) -> Result<Vec<Gate>, Error> {
+    if group.is_empty() {
+        return Err(Error::InvalidInput("group must not be empty".to_owned()));
+    }
+    Ok(storage
+        .find_by_group(group)
+        .await?
+        .into_iter()
+        .filter(|gate| {
+            environment
+                .as_ref()
+                .is_none_or(|environment| &gate.key.environment == environment)
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use std::collections::HashSet;
+
+    use chrono::{DateTime, Utc};
+    use mockall::predicate::{always, eq};
+    use similar_asserts::assert_eq;
+
+    use crate::clock::MockClock;
+    use crate::date_time_switch::MockDateTimeSwitch;
+    use crate::id_provider::MockIdProvider;
+    use crate::storage::MockStorage;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn should_close_gates_selected_by_filter_and_add_comment() {
+        // given
+        let mut mock_storage = MockStorage::new();
+        let mut mock_clock = MockClock::new();
+        let mut mock_date_time_switch = MockDateTimeSwitch::new();
+
+        mock_date_time_switch.expect_is_closed().return_const(false);
+
+        let now = some_date_time();
+        mock_clock.expect_now().return_const(now);
+
+        mock_storage
+            .expect_find_by_group()
+            .with(eq("some group".to_owned()))
+            .return_once(|_| {
+                Ok(vec![
+                    some_gate("some service", "live"),
+                    some_gate("some service", "develop"),
+                    some_gate("some other service", "live"),
+                ])
+            });
+
+        let comment = Comment {
+            id: "some id".to_owned(),
+            message: "incident".to_owned(),
+            created: now,
+        };
+        let keys = vec![
+            (some_gate("some service", "live").key, 0),
+            (some_gate("some other service", "live").key, 0),
+        ];
+        let expected_comment = comment.clone();
+        mock_storage
+            .expect_update_states_and_last_updated()
+            .withf(move |keys_to_update, state, comment, last_updated| {
+                keys_to_update == keys
+                    && *state == GateState::Closed
+                    && *comment == Some(expected_comment.clone())
+                    && *last_updated == now
+            })
+            .return_once(move |keys, state, comment, last_updated| {
+                Ok(keys
+                    .iter()
+                    .map(|(key, _)| Gate {
+                        state: state.clone(),
+                        comments: comment.clone().into_iter().collect(),
+                        last_updated,
+                        version: 1,
+                        ..some_gate(&key.service, &key.environment)
+                    })
+                    .collect())
+            });
+        mock_storage
+            .expect_insert_history_entry()
+            .withf(|entry| {
+                entry.event == GateEvent::StateChanged
+                    && entry.old_value == Some("open".to_owned())
+                    && entry.new_value == Some("closed".to_owned())
+            })
+            .times(2)
+            .returning(|_| Ok(()));
+        mock_storage
+            .expect_insert_history_entry()
+            .withf(|entry| {
+                entry.event == GateEvent::CommentAdded
+                    && entry.new_value == Some("incident".to_owned())
+            })
+            .times(2)
+            .returning(|_| Ok(()));
+
+        let mut mock_id_provider = MockIdProvider::new();
+        mock_id_provider
+            .expect_get()
+            .return_const("some id".to_owned());
+
+        // when
+        let result = UseCaseImpl {}
+            .execute(
+                Input {
+                    gates: None,
+                    filter: Some(Filter {
+                        group: "some group".to_owned(),
+                        environment: Some("live".to_owned()),
+                    }),
+                    state: GateState::Closed,
+                    comment: Some(" incident ".to_owned()),
+                    actor: Some("Max".to_owned()),
+                },
+                &mock_storage,
+                &mock_clock,
+                &mock_date_time_switch,
+                &mock_id_provider,
+            )
+            .await;
+
+        // then
+        let gates = result.expect("There is no error expected here!");
+        assert_eq!(gates.len(), 2);
+        assert!(gates
+            .iter()
+            .all(|gate| gate.state == models::GateState::Closed && gate.comments.len() == 1));
+    }
+
+    #[tokio::test]
+    async fn should_read_gates_again_if_one_was_changed_in_the_meantime() {
+        // given
+        let mut mock_storage = MockStorage::new();
+        let mut mock_clock = MockClock::new();
+        let mut mock_date_time_switch = MockDateTimeSwitch::new();
+
+        mock_date_time_switch.expect_is_closed().return_const(false);
+        mock_clock.expect_now().return_const(some_date_time());
+
+        let key = some_gate("some service", "live").key;
+        mock_storage
+            .expect_find_one()
+            .times(1)
+            .return_once(|_| Ok(Some(some_gate("some service", "live"))));
+        mock_storage.expect_find_one().times(1).return_once(|_| {
+            Ok(Some(Gate {
+                state: GateState::Closed,
+                version: 1,
+                ..some_gate("some service", "live")
+            }))
+        });
+        mock_storage
+            .expect_update_states_and_last_updated()
+            .with(eq(vec![(key.clone(), 0)]), always(), always(), always())
+            .times(1)
+            .return_once(|_, _, _, _| {
+                Err(UpdateError::VersionConflict(
+                    "gate was changed in the meantime".to_owned(),
+                ))
+            });
+        mock_storage
+            .expect_update_states_and_last_updated()
+            .with(
+                eq(vec![(key.clone(), 1)]),
+                eq(GateState::Open),
+                eq(None),
+                always(),
+            )
+            .times(1)
+            .return_once(|_, _, _, _| {
+                Ok(vec![Gate {
+                    version: 2,
+                    ..some_gate("some service", "live")
+                }])
+            });
+        mock_storage
+            .expect_insert_history_entry()
+            .withf(|entry| {
+                entry.event == GateEvent::StateChanged
+                    && entry.old_value == Some("closed".to_owned())
+                    && entry.new_value == Some("open".to_owned())
+            })
+            .times(1)
+            .returning(|_| Ok(()));
+
+        let mut mock_id_provider = MockIdProvider::new();
+        mock_id_provider
+            .expect_get()
+            .return_const("some id".to_owned());
+
+        // when
+        let result = UseCaseImpl {}
+            .execute(
+                Input {
+                    gates: Some(vec![key]),
+                    filter: None,
+                    state: GateState::Open,
+                    comment: None,
+                    actor: None,
+                },
+                &mock_storage,
+                &mock_clock,
+                &mock_date_time_switch,
+                &mock_id_provider,
+            )
+            .await;
+
+        // then
+        let gates = result.expect("There is no error expected here!");
+        assert_eq!(gates.len(), 1);
+        assert_eq!(gates[0].version, 2);
+    }
+
+    #[tokio::test]
+    async fn should_fail_with_version_conflict_if_gates_keep_being_changed() {
+        // given
+        let mut mock_storage = MockStorage::new();
+        let mut mock_clock = MockClock::new();
+        let mut mock_date_time_switch = MockDateTimeSwitch::new();
+
+        mock_date_time_switch.expect_is_closed().return_const(false);
+        mock_clock.expect_now().return_const(some_date_time());
+
+        let key = some_gate("some service", "live").key;
+        mock_storage
+            .expect_find_one()
+            .times(3)
+            .returning(|_| Ok(Some(some_gate("some service", "live"))));
+        mock_storage
+            .expect_update_states_and_last_updated()
+            .times(3)
+            .returning(|_, _, _, _| {
+                Err(UpdateError::VersionConflict(
+                    "gate was changed in the meantime".to_owned(),
+                ))
+            });
+        mock_storage.expect_insert_history_entry().never();
+
+        // when
+        let result = UseCaseImpl {}
+            .execute(
+                Input {
+                    gates: Some(vec![key]),
+                    filter: None,
+                    state: GateState::Open,
+                    comment: None,
+                    actor: None,
+                },
+                &mock_storage,
+                &mock_clock,
+                &mock_date_time_switch,
+                &MockIdProvider::new(),
+            )
+            .await;
+
+        // then
+        assert_eq!(
+            result,
+            Err(Error::VersionConflict(
+                "gate was changed in the meantime".to_owned()
+            ))
+        );
+    }
+
+    #[tokio::test]
+    async fn should_not_change_any_gate_if_one_does_not_exist() {
+        // given
+        let mut mock_storage = MockStorage::new();
+        let mut mock_clock = MockClock::new();
+        let mut mock_date_time_switch = MockDateTimeSwitch::new();
+
+        mock_date_time_switch.expect_is_closed().return_const(false);
+        mock_clock.expect_now().return_const(some_date_time());
+
+        mock_storage
+            .expect_find_one()
+            .with(eq(some_gate("some service", "live").key))
+            .return_once(|_| Ok(Some(some_gate("some service", "live"))));
+        mock_storage
+            .expect_find_one()
+            .with(eq(some_gate("some service", "develop").key))
+            .return_once(|_| Ok(None));
+        mock_storage.expect_update_states_and_last_updated().never();
+
+        // when
+        let result = UseCaseImpl {}
+            .execute(
+                Input {
+                    gates: Some(vec![
+                        some_gate("some service", "live").key,
+                        some_gate("some service", "develop").key,
+                    ]),
+                    filter: None,
+                    state: GateState::Closed,
+                    comment: None,
+                    actor: None,
+                },
+                &mock_storage,
+                &mock_clock,
+                &mock_date_time_switch,
+                &MockIdProvider::new(),
+            )
+            .await;
+
+        // then
+        assert_eq!(result, Err(Error::GateNotFound));
+    }
+
+    #[tokio::test]
+    async fn should_fail_when_neither_gates_nor_filter_are_given() {
+        // given
+        let mut mock_clock = MockClock::new();
+        let mut mock_date_time_switch = MockDateTimeSwitch::new();
+
+        mock_date_time_switch.expect_is_closed().return_const(false);
+        mock_clock.expect_now().return_const(some_date_time());
+
+        // when
+        let result = UseCaseImpl {}
+            .execute(
+                Input {
+                    gates: None,
+                    filter: None,
+                    state: GateState::Closed,
+                    comment: None,
+                    actor: None,
+                },
+                &MockStorage::new(),
+                &mock_clock,
+                &mock_date_time_switch,
+                &MockIdProvider::new(),
+            )
+            .await;
+
+        // then
+        assert_eq!(
+            result,
+            Err(Error::InvalidInput(
+                "either gates or filter must be given".to_owned()
+            ))
+        );
+    }
+
+    #[tokio::test]
+    async fn should_fail_when_one_of_the_gates_is_closed() {
+        // given
+        let mut mock_storage = MockStorage::new();
+        let mut mock_clock = MockClock::new();
+        let mut mock_date_time_switch = MockDateTimeSwitch::new();
+
+        mock_date_time_switch
+            .expect_is_closed()
+            .with(always(), eq(some_gate("some service", "develop").key))
+            .return_const(false);
+        mock_date_time_switch
+            .expect_is_closed()
+            .with(always(), eq(some_gate("some service", "live").key))
+            .return_const(true);
+        mock_clock.expect_now().return_const(some_date_time());
+
+        mock_storage
+            .expect_find_one()
+            .with(eq(some_gate("some service", "develop").key))
+            .return_once(|_| Ok(Some(some_gate("some service", "develop"))));
+        mock_storage
+            .expect_find_one()
+            .with(eq(some_gate("some service", "live").key))
+            .return_once(|_| Ok(Some(some_gate("some service", "live"))));
+        mock_storage.expect_update_states_and_last_updated().never();
+
+        // when
+        let result = UseCaseImpl {}
+            .execute(
+                Input {
+                    gates: Some(vec![
+                        some_gate("some service", "develop").key,
+                        some_gate("some service", "live").key,
+                    ]),
+                    filter: None,
+                    state: GateState::Open,
+                    comment: None,
+                    actor: None,
+                },
+                &mock_storage,
+                &mock_clock,
+                &mock_date_time_switch,
+                &MockIdProvider::new(),
+            )
+            .await;
+
+        // then
+        assert!(matches!(result, Err(Error::GateClosed(_))));
+    }
+
+    fn some_date_time() -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339("2023-04-12T12:10:57+02:00")
+            .expect("failed to parse date")
+            .to_utc()
+    }
+
+    fn some_gate(service: &str, environment: &str) -> Gate {
+        Gate {
+            key: GateKey {
+                group: "some group".to_owned(),
+                service: service.to_owned(),
+                environment: environment.to_owned(),
+            },
+            state: GateState::Open,
+            comments: HashSet::default(),
+            last_updated: DateTime::parse_from_rfc3339("2023-04-11T12:10:57+02:00")
+                .expect("failed to parse date")
+                .to_utc(),
+            display_order: None,
+            version: 0,
+            open_until: None,
+        }
+    }
+}
@@ -6,6 +6,8 @@ import * as route53_targets from "aws-cdk-lib/aws-route53-targets";
 import * as acm from "aws-cdk-lib/aws-certificatemanager";
 import * as lambda from "aws-cdk-lib/aws-lambda";
 import * as logs from "aws-cdk-lib/aws-logs";
+import * as events from "aws-cdk-lib/aws-events";
+import * as events_targets from "aws-cdk-lib/aws-events-targets";
 import * as apigatewayv2 from "aws-cdk-lib/aws-apigatewayv2";
 import * as apigatewayv2_integrations from "aws-cdk-lib/aws-apigatewayv2-integrations";
 import * as dynamodb from "aws-cdk-lib/aws-dynamodb";
@@ -44,6 +46,19 @@ export interface GatesProps {
     readonly frontendAssetsBucketName?: string;
 
     readonly demoMode?: boolean;
+
+    /**
+     * Whether gates are closed automatically outside of business hours and on closed days.
+     * If not specified, gates are never closed automatically.
+     */
+    readonly businessHours?: boolean;
+
+    /**
+     * Whether open gates are really closed with a comment at the end of business hours,
+     * so they have to be opened again on the next business day.
+     * Only effective together with `businessHours`. If not specified, gates are only reported closed.
+     */
+    readonly businessHoursAutoClose?: boolean;
 }
 
 const SCOPE_CLOUDFRONT = "CLOUDFRONT";
@@ -69,7 +84,24 @@ export class Gates extends Construct {
         const hostedZone = this.lookupHostedZone(props.domain);
 
         const gatesTable = this.createGatesTable(appName);
-        const apiFunction = this.createApiFunction(appName, gatesTable, props.demoMode);
+        const historyTable = this.createHistoryTable(appName);
+        const apiFunction = this.createApiFunction(
+            appName,
+            gatesTable,
+            historyTable,
+            props.demoMode,
+            props.businessHours,
+        );
+
+        if (!props.demoMode) {
+            this.createSchedulerFunction(
+                appName,
+                gatesTable,
+                historyTable,
+                props.businessHours,
+                props.businessHoursAutoClose,
+            );
+        }
 
         const verifyOriginSecret = this.createVerifyOriginSecret(appName);
         const verifyOriginAuthFunction = this.createVerifyOriginAuthFunction(
@@ -386,7 +418,13 @@ export class Gates extends Construct {
         });
     }
 
-    private createApiFunction(appName: string, gatesTable: dynamodb.TableV2, demoMode?: boolean) {
+    private createApiFunction(
+        appName: string,
+        gatesTable: dynamodb.TableV2,
+        historyTable: dynamodb.TableV2,
+        demoMode?: boolean,
+        businessHours?: boolean,
+    ) {
         const apiFunction = new lambda.Function(this, "ApiFunction", {
             functionName: `${appName}-api`,
             runtime: lambda.Runtime.PROVIDED_AL2023,
@@ -397,16 +435,56 @@ export class Gates extends Construct {
             handler: "provided",
             environment: {
                 GATES_DYNAMO_DB_TABLE_NAME: gatesTable.tableName,
+                GATES_DYNAMO_DB_HISTORY_TABLE_NAME: historyTable.tableName,
                 ...(demoMode && { DEMO_MODE: "true" }),
+                ...(businessHours && { GATES_BUSINESS_HOURS_ENABLED: "true" }),
             },
             logRetention: logs.RetentionDays.ONE_WEEK,
         });
 
         gatesTable.grantReadWriteData(apiFunction);
+        historyTable.grantReadWriteData(apiFunction);
 
         return apiFunction;
     }
 
+    private createSchedulerFunction(
+        appName: string,
+        gatesTable: dynamodb.TableV2,
+        historyTable: dynamodb.TableV2,
+        businessHours?: boolean,
+        businessHoursAutoClose?: boolean,
+    ) {
+        const schedulerFunction = new lambda.Function(this, "SchedulerFunction", {
+            functionName: `${appName}-scheduler`,
+            runtime: lambda.Runtime.PROVIDED_AL2023,
+            architecture: lambda.Architecture.ARM_64,
+            code: lambda.Code.fromAsset(
+                path.join(__dirname, "..", "build", "api"),
+            ),
+            handler: "provided",
+            environment: {
+                GATES_DYNAMO_DB_TABLE_NAME: gatesTable.tableName,
+                GATES_DYNAMO_DB_HISTORY_TABLE_NAME: historyTable.tableName,
+                GATES_SCHEDULER: "lambda",
+                ...(businessHours && { GATES_BUSINESS_HOURS_ENABLED: "true" }),
+                ...(businessHoursAutoClose && { GATES_BUSINESS_HOURS_AUTO_CLOSE: "true" }),
+            },
+            logRetention: logs.RetentionDays.ONE_WEEK,
+        });
+
+        gatesTable.grantReadWriteData(schedulerFunction);
+        historyTable.grantReadWriteData(schedulerFunction);
+
+        new events.Rule(this, "SchedulerRule", {
+            ruleName: `${appName}-scheduler`,
+            schedule: events.Schedule.rate(cdk.Duration.minutes(1)),
+            targets: [new events_targets.LambdaFunction(schedulerFunction)],
+        });
+
+        return schedulerFunction;
+    }
+
     private createViewerCertificate(domain?: Domain) {
         if (domain === undefined) {
             return undefined;
@@ -425,6 +503,23 @@ export class Gates extends Construct {
             tableName: `${appName}`,
             partitionKey: { name: "group", type: dynamodb.AttributeType.STRING },
             sortKey: { name: "service_environment", type: dynamodb.AttributeType.STRING },
+            globalSecondaryIndexes: [
+                {
+                    // sparse, only gates in the trash have the "trash" attribute
+                    indexName: "trash",
+                    partitionKey: { name: "trash", type: dynamodb.AttributeType.STRING },
+                    sortKey: { name: "deleted_at", type: dynamodb.AttributeType.STRING },
+                    projectionType: dynamodb.ProjectionType.KEYS_ONLY,
+                },
+            ],
+        });
+    }
+
+    private createHistoryTable(appName: string) {
+        return new dynamodb.TableV2(this, "HistoryTable", {
+            tableName: `${appName}-history`,
+            partitionKey: { name: "gate", type: dynamodb.AttributeType.STRING },
+            sortKey: { name: "position", type: dynamodb.AttributeType.STRING },
         });
     }
 